@@ -1,10 +1,14 @@
 use std::vec;
 
+use crate::repository::pdf_store::PdfStore;
 use anyhow::Result;
 use arxiv_tools::{ArXiv, Paper as ArxivPaper, QueryParams as ArXivQueryParams};
 use chrono::{DateTime, Local, TimeZone};
 use derive_new::new;
-use kernel::models::academic_paper::{AcademicPaper, Author, Journal, Status};
+use kernel::models::{
+    academic_paper::{AcademicPaper, Author, Journal, PaperSection, Status},
+    venue::{normalize_venue, venue_year},
+};
 use rsrpp::{config::ParserConfig, models::Section as RsrppSection, parser::parse};
 use serde::{Deserialize, Serialize};
 use shared::{
@@ -86,6 +90,51 @@ fn get_journal_name(arxiv_paper: &Option<ArxivPaper>, ss_paper: &Option<SsPaper>
     }
 }
 
+/// The IDs and open access PDF that Semantic Scholar knows for a paper.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsPaperLinks {
+    #[serde(default)]
+    pub external_ids: SsExternalIds,
+    pub open_access_pdf: Option<SsOpenAccessPdf>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SsExternalIds {
+    #[serde(rename = "ArXiv")]
+    pub arxiv: Option<String>,
+    #[serde(rename = "DOI")]
+    pub doi: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SsOpenAccessPdf {
+    pub url: Option<String>,
+}
+
+const SS_GRAPH_API_URL: &str = "https://api.semanticscholar.org/graph/v1";
+
+fn with_ss_api_key(request: request::RequestBuilder) -> request::RequestBuilder {
+    match std::env::var("SEMANTIC_SCHOLAR_API_KEY") {
+        Ok(api_key) => request.header("x-api-key", api_key),
+        Err(_) => request,
+    }
+}
+
+/// A GET request to the Semantic Scholar Graph API, authenticated with `SEMANTIC_SCHOLAR_API_KEY` if set.
+pub fn ss_graph_api_request(path: &str) -> request::RequestBuilder {
+    with_ss_api_key(request::Client::new().get(format!("{}/{}", SS_GRAPH_API_URL, path)))
+}
+
+/// A POST request to the Semantic Scholar Graph API, used by the batch endpoints.
+pub fn ss_graph_api_post(path: &str) -> request::RequestBuilder {
+    with_ss_api_key(request::Client::new().post(format!("{}/{}", SS_GRAPH_API_URL, path)))
+}
+
+const SS_PAPER_FIELDS: &str = "paperId,externalIds,title,abstract,authors.name,authors.authorId,authors.hIndex,url,\
+                               publicationDate,journal,citationCount,referenceCount,influentialCitationCount,\
+                               openAccessPdf";
+
 #[derive(Debug, Clone, Serialize, Deserialize, new)]
 pub struct AcademicPaperResource {
     pub title: String,
@@ -93,14 +142,32 @@ pub struct AcademicPaperResource {
     pub text: Vec<Section>,
     pub arxiv_paper: Option<ArxivPaper>,
     pub ss_paper: Option<SsPaper>,
+    /// The DOI resolved from the identifier or Semantic Scholar, for papers that are not on arXiv.
+    pub doi: Option<String>,
+    /// The SHA-256 of the PDF in the local PDF store, if it could be downloaded.
+    pub pdf_sha256: Option<String>,
 }
 
 impl AcademicPaperResource {
     /// Fills the `AcademicPaperResource` with data from a PDF URL.
-    /// target field: `text`
+    /// The PDF is kept in the local PDF store and parsed from there; if it cannot be downloaded,
+    /// the parser fetches the URL itself.
+    /// target field: `text`, `pdf_sha256`
     pub async fn parse_pdf_url(&mut self) -> Result<AcademicPaperResource> {
+        let store = PdfStore::from_env();
+        let source = match store.download(&self.url).await {
+            Ok(sha256) => {
+                let path = store.path_of(&sha256).to_string_lossy().to_string();
+                self.pdf_sha256 = Some(sha256);
+                path
+            }
+            Err(e) => {
+                tracing::warn!("Failed to store the PDF at {}: {}", self.url, e);
+                self.url.clone()
+            }
+        };
         let mut config = ParserConfig::new();
-        match parse(&self.url, &mut config, true).await {
+        match parse(&source, &mut config, true).await {
             Ok(pages) => {
                 let sections = RsrppSection::from_pages(&pages);
                 self.text = sections
@@ -149,6 +216,48 @@ impl AcademicPaperResource {
         tracing::info!("Successfully fetched ArXiv paper for title: {}", self.title);
         Ok(self.clone())
     }
+    /// Fetches the paper from ArXiv by its ID.
+    /// target field: `arxiv_paper`, `title`
+    pub async fn fetch_arxiv_paper_by_id(&mut self, arxiv_id: &str) -> Result<AcademicPaperResource> {
+        let mut arxiv = ArXiv::from_args(ArXivQueryParams::id(arxiv_id));
+        let found_papers = arxiv.query().await;
+        // The API returns the latest version, e.g. `2301.00001v2` for `2301.00001`.
+        self.arxiv_paper = found_papers.into_iter().find(|p| {
+            p.id.trim_start_matches("http://arxiv.org/abs/")
+                .trim_start_matches("https://arxiv.org/abs/")
+                .starts_with(arxiv_id)
+        });
+        match self.arxiv_paper.as_ref() {
+            Some(paper) => {
+                if self.title.is_empty() {
+                    self.title = paper.title.clone();
+                }
+                tracing::info!("Successfully fetched ArXiv paper for ID: {}", arxiv_id);
+            }
+            None => tracing::warn!("No papers found for ArXiv ID: {}", arxiv_id),
+        }
+        Ok(self.clone())
+    }
+    /// Fetches the paper from Semantic Scholar by its ID (`arXiv:<id>`, `DOI:<doi>` or the paper ID).
+    /// target field: `ss_paper`, `title`, `doi`
+    pub async fn fetch_ss_paper_by_id(&mut self, ss_lookup_id: &str) -> Result<SsPaperLinks> {
+        let response = ss_graph_api_request(&format!("paper/{}", ss_lookup_id))
+            .query(&[("fields", SS_PAPER_FIELDS)])
+            .send()
+            .await?
+            .error_for_status()?;
+        let body = response.json::<serde_json::Value>().await?;
+        let links = serde_json::from_value::<SsPaperLinks>(body.clone())?;
+        self.ss_paper = Some(serde_json::from_value::<SsPaper>(body)?);
+        if self.title.is_empty() {
+            self.title = self.ss_paper.as_ref().and_then(|p| p.title.clone()).unwrap_or_default();
+        }
+        if self.doi.is_none() {
+            self.doi = links.external_ids.doi.clone();
+        }
+        tracing::info!("Successfully fetched Semantic Scholar paper for ID: {}", ss_lookup_id);
+        Ok(links)
+    }
     /// Fetches the paper from Semantic Scholar using the title.
     /// target field: `ss_paper`, `title`
     pub async fn fetch_ss_paper(&mut self) -> Result<AcademicPaperResource> {
@@ -201,14 +310,25 @@ impl From<AcademicPaperResource> for AcademicPaper {
             text,
             arxiv_paper,
             ss_paper,
+            doi,
+            pdf_sha256,
         } = resource;
+        let published_date = datetime_from_str(
+            &ss_paper
+                .as_ref()
+                .and_then(|p| p.publication_date.clone())
+                .unwrap_or_default(),
+        );
+        let venue = normalize_venue(&get_journal_name(&arxiv_paper, &ss_paper));
+        let mut journal = Journal::new(JournalId::new(), venue.name.clone());
+        journal.venue_type = Some(venue.venue_type);
         AcademicPaper {
             paper_id: AcademicPaperId::new(),
             ss_id: ss_paper
                 .as_ref()
                 .map_or(String::new(), |p| p.paper_id.clone().unwrap_or(String::new())),
             arxiv_id: arxiv_paper.as_ref().map_or(String::new(), |p| p.id.clone()),
-            journal: Journal::new(JournalId::new(), get_journal_name(&arxiv_paper, &ss_paper)),
+            journal,
             authors: ss_paper.as_ref().map_or(vec![], |p| match p.authors.as_ref() {
                 None => vec![],
                 Some(authors) => authors
@@ -232,23 +352,23 @@ impl From<AcademicPaperResource> for AcademicPaper {
                     .unwrap_or_else(|| arxiv_paper.as_ref().map_or(String::new(), |p| p.abstract_text.clone())),
                 None => String::new(),
             },
-            abstract_text_ja: String::new(),
             text: text
-                .into_iter()
+                .iter()
                 .map(|s| format!("# {}\n\n{}", s.title, s.content))
                 .collect::<Vec<String>>()
                 .join("\n\n"),
+            sections: text
+                .into_iter()
+                .map(|s| PaperSection::new(s.title, s.content))
+                .collect(),
+            pdf_sha256: pdf_sha256.unwrap_or_default(),
             url,
             doi: match arxiv_paper.as_ref() {
-                Some(p) => p.doi.clone(),
-                None => String::new(),
+                Some(p) if !p.doi.is_empty() => p.doi.clone(),
+                _ => doi.unwrap_or_default(),
             },
-            published_date: datetime_from_str(
-                &ss_paper
-                    .as_ref()
-                    .and_then(|p| p.publication_date.clone())
-                    .unwrap_or_default(),
-            ),
+            published_date,
+            venue_year: venue_year(&venue, published_date),
             created_at: Local::now(),
             updated_at: Local::now(),
             primary_category: match arxiv_paper.as_ref() {
@@ -262,12 +382,7 @@ impl From<AcademicPaperResource> for AcademicPaper {
                 .and_then(|p| p.influential_citation_count)
                 .unwrap_or(0) as i32,
             bibtex: String::new(),
-            summary: String::new(),
-            background_and_purpose: String::new(),
-            methodology: String::new(),
-            dataset: String::new(),
-            results: String::new(),
-            advantages_limitations_and_future_work: String::new(),
+            translations: vec![],
             status: Status::New,
         }
     }
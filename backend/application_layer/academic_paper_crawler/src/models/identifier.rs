@@ -0,0 +1,127 @@
+use anyhow::{anyhow, Error};
+use kernel::models::academic_paper::AcademicPaper;
+use regex::Regex;
+use std::{fmt, str::FromStr};
+
+/// An identifier that points to exactly one paper.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaperIdentifier {
+    /// arXiv ID without the version, e.g. `2301.00001` or `cs/0112017`.
+    ArXiv(String),
+    Doi(String),
+    SemanticScholar(String),
+}
+
+impl PaperIdentifier {
+    /// The ID understood by the Semantic Scholar paper lookup.
+    pub fn ss_lookup_id(&self) -> String {
+        match self {
+            Self::ArXiv(id) => format!("arXiv:{}", id),
+            Self::Doi(doi) => format!("DOI:{}", doi),
+            Self::SemanticScholar(id) => id.clone(),
+        }
+    }
+
+    /// The most exact identifier stored for a paper, if any.
+    pub fn of_paper(paper: &AcademicPaper) -> Option<Self> {
+        [&paper.ss_id, &paper.arxiv_id, &paper.doi]
+            .into_iter()
+            .find_map(|id| Self::from_str(id).ok())
+    }
+
+    pub fn arxiv_id(&self) -> Option<&str> {
+        match self {
+            Self::ArXiv(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for PaperIdentifier {
+    type Err = Error;
+
+    /// Accepts arXiv IDs and abs/pdf URLs, DOIs (bare, `doi:` or doi.org URLs),
+    /// and Semantic Scholar paper IDs or URLs.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let arxiv_url = Regex::new(r"^https?://(?:www\.|export\.)?arxiv\.org/(?:abs|pdf)/(.+?)/?$").unwrap();
+        let arxiv_id =
+            Regex::new(r"^(?i:arxiv:)?(\d{4}\.\d{4,5}|[a-z\-]+(?:\.[A-Z]{2})?/\d{7})(?:v\d+)?(?:\.pdf)?$").unwrap();
+        let doi = Regex::new(r"^(?i:doi:|https?://(?:dx\.)?doi\.org/)?(10\.\d{4,9}/\S+)$").unwrap();
+        let ss_id =
+            Regex::new(r"^(?:https?://(?:www\.)?semanticscholar\.org/paper/(?:[^/]+/)?)?([0-9a-f]{40})$").unwrap();
+
+        let arxiv = arxiv_url.captures(s).map_or(s, |caps| caps.get(1).unwrap().as_str());
+        if let Some(caps) = arxiv_id.captures(arxiv) {
+            return Ok(Self::ArXiv(caps[1].to_string()));
+        }
+        if let Some(caps) = doi.captures(s) {
+            return Ok(Self::Doi(caps[1].to_string()));
+        }
+        if let Some(caps) = ss_id.captures(s) {
+            return Ok(Self::SemanticScholar(caps[1].to_string()));
+        }
+        Err(anyhow!("Unrecognized paper identifier: {}", s))
+    }
+}
+
+impl fmt::Display for PaperIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ArXiv(id) => write!(f, "arXiv:{}", id),
+            Self::Doi(doi) => write!(f, "DOI:{}", doi),
+            Self::SemanticScholar(id) => write!(f, "S2:{}", id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_paper_identifier() {
+        let arxiv = PaperIdentifier::ArXiv("2301.00001".into());
+        for s in [
+            "2301.00001",
+            "2301.00001v3",
+            "arXiv:2301.00001",
+            "https://arxiv.org/abs/2301.00001v2",
+            "https://arxiv.org/pdf/2301.00001",
+            "https://arxiv.org/pdf/2301.00001v1.pdf",
+        ] {
+            assert_eq!(PaperIdentifier::from_str(s).unwrap(), arxiv, "{}", s);
+        }
+        assert_eq!(
+            PaperIdentifier::from_str("https://arxiv.org/abs/cs/0112017").unwrap(),
+            PaperIdentifier::ArXiv("cs/0112017".into())
+        );
+        assert_eq!(
+            PaperIdentifier::from_str("math.GT/0309136v1").unwrap(),
+            PaperIdentifier::ArXiv("math.GT/0309136".into())
+        );
+
+        let doi = PaperIdentifier::Doi("10.1145/3292500.3330701".into());
+        for s in [
+            "10.1145/3292500.3330701",
+            "doi:10.1145/3292500.3330701",
+            "https://doi.org/10.1145/3292500.3330701",
+        ] {
+            assert_eq!(PaperIdentifier::from_str(s).unwrap(), doi, "{}", s);
+        }
+
+        let ss = PaperIdentifier::SemanticScholar("204e3073870fae3d05bcbc2f6a8e263d9b72e776".into());
+        for s in [
+            "204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+            "https://www.semanticscholar.org/paper/Attention-is-All-you-Need-Vaswani-Shazeer/204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+        ] {
+            assert_eq!(PaperIdentifier::from_str(s).unwrap(), ss, "{}", s);
+        }
+
+        assert_eq!(
+            PaperIdentifier::from_str("http://arxiv.org/abs/2301.00001v1").unwrap(),
+            arxiv
+        );
+        assert!(PaperIdentifier::from_str("Attention Is All You Need").is_err());
+    }
+}
@@ -1 +1,2 @@
 pub mod academic_paper;
+pub mod identifier;
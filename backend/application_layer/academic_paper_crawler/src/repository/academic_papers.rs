@@ -1,21 +1,34 @@
-use crate::models::academic_paper::AcademicPaperResource;
-use anyhow::Result;
+use crate::models::{academic_paper::AcademicPaperResource, identifier::PaperIdentifier};
+use anyhow::{anyhow, Result};
 
 pub async fn get_academic_paper(title: &str, url: &str) -> Result<AcademicPaperResource> {
+    fetch_academic_paper(title, url, true).await
+}
+
+/// Fetches the metadata of a paper by its title like `get_academic_paper`, without parsing the PDF.
+pub async fn get_academic_paper_metadata(title: &str, url: &str) -> Result<AcademicPaperResource> {
+    fetch_academic_paper(title, url, false).await
+}
+
+async fn fetch_academic_paper(title: &str, url: &str, parse_pdf: bool) -> Result<AcademicPaperResource> {
     let mut paper = AcademicPaperResource {
         title: title.to_string(),
         url: url.to_string(),
         text: vec![],
         arxiv_paper: None,
         ss_paper: None,
+        doi: None,
+        pdf_sha256: None,
     };
 
-    tracing::info!("Starting to parse academic paper from URL: {}", url);
-    match paper.parse_pdf_url().await {
-        Ok(_) => tracing::info!("Successfully parsed PDF URL: {}", url),
-        Err(e) => {
-            tracing::error!("Failed to parse PDF URL {}: {}", url, e);
-            return Err(e);
+    if parse_pdf {
+        tracing::info!("Starting to parse academic paper from URL: {}", url);
+        match paper.parse_pdf_url().await {
+            Ok(_) => tracing::info!("Successfully parsed PDF URL: {}", url),
+            Err(e) => {
+                tracing::error!("Failed to parse PDF URL {}: {}", url, e);
+                return Err(e);
+            }
         }
     }
 
@@ -39,3 +52,92 @@ pub async fn get_academic_paper(title: &str, url: &str) -> Result<AcademicPaperR
 
     Ok(paper)
 }
+
+/// Fetches a paper by an exact identifier instead of guessing it from the title.
+///
+/// The PDF URL defaults to arXiv, then to the open access PDF known to Semantic Scholar.
+/// If the identifier cannot be resolved, falls back to the title search when `title` and `pdf_url` are given.
+pub async fn get_academic_paper_by_identifier(
+    identifier: &PaperIdentifier,
+    title: Option<&str>,
+    pdf_url: Option<&str>,
+) -> Result<AcademicPaperResource> {
+    fetch_academic_paper_by_identifier(identifier, title, pdf_url, true).await
+}
+
+/// Fetches the metadata of a paper like `get_academic_paper_by_identifier`, without parsing the PDF,
+/// for papers whose sections are already stored.
+pub async fn get_academic_paper_metadata_by_identifier(
+    identifier: &PaperIdentifier,
+    title: Option<&str>,
+    pdf_url: Option<&str>,
+) -> Result<AcademicPaperResource> {
+    fetch_academic_paper_by_identifier(identifier, title, pdf_url, false).await
+}
+
+async fn fetch_academic_paper_by_identifier(
+    identifier: &PaperIdentifier,
+    title: Option<&str>,
+    pdf_url: Option<&str>,
+    parse_pdf: bool,
+) -> Result<AcademicPaperResource> {
+    let mut paper = AcademicPaperResource {
+        title: String::new(),
+        url: pdf_url.unwrap_or_default().to_string(),
+        text: vec![],
+        arxiv_paper: None,
+        ss_paper: None,
+        doi: match identifier {
+            PaperIdentifier::Doi(doi) => Some(doi.clone()),
+            _ => None,
+        },
+        pdf_sha256: None,
+    };
+
+    tracing::info!("Starting to fetch data from Semantic Scholar for {}", identifier);
+    let links = match paper.fetch_ss_paper_by_id(&identifier.ss_lookup_id()).await {
+        Ok(links) => links,
+        Err(e) => {
+            tracing::warn!("Failed to fetch Semantic Scholar data for {}: {}", identifier, e);
+            Default::default()
+        }
+    };
+
+    let arxiv_id = identifier.arxiv_id().map(str::to_string).or(links.external_ids.arxiv);
+    if let Some(arxiv_id) = arxiv_id.as_deref() {
+        tracing::info!("Starting to fetch data from ArXiv for ID: {}", arxiv_id);
+        if let Err(e) = paper.fetch_arxiv_paper_by_id(arxiv_id).await {
+            tracing::warn!("Failed to fetch arXiv data for {}: {}", arxiv_id, e);
+        }
+    }
+
+    if paper.arxiv_paper.is_none() && paper.ss_paper.is_none() {
+        return match (title, pdf_url) {
+            (Some(title), Some(pdf_url)) => {
+                tracing::warn!("Could not resolve {}, falling back to the title search", identifier);
+                fetch_academic_paper(title, pdf_url, parse_pdf).await
+            }
+            _ => Err(anyhow!("Paper not found: {}", identifier)),
+        };
+    }
+    if paper.title.is_empty() {
+        paper.title = title.unwrap_or_default().to_string();
+    }
+
+    if paper.url.is_empty() {
+        paper.url = match (paper.arxiv_paper.as_ref(), arxiv_id) {
+            (Some(p), _) if !p.pdf_url.is_empty() => p.pdf_url.clone(),
+            (_, Some(arxiv_id)) => format!("https://arxiv.org/pdf/{}", arxiv_id),
+            _ => links
+                .open_access_pdf
+                .and_then(|pdf| pdf.url)
+                .ok_or_else(|| anyhow!("No PDF available for {}, specify the PDF URL", identifier))?,
+        };
+    }
+    if parse_pdf {
+        tracing::info!("Starting to parse academic paper from URL: {}", paper.url);
+        paper.parse_pdf_url().await?;
+    }
+
+    Ok(paper)
+}
@@ -0,0 +1,155 @@
+use crate::models::identifier::PaperIdentifier;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use kernel::models::paper_candidate::PaperCandidate;
+use regex::Regex;
+use std::str::FromStr;
+
+pub(crate) const ARXIV_API_URL: &str = "http://export.arxiv.org/api/query";
+
+/// Lists the latest submissions to an arXiv category (e.g. `cs.CL`), newest first.
+pub async fn list_new_submissions(category: &str, max_results: usize) -> Result<Vec<PaperCandidate>> {
+    let response = request::Client::new()
+        .get(ARXIV_API_URL)
+        .query(&[
+            ("search_query", format!("cat:{}", category)),
+            ("sortBy", "submittedDate".to_string()),
+            ("sortOrder", "descending".to_string()),
+            ("max_results", max_results.to_string()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?;
+    let candidates = parse_arxiv_feed(&response.text().await?);
+    tracing::info!("Found {} submissions in {}", candidates.len(), category);
+    Ok(candidates)
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Collapses the line breaks and indentation that the API inserts into long titles and abstracts.
+fn normalize_text(text: &str) -> String {
+    unescape_xml(&text.split_whitespace().collect::<Vec<&str>>().join(" "))
+}
+
+/// Parses the Atom feed returned by the arXiv API into candidates.
+pub fn parse_arxiv_feed(xml: &str) -> Vec<PaperCandidate> {
+    let entry_re = Regex::new(r"(?s)<entry>(.*?)</entry>").unwrap();
+    let tag_re = |tag: &str| Regex::new(&format!(r"(?s)<{tag}[^>]*>(.*?)</{tag}>")).unwrap();
+    let (id_re, title_re, summary_re, published_re) =
+        (tag_re("id"), tag_re("title"), tag_re("summary"), tag_re("published"));
+    let author_re = Regex::new(r"(?s)<author>\s*<name>(.*?)</name>").unwrap();
+    let primary_category_re = Regex::new(r#"<arxiv:primary_category[^>]*term="([^"]+)""#).unwrap();
+    let category_re = Regex::new(r#"<category[^>]*term="([^"]+)""#).unwrap();
+    let pdf_link_re = Regex::new(r#"<link[^>]*title="pdf"[^>]*>"#).unwrap();
+    let href_re = Regex::new(r#"href="([^"]+)""#).unwrap();
+
+    let capture = |re: &Regex, text: &str| re.captures(text).map(|caps| normalize_text(&caps[1]));
+    entry_re
+        .captures_iter(xml)
+        .filter_map(|caps| {
+            let entry = caps.get(1).unwrap().as_str();
+            let arxiv_id = match PaperIdentifier::from_str(&capture(&id_re, entry)?).ok()? {
+                PaperIdentifier::ArXiv(id) => id,
+                _ => return None,
+            };
+            let mut categories = capture(&primary_category_re, entry)
+                .into_iter()
+                .collect::<Vec<String>>();
+            for caps in category_re.captures_iter(entry) {
+                if !categories.iter().any(|c| c == &caps[1]) {
+                    categories.push(caps[1].to_string());
+                }
+            }
+            let pdf_url = pdf_link_re
+                .find(entry)
+                .and_then(|link| capture(&href_re, link.as_str()))
+                .unwrap_or_else(|| format!("https://arxiv.org/pdf/{}", arxiv_id));
+            let published_date = capture(&published_re, entry)
+                .and_then(|date| DateTime::parse_from_rfc3339(&date).ok())
+                .map_or_else(Local::now, |date| date.with_timezone(&Local));
+            Some(PaperCandidate {
+                arxiv_id,
+                title: capture(&title_re, entry).unwrap_or_default(),
+                abstract_text: capture(&summary_re, entry).unwrap_or_default(),
+                authors: author_re
+                    .captures_iter(entry)
+                    .map(|caps| normalize_text(&caps[1]))
+                    .collect(),
+                categories,
+                pdf_url,
+                published_date,
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_arxiv_feed() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>http://arxiv.org/api/cHxbiOdZaP56ODnBPIenZhzg5f8</id>
+  <title type="html">ArXiv Query: search_query=cat:cs.CL</title>
+  <entry>
+    <id>http://arxiv.org/abs/2510.12345v2</id>
+    <updated>2025-10-18T10:00:00Z</updated>
+    <published>2025-10-17T17:59:59Z</published>
+    <title>Retrieval &amp; Reasoning:
+  A Study of Long-Context LLMs</title>
+    <summary>  We study retrieval
+and reasoning.
+</summary>
+    <author>
+      <name>Ada Lovelace</name>
+      <arxiv:affiliation xmlns:arxiv="http://arxiv.org/schemas/atom">Analytical Engines</arxiv:affiliation>
+    </author>
+    <author>
+      <name>Alan Turing</name>
+    </author>
+    <link href="http://arxiv.org/abs/2510.12345v2" rel="alternate" type="text/html"/>
+    <link title="pdf" href="http://arxiv.org/pdf/2510.12345v2" rel="related" type="application/pdf"/>
+    <arxiv:primary_category xmlns:arxiv="http://arxiv.org/schemas/atom" term="cs.CL" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="cs.CL" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="cs.AI" scheme="http://arxiv.org/schemas/atom"/>
+  </entry>
+  <entry>
+    <id>http://arxiv.org/abs/2510.00001v1</id>
+    <published>2025-10-16T08:00:00Z</published>
+    <title>Another Paper</title>
+    <summary>Abstract.</summary>
+    <author><name>Grace Hopper</name></author>
+    <category term="cs.LG" scheme="http://arxiv.org/schemas/atom"/>
+  </entry>
+</feed>"#;
+        let candidates = parse_arxiv_feed(xml);
+        assert_eq!(candidates.len(), 2);
+
+        let first = &candidates[0];
+        assert_eq!(first.arxiv_id, "2510.12345");
+        assert_eq!(first.title, "Retrieval & Reasoning: A Study of Long-Context LLMs");
+        assert_eq!(first.abstract_text, "We study retrieval and reasoning.");
+        assert_eq!(first.authors, vec!["Ada Lovelace", "Alan Turing"]);
+        assert_eq!(first.categories, vec!["cs.CL", "cs.AI"]);
+        assert_eq!(first.pdf_url, "http://arxiv.org/pdf/2510.12345v2");
+        assert_eq!(
+            first.published_date,
+            DateTime::parse_from_rfc3339("2025-10-17T17:59:59Z").unwrap()
+        );
+
+        let second = &candidates[1];
+        assert_eq!(second.arxiv_id, "2510.00001");
+        assert_eq!(second.categories, vec!["cs.LG"]);
+        assert_eq!(second.pdf_url, "https://arxiv.org/pdf/2510.00001");
+    }
+}
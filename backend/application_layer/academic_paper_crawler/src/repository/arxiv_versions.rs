@@ -0,0 +1,120 @@
+use crate::{models::identifier::PaperIdentifier, repository::arxiv_listing::ARXIV_API_URL};
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use derive_new::new;
+use kernel::models::paper_version::arxiv_version;
+use regex::Regex;
+use std::str::FromStr;
+
+/// The number of IDs per request to the arXiv API.
+pub const ARXIV_ID_BATCH_SIZE: usize = 100;
+
+/// The latest version of a paper on arXiv.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct ArxivVersion {
+    /// The arXiv ID without the version.
+    pub arxiv_id: String,
+    pub version: i32,
+    /// When the first version was submitted.
+    pub published_at: Option<DateTime<Local>>,
+    /// When the latest version was submitted.
+    pub updated_at: Option<DateTime<Local>>,
+}
+
+impl ArxivVersion {
+    /// The abs URL of the version, as `arxiv_id` is stored.
+    pub fn abs_url(&self) -> String {
+        format!("http://arxiv.org/abs/{}v{}", self.arxiv_id, self.version)
+    }
+
+    pub fn pdf_url(&self) -> String {
+        format!("https://arxiv.org/pdf/{}v{}", self.arxiv_id, self.version)
+    }
+}
+
+/// Looks up the latest versions of the papers by their arXiv IDs (without the version), at most
+/// `ARXIV_ID_BATCH_SIZE` at a time. The IDs that arXiv does not know are left out.
+pub async fn fetch_latest_versions(arxiv_ids: &[String]) -> Result<Vec<ArxivVersion>> {
+    let response = request::Client::new()
+        .get(ARXIV_API_URL)
+        .query(&[
+            ("id_list", arxiv_ids.join(",")),
+            ("max_results", arxiv_ids.len().to_string()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(parse_arxiv_versions(&response.text().await?))
+}
+
+/// Parses the versions of the entries of an Atom feed returned by the arXiv API.
+pub fn parse_arxiv_versions(xml: &str) -> Vec<ArxivVersion> {
+    let entry_re = Regex::new(r"(?s)<entry>(.*?)</entry>").unwrap();
+    let tag_re = |tag: &str| Regex::new(&format!(r"(?s)<{tag}[^>]*>(.*?)</{tag}>")).unwrap();
+    let (id_re, published_re, updated_re) = (tag_re("id"), tag_re("published"), tag_re("updated"));
+    let date = |re: &Regex, entry: &str| {
+        re.captures(entry)
+            .and_then(|caps| DateTime::parse_from_rfc3339(caps[1].trim()).ok())
+            .map(|date| date.with_timezone(&Local))
+    };
+
+    entry_re
+        .captures_iter(xml)
+        .filter_map(|caps| {
+            let entry = caps.get(1).unwrap().as_str();
+            let id = id_re.captures(entry)?[1].trim().to_string();
+            let arxiv_id = match PaperIdentifier::from_str(&id).ok()? {
+                PaperIdentifier::ArXiv(arxiv_id) => arxiv_id,
+                _ => return None,
+            };
+            Some(ArxivVersion::new(
+                arxiv_id,
+                arxiv_version(&id)?,
+                date(&published_re, entry),
+                date(&updated_re, entry),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_arxiv_versions() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>http://arxiv.org/api/Xg7Hgx1JoS1i3sN8f4sk3dSIhsM</id>
+  <updated>2025-10-19T00:00:00-04:00</updated>
+  <entry>
+    <id>http://arxiv.org/abs/2106.09685v2</id>
+    <updated>2021-10-16T21:48:05Z</updated>
+    <published>2021-06-17T17:37:18Z</published>
+    <title>LoRA: Low-Rank Adaptation of Large Language Models</title>
+  </entry>
+  <entry>
+    <id>http://arxiv.org/abs/cs/0112017v1</id>
+    <updated>2001-12-13T00:00:00Z</updated>
+    <published>2001-12-13T00:00:00Z</published>
+    <title>An Old Paper</title>
+  </entry>
+</feed>"#;
+        let versions = parse_arxiv_versions(xml);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].arxiv_id, "2106.09685");
+        assert_eq!(versions[0].version, 2);
+        assert_eq!(
+            versions[0].updated_at,
+            Some(
+                DateTime::parse_from_rfc3339("2021-10-16T21:48:05Z")
+                    .unwrap()
+                    .with_timezone(&Local)
+            )
+        );
+        assert_eq!(versions[0].abs_url(), "http://arxiv.org/abs/2106.09685v2");
+        assert_eq!(versions[0].pdf_url(), "https://arxiv.org/pdf/2106.09685v2");
+        assert_eq!(versions[1].arxiv_id, "cs/0112017");
+        assert_eq!(versions[1].version, 1);
+    }
+}
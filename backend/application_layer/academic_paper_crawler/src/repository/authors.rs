@@ -0,0 +1,32 @@
+use crate::models::academic_paper::ss_graph_api_post;
+use anyhow::Result;
+use serde::Deserialize;
+
+/// The batch endpoint accepts up to 1,000 IDs per request.
+const SS_AUTHOR_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsAuthorProfile {
+    pub author_id: String,
+    pub name: Option<String>,
+    pub h_index: Option<i32>,
+}
+
+/// Fetches the current profiles of Semantic Scholar authors; unknown IDs are left out.
+pub async fn fetch_ss_authors(ss_ids: &[String]) -> Result<Vec<SsAuthorProfile>> {
+    let mut profiles = vec![];
+    for ids in ss_ids.chunks(SS_AUTHOR_BATCH_SIZE) {
+        let page = ss_graph_api_post("author/batch")
+            .query(&[("fields", "authorId,name,hIndex")])
+            .json(&serde_json::json!({ "ids": ids }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<Option<SsAuthorProfile>>>()
+            .await?;
+        profiles.extend(page.into_iter().flatten());
+    }
+    tracing::info!("Fetched {} of {} authors", profiles.len(), ss_ids.len());
+    Ok(profiles)
+}
@@ -0,0 +1,151 @@
+use crate::models::academic_paper::ss_graph_api_request;
+use anyhow::{anyhow, Result};
+use chrono::Datelike;
+use kernel::models::{
+    academic_paper::AcademicPaper,
+    citation::{CitationEdge, CitationGraph, CitationNode},
+};
+use serde::Deserialize;
+
+const SS_CITATION_PAGE_SIZE: usize = 1000;
+/// The Graph API does not page past 10,000 references or citations of a paper.
+const SS_MAX_CITATIONS: usize = 10_000;
+
+#[derive(Debug, Deserialize)]
+struct SsCitationPage {
+    data: Vec<SsCitation>,
+    next: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SsCitation {
+    /// Set on the `references` endpoint.
+    cited_paper: Option<SsCitedPaper>,
+    /// Set on the `citations` endpoint.
+    citing_paper: Option<SsCitedPaper>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SsCitedPaper {
+    /// `null` for references that Semantic Scholar could not resolve to a paper.
+    paper_id: Option<String>,
+    title: Option<String>,
+    year: Option<i32>,
+}
+
+impl SsCitedPaper {
+    fn into_node(self) -> Option<CitationNode> {
+        let ss_id = self.paper_id?;
+        Some(CitationNode::new(
+            ss_id,
+            self.title.unwrap_or_default(),
+            self.year,
+            None,
+        ))
+    }
+}
+
+/// Fetches the papers linked to `ss_id` through `endpoint` (`references` or `citations`).
+async fn fetch_linked_papers(ss_id: &str, endpoint: &str) -> Result<Vec<CitationNode>> {
+    let mut nodes = vec![];
+    let mut offset = 0;
+    while offset < SS_MAX_CITATIONS {
+        let limit = SS_CITATION_PAGE_SIZE.min(SS_MAX_CITATIONS - offset);
+        let page = ss_graph_api_request(&format!("paper/{}/{}", ss_id, endpoint))
+            .query(&[
+                ("fields", "paperId,title,year".to_string()),
+                ("offset", offset.to_string()),
+                ("limit", limit.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<SsCitationPage>()
+            .await?;
+        nodes.extend(
+            page.data
+                .into_iter()
+                .filter_map(|citation| citation.cited_paper.or(citation.citing_paper))
+                .filter_map(SsCitedPaper::into_node),
+        );
+        match page.next {
+            Some(next) => offset = next,
+            None => break,
+        }
+    }
+    Ok(nodes)
+}
+
+/// Fetches the references and citations of a paper from Semantic Scholar as a graph around it.
+pub async fn fetch_citation_graph(paper: &AcademicPaper) -> Result<CitationGraph> {
+    if paper.ss_id.is_empty() {
+        return Err(anyhow!("{} has no Semantic Scholar ID", paper.title));
+    }
+    let references = fetch_linked_papers(&paper.ss_id, "references").await?;
+    let citations = fetch_linked_papers(&paper.ss_id, "citations").await?;
+    tracing::info!(
+        "Fetched {} references and {} citations of {}",
+        references.len(),
+        citations.len(),
+        paper.ss_id
+    );
+
+    let year = Some(paper.published_date.year()).filter(|year| *year > 1970);
+    let mut graph = CitationGraph::new(
+        vec![CitationNode::new(
+            paper.ss_id.clone(),
+            paper.title.clone(),
+            year,
+            Some(paper.paper_id),
+        )],
+        vec![],
+    );
+    for node in references.into_iter() {
+        graph
+            .edges
+            .push(CitationEdge::new(paper.ss_id.clone(), node.ss_id.clone()));
+        graph.nodes.push(node);
+    }
+    for node in citations.into_iter() {
+        graph
+            .edges
+            .push(CitationEdge::new(node.ss_id.clone(), paper.ss_id.clone()));
+        graph.nodes.push(node);
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_citation_page() {
+        let body = r#"{
+            "offset": 0,
+            "next": 2,
+            "data": [
+                {"citedPaper": {"paperId": "abc", "title": "Attention Is All You Need", "year": 2017}},
+                {"citedPaper": {"paperId": null, "title": "An unresolved reference", "year": null}},
+                {"citingPaper": {"paperId": "def", "title": null, "year": null}}
+            ]
+        }"#;
+        let page = serde_json::from_str::<SsCitationPage>(body).unwrap();
+        assert_eq!(page.next, Some(2));
+        let nodes = page
+            .data
+            .into_iter()
+            .filter_map(|citation| citation.cited_paper.or(citation.citing_paper))
+            .filter_map(SsCitedPaper::into_node)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            nodes,
+            vec![
+                CitationNode::new("abc".into(), "Attention Is All You Need".into(), Some(2017), None),
+                CitationNode::new("def".into(), "".into(), None, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::models::academic_paper::{ss_graph_api_post, SsExternalIds};
+use anyhow::Result;
+use kernel::models::{
+    paper_metadata::PaperMetadata,
+    venue::{normalize_venue, VenueType},
+};
+use serde::Deserialize;
+
+/// The paper batch endpoint accepts up to 500 IDs per request.
+pub const SS_PAPER_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct SsVenue {
+    name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SsPaperMetadata {
+    paper_id: String,
+    #[serde(default)]
+    external_ids: Option<SsExternalIds>,
+    journal: Option<SsVenue>,
+    publication_venue: Option<SsVenue>,
+    venue: Option<String>,
+    citation_count: Option<i32>,
+    influential_citation_count: Option<i32>,
+    reference_count: Option<i32>,
+}
+
+fn is_preprint_venue(name: &str) -> bool {
+    name.trim().is_empty() || normalize_venue(name).venue_type == VenueType::Preprint
+}
+
+impl SsPaperMetadata {
+    /// Where the paper was published, ignoring arXiv, which Semantic Scholar also reports as a journal.
+    fn venue_name(&self) -> Option<String> {
+        [
+            self.journal.as_ref().and_then(|journal| journal.name.clone()),
+            self.publication_venue.as_ref().and_then(|venue| venue.name.clone()),
+            self.venue.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|name| name.trim().to_string())
+        .find(|name| !is_preprint_venue(name))
+    }
+
+    /// `current` updated with the fetched metadata. Known values are never replaced by missing ones,
+    /// and a venue is never replaced by arXiv. The venue is normalized like the venues of added papers.
+    pub fn apply_to(&self, current: &PaperMetadata) -> PaperMetadata {
+        let doi = self.external_ids.as_ref().and_then(|ids| ids.doi.clone());
+        PaperMetadata {
+            ss_id: if current.ss_id.is_empty() {
+                self.paper_id.clone()
+            } else {
+                current.ss_id.clone()
+            },
+            doi: match doi {
+                Some(doi) if current.doi.is_empty() => doi,
+                _ => current.doi.clone(),
+            },
+            journal_name: self
+                .venue_name()
+                .map_or_else(|| current.journal_name.clone(), |name| normalize_venue(&name).name),
+            citations_count: self.citation_count.unwrap_or(current.citations_count),
+            influential_citation_count: self
+                .influential_citation_count
+                .unwrap_or(current.influential_citation_count),
+            references_count: self.reference_count.unwrap_or(current.references_count),
+            ..current.clone()
+        }
+    }
+}
+
+/// Fetches the current metadata of papers by their Semantic Scholar lookup IDs, in the same order;
+/// `None` for the papers Semantic Scholar does not know.
+pub async fn fetch_ss_paper_metadata(lookup_ids: &[String]) -> Result<Vec<Option<SsPaperMetadata>>> {
+    let mut papers = vec![];
+    for ids in lookup_ids.chunks(SS_PAPER_BATCH_SIZE) {
+        let page = ss_graph_api_post("paper/batch")
+            .query(&[(
+                "fields",
+                "paperId,externalIds,journal,publicationVenue,venue,citationCount,influentialCitationCount,referenceCount",
+            )])
+            .json(&serde_json::json!({ "ids": ids }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<Option<SsPaperMetadata>>>()
+            .await?;
+        papers.extend(page);
+    }
+    tracing::info!(
+        "Fetched the metadata of {} of {} papers",
+        papers.iter().flatten().count(),
+        lookup_ids.len()
+    );
+    Ok(papers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_to() {
+        let body = r#"[
+            {"paperId": "abc", "externalIds": {"ArXiv": "2106.09685", "DOI": "10.48550/arXiv.2106.09685"},
+             "journal": {"name": "ArXiv"}, "publicationVenue": {"name": "International Conference on Learning Representations"},
+             "venue": "ICLR", "citationCount": 15, "influentialCitationCount": 3, "referenceCount": 40},
+            {"paperId": "def", "journal": null, "venue": "arXiv.org", "citationCount": null},
+            null
+        ]"#;
+        let papers = serde_json::from_str::<Vec<Option<SsPaperMetadata>>>(body).unwrap();
+        let current = PaperMetadata {
+            arxiv_id: "http://arxiv.org/abs/2106.09685v2".into(),
+            journal_name: "arXiv".into(),
+            citations_count: 10,
+            ..Default::default()
+        };
+
+        let published = papers[0].as_ref().unwrap().apply_to(&current);
+        assert_eq!(published.ss_id, "abc");
+        assert_eq!(published.doi, "10.48550/arXiv.2106.09685");
+        assert_eq!(published.journal_name, "ICLR");
+        assert_eq!(published.citations_count, 15);
+        assert_eq!(published.references_count, 40);
+
+        let preprint = papers[1].as_ref().unwrap().apply_to(&current);
+        assert_eq!(preprint.journal_name, "arXiv");
+        assert_eq!(preprint.citations_count, 10);
+        assert!(papers[2].is_none());
+    }
+}
@@ -1 +1,7 @@
 pub mod academic_papers;
+pub mod arxiv_listing;
+pub mod arxiv_versions;
+pub mod authors;
+pub mod citations;
+pub mod metadata;
+pub mod pdf_store;
@@ -0,0 +1,97 @@
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+/// The hex-encoded SHA-256 of `bytes`, which is the address of a PDF in the store.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// A local store of the source PDFs, addressed by the SHA-256 of their content,
+/// so that the same PDF is kept only once however many times it is fetched.
+#[derive(Debug, Clone)]
+pub struct PdfStore {
+    root: PathBuf,
+}
+
+impl PdfStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The store under `PDF_STORE_DIR`, `pdfs` by default.
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("PDF_STORE_DIR").unwrap_or_else(|_| "pdfs".to_string()))
+    }
+
+    /// `<root>/<first two hex digits>/<sha256>.pdf`, so that no directory grows too large.
+    pub fn path_of(&self, sha256: &str) -> PathBuf {
+        self.root
+            .join(sha256.get(..2).unwrap_or("00"))
+            .join(format!("{}.pdf", sha256))
+    }
+
+    pub fn contains(&self, sha256: &str) -> bool {
+        self.path_of(sha256).is_file()
+    }
+
+    /// Stores `bytes` unless the same content is already stored, and returns its SHA-256.
+    pub fn put(&self, bytes: &[u8]) -> Result<String> {
+        let sha256 = sha256_hex(bytes);
+        let path = self.path_of(&sha256);
+        if !path.is_file() {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            // Written through a temporary file, so that an interruption never leaves a truncated PDF
+            let tmp = path.with_extension("pdf.tmp");
+            std::fs::write(&tmp, bytes)?;
+            std::fs::rename(&tmp, &path)?;
+        }
+        Ok(sha256)
+    }
+
+    pub fn get(&self, sha256: &str) -> Result<Vec<u8>> {
+        let path = self.path_of(sha256);
+        std::fs::read(&path).map_err(|e| anyhow!("Failed to read the stored PDF {}: {}", path.display(), e))
+    }
+
+    /// Downloads the PDF at `url` into the store and returns its SHA-256.
+    pub async fn download(&self, url: &str) -> Result<String> {
+        let bytes = request::Client::new()
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        if !bytes.starts_with(b"%PDF") {
+            return Err(anyhow!("Not a PDF: {}", url));
+        }
+        let sha256 = self.put(&bytes)?;
+        tracing::info!("Stored the PDF at {} as {}", url, sha256);
+        Ok(sha256)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdf_store() {
+        let store = PdfStore::new(std::env::temp_dir().join(format!("pdf-store-{}", std::process::id())));
+        let sha256 = store.put(b"%PDF-1.5 test").unwrap();
+        assert_eq!(sha256, sha256_hex(b"%PDF-1.5 test"));
+        assert_eq!(sha256.len(), 64);
+        assert!(store
+            .path_of(&sha256)
+            .ends_with(format!("{}/{}.pdf", &sha256[..2], sha256)));
+        assert!(store.contains(&sha256));
+        // The same content is stored once
+        assert_eq!(store.put(b"%PDF-1.5 test").unwrap(), sha256);
+        assert_eq!(store.get(&sha256).unwrap(), b"%PDF-1.5 test");
+        assert!(!store.contains(&sha256_hex(b"other")));
+        std::fs::remove_dir_all(store.root).unwrap();
+    }
+}
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{header::ACCEPT_LANGUAGE, request::Parts},
+};
+use kernel::models::language::Language;
+use serde::Deserialize;
+use std::{convert::Infallible, str::FromStr};
+
+#[derive(Debug, Deserialize)]
+struct LanguageQuery {
+    lang: Option<String>,
+}
+
+/// The language the caller wants generated content in.
+///
+/// Resolved from the `lang` query parameter, then the `Accept-Language` header, then `OUTPUT_LANGUAGE`.
+#[derive(Debug, Clone, Copy)]
+pub struct PreferredLanguage(pub Language);
+
+impl<S: Send + Sync> FromRequestParts<S> for PreferredLanguage {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let from_query = Query::<LanguageQuery>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|Query(query)| query.lang)
+            .and_then(|lang| Language::from_str(&lang).ok());
+        let from_header = || {
+            parts
+                .headers
+                .get(ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(Language::from_accept_language)
+        };
+        Ok(Self(from_query.or_else(from_header).unwrap_or_else(Language::from_env)))
+    }
+}
@@ -1,23 +1,45 @@
-use crate::models::academic_paper::{
-    AcademicPaperCreateRequest, AcademicPaperIdQuery, AcademicPaperListQuery, AcademicPaperListResponse,
-    AcademicPaperResponse, AcademicPaperUpdateRequest,
+use crate::{
+    extractor::PreferredLanguage,
+    handler::citation::save_citation_graph,
+    models::academic_paper::{
+        AcademicPaperCreateRequest, AcademicPaperIdQuery, AcademicPaperListQuery, AcademicPaperListResponse,
+        AcademicPaperResponse, AcademicPaperUpdateRequest, PaperSectionResponse, PaperSectionsResponse,
+    },
+};
+use academic_paper_crawler::{
+    models::{academic_paper::AcademicPaperResource, identifier::PaperIdentifier},
+    repository::{
+        academic_papers::{
+            get_academic_paper, get_academic_paper_by_identifier, get_academic_paper_metadata,
+            get_academic_paper_metadata_by_identifier,
+        },
+        pdf_store::PdfStore,
+    },
 };
-use academic_paper_crawler::repository::academic_papers::get_academic_paper;
 use axum::{
     extract::{Json, Query, State},
-    response::sse::{Event, KeepAlive, Sse},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
 };
 use garde::Validate;
-use kernel::models::academic_paper::AcademicPaper;
+use kernel::models::{academic_paper::AcademicPaper, language::Language};
 use registry::AppRegistry;
 use shared::errors::{AppError, AppResult};
 use shared::id::AcademicPaperId;
+use std::str::FromStr;
 use tokio::sync::mpsc;
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
 
 pub async fn select_paginated_academic_papers(
     State(registry): State<AppRegistry>,
     Query(query): Query<AcademicPaperListQuery>,
+    PreferredLanguage(language): PreferredLanguage,
 ) -> AppResult<Json<AcademicPaperListResponse>> {
     query.validate()?;
 
@@ -26,7 +48,7 @@ pub async fn select_paginated_academic_papers(
         .academic_paper_repository()
         .select_paginated_academic_papers(&mut tx, query.into())
         .await
-        .map(AcademicPaperListResponse::from)
+        .map(|papers| AcademicPaperListResponse::from((papers, language)))
         .map(Json);
     tx.commit().await?;
     result
@@ -35,39 +57,117 @@ pub async fn select_paginated_academic_papers(
 pub async fn select_academic_papers_by_id(
     State(registry): State<AppRegistry>,
     Query(query): Query<AcademicPaperIdQuery>,
+    PreferredLanguage(language): PreferredLanguage,
 ) -> AppResult<Json<AcademicPaperResponse>> {
     let mut tx = registry.db().inner_ref().begin().await?;
     let result = registry
         .academic_paper_repository()
         .select_academic_paper_by_id(&mut tx, query.paper_id.as_str())
         .await
-        .map(AcademicPaperResponse::from)
+        .map(|paper| AcademicPaperResponse::from((paper, language)))
         .map(Json);
     tx.commit().await?;
     result
 }
 
+/// The sections of a paper as parsed from its PDF, in order.
+pub async fn select_paper_sections(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<AcademicPaperIdQuery>,
+) -> AppResult<Json<PaperSectionsResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let paper = registry
+        .academic_paper_repository()
+        .select_academic_paper_by_id(&mut tx, query.paper_id.as_str())
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperSectionsResponse::new(
+        paper.paper_id,
+        !paper.pdf_sha256.is_empty(),
+        paper.sections.into_iter().map(PaperSectionResponse::from).collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// The source PDF of a paper from the local PDF store.
+pub async fn download_paper_pdf(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<AcademicPaperIdQuery>,
+) -> AppResult<impl IntoResponse> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let paper = registry
+        .academic_paper_repository()
+        .select_academic_paper_by_id(&mut tx, query.paper_id.as_str())
+        .await?;
+    tx.commit().await?;
+
+    let store = PdfStore::from_env();
+    if paper.pdf_sha256.is_empty() || !store.contains(&paper.pdf_sha256) {
+        return Err(AppError::EntityNotFound(format!(
+            "The PDF of {} is not stored",
+            paper.title
+        )));
+    }
+    let bytes = store.get(&paper.pdf_sha256)?;
+    Ok((
+        [
+            (CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("inline; filename=\"{}.pdf\"", paper.paper_id),
+            ),
+        ],
+        bytes,
+    ))
+}
+
+/// Fetches the paper by its identifier if given, otherwise by searching for the title.
+async fn get_academic_paper_resource(query: &AcademicPaperCreateRequest) -> AppResult<AcademicPaperResource> {
+    let resource = match (&query.identifier, &query.title, &query.pdf_url) {
+        (Some(identifier), title, pdf_url) => {
+            let identifier =
+                PaperIdentifier::from_str(identifier).map_err(|e| AppError::Unprocessable(e.to_string()))?;
+            get_academic_paper_by_identifier(&identifier, title.as_deref(), pdf_url.as_deref()).await
+        }
+        (None, Some(title), Some(pdf_url)) => get_academic_paper(title, pdf_url).await,
+        _ => {
+            return Err(AppError::Unprocessable(
+                "Either identifier or both title and pdf_url are required".into(),
+            ))
+        }
+    };
+    resource.map_err(AppError::from)
+}
+
 pub async fn add_academic_paper(
     State(registry): State<AppRegistry>,
     Json(query): Json<AcademicPaperCreateRequest>,
 ) -> AppResult<Json<AcademicPaperResponse>> {
     query.validate()?;
+    let language = query.language.unwrap_or_else(Language::from_env);
 
     // This function is a placeholder for adding an academic paper
     tracing::info!("Adding academic paper...");
 
     // crawler
-    let paper_rsc = get_academic_paper(&query.title, &query.pdf_url).await.map_err(|e| {
-        tracing::error!("Failed to get academic paper by URL: {}", e);
-        AppError::from(e)
+    let paper_rsc = get_academic_paper_resource(&query).await.map_err(|e| {
+        tracing::error!("Failed to get academic paper: {}", e);
+        e
     })?;
 
     // kernel
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let vocabulary = registry.task_repository().select_task_vocabulary(&mut tx).await?;
+    tx.commit().await?;
     let mut paper = AcademicPaper::from(paper_rsc);
-    paper.fill_fields_with_ai().await.map_err(|e| {
-        tracing::error!("Failed to fill fields with AI: {}", e);
-        AppError::from(e)
-    })?;
+    paper
+        .fill_fields_with_ai(registry.llm_provider().as_ref(), language, vocabulary)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fill fields with AI: {}", e);
+            e
+        })?;
     paper.fill_bibtex().map_err(|e| {
         tracing::error!("Failed to fill BibTeX: {}", e);
         AppError::from(e)
@@ -78,16 +178,19 @@ pub async fn add_academic_paper(
     tracing::info!("ArXiv ID: {}", paper.arxiv_id);
     tracing::info!("Semantic Scholar ID: {}", paper.ss_id);
     tracing::info!("Title: {}", paper.title);
-    tracing::info!("Abstract: {}", paper.abstract_text_ja);
-    tracing::info!("Summary: {}", paper.summary);
-    tracing::info!("Background and Purpose: {}", paper.background_and_purpose);
-    tracing::info!("Methodology: {}", paper.methodology);
-    tracing::info!("Dataset: {}", paper.dataset);
-    tracing::info!("Results: {}", paper.results);
-    tracing::info!(
-        "Advantages, Limitations, and Future Work: {}",
-        paper.advantages_limitations_and_future_work
-    );
+    if let Some(analysis) = paper.translation(language) {
+        tracing::info!("Language: {}", analysis.language);
+        tracing::info!("Abstract: {}", analysis.abstract_text);
+        tracing::info!("Summary: {}", analysis.summary);
+        tracing::info!("Background and Purpose: {}", analysis.background_and_purpose);
+        tracing::info!("Methodology: {}", analysis.methodology);
+        tracing::info!("Dataset: {}", analysis.dataset);
+        tracing::info!("Results: {}", analysis.results);
+        tracing::info!(
+            "Advantages, Limitations, and Future Work: {}",
+            analysis.advantages_limitations_and_future_work
+        );
+    }
     tracing::info!("BibTeX: {}", paper.bibtex);
     tracing::info!("PDF URL: {}", paper.url);
     tracing::info!("Published Date: {}", paper.published_date);
@@ -98,19 +201,22 @@ pub async fn add_academic_paper(
     tracing::info!("Primary Category: {}", paper.primary_category);
 
     let mut tx = registry.db().inner_ref().begin().await?;
-    let _ = registry
+    let created = registry
         .academic_paper_repository()
         .create_academic_paper(&mut tx, paper.clone())
         .await?;
     tx.commit().await?;
+    save_citation_graph(&registry, &created).await;
 
-    Ok(Json(AcademicPaperResponse::from(paper)))
+    // the paper already in the library if it was added before
+    Ok(Json(AcademicPaperResponse::from((created, language))))
 }
 
 pub async fn add_academic_paper_with_sse(
     State(registry): State<AppRegistry>,
     Query(query): Query<AcademicPaperCreateRequest>,
 ) -> Sse<impl futures_core::Stream<Item = AppResult<Event>>> {
+    let language = query.language.unwrap_or_else(Language::from_env);
     let (stx, srx) = mpsc::channel::<(usize, String, Option<AcademicPaper>)>(1);
 
     tokio::spawn(async move {
@@ -119,7 +225,19 @@ pub async fn add_academic_paper_with_sse(
 
         // Send initial status
         if stx
-            .send((10, format!("Starting academic paper addition: {}", query.title), None))
+            .send((
+                10,
+                format!(
+                    "Starting academic paper addition: {}",
+                    query
+                        .identifier
+                        .as_ref()
+                        .or(query.title.as_ref())
+                        .cloned()
+                        .unwrap_or_default()
+                ),
+                None,
+            ))
             .await
             .is_err()
         {
@@ -128,7 +246,7 @@ pub async fn add_academic_paper_with_sse(
         }
 
         // crawler
-        let paper_rsc = match get_academic_paper(&query.title, &query.pdf_url).await {
+        let paper_rsc = match get_academic_paper_resource(&query).await {
             Ok(paper) => {
                 tracing::info!("Successfully retrieved academic paper");
                 if stx
@@ -147,13 +265,19 @@ pub async fn add_academic_paper_with_sse(
             }
             Err(err) => {
                 tracing::error!("Failed to get academic paper: {}", err);
-                return Err(AppError::from(err));
+                return Err(err);
             }
         };
 
         // kernel
+        let mut tx = registry.db().inner_ref().begin().await?;
+        let vocabulary = registry.task_repository().select_task_vocabulary(&mut tx).await?;
+        tx.commit().await?;
         let mut paper = AcademicPaper::from(paper_rsc);
-        match paper.fill_fields_with_ai().await {
+        match paper
+            .fill_fields_with_ai(registry.llm_provider().as_ref(), language, vocabulary)
+            .await
+        {
             Ok(_) => {
                 tracing::info!("Successfully filled fields with AI");
                 if stx
@@ -167,7 +291,7 @@ pub async fn add_academic_paper_with_sse(
             }
             Err(err) => {
                 tracing::error!("Failed to fill fields with AI: {}", err);
-                return Err(AppError::from(err));
+                return Err(err);
             }
         }
         match paper.fill_bibtex() {
@@ -193,16 +317,19 @@ pub async fn add_academic_paper_with_sse(
         tracing::info!("ArXiv ID: {}", paper.arxiv_id);
         tracing::info!("Semantic Scholar ID: {}", paper.ss_id);
         tracing::info!("Title: {}", paper.title);
-        tracing::info!("Abstract: {}", paper.abstract_text_ja);
-        tracing::info!("Summary: {}", paper.summary);
-        tracing::info!("Background and Purpose: {}", paper.background_and_purpose);
-        tracing::info!("Methodology: {}", paper.methodology);
-        tracing::info!("Dataset: {}", paper.dataset);
-        tracing::info!("Results: {}", paper.results);
-        tracing::info!(
-            "Advantages, Limitations, and Future Work: {}",
-            paper.advantages_limitations_and_future_work
-        );
+        if let Some(analysis) = paper.translation(language) {
+            tracing::info!("Language: {}", analysis.language);
+            tracing::info!("Abstract: {}", analysis.abstract_text);
+            tracing::info!("Summary: {}", analysis.summary);
+            tracing::info!("Background and Purpose: {}", analysis.background_and_purpose);
+            tracing::info!("Methodology: {}", analysis.methodology);
+            tracing::info!("Dataset: {}", analysis.dataset);
+            tracing::info!("Results: {}", analysis.results);
+            tracing::info!(
+                "Advantages, Limitations, and Future Work: {}",
+                analysis.advantages_limitations_and_future_work
+            );
+        }
         tracing::info!("BibTeX: {}", paper.bibtex);
         tracing::info!("PDF URL: {}", paper.url);
         tracing::info!("Published Date: {}", paper.published_date);
@@ -216,7 +343,7 @@ pub async fn add_academic_paper_with_sse(
         tracing::info!("Tasks: {:?}", paper.tasks);
 
         let mut tx = registry.db().inner_ref().begin().await?;
-        let _ = registry
+        let created = registry
             .academic_paper_repository()
             .create_academic_paper(&mut tx, paper.clone())
             .await?;
@@ -227,22 +354,20 @@ pub async fn add_academic_paper_with_sse(
             .send((
                 100,
                 "Successfully added academic paper to the database".into(),
-                Some(paper.clone()),
+                Some(created.clone()),
             ))
             .await
             .is_err()
         {
             tracing::info!("Client disconnected after paper was successfully added to database");
         }
+        save_citation_graph(&registry, &created).await;
 
         Ok::<(), AppError>(())
     });
 
-    let stream = ReceiverStream::new(srx).map(|(progress, message, paper)| {
-        let paper = match paper {
-            Some(p) => AcademicPaperResponse::from(p),
-            None => AcademicPaperResponse::from(AcademicPaper::default()),
-        };
+    let stream = ReceiverStream::new(srx).map(move |(progress, message, paper)| {
+        let paper = AcademicPaperResponse::from((paper.unwrap_or_default(), language));
         let json = serde_json::json!({ "progress": progress, "message": message, "paper": paper });
         Ok::<Event, AppError>(
             Event::default()
@@ -263,6 +388,7 @@ pub async fn update_academic_paper_with_sse(
     State(registry): State<AppRegistry>,
     Query(query): Query<AcademicPaperUpdateRequest>,
 ) -> Sse<impl futures_core::Stream<Item = AppResult<Event>>> {
+    let language = query.language.unwrap_or_else(Language::from_env);
     let (stx, srx) = mpsc::channel::<(usize, String, Option<AcademicPaper>)>(1);
 
     tokio::spawn(async move {
@@ -294,8 +420,20 @@ pub async fn update_academic_paper_with_sse(
             return Ok::<(), AppError>(());
         }
 
-        // crawler
-        let paper_rsc = match get_academic_paper(&paper.title, &paper.url).await {
+        // crawler; the PDF is parsed again only if its sections are not stored
+        let stored = paper;
+        let reparse = query.reparse || stored.sections.is_empty();
+        let paper_rsc = match (PaperIdentifier::of_paper(&stored), reparse) {
+            (Some(identifier), true) => {
+                get_academic_paper_by_identifier(&identifier, Some(&stored.title), Some(&stored.url)).await
+            }
+            (Some(identifier), false) => {
+                get_academic_paper_metadata_by_identifier(&identifier, Some(&stored.title), Some(&stored.url)).await
+            }
+            (None, true) => get_academic_paper(&stored.title, &stored.url).await,
+            (None, false) => get_academic_paper_metadata(&stored.title, &stored.url).await,
+        };
+        let paper_rsc = match paper_rsc {
             Ok(paper) => {
                 tracing::info!("Successfully retrieved academic paper");
                 if stx
@@ -319,12 +457,24 @@ pub async fn update_academic_paper_with_sse(
         };
 
         // kernel
+        let mut tx = registry.db().inner_ref().begin().await?;
+        let vocabulary = registry.task_repository().select_task_vocabulary(&mut tx).await?;
+        tx.commit().await?;
         let mut paper = AcademicPaper::from(paper_rsc);
         paper.paper_id = AcademicPaperId::from(query.paper_id.clone());
+        paper.status = stored.status.clone();
+        if !reparse {
+            paper.text = stored.text;
+            paper.sections = stored.sections;
+            paper.pdf_sha256 = stored.pdf_sha256;
+        }
         if paper.journal.name == "Unknown Journal" {
             paper.journal.name = "arXiv".to_string();
         }
-        match paper.fill_fields_with_ai().await {
+        match paper
+            .fill_fields_with_ai(registry.llm_provider().as_ref(), language, vocabulary)
+            .await
+        {
             Ok(_) => {
                 tracing::info!("Successfully filled fields with AI");
                 if stx
@@ -338,7 +488,7 @@ pub async fn update_academic_paper_with_sse(
             }
             Err(err) => {
                 tracing::error!("Failed to fill fields with AI: {}", err);
-                return Err(AppError::from(err));
+                return Err(err);
             }
         }
         match paper.fill_bibtex() {
@@ -363,16 +513,19 @@ pub async fn update_academic_paper_with_sse(
         tracing::info!("ArXiv ID: {}", paper.arxiv_id);
         tracing::info!("Semantic Scholar ID: {}", paper.ss_id);
         tracing::info!("Title: {}", paper.title);
-        tracing::info!("Abstract: {}", paper.abstract_text_ja);
-        tracing::info!("Summary: {}", paper.summary);
-        tracing::info!("Background and Purpose: {}", paper.background_and_purpose);
-        tracing::info!("Methodology: {}", paper.methodology);
-        tracing::info!("Dataset: {}", paper.dataset);
-        tracing::info!("Results: {}", paper.results);
-        tracing::info!(
-            "Advantages, Limitations, and Future Work: {}",
-            paper.advantages_limitations_and_future_work
-        );
+        if let Some(analysis) = paper.translation(language) {
+            tracing::info!("Language: {}", analysis.language);
+            tracing::info!("Abstract: {}", analysis.abstract_text);
+            tracing::info!("Summary: {}", analysis.summary);
+            tracing::info!("Background and Purpose: {}", analysis.background_and_purpose);
+            tracing::info!("Methodology: {}", analysis.methodology);
+            tracing::info!("Dataset: {}", analysis.dataset);
+            tracing::info!("Results: {}", analysis.results);
+            tracing::info!(
+                "Advantages, Limitations, and Future Work: {}",
+                analysis.advantages_limitations_and_future_work
+            );
+        }
         tracing::info!("BibTeX: {}", paper.bibtex);
         tracing::info!("PDF URL: {}", paper.url);
         tracing::info!("Published Date: {}", paper.published_date);
@@ -407,11 +560,8 @@ pub async fn update_academic_paper_with_sse(
         }
         Ok::<(), AppError>(())
     });
-    let stream = ReceiverStream::new(srx).map(|(progress, message, paper)| {
-        let paper = match paper {
-            Some(p) => AcademicPaperResponse::from(p),
-            None => AcademicPaperResponse::from(AcademicPaper::default()),
-        };
+    let stream = ReceiverStream::new(srx).map(move |(progress, message, paper)| {
+        let paper = AcademicPaperResponse::from((paper.unwrap_or_default(), language));
         let json = serde_json::json!({ "progress": progress, "message": message, "paper": paper });
         Ok::<Event, AppError>(
             Event::default()
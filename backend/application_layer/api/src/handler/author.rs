@@ -0,0 +1,101 @@
+use crate::{
+    extractor::PreferredLanguage,
+    models::{
+        academic_paper::AcademicPaperResponse,
+        author::{
+            AuthorDetailResponse, AuthorIdQuery, AuthorListQuery, AuthorListResponse, AuthorMergeRequest,
+            AuthorSummaryResponse, CoauthorshipGraphResponse, CoauthorshipQuery,
+        },
+    },
+};
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+use garde::Validate;
+use registry::AppRegistry;
+use shared::errors::AppResult;
+
+pub async fn select_paginated_authors(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<AuthorListQuery>,
+) -> AppResult<Json<AuthorListResponse>> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let result = registry
+        .author_repository()
+        .select_all_authors_paginated(&mut tx, query.into())
+        .await
+        .map(AuthorListResponse::from)
+        .map(Json);
+    tx.commit().await?;
+    result
+}
+
+/// An author and their papers in the library.
+pub async fn select_author_by_id(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<AuthorIdQuery>,
+    PreferredLanguage(language): PreferredLanguage,
+) -> AppResult<Json<AuthorDetailResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let author = registry
+        .author_repository()
+        .select_author_summary_by_id(&mut tx, &query.author_id)
+        .await?;
+    let papers = registry
+        .academic_paper_repository()
+        .select_academic_papers_by_author(&mut tx, &query.author_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(AuthorDetailResponse::new(
+        AuthorSummaryResponse::from(author),
+        papers
+            .into_iter()
+            .map(|paper| AcademicPaperResponse::from((paper, language)))
+            .collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+pub async fn select_coauthorship_graph(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<CoauthorshipQuery>,
+) -> AppResult<Json<CoauthorshipGraphResponse>> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let result = registry
+        .author_repository()
+        .select_coauthorship_graph(
+            &mut tx,
+            query.author_id.as_deref(),
+            query.limit.expect("Limit must be provided"),
+        )
+        .await
+        .map(CoauthorshipGraphResponse::from)
+        .map(Json);
+    tx.commit().await?;
+    result
+}
+
+/// Merges two entries of the same person, e.g. one added without a Semantic Scholar ID.
+pub async fn merge_authors(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<AuthorMergeRequest>,
+) -> AppResult<Json<AuthorSummaryResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    registry
+        .author_repository()
+        .merge_authors(&mut tx, &body.author_id, &body.duplicate_id)
+        .await?;
+    let author = registry
+        .author_repository()
+        .select_author_summary_by_id(&mut tx, &body.author_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(AuthorSummaryResponse::from(author)))
+}
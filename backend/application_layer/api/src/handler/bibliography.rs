@@ -0,0 +1,51 @@
+use crate::models::bibliography::LibraryExportQuery;
+use axum::{
+    extract::{Query, State},
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::IntoResponse,
+};
+use garde::Validate;
+use kernel::models::{academic_paper::AcademicPaper, bibliography::export_papers};
+use registry::AppRegistry;
+use shared::errors::AppResult;
+
+/// Exports single papers, a filtered set or the whole library as BibTeX, RIS or CSL-JSON.
+pub async fn export_library(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<LibraryExportQuery>,
+) -> AppResult<impl IntoResponse> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let repository = registry.academic_paper_repository();
+    let paper_ids = query.paper_ids();
+    let mut papers: Vec<AcademicPaper> = if !paper_ids.is_empty() {
+        let mut papers = vec![];
+        for paper_id in paper_ids {
+            papers.push(repository.select_academic_paper_by_id(&mut tx, paper_id).await?);
+        }
+        papers
+    } else if let Some(keyword) = query.keyword.as_deref() {
+        repository.select_academic_papers_by_keyword(&mut tx, keyword).await?
+    } else if let Some(author_id) = query.author_id.as_deref() {
+        repository.select_academic_papers_by_author(&mut tx, author_id).await?
+    } else {
+        repository.select_all_academic_papers(&mut tx).await?
+    };
+    tx.commit().await?;
+    if let Some(status) = query.status.as_ref() {
+        papers.retain(|paper| &paper.status == status);
+    }
+
+    let format = query.format;
+    Ok((
+        [
+            (CONTENT_TYPE, format!("{}; charset=utf-8", format.content_type())),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"library.{}\"", format.extension()),
+            ),
+        ],
+        export_papers(&papers, format),
+    ))
+}
@@ -0,0 +1,165 @@
+use crate::models::{
+    academic_paper::JournalResponse,
+    browse::{
+        BrowseListQuery, JournalListResponse, JournalMergeRequest, JournalsResponse, SimilarTasksListResponse,
+        SimilarTasksQuery, SimilarTasksResponse, TaskEntryResponse, TaskListResponse, TaskMergeRequest,
+        TaskTrendListResponse, TaskTrendQuery, TaskTrendResponse, VenueNormalizationResponse, VenueTiersRequest,
+    },
+};
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+use chrono::{Local, Months};
+use garde::Validate;
+use kernel::models::browse::month_start;
+use registry::AppRegistry;
+use shared::errors::{AppError, AppResult};
+
+/// The tasks with their number of papers and the most recently added ones, the task with the most papers first.
+pub async fn select_paginated_tasks(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<BrowseListQuery>,
+) -> AppResult<Json<TaskListResponse>> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let result = registry
+        .task_repository()
+        .select_task_summaries(&mut tx, query.into())
+        .await
+        .map(TaskListResponse::from)
+        .map(Json);
+    tx.commit().await?;
+    result
+}
+
+/// The papers added per month for the tasks with the most papers added in the last months.
+pub async fn select_task_trends(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<TaskTrendQuery>,
+) -> AppResult<Json<TaskTrendListResponse>> {
+    query.validate()?;
+
+    let months = query.months.expect("Months must be provided") as u32;
+    let since = month_start(Local::now().date_naive()) - Months::new(months - 1);
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let trends = registry
+        .task_repository()
+        .select_task_trends(&mut tx, since, query.limit.expect("Limit must be provided"))
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(TaskTrendListResponse::new(
+        since,
+        trends.into_iter().map(TaskTrendResponse::from).collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// The pairs of tasks that look like the same task, to be reviewed and merged.
+pub async fn select_similar_tasks(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<SimilarTasksQuery>,
+) -> AppResult<Json<SimilarTasksListResponse>> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let mut vocabulary = registry.task_repository().select_task_vocabulary(&mut tx).await?;
+    tx.commit().await?;
+    if query.embedding {
+        vocabulary.embed(registry.llm_provider().as_ref()).await;
+    }
+
+    Ok(Json(SimilarTasksListResponse::new(
+        vocabulary
+            .similar_tasks()
+            .into_iter()
+            .map(SimilarTasksResponse::from)
+            .collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// Merges a task extracted under another name into the task to keep.
+pub async fn merge_tasks(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<TaskMergeRequest>,
+) -> AppResult<Json<TaskEntryResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let task = registry
+        .task_repository()
+        .merge_tasks(&mut tx, &body.task_id, &body.duplicate_id)
+        .await?;
+    let vocabulary = registry.task_repository().select_task_vocabulary(&mut tx).await?;
+    tx.commit().await?;
+
+    vocabulary
+        .entries
+        .into_iter()
+        .find(|entry| entry.task.task_id == task.task_id)
+        .map(|entry| Json(TaskEntryResponse::from(entry)))
+        .ok_or_else(|| AppError::EntityNotFound(format!("Task {} not found", body.task_id)))
+}
+
+/// The journals with their number of papers and the most recently added ones, the journal with the most papers first.
+pub async fn select_paginated_journals(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<BrowseListQuery>,
+) -> AppResult<Json<JournalListResponse>> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let result = registry
+        .journal_repository()
+        .select_journal_summaries(&mut tx, query.into())
+        .await
+        .map(JournalListResponse::from)
+        .map(Json);
+    tx.commit().await?;
+    result
+}
+
+/// Sets the tiers of venues from a user-maintained list, such as a conference ranking.
+pub async fn update_journal_tiers(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<VenueTiersRequest>,
+) -> AppResult<Json<JournalsResponse>> {
+    body.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let journals = registry
+        .journal_repository()
+        .update_journal_tiers(&mut tx, body.tiers.into_iter().map(Into::into).collect())
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(JournalsResponse::new(
+        journals.into_iter().map(JournalResponse::from).collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// Merges a venue stored under another name into the venue to keep.
+pub async fn merge_journals(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<JournalMergeRequest>,
+) -> AppResult<Json<JournalResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let journal = registry
+        .journal_repository()
+        .merge_journals(&mut tx, &body.journal_id, &body.duplicate_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(JournalResponse::from(journal)))
+}
+
+/// Renames the stored venues to their canonical names, merges the duplicates and fills the venue years.
+pub async fn normalize_journals(State(registry): State<AppRegistry>) -> AppResult<Json<VenueNormalizationResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let normalization = registry.journal_repository().normalize_journals(&mut tx).await?;
+    tx.commit().await?;
+
+    Ok(Json(VenueNormalizationResponse::from(normalization)))
+}
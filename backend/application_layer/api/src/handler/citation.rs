@@ -0,0 +1,130 @@
+use crate::{
+    extractor::PreferredLanguage,
+    models::{
+        academic_paper::AcademicPaperResponse,
+        citation::{
+            CitationGraphQuery, CitationGraphResponse, CitationPaperQuery, CitedReferenceResponse,
+            CitingPapersResponse, MostCitedReferencesQuery, MostCitedReferencesResponse,
+        },
+    },
+};
+use academic_paper_crawler::repository::citations::fetch_citation_graph;
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+use garde::Validate;
+use kernel::models::academic_paper::AcademicPaper;
+use registry::AppRegistry;
+use shared::errors::{AppError, AppResult};
+
+/// Fetches the references and citations of a newly added paper into the citation graph.
+/// Failures are only logged, since the paper itself has already been stored.
+pub async fn save_citation_graph(registry: &AppRegistry, paper: &AcademicPaper) {
+    let result = async {
+        let graph = fetch_citation_graph(paper).await?;
+        let mut tx = registry.db().inner_ref().begin().await?;
+        registry
+            .citation_repository()
+            .save_citation_graph(&mut tx, &graph)
+            .await?;
+        tx.commit().await?;
+        Ok::<usize, AppError>(graph.edges.len())
+    }
+    .await;
+    match result {
+        Ok(count) => tracing::info!("Stored {} citation edges of {}", count, paper.ss_id),
+        Err(e) => tracing::warn!("Failed to fetch citations of {}: {}", paper.title, e),
+    }
+}
+
+/// The Semantic Scholar ID of the paper given by `ss_id`, or else by its library `paper_id`.
+async fn resolve_ss_id(registry: &AppRegistry, paper_id: Option<&str>, ss_id: Option<&str>) -> AppResult<String> {
+    if let Some(ss_id) = ss_id.filter(|ss_id| !ss_id.is_empty()) {
+        return Ok(ss_id.to_string());
+    }
+    let Some(paper_id) = paper_id else {
+        return Err(AppError::Unprocessable("Either paper_id or ss_id is required".into()));
+    };
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let paper = registry
+        .academic_paper_repository()
+        .select_academic_paper_by_id(&mut tx, paper_id)
+        .await?;
+    tx.commit().await?;
+    if paper.ss_id.is_empty() {
+        return Err(AppError::Unprocessable(format!(
+            "{} has no Semantic Scholar ID",
+            paper.title
+        )));
+    }
+    Ok(paper.ss_id)
+}
+
+/// Papers in the library that cite the given paper.
+pub async fn select_citing_papers(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<CitationPaperQuery>,
+    PreferredLanguage(language): PreferredLanguage,
+) -> AppResult<Json<CitingPapersResponse>> {
+    let ss_id = resolve_ss_id(&registry, query.paper_id.as_deref(), query.ss_id.as_deref()).await?;
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let papers = registry
+        .citation_repository()
+        .select_library_papers_citing(&mut tx, &ss_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(CitingPapersResponse::new(
+        ss_id,
+        papers
+            .into_iter()
+            .map(|paper| AcademicPaperResponse::from((paper, language)))
+            .collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// The references cited by the most papers in the library.
+pub async fn select_most_cited_references(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<MostCitedReferencesQuery>,
+) -> AppResult<Json<MostCitedReferencesResponse>> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let references = registry
+        .citation_repository()
+        .select_most_cited_references(&mut tx, query.limit.expect("Limit must be provided"))
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(MostCitedReferencesResponse::new(
+        references.into_iter().map(CitedReferenceResponse::from).collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// The citation graph within `hops` of the given paper, as nodes and edges for visualisation.
+pub async fn select_citation_graph(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<CitationGraphQuery>,
+) -> AppResult<Json<CitationGraphResponse>> {
+    query.validate()?;
+
+    let ss_id = resolve_ss_id(&registry, query.paper_id.as_deref(), query.ss_id.as_deref()).await?;
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let result = registry
+        .citation_repository()
+        .select_citation_neighbourhood(
+            &mut tx,
+            &ss_id,
+            query.hops.expect("Hops must be provided"),
+            query.max_nodes.expect("Max nodes must be provided"),
+        )
+        .await
+        .map(CitationGraphResponse::from)
+        .map(Json);
+    tx.commit().await?;
+    result
+}
@@ -0,0 +1,170 @@
+use crate::models::keyword::{
+    KeywordCreateRequest, KeywordDeleteRequest, KeywordDeleteResponse, KeywordListQuery, KeywordListResponse,
+    KeywordPapersRequest, KeywordPapersResponse, KeywordRenameRequest, KeywordResponse, KeywordSearchAddRequest,
+    KeywordUpdateResponse, PaperKeywordsQuery,
+};
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+use garde::Validate;
+use kernel::models::keyword::Keyword;
+use registry::AppRegistry;
+use shared::{errors::AppResult, id::AcademicPaperId};
+
+fn keyword_list_response(keywords: Vec<Keyword>) -> KeywordListResponse {
+    KeywordListResponse::new(
+        keywords.into_iter().map(KeywordResponse::from).collect(),
+        StatusCode::OK.as_u16() as usize,
+    )
+}
+
+/// The tags or the collections, with the number of their papers.
+pub async fn select_keywords(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<KeywordListQuery>,
+) -> AppResult<Json<KeywordListResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let keywords = registry
+        .keyword_repository()
+        .select_keywords(&mut tx, query.category)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(keyword_list_response(keywords)))
+}
+
+/// The tags and collections of a paper.
+pub async fn select_paper_keywords(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<PaperKeywordsQuery>,
+) -> AppResult<Json<KeywordListResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let keywords = registry
+        .keyword_repository()
+        .select_paper_keywords(&mut tx, query.paper_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(keyword_list_response(keywords)))
+}
+
+/// Creates a tag or a collection; an existing one with the same name is returned as is.
+pub async fn create_keyword(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<KeywordCreateRequest>,
+) -> AppResult<Json<KeywordUpdateResponse>> {
+    body.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let keyword = registry
+        .keyword_repository()
+        .create_keyword(&mut tx, body.category, &body.name)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(KeywordUpdateResponse::new(
+        KeywordResponse::from(keyword),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+pub async fn rename_keyword(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<KeywordRenameRequest>,
+) -> AppResult<Json<KeywordUpdateResponse>> {
+    body.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let keyword = registry
+        .keyword_repository()
+        .rename_keyword(&mut tx, body.keyword_id, &body.name)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(KeywordUpdateResponse::new(
+        KeywordResponse::from(keyword),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+pub async fn delete_keyword(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<KeywordDeleteRequest>,
+) -> AppResult<Json<KeywordDeleteResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    registry
+        .keyword_repository()
+        .delete_keyword(&mut tx, body.keyword_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(KeywordDeleteResponse::new(StatusCode::OK.as_u16() as usize)))
+}
+
+/// Tags the papers, or adds them to the collection.
+pub async fn add_papers_to_keyword(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<KeywordPapersRequest>,
+) -> AppResult<Json<KeywordPapersResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let count = registry
+        .keyword_repository()
+        .add_papers_to_keyword(&mut tx, body.keyword_id, &body.paper_ids)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(KeywordPapersResponse::new(
+        body.keyword_id,
+        count,
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// Untags the papers, or removes them from the collection.
+pub async fn remove_papers_from_keyword(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<KeywordPapersRequest>,
+) -> AppResult<Json<KeywordPapersResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let count = registry
+        .keyword_repository()
+        .remove_papers_from_keyword(&mut tx, body.keyword_id, &body.paper_ids)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(KeywordPapersResponse::new(
+        body.keyword_id,
+        count,
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// Adds all the papers matching the search to the tag or collection.
+pub async fn add_search_results_to_keyword(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<KeywordSearchAddRequest>,
+) -> AppResult<Json<KeywordPapersResponse>> {
+    body.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let papers = registry
+        .academic_paper_repository()
+        .select_academic_papers_by_keyword(&mut tx, body.query.trim())
+        .await?;
+    let paper_ids = papers
+        .iter()
+        .map(|paper| paper.paper_id)
+        .collect::<Vec<AcademicPaperId>>();
+    let count = registry
+        .keyword_repository()
+        .add_papers_to_keyword(&mut tx, body.keyword_id, &paper_ids)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(KeywordPapersResponse::new(
+        body.keyword_id,
+        count,
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
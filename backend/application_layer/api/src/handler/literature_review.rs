@@ -0,0 +1,160 @@
+use crate::{
+    extractor::PreferredLanguage,
+    models::literature_review::{LiteratureReviewQuery, LiteratureReviewResponse},
+};
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use garde::Validate;
+use kernel::models::{
+    academic_paper::{AcademicPaper, AcademicPaperListOptions},
+    bibliography::{citation_keys, export_papers, BibliographyFormat},
+    literature_review::{group_by_task, write_review_section, LiteratureReview, MAX_REVIEW_PAPERS},
+};
+use registry::AppRegistry;
+use shared::errors::{AppError, AppResult};
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+
+async fn select_review_papers(registry: &AppRegistry, query: &LiteratureReviewQuery) -> AppResult<Vec<AcademicPaper>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let repository = registry.academic_paper_repository();
+    let paper_ids = query.paper_ids();
+    let papers = if !paper_ids.is_empty() {
+        let mut papers = vec![];
+        for paper_id in paper_ids {
+            papers.push(repository.select_academic_paper_by_id(&mut tx, paper_id).await?);
+        }
+        papers
+    } else if let Some(keyword) = query.keyword.as_deref() {
+        repository.select_academic_papers_by_keyword(&mut tx, keyword).await?
+    } else if query.tag.is_some() || query.collection.is_some() {
+        let options = AcademicPaperListOptions {
+            tag: query.tag.clone(),
+            collection: query.collection.clone(),
+            limit: MAX_REVIEW_PAPERS as i64 + 1,
+            ..Default::default()
+        };
+        repository
+            .select_paginated_academic_papers(&mut tx, options)
+            .await?
+            .items
+    } else {
+        return Err(AppError::Unprocessable(
+            "Select the papers by paper_ids, keyword, tag or collection".into(),
+        ));
+    };
+    tx.commit().await?;
+
+    if papers.is_empty() {
+        return Err(AppError::EntityNotFound("No papers matched the selection".into()));
+    }
+    if papers.len() > MAX_REVIEW_PAPERS {
+        return Err(AppError::Unprocessable(format!(
+            "At most {} papers can be reviewed at once",
+            MAX_REVIEW_PAPERS
+        )));
+    }
+    Ok(papers)
+}
+
+/// Writes a related-work draft of the selected papers, grouped by task and approach, streaming the progress.
+pub async fn write_literature_review_with_sse(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<LiteratureReviewQuery>,
+    PreferredLanguage(language): PreferredLanguage,
+) -> AppResult<Sse<impl futures_core::Stream<Item = AppResult<Event>>>> {
+    query.validate()?;
+    let papers = select_review_papers(&registry, &query).await?;
+    let (stx, srx) = mpsc::channel::<(usize, String, Option<LiteratureReviewResponse>)>(1);
+
+    tokio::spawn(async move {
+        // the keys are fixed per paper, so the draft cites the papers as their stored BibTeX and the library export do
+        let keys = citation_keys(&papers);
+        let groups = group_by_task(&papers);
+        if stx
+            .send((
+                10,
+                format!("Grouped {} papers into {} tasks", papers.len(), groups.len()),
+                None,
+            ))
+            .await
+            .is_err()
+        {
+            tracing::warn!("Client disconnected, stopping status updates");
+            return Ok::<(), AppError>(());
+        }
+
+        let mut sections = vec![];
+        for (index, group) in groups.iter().enumerate() {
+            let group_papers = group
+                .papers
+                .iter()
+                .map(|paper| (&papers[*paper], keys[*paper].as_str()))
+                .collect::<Vec<(&AcademicPaper, &str)>>();
+            let section = match write_review_section(
+                registry.llm_provider().as_ref(),
+                group.task.as_deref(),
+                &group_papers,
+                language,
+            )
+            .await
+            {
+                Ok(section) => section,
+                Err(err) => {
+                    tracing::error!(
+                        "Failed to write the review of {}: {}",
+                        group.task.as_deref().unwrap_or("-"),
+                        err
+                    );
+                    // the client gets the failure as the last message
+                    let _ = stx
+                        .send((100, format!("Failed to write the review: {}", err), None))
+                        .await;
+                    return Err(err);
+                }
+            };
+            if stx
+                .send((
+                    10 + 80 * (index + 1) / groups.len(),
+                    format!("Wrote the review of {}", section.title),
+                    None,
+                ))
+                .await
+                .is_err()
+            {
+                tracing::warn!("Client disconnected, stopping status updates");
+                return Ok::<(), AppError>(());
+            }
+            sections.push(section);
+        }
+
+        let review = LiteratureReview::new(sections, language);
+        let bibtex = export_papers(&papers, BibliographyFormat::BibTeX);
+        if stx
+            .send((
+                100,
+                "Successfully wrote the literature review".into(),
+                Some(LiteratureReviewResponse::from((review, bibtex))),
+            ))
+            .await
+            .is_err()
+        {
+            tracing::info!("Client disconnected before the literature review was sent");
+        }
+
+        Ok::<(), AppError>(())
+    });
+
+    let stream = ReceiverStream::new(srx).map(move |(progress, message, review)| {
+        let json = serde_json::json!({ "progress": progress, "message": message, "review": review });
+        Ok::<Event, AppError>(Event::default().event("message").data(json.to_string()))
+    });
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(std::time::Duration::from_secs(15))
+            .text("keep-alive"),
+    ))
+}
@@ -0,0 +1,114 @@
+use crate::{
+    handler::citation::save_citation_graph,
+    models::{
+        academic_paper::AcademicPaperResponse,
+        paper_candidate::{
+            PaperCandidateDismissRequest, PaperCandidateDismissResponse, PaperCandidateListQuery,
+            PaperCandidateListResponse, PaperCandidatePromoteRequest, PaperCandidateResponse,
+        },
+    },
+};
+use academic_paper_crawler::{
+    models::identifier::PaperIdentifier, repository::academic_papers::get_academic_paper_by_identifier,
+};
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+use garde::Validate;
+use kernel::models::{academic_paper::AcademicPaper, language::Language, paper_candidate::CandidateStatus};
+use registry::AppRegistry;
+use shared::errors::{AppError, AppResult};
+
+pub async fn select_paginated_paper_candidates(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<PaperCandidateListQuery>,
+) -> AppResult<Json<PaperCandidateListResponse>> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let result = registry
+        .paper_candidate_repository()
+        .select_paginated_paper_candidates(&mut tx, query.into())
+        .await
+        .map(PaperCandidateListResponse::from)
+        .map(Json);
+    tx.commit().await?;
+    result
+}
+
+/// Adds a candidate found by `watch-arxiv` to the library.
+pub async fn promote_paper_candidate(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<PaperCandidatePromoteRequest>,
+) -> AppResult<Json<AcademicPaperResponse>> {
+    let language = body.language.unwrap_or_else(Language::from_env);
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let candidate = registry
+        .paper_candidate_repository()
+        .select_paper_candidate_by_id(&mut tx, body.candidate_id)
+        .await?;
+    tx.commit().await?;
+    if candidate.status == CandidateStatus::Added {
+        return Err(AppError::Unprocessable(format!(
+            "{} has already been added",
+            candidate.arxiv_id
+        )));
+    }
+
+    let identifier = PaperIdentifier::ArXiv(candidate.arxiv_id.clone());
+    let paper_rsc = get_academic_paper_by_identifier(&identifier, Some(&candidate.title), Some(&candidate.pdf_url))
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get academic paper: {}", e);
+            AppError::from(e)
+        })?;
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let vocabulary = registry.task_repository().select_task_vocabulary(&mut tx).await?;
+    tx.commit().await?;
+    let mut paper = AcademicPaper::from(paper_rsc);
+    paper
+        .fill_fields_with_ai(registry.llm_provider().as_ref(), language, vocabulary)
+        .await?;
+    paper.fill_bibtex()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let paper = registry
+        .academic_paper_repository()
+        .create_academic_paper(&mut tx, paper)
+        .await?;
+    registry
+        .paper_candidate_repository()
+        .update_paper_candidate_status(
+            &mut tx,
+            candidate.candidate_id,
+            CandidateStatus::Added,
+            Some(paper.paper_id),
+        )
+        .await?;
+    tx.commit().await?;
+    save_citation_graph(&registry, &paper).await;
+
+    Ok(Json(AcademicPaperResponse::from((paper, language))))
+}
+
+pub async fn dismiss_paper_candidate(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<PaperCandidateDismissRequest>,
+) -> AppResult<Json<PaperCandidateDismissResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    registry
+        .paper_candidate_repository()
+        .update_paper_candidate_status(&mut tx, body.candidate_id, CandidateStatus::Dismissed, None)
+        .await?;
+    let candidate = registry
+        .paper_candidate_repository()
+        .select_paper_candidate_by_id(&mut tx, body.candidate_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperCandidateDismissResponse::new(
+        PaperCandidateResponse::from(candidate),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
@@ -0,0 +1,116 @@
+use crate::{
+    extractor::PreferredLanguage,
+    models::paper_comparison::{
+        PaperComparisonDeleteResponse, PaperComparisonDetailResponse, PaperComparisonIdQuery, PaperComparisonListQuery,
+        PaperComparisonListResponse, PaperComparisonRequest, PaperComparisonResponse,
+    },
+};
+use axum::{
+    extract::{Json, Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::IntoResponse,
+};
+use garde::Validate;
+use registry::AppRegistry;
+use shared::errors::AppResult;
+
+/// Compares the papers with their stored analyses and saves the comparison.
+pub async fn compare_papers(
+    State(registry): State<AppRegistry>,
+    PreferredLanguage(language): PreferredLanguage,
+    Json(body): Json<PaperComparisonRequest>,
+) -> AppResult<Json<PaperComparisonDetailResponse>> {
+    body.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let comparison = registry
+        .paper_comparison_repository()
+        .compare_papers(&mut tx, body.paper_ids, language)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperComparisonDetailResponse::new(
+        PaperComparisonResponse::from(comparison),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+pub async fn select_paper_comparison(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<PaperComparisonIdQuery>,
+) -> AppResult<Json<PaperComparisonDetailResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let comparison = registry
+        .paper_comparison_repository()
+        .select_paper_comparison(&mut tx, query.paper_comparison_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperComparisonDetailResponse::new(
+        PaperComparisonResponse::from(comparison),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+pub async fn select_paper_comparisons(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<PaperComparisonListQuery>,
+) -> AppResult<Json<PaperComparisonListResponse>> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let result = registry
+        .paper_comparison_repository()
+        .select_paper_comparisons(&mut tx, query.into())
+        .await
+        .map(PaperComparisonListResponse::from)
+        .map(Json);
+    tx.commit().await?;
+    result
+}
+
+/// Exports a saved comparison as a Markdown document.
+pub async fn export_paper_comparison(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<PaperComparisonIdQuery>,
+) -> AppResult<impl IntoResponse> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let comparison = registry
+        .paper_comparison_repository()
+        .select_paper_comparison(&mut tx, query.paper_comparison_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, "text/markdown; charset=utf-8".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"comparison-{}.md\"",
+                    comparison.paper_comparison_id
+                ),
+            ),
+        ],
+        comparison.to_markdown(),
+    ))
+}
+
+pub async fn delete_paper_comparison(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<PaperComparisonIdQuery>,
+) -> AppResult<Json<PaperComparisonDeleteResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    registry
+        .paper_comparison_repository()
+        .delete_paper_comparison(&mut tx, body.paper_comparison_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperComparisonDeleteResponse::new(
+        StatusCode::OK.as_u16() as usize
+    )))
+}
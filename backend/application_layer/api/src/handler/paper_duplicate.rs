@@ -0,0 +1,42 @@
+use crate::{
+    extractor::PreferredLanguage,
+    models::{
+        academic_paper::AcademicPaperResponse,
+        paper_duplicate::{AcademicPaperMergeRequest, DuplicateGroupListResponse, DuplicateGroupResponse},
+    },
+};
+use axum::extract::{Json, State};
+use registry::AppRegistry;
+use shared::errors::AppResult;
+
+/// The papers that were added more than once, grouped by the identifiers they share.
+pub async fn find_duplicate_academic_papers(
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<DuplicateGroupListResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let groups = registry
+        .academic_paper_repository()
+        .select_duplicate_academic_papers(&mut tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(DuplicateGroupListResponse::new(
+        groups.into_iter().map(DuplicateGroupResponse::from).collect(),
+    )))
+}
+
+/// Merges two entries of the same paper into the one to keep.
+pub async fn merge_academic_papers(
+    State(registry): State<AppRegistry>,
+    PreferredLanguage(language): PreferredLanguage,
+    Json(body): Json<AcademicPaperMergeRequest>,
+) -> AppResult<Json<AcademicPaperResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let paper = registry
+        .academic_paper_repository()
+        .merge_academic_papers(&mut tx, &body.paper_id, &body.duplicate_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(AcademicPaperResponse::from((paper, language))))
+}
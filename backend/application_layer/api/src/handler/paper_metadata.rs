@@ -0,0 +1,62 @@
+use crate::models::paper_metadata::{
+    CitationGainResponse, CitationGainsQuery, CitationGainsResponse, MetadataChangeResponse, MetadataHistoryQuery,
+    MetadataHistoryResponse,
+};
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Datelike, Local, TimeZone};
+use garde::Validate;
+use registry::AppRegistry;
+use shared::errors::AppResult;
+
+/// Midnight on the first day of the current month.
+fn start_of_month() -> DateTime<Local> {
+    let now = Local::now();
+    Local
+        .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .earliest()
+        .unwrap_or(now)
+}
+
+/// The metadata changes recorded by the refresh job for a paper, newest first.
+pub async fn select_metadata_history(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<MetadataHistoryQuery>,
+) -> AppResult<Json<MetadataHistoryResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let changes = registry
+        .paper_metadata_repository()
+        .select_metadata_history(&mut tx, &query.paper_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(MetadataHistoryResponse::new(
+        query.paper_id,
+        changes.into_iter().map(MetadataChangeResponse::from).collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// The papers that gained the most citations since `since`, this month by default.
+pub async fn select_citation_gains(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<CitationGainsQuery>,
+) -> AppResult<Json<CitationGainsResponse>> {
+    query.validate()?;
+
+    let since = query.since.unwrap_or_else(start_of_month);
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let gains = registry
+        .paper_metadata_repository()
+        .select_citation_gains(&mut tx, since, query.limit.expect("Limit must be provided"))
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(CitationGainsResponse::new(
+        since,
+        gains.into_iter().map(CitationGainResponse::from).collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
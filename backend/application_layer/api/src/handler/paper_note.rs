@@ -1,7 +1,8 @@
 use crate::models::paper_note::{
     PaperNoteAskToAgentRequest, PaperNoteCreateRequest, PaperNoteCreateResponse, PaperNoteDeleteRequest,
-    PaperNoteDeleteResponse, PaperNoteResponse, PaperNoteSelectRequest, PaperNoteSelectResponse,
-    PaperNoteUpdateRequest, PaperNoteUpdateResponse,
+    PaperNoteDeleteResponse, PaperNoteMessageRegenerateResponse, PaperNoteMessageRequest, PaperNoteMessageResponse,
+    PaperNoteMessagesRequest, PaperNoteMessagesResponse, PaperNoteResponse, PaperNoteSelectRequest,
+    PaperNoteSelectResponse, PaperNoteUpdateRequest, PaperNoteUpdateResponse,
 };
 use axum::{
     extract::{Json, Query, State},
@@ -91,14 +92,65 @@ pub async fn delete_paper_note(
 pub async fn ask_to_agent(
     State(registry): State<AppRegistry>,
     Json(body): Json<PaperNoteAskToAgentRequest>,
-) -> AppResult<Json<PaperNoteResponse>> {
+) -> AppResult<Json<PaperNoteMessagesResponse>> {
     let mut tx = registry.db().inner_ref().begin().await?;
 
-    let response = registry
+    let messages = registry
         .paper_note_repository()
         .ask_to_agent(&mut tx, body.paper_note_id, body.query)
         .await?;
     tx.commit().await?;
 
-    Ok(Json(response.into()))
+    Ok(Json(PaperNoteMessagesResponse::from_messages(
+        body.paper_note_id,
+        messages,
+    )))
+}
+
+pub async fn select_paper_note_messages(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<PaperNoteMessagesRequest>,
+) -> AppResult<Json<PaperNoteMessagesResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let messages = registry
+        .paper_note_repository()
+        .select_paper_note_messages(&mut tx, query.paper_note_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperNoteMessagesResponse::from_messages(
+        query.paper_note_id,
+        messages,
+    )))
+}
+
+pub async fn regenerate_paper_note_message(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<PaperNoteMessageRequest>,
+) -> AppResult<Json<PaperNoteMessageRegenerateResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let message = registry
+        .paper_note_repository()
+        .regenerate_paper_note_message(&mut tx, body.paper_note_message_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperNoteMessageRegenerateResponse::new(
+        PaperNoteMessageResponse::from(message),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+pub async fn delete_paper_note_message(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<PaperNoteMessageRequest>,
+) -> AppResult<Json<PaperNoteDeleteResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    registry
+        .paper_note_repository()
+        .delete_paper_note_message(&mut tx, body.paper_note_message_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperNoteDeleteResponse::new(StatusCode::OK.as_u16() as usize)))
 }
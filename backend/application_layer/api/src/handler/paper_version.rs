@@ -0,0 +1,30 @@
+use crate::models::{
+    academic_paper::AcademicPaperIdQuery,
+    paper_version::{PaperVersionListResponse, PaperVersionResponse},
+};
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+use registry::AppRegistry;
+use shared::{errors::AppResult, id::AcademicPaperId};
+
+/// The arXiv versions of a paper found by the version check, newest first, with what changed in each.
+pub async fn select_paper_versions(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<AcademicPaperIdQuery>,
+) -> AppResult<Json<PaperVersionListResponse>> {
+    let paper_id = query.paper_id.parse::<AcademicPaperId>()?;
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let versions = registry
+        .paper_version_repository()
+        .select_paper_versions(&mut tx, paper_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperVersionListResponse::new(
+        paper_id,
+        versions.into_iter().map(PaperVersionResponse::from).collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
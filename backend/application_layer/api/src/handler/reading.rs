@@ -0,0 +1,148 @@
+use crate::models::reading::{
+    PaperStatusChangeResponse, PaperStatusHistoryQuery, PaperStatusHistoryResponse, PaperStatusUpdateRequest,
+    PaperStatusUpdateResponse, ReadingNextResponse, ReadingQueueDeleteRequest, ReadingQueueDeleteResponse,
+    ReadingQueueItemResponse, ReadingQueueQuery, ReadingQueueResponse, ReadingQueueUpsertRequest,
+    ReadingQueueUpsertResponse, ReadingStatsQuery, ReadingStatsResponse, WeeklyReadingStatsResponse,
+};
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+use chrono::{Days, Local, TimeZone};
+use garde::Validate;
+use kernel::models::reading::{week_start, weekly_reading_stats, ReadingQueueListOptions};
+use registry::AppRegistry;
+use shared::errors::AppResult;
+
+/// Moves a paper to another reading status.
+pub async fn update_paper_status(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<PaperStatusUpdateRequest>,
+) -> AppResult<Json<PaperStatusUpdateResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let change = registry
+        .reading_repository()
+        .update_paper_status(&mut tx, body.paper_id, body.status)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperStatusUpdateResponse::new(
+        PaperStatusChangeResponse::from(change),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+pub async fn select_paper_status_history(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<PaperStatusHistoryQuery>,
+) -> AppResult<Json<PaperStatusHistoryResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let changes = registry
+        .reading_repository()
+        .select_paper_status_history(&mut tx, query.paper_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(PaperStatusHistoryResponse::new(
+        query.paper_id,
+        changes.into_iter().map(PaperStatusChangeResponse::from).collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+pub async fn select_reading_queue(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<ReadingQueueQuery>,
+) -> AppResult<Json<ReadingQueueResponse>> {
+    query.validate()?;
+
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let result = registry
+        .reading_repository()
+        .select_reading_queue(&mut tx, query.into())
+        .await
+        .map(ReadingQueueResponse::from)
+        .map(Json);
+    tx.commit().await?;
+    result
+}
+
+/// The paper to read next: the first one in the queue.
+pub async fn select_next_paper(State(registry): State<AppRegistry>) -> AppResult<Json<ReadingNextResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let queue = registry
+        .reading_repository()
+        .select_reading_queue(&mut tx, ReadingQueueListOptions::new(1, 0))
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(ReadingNextResponse::new(
+        queue
+            .into_inner()
+            .into_iter()
+            .next()
+            .map(ReadingQueueItemResponse::from),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+/// Adds a paper to the reading queue, or updates its priority and due date.
+pub async fn upsert_reading_queue_item(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<ReadingQueueUpsertRequest>,
+) -> AppResult<Json<ReadingQueueUpsertResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let item = registry
+        .reading_repository()
+        .upsert_reading_queue_item(&mut tx, body.paper_id, body.priority, body.due_date)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(ReadingQueueUpsertResponse::new(
+        ReadingQueueItemResponse::from(item),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
+
+pub async fn delete_reading_queue_item(
+    State(registry): State<AppRegistry>,
+    Json(body): Json<ReadingQueueDeleteRequest>,
+) -> AppResult<Json<ReadingQueueDeleteResponse>> {
+    let mut tx = registry.db().inner_ref().begin().await?;
+    registry
+        .reading_repository()
+        .delete_reading_queue_item(&mut tx, body.paper_id)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(ReadingQueueDeleteResponse::new(StatusCode::OK.as_u16() as usize)))
+}
+
+/// The papers completed and the hours spent in deep dives per week, for the last `weeks` weeks.
+pub async fn select_reading_stats(
+    State(registry): State<AppRegistry>,
+    Query(query): Query<ReadingStatsQuery>,
+) -> AppResult<Json<ReadingStatsResponse>> {
+    query.validate()?;
+
+    let now = Local::now();
+    let first_week = week_start(now.date_naive()) - Days::new(7 * (query.weeks as u64 - 1));
+    let since = Local
+        .from_local_datetime(&first_week.and_hms_opt(0, 0, 0).expect("Midnight is a valid time"))
+        .earliest()
+        .unwrap_or(now);
+    let mut tx = registry.db().inner_ref().begin().await?;
+    let changes = registry
+        .reading_repository()
+        .select_status_changes(&mut tx, since, now)
+        .await?;
+    tx.commit().await?;
+
+    Ok(Json(ReadingStatsResponse::new(
+        weekly_reading_stats(&changes, first_week, now)
+            .into_iter()
+            .map(WeeklyReadingStatsResponse::from)
+            .collect(),
+        StatusCode::OK.as_u16() as usize,
+    )))
+}
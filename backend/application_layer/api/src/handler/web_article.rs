@@ -1,6 +1,9 @@
-use crate::models::web_article::{
-    PaginatedWebArticleResponse, PaginatedWebSiteResponse, WebArticleFilteredRequest, WebArticleListRequest,
-    WebArticleResponse, WebArticleUpdateRequest, WebSiteListRequest,
+use crate::{
+    extractor::PreferredLanguage,
+    models::web_article::{
+        PaginatedWebArticleResponse, PaginatedWebSiteResponse, WebArticleFilteredRequest, WebArticleListRequest,
+        WebArticleResponse, WebArticleUpdateRequest, WebSiteListRequest,
+    },
 };
 use axum::extract::{Json, Query, State};
 use garde::Validate;
@@ -24,6 +27,7 @@ pub async fn select_paginated_web_sites(
 pub async fn select_paginated_web_articles(
     State(registry): State<AppRegistry>,
     Query(query): Query<WebArticleListRequest>,
+    PreferredLanguage(language): PreferredLanguage,
 ) -> AppResult<Json<PaginatedWebArticleResponse>> {
     query.validate()?;
 
@@ -31,24 +35,26 @@ pub async fn select_paginated_web_articles(
         .web_article_repository()
         .select_paginated_web_articles(query.into())
         .await
-        .map(PaginatedWebArticleResponse::from)
+        .map(|articles| PaginatedWebArticleResponse::from((articles, language)))
         .map(Json)
 }
 
 pub async fn select_filtered_web_articles(
     State(registry): State<AppRegistry>,
     Query(query): Query<WebArticleFilteredRequest>,
+    PreferredLanguage(language): PreferredLanguage,
 ) -> AppResult<Json<PaginatedWebArticleResponse>> {
     registry
         .web_article_repository()
         .select_filtered_web_articles(query.into())
         .await
-        .map(PaginatedWebArticleResponse::from)
+        .map(|articles| PaginatedWebArticleResponse::from((articles, language)))
         .map(Json)
 }
 
 pub async fn update_web_article_status(
     State(registry): State<AppRegistry>,
+    PreferredLanguage(language): PreferredLanguage,
     Json(payload): Json<WebArticleUpdateRequest>,
 ) -> AppResult<Json<WebArticleResponse>> {
     registry
@@ -59,6 +65,6 @@ pub async fn update_web_article_status(
                 .map_err(|e| AppError::EnumParseError(format!("Invalid status: {}", e)))?,
         )
         .await
-        .map(WebArticleResponse::from)
+        .map(|article| WebArticleResponse::from((article, language)))
         .map(Json)
 }
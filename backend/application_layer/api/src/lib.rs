@@ -1,3 +1,4 @@
+pub mod extractor;
 pub mod handler;
 pub mod models;
 pub mod route;
@@ -1,10 +1,15 @@
 use axum::http::StatusCode;
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate};
 use derive_new::new;
 use garde::Validate;
 use kernel::models::{
-    academic_paper::{AcademicPaper, AcademicPaperListOptions, Author, Journal, Task},
+    academic_paper::{
+        AcademicPaper, AcademicPaperListOptions, AcademicPaperSort, Author, Journal, PaperSection, SortOrder, Status,
+        Task,
+    },
+    language::Language,
     list::PaginatedList,
+    venue::VenueType,
 };
 use serde::{Deserialize, Serialize};
 use shared::id::{AcademicPaperId, AuthorId, JournalId, TaskId};
@@ -51,12 +56,24 @@ impl From<Task> for TaskResponse {
 pub struct JournalResponse {
     pub journal_id: JournalId,
     pub name: String,
+    pub venue_type: Option<VenueType>,
+    pub tier: Option<String>,
 }
 
 impl From<Journal> for JournalResponse {
     fn from(journal: Journal) -> Self {
-        let Journal { journal_id, name } = journal;
-        Self { journal_id, name }
+        let Journal {
+            journal_id,
+            name,
+            venue_type,
+            tier,
+        } = journal;
+        Self {
+            journal_id,
+            name,
+            venue_type,
+            tier,
+        }
     }
 }
 
@@ -75,6 +92,7 @@ pub struct AcademicPaperResponse {
     pub created_at: DateTime<Local>,
     pub updated_at: DateTime<Local>,
     pub journal: JournalResponse,
+    pub venue_year: Option<i32>,
     pub text: String,
     pub url: String,
     pub citation_count: i32,
@@ -88,37 +106,39 @@ pub struct AcademicPaperResponse {
     pub results: String,
     pub advantages_limitations_and_future_work: String,
     pub status: String,
+    /// The language of the generated fields (`summary`, `methodology`...).
+    pub language: Language,
+    pub available_languages: Vec<Language>,
 }
 
-impl From<AcademicPaper> for AcademicPaperResponse {
-    fn from(paper: AcademicPaper) -> Self {
+impl From<(AcademicPaper, Language)> for AcademicPaperResponse {
+    fn from((paper, preferred): (AcademicPaper, Language)) -> Self {
+        let translation = paper.translation(preferred).cloned().unwrap_or_default();
+        let available_languages = paper.translations.iter().map(|t| t.language).collect();
         let AcademicPaper {
             paper_id,
             ss_id,
             arxiv_id,
             title,
             abstract_text,
-            abstract_text_ja: _,
             primary_category,
             published_date,
+            venue_year,
             created_at,
             updated_at,
             authors,
             tasks,
             journal,
             text,
+            sections: _,
+            pdf_sha256: _,
             url,
             doi,
             citations_count: citation_count,
             references_count: reference_count,
             influential_citation_count,
             bibtex,
-            summary,
-            background_and_purpose,
-            methodology,
-            dataset,
-            results,
-            advantages_limitations_and_future_work,
+            translations: _,
             status,
         } = paper;
         Self {
@@ -135,19 +155,22 @@ impl From<AcademicPaper> for AcademicPaperResponse {
             created_at,
             updated_at,
             journal: JournalResponse::from(journal),
+            venue_year,
             text,
             url,
             citation_count,
             reference_count,
             influential_citation_count,
             bibtex,
-            summary,
-            background_and_purpose,
-            methodology,
-            dataset,
-            results,
-            advantages_limitations_and_future_work,
+            summary: translation.summary,
+            background_and_purpose: translation.background_and_purpose,
+            methodology: translation.methodology,
+            dataset: translation.dataset,
+            results: translation.results,
+            advantages_limitations_and_future_work: translation.advantages_limitations_and_future_work,
             status: status.to_string(),
+            language: translation.language,
+            available_languages,
         }
     }
 }
@@ -161,8 +184,8 @@ pub struct AcademicPaperListResponse {
     pub status_code: usize,
 }
 
-impl From<PaginatedList<AcademicPaper>> for AcademicPaperListResponse {
-    fn from(paginated_list: PaginatedList<AcademicPaper>) -> Self {
+impl From<(PaginatedList<AcademicPaper>, Language)> for AcademicPaperListResponse {
+    fn from((paginated_list, language): (PaginatedList<AcademicPaper>, Language)) -> Self {
         let PaginatedList {
             total,
             limit,
@@ -173,7 +196,10 @@ impl From<PaginatedList<AcademicPaper>> for AcademicPaperListResponse {
             total,
             limit,
             offset,
-            items: items.into_iter().map(AcademicPaperResponse::from).collect(),
+            items: items
+                .into_iter()
+                .map(|paper| AcademicPaperResponse::from((paper, language)))
+                .collect(),
             status_code: StatusCode::OK.as_u16() as usize,
         }
     }
@@ -185,8 +211,42 @@ pub fn default_limit() -> Option<i64> {
 pub fn default_offset() -> Option<i64> {
     Some(0)
 }
-#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
 pub struct AcademicPaperListQuery {
+    #[garde(skip)]
+    pub tag: Option<String>,
+    #[garde(skip)]
+    pub collection: Option<String>,
+    #[garde(skip)]
+    pub status: Option<Status>,
+    #[garde(skip)]
+    pub task: Option<String>,
+    #[garde(skip)]
+    pub journal: Option<String>,
+    #[garde(skip)]
+    pub venue_type: Option<VenueType>,
+    #[garde(skip)]
+    pub tier: Option<String>,
+    #[garde(skip)]
+    pub venue_year: Option<i32>,
+    #[garde(skip)]
+    pub author: Option<String>,
+    #[garde(skip)]
+    pub primary_category: Option<String>,
+    #[garde(skip)]
+    pub published_from: Option<NaiveDate>,
+    #[garde(skip)]
+    pub published_to: Option<NaiveDate>,
+    #[garde(range(min = 0))]
+    pub min_citations: Option<i32>,
+    #[garde(skip)]
+    pub q: Option<String>,
+    #[garde(skip)]
+    #[serde(default)]
+    pub sort: AcademicPaperSort,
+    #[garde(skip)]
+    #[serde(default)]
+    pub order: SortOrder,
     #[garde(range(min = 0))]
     #[serde(default = "default_limit")]
     pub limit: Option<i64>,
@@ -195,10 +255,52 @@ pub struct AcademicPaperListQuery {
     pub offset: Option<i64>,
 }
 
+/// Drops an empty query parameter such as `?task=`, which the forms send for "any".
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
 impl From<AcademicPaperListQuery> for AcademicPaperListOptions {
     fn from(query: AcademicPaperListQuery) -> Self {
-        let AcademicPaperListQuery { limit, offset } = query;
+        let AcademicPaperListQuery {
+            tag,
+            collection,
+            status,
+            task,
+            journal,
+            venue_type,
+            tier,
+            venue_year,
+            author,
+            primary_category,
+            published_from,
+            published_to,
+            min_citations,
+            q,
+            sort,
+            order,
+            limit,
+            offset,
+        } = query;
         Self {
+            tag,
+            collection,
+            status,
+            task: non_empty(task),
+            journal: non_empty(journal),
+            venue_type,
+            tier: non_empty(tier),
+            venue_year,
+            author: non_empty(author),
+            primary_category: non_empty(primary_category),
+            published_from,
+            published_to,
+            min_citations,
+            text: non_empty(q),
+            sort,
+            order,
             limit: limit.expect("Limit must be provided"),
             offset: offset.expect("Offset must be provided"),
         }
@@ -210,15 +312,48 @@ pub struct AcademicPaperIdQuery {
     pub paper_id: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct PaperSectionResponse {
+    pub title: String,
+    pub content: String,
+}
+
+impl From<PaperSection> for PaperSectionResponse {
+    fn from(section: PaperSection) -> Self {
+        let PaperSection { title, content } = section;
+        Self { title, content }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct PaperSectionsResponse {
+    pub paper_id: AcademicPaperId,
+    /// Whether the source PDF is in the local PDF store.
+    pub pdf_stored: bool,
+    pub items: Vec<PaperSectionResponse>,
+    pub status_code: usize,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, new)]
 pub struct AcademicPaperCreateRequest {
-    #[garde(length(min = 1))]
-    pub title: String,
-    #[garde(url)]
-    pub pdf_url: String,
+    /// arXiv ID/URL, DOI or Semantic Scholar paper ID; the title search is used when omitted.
+    #[garde(skip)]
+    pub identifier: Option<String>,
+    #[garde(inner(length(min = 1)))]
+    pub title: Option<String>,
+    #[garde(inner(url))]
+    pub pdf_url: Option<String>,
+    /// The language of the generated fields; defaults to `OUTPUT_LANGUAGE`.
+    #[garde(skip)]
+    pub language: Option<Language>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, new)]
 pub struct AcademicPaperUpdateRequest {
     pub paper_id: String,
+    /// The language of the generated fields; defaults to `OUTPUT_LANGUAGE`.
+    pub language: Option<Language>,
+    /// Fetch and parse the PDF again even if its sections are stored.
+    #[serde(default)]
+    pub reparse: bool,
 }
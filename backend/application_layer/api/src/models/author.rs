@@ -0,0 +1,175 @@
+use crate::models::{
+    academic_paper::{default_limit, default_offset, AcademicPaperResponse, AuthorResponse},
+    browse::{default_recent, PaperBriefResponse},
+};
+use axum::http::StatusCode;
+use derive_new::new;
+use garde::Validate;
+use kernel::models::{
+    academic_paper::AuthorListOptions,
+    author::{AuthorSummary, CoauthorEdge, CoauthorshipGraph},
+    list::PaginatedList,
+};
+use serde::{Deserialize, Serialize};
+use shared::id::AuthorId;
+
+fn default_graph_limit() -> Option<i64> {
+    Some(50)
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct AuthorSummaryResponse {
+    #[serde(flatten)]
+    pub author: AuthorResponse,
+    /// The number of papers of the author in the library.
+    pub paper_count: i64,
+    /// The most recently added papers, newest first; empty except in the author list.
+    pub recent_papers: Vec<PaperBriefResponse>,
+}
+
+impl From<AuthorSummary> for AuthorSummaryResponse {
+    fn from(summary: AuthorSummary) -> Self {
+        let AuthorSummary {
+            author,
+            paper_count,
+            recent_papers,
+        } = summary;
+        Self {
+            author: AuthorResponse::from(author),
+            paper_count,
+            recent_papers: recent_papers.into_iter().map(PaperBriefResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct AuthorListResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<AuthorSummaryResponse>,
+    pub status_code: usize,
+}
+
+impl From<PaginatedList<AuthorSummary>> for AuthorListResponse {
+    fn from(paginated_list: PaginatedList<AuthorSummary>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = paginated_list;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(AuthorSummaryResponse::from).collect(),
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct AuthorListQuery {
+    #[garde(skip)]
+    pub name: Option<String>,
+    /// The number of the most recently added papers listed for each author.
+    #[garde(range(min = 0, max = 20))]
+    #[serde(default = "default_recent")]
+    pub recent: Option<i64>,
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: Option<i64>,
+    #[garde(range(min = 0))]
+    #[serde(default = "default_offset")]
+    pub offset: Option<i64>,
+}
+
+impl From<AuthorListQuery> for AuthorListOptions {
+    fn from(query: AuthorListQuery) -> Self {
+        let AuthorListQuery {
+            name,
+            recent,
+            limit,
+            offset,
+        } = query;
+        Self {
+            name: name.filter(|name| !name.is_empty()),
+            recent_count: recent.expect("Recent must be provided"),
+            limit: limit.expect("Limit must be provided"),
+            offset: offset.expect("Offset must be provided"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct AuthorIdQuery {
+    pub author_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct AuthorDetailResponse {
+    pub author: AuthorSummaryResponse,
+    /// The papers of the author in the library, newest first.
+    pub papers: Vec<AcademicPaperResponse>,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct CoauthorshipQuery {
+    /// Defaults to the network of the most prolific authors.
+    #[garde(skip)]
+    pub author_id: Option<String>,
+    #[garde(range(min = 1, max = 500))]
+    #[serde(default = "default_graph_limit")]
+    pub limit: Option<i64>,
+}
+
+/// `source` and `target` wrote `paper_count` papers together.
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct CoauthorEdgeResponse {
+    pub source: AuthorId,
+    pub target: AuthorId,
+    pub paper_count: i64,
+}
+
+impl From<CoauthorEdge> for CoauthorEdgeResponse {
+    fn from(edge: CoauthorEdge) -> Self {
+        let CoauthorEdge {
+            author_id,
+            coauthor_id,
+            paper_count,
+        } = edge;
+        Self {
+            source: author_id,
+            target: coauthor_id,
+            paper_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct CoauthorshipGraphResponse {
+    pub nodes: Vec<AuthorSummaryResponse>,
+    pub edges: Vec<CoauthorEdgeResponse>,
+    pub status_code: usize,
+}
+
+impl From<CoauthorshipGraph> for CoauthorshipGraphResponse {
+    fn from(graph: CoauthorshipGraph) -> Self {
+        let CoauthorshipGraph { nodes, edges } = graph;
+        Self {
+            nodes: nodes.into_iter().map(AuthorSummaryResponse::from).collect(),
+            edges: edges.into_iter().map(CoauthorEdgeResponse::from).collect(),
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct AuthorMergeRequest {
+    /// The author to keep.
+    pub author_id: String,
+    /// The author whose papers are moved to `author_id` before it is deleted.
+    pub duplicate_id: String,
+}
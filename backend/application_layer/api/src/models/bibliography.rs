@@ -0,0 +1,32 @@
+use derive_new::new;
+use garde::Validate;
+use kernel::models::{academic_paper::Status, bibliography::BibliographyFormat};
+use serde::{Deserialize, Serialize};
+
+/// Selects the papers to export; without `paper_ids`, `keyword` or `author_id` the whole library is exported.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate, new)]
+pub struct LibraryExportQuery {
+    #[garde(skip)]
+    #[serde(default)]
+    pub format: BibliographyFormat,
+    /// Comma-separated paper IDs.
+    #[garde(skip)]
+    pub paper_ids: Option<String>,
+    #[garde(inner(length(min = 1)))]
+    pub keyword: Option<String>,
+    #[garde(skip)]
+    pub author_id: Option<String>,
+    #[garde(skip)]
+    pub status: Option<Status>,
+}
+
+impl LibraryExportQuery {
+    pub fn paper_ids(&self) -> Vec<&str> {
+        self.paper_ids
+            .iter()
+            .flat_map(|ids| ids.split(','))
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .collect()
+    }
+}
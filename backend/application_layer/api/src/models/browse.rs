@@ -0,0 +1,369 @@
+use crate::models::academic_paper::{default_limit, default_offset, JournalResponse, TaskResponse};
+use axum::http::StatusCode;
+use chrono::{DateTime, Local, NaiveDate};
+use derive_new::new;
+use garde::Validate;
+use kernel::models::{
+    browse::{BrowseListOptions, JournalSummary, MonthlyPaperCount, PaperBrief, TaskSummary, TaskTrend},
+    list::PaginatedList,
+    task::{SimilarTasks, TaskEntry, TaskMatchReason},
+    venue::{VenueNormalization, VenueTier},
+};
+use serde::{Deserialize, Serialize};
+use shared::id::AcademicPaperId;
+
+pub fn default_recent() -> Option<i64> {
+    Some(3)
+}
+fn default_months() -> Option<i64> {
+    Some(12)
+}
+fn default_trend_limit() -> Option<i64> {
+    Some(10)
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperBriefResponse {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub published_date: NaiveDate,
+    pub created_at: DateTime<Local>,
+}
+
+impl From<PaperBrief> for PaperBriefResponse {
+    fn from(paper: PaperBrief) -> Self {
+        let PaperBrief {
+            paper_id,
+            title,
+            published_date,
+            created_at,
+        } = paper;
+        Self {
+            paper_id,
+            title,
+            published_date,
+            created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct BrowseListQuery {
+    #[garde(skip)]
+    pub name: Option<String>,
+    /// The number of the most recently added papers listed for each entry.
+    #[garde(range(min = 0, max = 20))]
+    #[serde(default = "default_recent")]
+    pub recent: Option<i64>,
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: Option<i64>,
+    #[garde(range(min = 0))]
+    #[serde(default = "default_offset")]
+    pub offset: Option<i64>,
+}
+
+impl From<BrowseListQuery> for BrowseListOptions {
+    fn from(query: BrowseListQuery) -> Self {
+        let BrowseListQuery {
+            name,
+            recent,
+            limit,
+            offset,
+        } = query;
+        Self {
+            name: name.filter(|name| !name.is_empty()),
+            recent_count: recent.expect("Recent must be provided"),
+            limit: limit.expect("Limit must be provided"),
+            offset: offset.expect("Offset must be provided"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct TaskSummaryResponse {
+    #[serde(flatten)]
+    pub task: TaskResponse,
+    pub paper_count: i64,
+    /// The most recently added papers, newest first.
+    pub recent_papers: Vec<PaperBriefResponse>,
+}
+
+impl From<TaskSummary> for TaskSummaryResponse {
+    fn from(summary: TaskSummary) -> Self {
+        let TaskSummary {
+            task,
+            paper_count,
+            recent_papers,
+        } = summary;
+        Self {
+            task: TaskResponse::from(task),
+            paper_count,
+            recent_papers: recent_papers.into_iter().map(PaperBriefResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct TaskListResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<TaskSummaryResponse>,
+    pub status_code: usize,
+}
+
+impl From<PaginatedList<TaskSummary>> for TaskListResponse {
+    fn from(paginated_list: PaginatedList<TaskSummary>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = paginated_list;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(TaskSummaryResponse::from).collect(),
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct JournalSummaryResponse {
+    #[serde(flatten)]
+    pub journal: JournalResponse,
+    pub paper_count: i64,
+    /// The most recently added papers, newest first.
+    pub recent_papers: Vec<PaperBriefResponse>,
+}
+
+impl From<JournalSummary> for JournalSummaryResponse {
+    fn from(summary: JournalSummary) -> Self {
+        let JournalSummary {
+            journal,
+            paper_count,
+            recent_papers,
+        } = summary;
+        Self {
+            journal: JournalResponse::from(journal),
+            paper_count,
+            recent_papers: recent_papers.into_iter().map(PaperBriefResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct JournalListResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<JournalSummaryResponse>,
+    pub status_code: usize,
+}
+
+impl From<PaginatedList<JournalSummary>> for JournalListResponse {
+    fn from(paginated_list: PaginatedList<JournalSummary>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = paginated_list;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(JournalSummaryResponse::from).collect(),
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct TaskTrendQuery {
+    /// The number of months up to the current one.
+    #[garde(range(min = 1, max = 60))]
+    #[serde(default = "default_months")]
+    pub months: Option<i64>,
+    /// The number of tasks, those with the most papers added in the months.
+    #[garde(range(min = 1, max = 50))]
+    #[serde(default = "default_trend_limit")]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct MonthlyPaperCountResponse {
+    pub month: NaiveDate,
+    pub paper_count: i64,
+}
+
+impl From<MonthlyPaperCount> for MonthlyPaperCountResponse {
+    fn from(count: MonthlyPaperCount) -> Self {
+        let MonthlyPaperCount { month, paper_count } = count;
+        Self { month, paper_count }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct TaskTrendResponse {
+    #[serde(flatten)]
+    pub task: TaskResponse,
+    pub total: i64,
+    pub months: Vec<MonthlyPaperCountResponse>,
+}
+
+impl From<TaskTrend> for TaskTrendResponse {
+    fn from(trend: TaskTrend) -> Self {
+        let TaskTrend { task, total, months } = trend;
+        Self {
+            task: TaskResponse::from(task),
+            total,
+            months: months.into_iter().map(MonthlyPaperCountResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct TaskTrendListResponse {
+    /// The first day of the first month.
+    pub since: NaiveDate,
+    pub items: Vec<TaskTrendResponse>,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct SimilarTasksQuery {
+    /// Also compares the names of the most used tasks by embedding.
+    #[garde(skip)]
+    #[serde(default)]
+    pub embedding: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct TaskEntryResponse {
+    #[serde(flatten)]
+    pub task: TaskResponse,
+    /// The other names the task was extracted under.
+    pub aliases: Vec<String>,
+    pub paper_count: i64,
+}
+
+impl From<TaskEntry> for TaskEntryResponse {
+    fn from(entry: TaskEntry) -> Self {
+        let TaskEntry {
+            task,
+            aliases,
+            paper_count,
+            ..
+        } = entry;
+        Self {
+            task: TaskResponse::from(task),
+            aliases,
+            paper_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct SimilarTasksResponse {
+    /// The task with more papers, to keep.
+    pub task: TaskEntryResponse,
+    pub duplicate: TaskEntryResponse,
+    pub reason: TaskMatchReason,
+}
+
+impl From<SimilarTasks> for SimilarTasksResponse {
+    fn from(similar: SimilarTasks) -> Self {
+        let SimilarTasks {
+            task,
+            duplicate,
+            reason,
+        } = similar;
+        Self {
+            task: TaskEntryResponse::from(task),
+            duplicate: TaskEntryResponse::from(duplicate),
+            reason,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct SimilarTasksListResponse {
+    pub items: Vec<SimilarTasksResponse>,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct TaskMergeRequest {
+    /// The task to keep.
+    pub task_id: String,
+    /// The task whose papers and names are moved to `task_id` before it is deleted.
+    pub duplicate_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, new)]
+pub struct VenueTierRequest {
+    /// A name of the venue, e.g. `NeurIPS` or `Advances in Neural Information Processing Systems`.
+    #[garde(length(min = 1, max = 255))]
+    pub name: String,
+    /// The tier or rank, e.g. `A*` or `Q1`; `null` clears it.
+    #[garde(inner(length(min = 1, max = 32)))]
+    pub tier: Option<String>,
+}
+
+impl From<VenueTierRequest> for VenueTier {
+    fn from(request: VenueTierRequest) -> Self {
+        let VenueTierRequest { name, tier } = request;
+        Self::new(name, tier)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, new)]
+pub struct VenueTiersRequest {
+    #[garde(length(min = 1), dive)]
+    pub tiers: Vec<VenueTierRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct JournalsResponse {
+    pub items: Vec<JournalResponse>,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct JournalMergeRequest {
+    /// The venue to keep.
+    pub journal_id: String,
+    /// The venue whose papers and names are moved to `journal_id` before it is deleted.
+    pub duplicate_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct VenueNormalizationResponse {
+    /// The venues renamed to their canonical name or classified.
+    pub normalized: i64,
+    /// The venues merged into another one with the same canonical name.
+    pub merged: i64,
+    /// The papers whose venue year was filled.
+    pub venue_years: i64,
+    pub status_code: usize,
+}
+
+impl From<VenueNormalization> for VenueNormalizationResponse {
+    fn from(normalization: VenueNormalization) -> Self {
+        let VenueNormalization {
+            normalized,
+            merged,
+            venue_years,
+        } = normalization;
+        Self {
+            normalized,
+            merged,
+            venue_years,
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::models::academic_paper::{default_limit, AcademicPaperResponse};
+use axum::http::StatusCode;
+use derive_new::new;
+use garde::Validate;
+use kernel::models::citation::{CitationEdge, CitationGraph, CitationNode, CitedReference};
+use serde::{Deserialize, Serialize};
+use shared::id::AcademicPaperId;
+
+fn default_hops() -> Option<i32> {
+    Some(1)
+}
+fn default_max_nodes() -> Option<i64> {
+    Some(200)
+}
+
+/// A paper given by its library ID or by its Semantic Scholar ID.
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct CitationPaperQuery {
+    #[garde(skip)]
+    pub paper_id: Option<String>,
+    #[garde(skip)]
+    pub ss_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct CitationGraphQuery {
+    #[garde(skip)]
+    pub paper_id: Option<String>,
+    #[garde(skip)]
+    pub ss_id: Option<String>,
+    #[garde(range(min = 1, max = 3))]
+    #[serde(default = "default_hops")]
+    pub hops: Option<i32>,
+    #[garde(range(min = 1, max = 1000))]
+    #[serde(default = "default_max_nodes")]
+    pub max_nodes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct MostCitedReferencesQuery {
+    #[garde(range(min = 1, max = 200))]
+    #[serde(default = "default_limit")]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct CitingPapersResponse {
+    pub ss_id: String,
+    pub items: Vec<AcademicPaperResponse>,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct CitationNodeResponse {
+    /// The Semantic Scholar ID of the paper.
+    pub id: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub paper_id: Option<AcademicPaperId>,
+    pub in_library: bool,
+}
+
+impl From<CitationNode> for CitationNodeResponse {
+    fn from(node: CitationNode) -> Self {
+        let CitationNode {
+            ss_id,
+            title,
+            year,
+            paper_id,
+        } = node;
+        Self {
+            id: ss_id,
+            title,
+            year,
+            paper_id,
+            in_library: paper_id.is_some(),
+        }
+    }
+}
+
+/// `source` cites `target`.
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct CitationEdgeResponse {
+    pub source: String,
+    pub target: String,
+}
+
+impl From<CitationEdge> for CitationEdgeResponse {
+    fn from(edge: CitationEdge) -> Self {
+        Self {
+            source: edge.citing_ss_id,
+            target: edge.cited_ss_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct CitationGraphResponse {
+    pub nodes: Vec<CitationNodeResponse>,
+    pub edges: Vec<CitationEdgeResponse>,
+    pub status_code: usize,
+}
+
+impl From<CitationGraph> for CitationGraphResponse {
+    fn from(graph: CitationGraph) -> Self {
+        let CitationGraph { nodes, edges } = graph;
+        Self {
+            nodes: nodes.into_iter().map(CitationNodeResponse::from).collect(),
+            edges: edges.into_iter().map(CitationEdgeResponse::from).collect(),
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct CitedReferenceResponse {
+    #[serde(flatten)]
+    pub node: CitationNodeResponse,
+    /// The number of papers in the library that cite this paper.
+    pub cited_by_count: i64,
+}
+
+impl From<CitedReference> for CitedReferenceResponse {
+    fn from(reference: CitedReference) -> Self {
+        let CitedReference { node, cited_by_count } = reference;
+        Self {
+            node: CitationNodeResponse::from(node),
+            cited_by_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct MostCitedReferencesResponse {
+    pub items: Vec<CitedReferenceResponse>,
+    pub status_code: usize,
+}
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Local};
+use derive_new::new;
+use garde::Validate;
+use kernel::models::keyword::{Keyword, KeywordCategory};
+use serde::{Deserialize, Serialize};
+use shared::id::{AcademicPaperId, KeywordId};
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct KeywordResponse {
+    pub keyword_id: KeywordId,
+    pub category: KeywordCategory,
+    pub name: String,
+    pub paper_count: i64,
+    pub created_at: DateTime<Local>,
+}
+
+impl From<Keyword> for KeywordResponse {
+    fn from(keyword: Keyword) -> Self {
+        let Keyword {
+            keyword_id,
+            category,
+            name,
+            paper_count,
+            created_at,
+        } = keyword;
+        Self {
+            keyword_id,
+            category,
+            name,
+            paper_count,
+            created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct KeywordListQuery {
+    pub category: KeywordCategory,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperKeywordsQuery {
+    pub paper_id: AcademicPaperId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct KeywordListResponse {
+    pub items: Vec<KeywordResponse>,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, new)]
+pub struct KeywordCreateRequest {
+    #[garde(skip)]
+    pub category: KeywordCategory,
+    #[garde(length(min = 1, max = 255))]
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, new)]
+pub struct KeywordRenameRequest {
+    #[garde(skip)]
+    pub keyword_id: KeywordId,
+    #[garde(length(min = 1, max = 255))]
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct KeywordUpdateResponse {
+    pub keyword: KeywordResponse,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct KeywordDeleteRequest {
+    pub keyword_id: KeywordId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct KeywordDeleteResponse {
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct KeywordPapersRequest {
+    pub keyword_id: KeywordId,
+    pub paper_ids: Vec<AcademicPaperId>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, new)]
+pub struct KeywordSearchAddRequest {
+    #[garde(skip)]
+    pub keyword_id: KeywordId,
+    /// The search words, matched against the titles and abstracts as in the bibliography search.
+    #[garde(length(min = 1))]
+    pub query: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct KeywordPapersResponse {
+    pub keyword_id: KeywordId,
+    /// The number of papers added or removed.
+    pub count: u64,
+    pub status_code: usize,
+}
@@ -0,0 +1,78 @@
+use derive_new::new;
+use garde::Validate;
+use kernel::models::literature_review::{LiteratureReview, ReviewApproach, ReviewSection};
+use serde::{Deserialize, Serialize};
+
+/// Selects the papers to review: `paper_ids`, or else the papers matching `keyword`, or else those with `tag` or in `collection`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate, new)]
+pub struct LiteratureReviewQuery {
+    /// Comma-separated paper IDs.
+    #[garde(skip)]
+    pub paper_ids: Option<String>,
+    #[garde(inner(length(min = 1)))]
+    pub keyword: Option<String>,
+    #[garde(inner(length(min = 1)))]
+    pub tag: Option<String>,
+    #[garde(inner(length(min = 1)))]
+    pub collection: Option<String>,
+}
+
+impl LiteratureReviewQuery {
+    pub fn paper_ids(&self) -> Vec<&str> {
+        self.paper_ids
+            .iter()
+            .flat_map(|ids| ids.split(','))
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct ReviewApproachResponse {
+    pub name: String,
+    pub paragraph: String,
+}
+
+impl From<ReviewApproach> for ReviewApproachResponse {
+    fn from(approach: ReviewApproach) -> Self {
+        let ReviewApproach { name, paragraph } = approach;
+        Self { name, paragraph }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct ReviewSectionResponse {
+    pub title: String,
+    pub approaches: Vec<ReviewApproachResponse>,
+}
+
+impl From<ReviewSection> for ReviewSectionResponse {
+    fn from(section: ReviewSection) -> Self {
+        let ReviewSection { title, approaches } = section;
+        Self {
+            title,
+            approaches: approaches.into_iter().map(ReviewApproachResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct LiteratureReviewResponse {
+    pub sections: Vec<ReviewSectionResponse>,
+    /// The draft with pandoc citations `[@key]`.
+    pub markdown: String,
+    /// The BibTeX entries of the reviewed papers, whose keys the draft cites.
+    pub bibtex: String,
+}
+
+impl From<(LiteratureReview, String)> for LiteratureReviewResponse {
+    fn from((review, bibtex): (LiteratureReview, String)) -> Self {
+        let markdown = review.to_markdown();
+        Self {
+            sections: review.sections.into_iter().map(ReviewSectionResponse::from).collect(),
+            markdown,
+            bibtex,
+        }
+    }
+}
@@ -1,4 +1,16 @@
 pub mod academic_paper;
+pub mod author;
+pub mod bibliography;
+pub mod browse;
+pub mod citation;
 pub mod health;
+pub mod keyword;
+pub mod literature_review;
+pub mod paper_candidate;
+pub mod paper_comparison;
+pub mod paper_duplicate;
+pub mod paper_metadata;
 pub mod paper_note;
+pub mod paper_version;
+pub mod reading;
 pub mod web_article;
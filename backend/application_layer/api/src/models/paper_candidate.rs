@@ -0,0 +1,131 @@
+use crate::models::academic_paper::{default_limit, default_offset};
+use axum::http::StatusCode;
+use chrono::{DateTime, Local};
+use derive_new::new;
+use garde::Validate;
+use kernel::models::{
+    language::Language,
+    list::PaginatedList,
+    paper_candidate::{CandidateStatus, PaperCandidate, PaperCandidateListOptions},
+};
+use serde::{Deserialize, Serialize};
+use shared::id::{AcademicPaperId, PaperCandidateId};
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperCandidateResponse {
+    pub candidate_id: PaperCandidateId,
+    pub arxiv_id: String,
+    pub title: String,
+    pub abstract_text: String,
+    pub authors: Vec<String>,
+    pub categories: Vec<String>,
+    pub pdf_url: String,
+    pub published_date: DateTime<Local>,
+    pub score: i32,
+    pub matched: Vec<String>,
+    pub status: CandidateStatus,
+    pub paper_id: Option<AcademicPaperId>,
+}
+
+impl From<PaperCandidate> for PaperCandidateResponse {
+    fn from(candidate: PaperCandidate) -> Self {
+        let PaperCandidate {
+            candidate_id,
+            arxiv_id,
+            title,
+            abstract_text,
+            authors,
+            categories,
+            pdf_url,
+            published_date,
+            score,
+            matched,
+            status,
+            paper_id,
+        } = candidate;
+        Self {
+            candidate_id,
+            arxiv_id,
+            title,
+            abstract_text,
+            authors,
+            categories,
+            pdf_url,
+            published_date,
+            score,
+            matched,
+            status,
+            paper_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct PaperCandidateListResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<PaperCandidateResponse>,
+    pub status_code: usize,
+}
+
+impl From<PaginatedList<PaperCandidate>> for PaperCandidateListResponse {
+    fn from(paginated_list: PaginatedList<PaperCandidate>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = paginated_list;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(PaperCandidateResponse::from).collect(),
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct PaperCandidateListQuery {
+    /// Defaults to the candidates that have not been added or dismissed.
+    #[garde(skip)]
+    #[serde(default)]
+    pub status: Option<CandidateStatus>,
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: Option<i64>,
+    #[garde(range(min = 0))]
+    #[serde(default = "default_offset")]
+    pub offset: Option<i64>,
+}
+
+impl From<PaperCandidateListQuery> for PaperCandidateListOptions {
+    fn from(query: PaperCandidateListQuery) -> Self {
+        let PaperCandidateListQuery { status, limit, offset } = query;
+        Self {
+            status: Some(status.unwrap_or_default()),
+            limit: limit.expect("Limit must be provided"),
+            offset: offset.expect("Offset must be provided"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperCandidatePromoteRequest {
+    pub candidate_id: PaperCandidateId,
+    /// The language of the generated fields; defaults to `OUTPUT_LANGUAGE`.
+    pub language: Option<Language>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperCandidateDismissRequest {
+    pub candidate_id: PaperCandidateId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperCandidateDismissResponse {
+    pub candidate: PaperCandidateResponse,
+    pub status_code: usize,
+}
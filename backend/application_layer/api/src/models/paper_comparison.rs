@@ -0,0 +1,143 @@
+use crate::models::academic_paper::{default_limit, default_offset};
+use axum::http::StatusCode;
+use chrono::{DateTime, Local};
+use derive_new::new;
+use garde::Validate;
+use kernel::models::{
+    language::Language,
+    list::PaginatedList,
+    paper_comparison::{ComparedPaper, ComparisonRow, PaperComparison, PaperComparisonListOptions},
+};
+use serde::{Deserialize, Serialize};
+use shared::id::{AcademicPaperId, PaperComparisonId};
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, new)]
+pub struct PaperComparisonRequest {
+    /// The papers in the order of the columns of the matrix; 2 to 10 of them.
+    #[garde(length(min = 2, max = 10))]
+    pub paper_ids: Vec<AcademicPaperId>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct ComparedPaperResponse {
+    /// `null` once the paper is deleted from the library.
+    pub paper_id: Option<AcademicPaperId>,
+    pub title: String,
+}
+
+impl From<ComparedPaper> for ComparedPaperResponse {
+    fn from(paper: ComparedPaper) -> Self {
+        let ComparedPaper { paper_id, title } = paper;
+        Self { paper_id, title }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct ComparisonRowResponse {
+    pub dimension: String,
+    pub values: Vec<String>,
+}
+
+impl From<ComparisonRow> for ComparisonRowResponse {
+    fn from(row: ComparisonRow) -> Self {
+        let ComparisonRow { dimension, values } = row;
+        Self { dimension, values }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperComparisonResponse {
+    pub paper_comparison_id: PaperComparisonId,
+    pub papers: Vec<ComparedPaperResponse>,
+    pub rows: Vec<ComparisonRowResponse>,
+    pub synthesis: String,
+    pub language: Language,
+    pub model: String,
+    pub created_at: DateTime<Local>,
+}
+
+impl From<PaperComparison> for PaperComparisonResponse {
+    fn from(comparison: PaperComparison) -> Self {
+        let PaperComparison {
+            paper_comparison_id,
+            papers,
+            rows,
+            synthesis,
+            language,
+            model,
+            created_at,
+        } = comparison;
+        Self {
+            paper_comparison_id,
+            papers: papers.into_iter().map(ComparedPaperResponse::from).collect(),
+            rows: rows.into_iter().map(ComparisonRowResponse::from).collect(),
+            synthesis,
+            language,
+            model,
+            created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperComparisonDetailResponse {
+    pub comparison: PaperComparisonResponse,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperComparisonIdQuery {
+    pub paper_comparison_id: PaperComparisonId,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct PaperComparisonListQuery {
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: Option<i64>,
+    #[garde(range(min = 0))]
+    #[serde(default = "default_offset")]
+    pub offset: Option<i64>,
+}
+
+impl From<PaperComparisonListQuery> for PaperComparisonListOptions {
+    fn from(query: PaperComparisonListQuery) -> Self {
+        let PaperComparisonListQuery { limit, offset } = query;
+        Self {
+            limit: limit.expect("Limit must be provided"),
+            offset: offset.expect("Offset must be provided"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct PaperComparisonListResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<PaperComparisonResponse>,
+    pub status_code: usize,
+}
+
+impl From<PaginatedList<PaperComparison>> for PaperComparisonListResponse {
+    fn from(paginated_list: PaginatedList<PaperComparison>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = paginated_list;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(PaperComparisonResponse::from).collect(),
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperComparisonDeleteResponse {
+    pub status_code: usize,
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Local};
+use derive_new::new;
+use kernel::models::paper_duplicate::{DuplicateGroup, DuplicatePaper, DuplicateReason};
+use serde::{Deserialize, Serialize};
+use shared::id::AcademicPaperId;
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct DuplicatePaperResponse {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub ss_id: String,
+    pub arxiv_id: String,
+    pub doi: String,
+    pub created_at: DateTime<Local>,
+}
+
+impl From<DuplicatePaper> for DuplicatePaperResponse {
+    fn from(paper: DuplicatePaper) -> Self {
+        let DuplicatePaper {
+            paper_id,
+            title,
+            ss_id,
+            arxiv_id,
+            doi,
+            created_at,
+        } = paper;
+        Self {
+            paper_id,
+            title,
+            ss_id,
+            arxiv_id,
+            doi,
+            created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct DuplicateGroupResponse {
+    /// The oldest paper first; it is the one to keep by default.
+    pub papers: Vec<DuplicatePaperResponse>,
+    pub reasons: Vec<DuplicateReason>,
+}
+
+impl From<DuplicateGroup> for DuplicateGroupResponse {
+    fn from(group: DuplicateGroup) -> Self {
+        let DuplicateGroup { papers, reasons } = group;
+        Self {
+            papers: papers.into_iter().map(DuplicatePaperResponse::from).collect(),
+            reasons,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct DuplicateGroupListResponse {
+    pub items: Vec<DuplicateGroupResponse>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct AcademicPaperMergeRequest {
+    /// The paper to keep.
+    pub paper_id: String,
+    /// The paper whose notes, tags and relations are moved to `paper_id` before it is deleted.
+    pub duplicate_id: String,
+}
@@ -0,0 +1,88 @@
+use crate::models::academic_paper::default_limit;
+use chrono::{DateTime, Local};
+use derive_new::new;
+use garde::Validate;
+use kernel::models::paper_metadata::{CitationGain, MetadataChange};
+use serde::{Deserialize, Serialize};
+use shared::id::AcademicPaperId;
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct MetadataHistoryQuery {
+    #[garde(skip)]
+    pub paper_id: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct CitationGainsQuery {
+    #[garde(range(min = 1, max = 200))]
+    #[serde(default = "default_limit")]
+    pub limit: Option<i64>,
+    /// Defaults to the start of the current month.
+    #[garde(skip)]
+    pub since: Option<DateTime<Local>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct MetadataChangeResponse {
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub changed_at: DateTime<Local>,
+}
+
+impl From<MetadataChange> for MetadataChangeResponse {
+    fn from(change: MetadataChange) -> Self {
+        let MetadataChange {
+            field,
+            old_value,
+            new_value,
+            changed_at,
+            ..
+        } = change;
+        Self {
+            field,
+            old_value,
+            new_value,
+            changed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct MetadataHistoryResponse {
+    pub paper_id: String,
+    pub items: Vec<MetadataChangeResponse>,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct CitationGainResponse {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub citations_count: i32,
+    pub citations_gained: i64,
+}
+
+impl From<CitationGain> for CitationGainResponse {
+    fn from(gain: CitationGain) -> Self {
+        let CitationGain {
+            paper_id,
+            title,
+            citations_count,
+            citations_gained,
+        } = gain;
+        Self {
+            paper_id,
+            title,
+            citations_count,
+            citations_gained,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct CitationGainsResponse {
+    pub since: DateTime<Local>,
+    pub items: Vec<CitationGainResponse>,
+    pub status_code: usize,
+}
@@ -1,9 +1,9 @@
 use axum::http::StatusCode;
 use chrono::{DateTime, Local};
 use derive_new::new;
-use kernel::models::paper_note::PaperNote;
+use kernel::models::paper_note::{PaperNote, PaperNoteMessage, PaperNoteMessageRole};
 use serde::{Deserialize, Serialize};
-use shared::id::{AcademicPaperId, PaperNoteId};
+use shared::id::{AcademicPaperId, PaperNoteId, PaperNoteMessageId};
 
 #[derive(Debug, Clone, Deserialize, Serialize, new)]
 pub struct PaperNoteResponse {
@@ -125,3 +125,73 @@ pub struct PaperNoteAskToAgentRequest {
     pub paper_note_id: PaperNoteId,
     pub query: String,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PaperNoteMessageResponse {
+    pub paper_note_message_id: PaperNoteMessageId,
+    pub paper_note_id: PaperNoteId,
+    pub position: i32,
+    pub role: PaperNoteMessageRole,
+    pub content: String,
+    pub model: String,
+    pub created_at: DateTime<Local>,
+    pub updated_at: DateTime<Local>,
+}
+
+impl From<PaperNoteMessage> for PaperNoteMessageResponse {
+    fn from(message: PaperNoteMessage) -> Self {
+        let PaperNoteMessage {
+            paper_note_message_id,
+            paper_note_id,
+            position,
+            role,
+            content,
+            model,
+            created_at,
+            updated_at,
+        } = message;
+        Self {
+            paper_note_message_id,
+            paper_note_id,
+            position,
+            role,
+            content,
+            model,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperNoteMessagesRequest {
+    pub paper_note_id: PaperNoteId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperNoteMessagesResponse {
+    pub paper_note_id: PaperNoteId,
+    pub items: Vec<PaperNoteMessageResponse>,
+    pub status_code: usize,
+}
+
+impl PaperNoteMessagesResponse {
+    pub fn from_messages(paper_note_id: PaperNoteId, messages: Vec<PaperNoteMessage>) -> Self {
+        Self {
+            paper_note_id,
+            items: messages.into_iter().map(PaperNoteMessageResponse::from).collect(),
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperNoteMessageRequest {
+    pub paper_note_message_id: PaperNoteMessageId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperNoteMessageRegenerateResponse {
+    pub message: PaperNoteMessageResponse,
+    pub status_code: usize,
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Local};
+use derive_new::new;
+use kernel::models::paper_version::PaperVersion;
+use serde::{Deserialize, Serialize};
+use shared::id::AcademicPaperId;
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct PaperVersionResponse {
+    pub version: i32,
+    pub submitted_at: Option<DateTime<Local>>,
+    pub changed_sections: Vec<String>,
+    pub change_summary: String,
+    pub detected_at: DateTime<Local>,
+}
+
+impl From<PaperVersion> for PaperVersionResponse {
+    fn from(version: PaperVersion) -> Self {
+        let PaperVersion {
+            version,
+            submitted_at,
+            changed_sections,
+            change_summary,
+            detected_at,
+            ..
+        } = version;
+        Self {
+            version,
+            submitted_at,
+            changed_sections,
+            change_summary,
+            detected_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct PaperVersionListResponse {
+    pub paper_id: AcademicPaperId,
+    pub items: Vec<PaperVersionResponse>,
+    pub status_code: usize,
+}
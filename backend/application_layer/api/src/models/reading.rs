@@ -0,0 +1,214 @@
+use crate::models::academic_paper::{default_limit, default_offset};
+use axum::http::StatusCode;
+use chrono::{DateTime, Local, NaiveDate};
+use derive_new::new;
+use garde::Validate;
+use kernel::models::{
+    academic_paper::Status,
+    list::PaginatedList,
+    reading::{PaperStatusChange, ReadingQueueItem, ReadingQueueListOptions, WeeklyReadingStats},
+};
+use serde::{Deserialize, Serialize};
+use shared::id::AcademicPaperId;
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperStatusUpdateRequest {
+    pub paper_id: AcademicPaperId,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperStatusChangeResponse {
+    pub paper_id: AcademicPaperId,
+    pub from_status: Option<Status>,
+    pub to_status: Status,
+    pub changed_at: DateTime<Local>,
+}
+
+impl From<PaperStatusChange> for PaperStatusChangeResponse {
+    fn from(change: PaperStatusChange) -> Self {
+        let PaperStatusChange {
+            paper_id,
+            from_status,
+            to_status,
+            changed_at,
+        } = change;
+        Self {
+            paper_id,
+            from_status,
+            to_status,
+            changed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperStatusUpdateResponse {
+    pub change: PaperStatusChangeResponse,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperStatusHistoryQuery {
+    pub paper_id: AcademicPaperId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct PaperStatusHistoryResponse {
+    pub paper_id: AcademicPaperId,
+    pub items: Vec<PaperStatusChangeResponse>,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct ReadingQueueItemResponse {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub url: String,
+    pub status: Status,
+    pub priority: i32,
+    pub due_date: Option<NaiveDate>,
+    pub added_at: DateTime<Local>,
+}
+
+impl From<ReadingQueueItem> for ReadingQueueItemResponse {
+    fn from(item: ReadingQueueItem) -> Self {
+        let ReadingQueueItem {
+            paper_id,
+            title,
+            url,
+            status,
+            priority,
+            due_date,
+            added_at,
+        } = item;
+        Self {
+            paper_id,
+            title,
+            url,
+            status,
+            priority,
+            due_date,
+            added_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct ReadingQueueQuery {
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: Option<i64>,
+    #[garde(range(min = 0))]
+    #[serde(default = "default_offset")]
+    pub offset: Option<i64>,
+}
+
+impl From<ReadingQueueQuery> for ReadingQueueListOptions {
+    fn from(query: ReadingQueueQuery) -> Self {
+        let ReadingQueueQuery { limit, offset } = query;
+        Self {
+            limit: limit.expect("Limit must be provided"),
+            offset: offset.expect("Offset must be provided"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct ReadingQueueResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<ReadingQueueItemResponse>,
+    pub status_code: usize,
+}
+
+impl From<PaginatedList<ReadingQueueItem>> for ReadingQueueResponse {
+    fn from(paginated_list: PaginatedList<ReadingQueueItem>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = paginated_list;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(ReadingQueueItemResponse::from).collect(),
+            status_code: StatusCode::OK.as_u16() as usize,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct ReadingNextResponse {
+    /// `None` when the queue is empty.
+    pub item: Option<ReadingQueueItemResponse>,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct ReadingQueueUpsertRequest {
+    pub paper_id: AcademicPaperId,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub due_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct ReadingQueueUpsertResponse {
+    pub item: ReadingQueueItemResponse,
+    pub status_code: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct ReadingQueueDeleteRequest {
+    pub paper_id: AcademicPaperId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, new)]
+pub struct ReadingQueueDeleteResponse {
+    pub status_code: usize,
+}
+
+fn default_weeks() -> i64 {
+    8
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate, new)]
+pub struct ReadingStatsQuery {
+    /// The number of weeks up to the current one.
+    #[garde(range(min = 1, max = 52))]
+    #[serde(default = "default_weeks")]
+    pub weeks: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct WeeklyReadingStatsResponse {
+    pub week_start: NaiveDate,
+    pub completed: i64,
+    pub deep_dive_hours: f64,
+}
+
+impl From<WeeklyReadingStats> for WeeklyReadingStatsResponse {
+    fn from(stats: WeeklyReadingStats) -> Self {
+        let WeeklyReadingStats {
+            week_start,
+            completed,
+            deep_dive_hours,
+        } = stats;
+        Self {
+            week_start,
+            completed,
+            deep_dive_hours,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, new)]
+pub struct ReadingStatsResponse {
+    pub items: Vec<WeeklyReadingStatsResponse>,
+    pub status_code: usize,
+}
@@ -3,6 +3,7 @@ use chrono::{DateTime, Local};
 use derive_new::new;
 use garde::Validate;
 use kernel::models::{
+    language::{select_translation, Language},
     list::PaginatedList,
     web_article::{WebArticle, WebArticleFilteredOptions, WebArticleListOptions, WebSite, WebSiteListOptions},
 };
@@ -89,10 +90,17 @@ pub struct WebArticleResponse {
     pub is_new_product_related: bool,
     pub is_security_related: bool,
     pub status: String,
+    /// The language of `summary`.
+    pub language: Language,
+    pub available_languages: Vec<Language>,
 }
 
-impl From<WebArticle> for WebArticleResponse {
-    fn from(article: WebArticle) -> Self {
+impl From<(WebArticle, Language)> for WebArticleResponse {
+    fn from((article, preferred): (WebArticle, Language)) -> Self {
+        let translation = select_translation(&article.translations, preferred)
+            .cloned()
+            .unwrap_or_default();
+        let available_languages = article.translations.iter().map(|t| t.language).collect();
         let WebArticle {
             site,
             article_id,
@@ -102,7 +110,7 @@ impl From<WebArticle> for WebArticleResponse {
             text,
             html,
             timestamp,
-            summary,
+            translations: _,
             is_new_technology_related,
             is_new_academic_paper_related,
             is_ai_related,
@@ -110,6 +118,8 @@ impl From<WebArticle> for WebArticleResponse {
             is_new_product_related,
             is_security_related,
             status,
+            enrichment_status: _,
+            enrichment_batch_id: _,
         } = article;
         Self {
             site_id: site.site_id,
@@ -122,7 +132,7 @@ impl From<WebArticle> for WebArticleResponse {
             text,
             html,
             timestamp,
-            summary,
+            summary: translation.summary,
             is_new_technology_related,
             is_new_academic_paper_related,
             is_ai_related,
@@ -130,6 +140,8 @@ impl From<WebArticle> for WebArticleResponse {
             is_new_product_related,
             is_security_related,
             status: status.to_string(),
+            language: translation.language,
+            available_languages,
         }
     }
 }
@@ -160,8 +172,8 @@ pub struct PaginatedWebArticleResponse {
     pub status_code: usize,
 }
 
-impl From<PaginatedList<WebArticle>> for PaginatedWebArticleResponse {
-    fn from(paginated_list: PaginatedList<WebArticle>) -> Self {
+impl From<(PaginatedList<WebArticle>, Language)> for PaginatedWebArticleResponse {
+    fn from((paginated_list, language): (PaginatedList<WebArticle>, Language)) -> Self {
         let PaginatedList {
             total,
             limit,
@@ -172,7 +184,7 @@ impl From<PaginatedList<WebArticle>> for PaginatedWebArticleResponse {
             total,
             limit,
             offset,
-            items: items.into_iter().map(|article| article.into()).collect(),
+            items: items.into_iter().map(|article| (article, language).into()).collect(),
             status_code: StatusCode::OK.as_u16() as usize,
         }
     }
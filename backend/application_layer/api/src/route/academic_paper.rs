@@ -1,9 +1,15 @@
 use crate::{
     handler::academic_paper::{
-        add_academic_paper_with_sse, select_academic_papers_by_id, select_paginated_academic_papers,
-        update_academic_paper_with_sse,
+        add_academic_paper_with_sse, download_paper_pdf, select_academic_papers_by_id,
+        select_paginated_academic_papers, select_paper_sections, update_academic_paper_with_sse,
+    },
+    route::{
+        bibliography::build_bibliography_router, citation::build_citation_router, keyword::build_keyword_router,
+        literature_review::build_literature_review_router, paper_candidate::build_paper_candidate_router,
+        paper_comparison::build_paper_comparison_router, paper_duplicate::build_paper_duplicate_router,
+        paper_metadata::build_paper_metadata_router, paper_note::build_paper_note_router,
+        paper_version::build_paper_version_router, reading::build_reading_router,
     },
-    route::paper_note::build_paper_note_router,
 };
 use axum::{routing::get, Router};
 use registry::AppRegistry;
@@ -13,8 +19,21 @@ pub fn build_academic_paper_router() -> Router<AppRegistry> {
         .route("/select-all", get(select_paginated_academic_papers))
         .route("/select-paper", get(select_academic_papers_by_id))
         .route("/add-sse", get(add_academic_paper_with_sse))
-        .route("/update-sse", get(update_academic_paper_with_sse));
-    let routers = routers.merge(build_paper_note_router());
+        .route("/update-sse", get(update_academic_paper_with_sse))
+        .route("/sections", get(select_paper_sections))
+        .route("/pdf", get(download_paper_pdf));
+    let routers = routers
+        .merge(build_paper_note_router())
+        .merge(build_paper_candidate_router())
+        .merge(build_citation_router())
+        .merge(build_bibliography_router())
+        .merge(build_paper_metadata_router())
+        .merge(build_reading_router())
+        .merge(build_keyword_router())
+        .merge(build_paper_comparison_router())
+        .merge(build_literature_review_router())
+        .merge(build_paper_duplicate_router())
+        .merge(build_paper_version_router());
 
     Router::new().nest("/academic-paper", routers)
 }
@@ -0,0 +1,16 @@
+use crate::handler::author::{merge_authors, select_author_by_id, select_coauthorship_graph, select_paginated_authors};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use registry::AppRegistry;
+
+pub fn build_author_router() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route("/select-all", get(select_paginated_authors))
+        .route("/select-author", get(select_author_by_id))
+        .route("/coauthorship", get(select_coauthorship_graph))
+        .route("/merge", post(merge_authors));
+
+    Router::new().nest("/authors", routers)
+}
@@ -0,0 +1,7 @@
+use crate::handler::bibliography::export_library;
+use axum::{routing::get, Router};
+use registry::AppRegistry;
+
+pub fn build_bibliography_router() -> Router<AppRegistry> {
+    Router::new().route("/export", get(export_library))
+}
@@ -0,0 +1,29 @@
+use crate::handler::browse::{
+    merge_journals, merge_tasks, normalize_journals, select_paginated_journals, select_paginated_tasks,
+    select_similar_tasks, select_task_trends, update_journal_tiers,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use registry::AppRegistry;
+
+pub fn build_task_router() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route("/select-all", get(select_paginated_tasks))
+        .route("/trend", get(select_task_trends))
+        .route("/similar", get(select_similar_tasks))
+        .route("/merge", post(merge_tasks));
+
+    Router::new().nest("/tasks", routers)
+}
+
+pub fn build_journal_router() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route("/select-all", get(select_paginated_journals))
+        .route("/tiers", post(update_journal_tiers))
+        .route("/merge", post(merge_journals))
+        .route("/normalize", post(normalize_journals));
+
+    Router::new().nest("/journals", routers)
+}
@@ -0,0 +1,12 @@
+use crate::handler::citation::{select_citation_graph, select_citing_papers, select_most_cited_references};
+use axum::{routing::get, Router};
+use registry::AppRegistry;
+
+pub fn build_citation_router() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route("/citing", get(select_citing_papers))
+        .route("/most-cited", get(select_most_cited_references))
+        .route("/graph", get(select_citation_graph));
+
+    Router::new().nest("/citation", routers)
+}
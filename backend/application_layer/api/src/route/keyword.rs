@@ -0,0 +1,23 @@
+use crate::handler::keyword::{
+    add_papers_to_keyword, add_search_results_to_keyword, create_keyword, delete_keyword, remove_papers_from_keyword,
+    rename_keyword, select_keywords, select_paper_keywords,
+};
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+use registry::AppRegistry;
+
+pub fn build_keyword_router() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route("/select-all", get(select_keywords))
+        .route("/paper", get(select_paper_keywords))
+        .route("/create", post(create_keyword))
+        .route("/rename", post(rename_keyword))
+        .route("/delete", delete(delete_keyword))
+        .route("/add", post(add_papers_to_keyword))
+        .route("/remove", post(remove_papers_from_keyword))
+        .route("/add-search", post(add_search_results_to_keyword));
+
+    Router::new().nest("/keyword", routers)
+}
@@ -0,0 +1,7 @@
+use crate::handler::literature_review::write_literature_review_with_sse;
+use axum::{routing::get, Router};
+use registry::AppRegistry;
+
+pub fn build_literature_review_router() -> Router<AppRegistry> {
+    Router::new().route("/literature-review-sse", get(write_literature_review_with_sse))
+}
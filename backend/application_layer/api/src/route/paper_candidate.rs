@@ -0,0 +1,17 @@
+use crate::handler::paper_candidate::{
+    dismiss_paper_candidate, promote_paper_candidate, select_paginated_paper_candidates,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use registry::AppRegistry;
+
+pub fn build_paper_candidate_router() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route("/select-all", get(select_paginated_paper_candidates))
+        .route("/promote", post(promote_paper_candidate))
+        .route("/dismiss", post(dismiss_paper_candidate));
+
+    Router::new().nest("/candidate", routers)
+}
@@ -0,0 +1,17 @@
+use crate::handler::paper_comparison::{
+    compare_papers, delete_paper_comparison, export_paper_comparison, select_paper_comparison, select_paper_comparisons,
+};
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+use registry::AppRegistry;
+
+pub fn build_paper_comparison_router() -> Router<AppRegistry> {
+    Router::new()
+        .route("/compare", post(compare_papers))
+        .route("/compare/select", get(select_paper_comparison))
+        .route("/compare/select-all", get(select_paper_comparisons))
+        .route("/compare/markdown", get(export_paper_comparison))
+        .route("/compare/delete", delete(delete_paper_comparison))
+}
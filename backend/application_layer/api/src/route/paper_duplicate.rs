@@ -0,0 +1,12 @@
+use crate::handler::paper_duplicate::{find_duplicate_academic_papers, merge_academic_papers};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use registry::AppRegistry;
+
+pub fn build_paper_duplicate_router() -> Router<AppRegistry> {
+    Router::new()
+        .route("/find-duplicates", get(find_duplicate_academic_papers))
+        .route("/merge", post(merge_academic_papers))
+}
@@ -0,0 +1,11 @@
+use crate::handler::paper_metadata::{select_citation_gains, select_metadata_history};
+use axum::{routing::get, Router};
+use registry::AppRegistry;
+
+pub fn build_paper_metadata_router() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route("/history", get(select_metadata_history))
+        .route("/citation-gains", get(select_citation_gains));
+
+    Router::new().nest("/metadata", routers)
+}
@@ -1,5 +1,6 @@
 use crate::handler::paper_note::{
-    ask_to_agent, create_paper_note, delete_paper_note, select_paper_note, update_paper_note,
+    ask_to_agent, create_paper_note, delete_paper_note, delete_paper_note_message, regenerate_paper_note_message,
+    select_paper_note, select_paper_note_messages, update_paper_note,
 };
 use axum::{
     routing::{delete, get, post, put},
@@ -13,7 +14,10 @@ pub fn build_paper_note_router() -> Router<AppRegistry> {
         .route("/create", post(create_paper_note))
         .route("/update", put(update_paper_note))
         .route("/delete", delete(delete_paper_note))
-        .route("/ask-to-agent", post(ask_to_agent));
+        .route("/ask-to-agent", post(ask_to_agent))
+        .route("/message/select", get(select_paper_note_messages))
+        .route("/message/regenerate", post(regenerate_paper_note_message))
+        .route("/message/delete", delete(delete_paper_note_message));
 
     Router::new().nest("/paper-note", routers)
 }
@@ -0,0 +1,7 @@
+use crate::handler::paper_version::select_paper_versions;
+use axum::{routing::get, Router};
+use registry::AppRegistry;
+
+pub fn build_paper_version_router() -> Router<AppRegistry> {
+    Router::new().route("/versions", get(select_paper_versions))
+}
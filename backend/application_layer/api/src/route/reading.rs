@@ -0,0 +1,25 @@
+use crate::handler::reading::{
+    delete_reading_queue_item, select_next_paper, select_paper_status_history, select_reading_queue,
+    select_reading_stats, update_paper_status, upsert_reading_queue_item,
+};
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use registry::AppRegistry;
+
+pub fn build_reading_router() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route("/status", post(update_paper_status))
+        .route("/history", get(select_paper_status_history))
+        .route(
+            "/queue",
+            get(select_reading_queue)
+                .post(upsert_reading_queue_item)
+                .delete(delete_reading_queue_item),
+        )
+        .route("/next", get(select_next_paper))
+        .route("/stats", get(select_reading_stats));
+
+    Router::new().nest("/reading", routers)
+}
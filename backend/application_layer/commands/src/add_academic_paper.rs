@@ -1,35 +1,78 @@
 use std::sync::Arc;
 
-use academic_paper_crawler::repository::academic_papers::get_academic_paper;
+use academic_paper_crawler::{
+    models::identifier::PaperIdentifier,
+    repository::academic_papers::{get_academic_paper, get_academic_paper_by_identifier},
+};
 use adapter::database::connect_database_with;
 use clap::Parser;
-use kernel::models::academic_paper::AcademicPaper;
+use kernel::models::{academic_paper::AcademicPaper, language::Language};
 use registry::AppRegistryImpl;
 use shared::config::AppConfig;
 
+use crate::fetch_citations::save_citation_graph;
+
 #[derive(Parser, Debug, Clone)]
 #[command(version, about, long_about = None)]
 pub struct AddAcademicPaperArgs {
+    /// arXiv ID/URL, DOI or Semantic Scholar paper ID of the paper to add
     #[arg(long)]
-    title: String,
-    /// The URL of the academic paper to add
+    id: Option<PaperIdentifier>,
+    /// The title to search for when no identifier is given
+    #[arg(long, required_unless_present = "id")]
+    title: Option<String>,
+    /// The URL of the academic paper to add (resolved from the identifier when omitted)
+    #[arg(long, required_unless_present = "id")]
+    pdf_url: Option<String>,
+    /// The language of the generated analysis (defaults to OUTPUT_LANGUAGE)
     #[arg(long)]
-    pdf_url: String,
+    language: Option<Language>,
 }
 
 pub async fn add_academic_paper(args: &AddAcademicPaperArgs) {
     // This function is a placeholder for adding an academic paper
     tracing::info!("Adding academic paper...");
 
+    let config = AppConfig::new().expect("Failed to load config");
+    tracing::info!(
+        "Connecting to database...: {}:{}/{}",
+        config.database.host,
+        config.database.port,
+        config.database.database
+    );
+    let db = connect_database_with(&config.database);
+    let registry = Arc::new(AppRegistryImpl::new(db));
+    let language = args.language.unwrap_or_else(Language::from_env);
+
     // crawler
-    let paper_rsc = get_academic_paper(&args.title, &args.pdf_url)
-        .await
-        .expect("Failed to get academic paper by URL");
+    let paper_rsc = match &args.id {
+        Some(id) => get_academic_paper_by_identifier(id, args.title.as_deref(), args.pdf_url.as_deref()).await,
+        None => {
+            get_academic_paper(
+                args.title.as_deref().unwrap_or_default(),
+                args.pdf_url.as_deref().unwrap_or_default(),
+            )
+            .await
+        }
+    }
+    .expect("Failed to get academic paper");
 
     // kernel
+    let mut tx = registry
+        .db
+        .inner_ref()
+        .begin()
+        .await
+        .expect("Failed to begin transaction");
+    let vocabulary = registry
+        .task_repository()
+        .select_task_vocabulary(&mut tx)
+        .await
+        .expect("Failed to select the task vocabulary");
+    tx.commit().await.expect("Failed to commit transaction");
     let mut paper = AcademicPaper::from(paper_rsc);
     paper
-        .fill_fields_with_ai()
+        .fill_fields_with_ai(registry.llm_provider().as_ref(), language, vocabulary)
         .await
         .expect("Failed to fill fields with AI");
     paper.fill_bibtex().expect("Failed to fill BibTeX");
@@ -39,16 +82,19 @@ pub async fn add_academic_paper(args: &AddAcademicPaperArgs) {
     tracing::info!("ArXiv ID: {}", paper.arxiv_id);
     tracing::info!("Semantic Scholar ID: {}", paper.ss_id);
     tracing::info!("Title: {}", paper.title);
-    tracing::info!("Abstract: {}", paper.abstract_text_ja);
-    tracing::info!("Summary: {}", paper.summary);
-    tracing::info!("Background and Purpose: {}", paper.background_and_purpose);
-    tracing::info!("Methodology: {}", paper.methodology);
-    tracing::info!("Dataset: {}", paper.dataset);
-    tracing::info!("Results: {}", paper.results);
-    tracing::info!(
-        "Advantages, Limitations, and Future Work: {}",
-        paper.advantages_limitations_and_future_work
-    );
+    if let Some(analysis) = paper.translation(language) {
+        tracing::info!("Language: {}", analysis.language);
+        tracing::info!("Abstract: {}", analysis.abstract_text);
+        tracing::info!("Summary: {}", analysis.summary);
+        tracing::info!("Background and Purpose: {}", analysis.background_and_purpose);
+        tracing::info!("Methodology: {}", analysis.methodology);
+        tracing::info!("Dataset: {}", analysis.dataset);
+        tracing::info!("Results: {}", analysis.results);
+        tracing::info!(
+            "Advantages, Limitations, and Future Work: {}",
+            analysis.advantages_limitations_and_future_work
+        );
+    }
     tracing::info!("BibTeX: {}", paper.bibtex);
     tracing::info!("PDF URL: {}", paper.url);
     tracing::info!("Published Date: {}", paper.published_date);
@@ -62,16 +108,6 @@ pub async fn add_academic_paper(args: &AddAcademicPaperArgs) {
     tracing::info!("Tasks: {:?}", paper.tasks);
 
     // Save to DB
-    let config = AppConfig::new().expect("Failed to load config");
-    tracing::info!(
-        "Connecting to database...: {}:{}/{}",
-        config.database.host,
-        config.database.port,
-        config.database.database
-    );
-    let db = connect_database_with(&config.database);
-    let registry = Arc::new(AppRegistryImpl::new(db));
-
     let mut tx = registry
         .db
         .inner_ref()
@@ -80,9 +116,12 @@ pub async fn add_academic_paper(args: &AddAcademicPaperArgs) {
         .expect("Failed to begin transaction");
     let academic_paper_repository = registry.academic_paper_repository();
     match academic_paper_repository.create_academic_paper(&mut tx, paper).await {
-        Ok(_) => {
+        Ok(paper) => {
             tracing::info!("Successfully added academic paper");
             tx.commit().await.expect("Failed to commit transaction");
+            if let Err(e) = save_citation_graph(&registry, &paper).await {
+                tracing::warn!("Failed to fetch citations: {}", e);
+            }
         }
         Err(e) => {
             tracing::error!("Failed to add academic paper: {}", e);
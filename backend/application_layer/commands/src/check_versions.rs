@@ -0,0 +1,244 @@
+use std::{sync::Arc, time::Duration};
+
+use academic_paper_crawler::{
+    models::academic_paper::AcademicPaperResource,
+    repository::arxiv_versions::{fetch_latest_versions, ArxivVersion, ARXIV_ID_BATCH_SIZE},
+};
+use adapter::database::connect_database_with;
+use chrono::Local;
+use clap::Parser;
+use kernel::models::{
+    academic_paper::PaperSection,
+    language::Language,
+    paper_version::{diff_sections, summarize_version_changes, PaperVersion, VersionedPaper},
+};
+use registry::AppRegistryImpl;
+use shared::{
+    config::AppConfig,
+    errors::{AppError, AppResult},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct CheckVersionsArgs {
+    /// Check the papers whose versions were checked more than this many days ago
+    #[arg(long, default_value_t = 7)]
+    stale_days: i64,
+    /// The maximum number of papers to check per run
+    #[arg(long, default_value_t = 2000)]
+    limit: i64,
+    /// Seconds to wait between requests to arXiv, which asks for 3 seconds
+    #[arg(long, default_value_t = 3)]
+    interval_secs: u64,
+    /// Generate the summary and analysis again from the new version
+    #[arg(long, default_value_t = false)]
+    reanalyze: bool,
+    /// Keep running and check again every N hours
+    #[arg(long)]
+    every_hours: Option<u64>,
+    /// The language of the change summaries and the analysis (defaults to OUTPUT_LANGUAGE)
+    #[arg(long)]
+    language: Option<Language>,
+}
+
+/// Fetches the PDF of the new version, records what changed from the stored sections and stores the new version.
+async fn update_to_version(
+    registry: &AppRegistryImpl,
+    paper: &VersionedPaper,
+    latest: &ArxivVersion,
+    args: &CheckVersionsArgs,
+    language: Language,
+) -> AppResult<PaperVersion> {
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let mut stored = registry
+        .academic_paper_repository()
+        .select_academic_paper_by_id(&mut tx, &paper.paper_id.to_string())
+        .await?;
+    let vocabulary = registry.task_repository().select_task_vocabulary(&mut tx).await?;
+    tx.commit().await?;
+
+    let resource = AcademicPaperResource::new(stored.title.clone(), latest.pdf_url(), vec![], None, None, None, None)
+        .parse_pdf_url()
+        .await?;
+    if resource.text.is_empty() {
+        return Err(AppError::InternalServerError(format!(
+            "Failed to parse the PDF of {}",
+            latest.abs_url()
+        )));
+    }
+    let sections = resource
+        .text
+        .iter()
+        .map(|section| PaperSection::new(section.title.clone(), section.content.clone()))
+        .collect::<Vec<PaperSection>>();
+
+    let changes = diff_sections(&stored.sections, &sections);
+    let llm = registry.llm_provider();
+    let summary = summarize_version_changes(
+        llm.as_ref(),
+        &stored.title,
+        paper.known_version(),
+        latest.version,
+        &changes,
+        language,
+    )
+    .await?;
+
+    stored.arxiv_id = latest.abs_url();
+    stored.url = latest.pdf_url();
+    stored.text = sections
+        .iter()
+        .map(|section| format!("# {}\n\n{}", section.title, section.content))
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    stored.sections = sections;
+    stored.pdf_sha256 = resource.pdf_sha256.clone().unwrap_or_default();
+    if args.reanalyze {
+        stored.fill_fields_with_ai(llm.as_ref(), language, vocabulary).await?;
+    }
+
+    let mut tx = registry.db.inner_ref().begin().await?;
+    registry
+        .academic_paper_repository()
+        .update_academic_paper(&mut tx, stored)
+        .await?;
+    let version = registry
+        .paper_version_repository()
+        .create_paper_version(
+            &mut tx,
+            PaperVersion::new(
+                paper.paper_id,
+                latest.version,
+                latest.updated_at,
+                resource.pdf_sha256.unwrap_or_default(),
+                changes.into_iter().map(|change| change.title).collect(),
+                summary,
+                Local::now(),
+            ),
+        )
+        .await?;
+    tx.commit().await?;
+    Ok(version)
+}
+
+/// Checks one batch of papers; returns the number of papers updated to a new version.
+async fn check_batch(
+    registry: &AppRegistryImpl,
+    papers: &[VersionedPaper],
+    args: &CheckVersionsArgs,
+    language: Language,
+) -> AppResult<usize> {
+    let arxiv_ids = papers
+        .iter()
+        .filter_map(VersionedPaper::base_id)
+        .collect::<Vec<String>>();
+    let latest_versions = fetch_latest_versions(&arxiv_ids).await?;
+
+    let mut updated = 0;
+    for paper in papers.iter() {
+        let Some(latest) = paper
+            .base_id()
+            .and_then(|arxiv_id| latest_versions.iter().find(|latest| latest.arxiv_id == arxiv_id))
+        else {
+            continue;
+        };
+        // the first check records the version the stored sections were parsed from;
+        // a paper stored without its version is assumed to be at the latest one
+        let known = paper.known_version().unwrap_or(latest.version);
+        if paper.latest_version.is_none() {
+            let submitted_at = match known {
+                _ if known == latest.version => latest.updated_at,
+                1 => latest.published_at,
+                _ => None,
+            };
+            let mut tx = registry.db.inner_ref().begin().await?;
+            registry
+                .paper_version_repository()
+                .create_paper_version(
+                    &mut tx,
+                    PaperVersion::new(
+                        paper.paper_id,
+                        known,
+                        submitted_at,
+                        String::new(),
+                        vec![],
+                        String::new(),
+                        Local::now(),
+                    ),
+                )
+                .await?;
+            tx.commit().await?;
+        }
+        if known >= latest.version {
+            continue;
+        }
+
+        tracing::info!("{} has a new version: v{} -> v{}", paper.title, known, latest.version);
+        match update_to_version(registry, paper, latest, args, language).await {
+            Ok(version) => {
+                updated += 1;
+                tracing::info!("{} v{}:\n{}", paper.title, version.version, version.change_summary);
+            }
+            // the version is detected again on the next check
+            Err(e) => tracing::error!("Failed to update {} to v{}: {}", paper.title, latest.version, e),
+        }
+    }
+
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let paper_ids = papers.iter().map(|paper| paper.paper_id).collect::<Vec<_>>();
+    registry
+        .paper_version_repository()
+        .update_versions_checked_at(&mut tx, &paper_ids)
+        .await?;
+    tx.commit().await?;
+    Ok(updated)
+}
+
+async fn check_once(registry: &AppRegistryImpl, args: &CheckVersionsArgs, language: Language) -> AppResult<()> {
+    let checked_before = Local::now() - chrono::Duration::days(args.stale_days);
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let papers = registry
+        .paper_version_repository()
+        .select_papers_to_check_versions(&mut tx, checked_before, args.limit)
+        .await?;
+    tx.commit().await?;
+    if papers.is_empty() {
+        tracing::info!("All papers are checked");
+        return Ok(());
+    }
+
+    let mut updated = 0;
+    for (i, batch) in papers.chunks(ARXIV_ID_BATCH_SIZE).enumerate() {
+        if i > 0 {
+            tokio::time::sleep(Duration::from_secs(args.interval_secs)).await;
+        }
+        // a failed batch is checked again on the next run, since it is not marked as checked
+        match check_batch(registry, batch, args, language).await {
+            Ok(count) => updated += count,
+            Err(e) => tracing::error!("Failed to check a batch of {} papers: {}", batch.len(), e),
+        }
+    }
+    tracing::info!("Checked {} papers ({} with a new version)", papers.len(), updated);
+    Ok(())
+}
+
+pub async fn check_versions(args: &CheckVersionsArgs) {
+    let config = AppConfig::new().expect("Failed to load config");
+    let db = connect_database_with(&config.database);
+    let registry = Arc::new(AppRegistryImpl::new(db));
+    let language = args.language.unwrap_or_else(Language::from_env);
+
+    loop {
+        tracing::info!("Checking the arXiv versions of the papers...");
+        if let Err(e) = check_once(&registry, args, language).await {
+            tracing::error!("Failed to check versions: {}", e);
+        }
+        match args.every_hours {
+            Some(hours) => {
+                tracing::info!("Checking again in {} hours", hours);
+                tokio::time::sleep(Duration::from_secs(hours * 60 * 60)).await;
+            }
+            None => break,
+        }
+    }
+}
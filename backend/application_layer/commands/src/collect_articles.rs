@@ -1,23 +1,44 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use adapter::database::connect_database_with;
 use clap::Parser;
-use kernel::models::web_article::WebArticle;
+use kernel::{
+    llm::batch::wait_for_batch,
+    models::{
+        language::Language,
+        web_article::{EnrichmentStatus, WebArticle},
+    },
+};
 use registry::AppRegistryImpl;
-use shared::{config::AppConfig, utils::create_progress_bar};
+use shared::{config::AppConfig, errors::AppError, utils::create_progress_bar};
 use web_article_crawler::models::{get_all_sites, web_article::WebSiteResource};
 
 #[derive(Parser, Debug, Clone)]
 #[command(version, about, long_about = None)]
-pub struct CollectArticlesArgs {}
+pub struct CollectArticlesArgs {
+    /// The language of the generated summaries (defaults to OUTPUT_LANGUAGE)
+    #[arg(long)]
+    language: Option<Language>,
+    /// Store the articles as pending and fill their attributes through a batch job
+    #[arg(long, default_value_t = false)]
+    batch: bool,
+    /// How often the batch job is polled, in seconds
+    #[arg(long, default_value_t = 60)]
+    poll_interval_secs: u64,
+    /// How long to wait for the batch job, in hours; unfinished articles are picked up by the next run
+    #[arg(long, default_value_t = 24)]
+    max_wait_hours: u64,
+}
 
-pub async fn collect_articles(_args: &CollectArticlesArgs) {
+pub async fn collect_articles(args: &CollectArticlesArgs) {
     let config = AppConfig::new().expect("Failed to load config");
     let db = connect_database_with(&config.database);
     let registry = Arc::new(AppRegistryImpl::new(db));
 
     tracing::info!("Starting to collect articles...");
     let mut sites: Vec<Box<dyn WebSiteResource>> = get_all_sites(&registry).await.unwrap();
+    let llm = registry.llm_provider();
+    let language = args.language.unwrap_or_else(Language::from_env);
     let mut articles = Vec::<WebArticle>::new();
     let today = chrono::Local::now();
     let pb = create_progress_bar(sites.len() as usize, Some("Collecting articles".into()));
@@ -49,7 +70,12 @@ pub async fn collect_articles(_args: &CollectArticlesArgs) {
 
                     //Fill the article attributes
                     let mut web_article = WebArticle::from(article.clone());
-                    if let Err(e) = web_article.fill_attributes().await {
+                    if args.batch {
+                        web_article.enrichment_status = EnrichmentStatus::Pending;
+                        articles.push(web_article);
+                        continue;
+                    }
+                    if let Err(e) = web_article.fill_attributes(llm.as_ref(), language).await {
                         tracing::error!(
                             "Failed to fill attributes for article {}: {}",
                             web_article.article_id,
@@ -58,13 +84,7 @@ pub async fn collect_articles(_args: &CollectArticlesArgs) {
                     }
 
                     // Check if the article is related to AI etc
-                    if !web_article.is_ai_related
-                        && !web_article.is_new_technology_related
-                        && !web_article.is_new_product_related
-                        && !web_article.is_new_academic_paper_related
-                        && !web_article.is_security_related
-                        && !web_article.is_it_related
-                    {
+                    if !web_article.is_relevant() {
                         tracing::info!("Skipped an irrelevant article: {}", web_article.title);
                         continue;
                     }
@@ -102,4 +122,114 @@ pub async fn collect_articles(_args: &CollectArticlesArgs) {
     }
     pb.finish_and_clear();
     tracing::info!("Saved {} articles to DB", articles.len());
+
+    if args.batch {
+        enrich_pending_articles(&registry, language, args).await;
+    }
+}
+
+/// Fills the attributes of the pending articles through batch jobs.
+///
+/// Pending articles that are not part of a batch yet are submitted as a new one, then every batch is polled
+/// until it completes; batches that are still running after `max_wait_hours` are left for the next run.
+async fn enrich_pending_articles(registry: &AppRegistryImpl, language: Language, args: &CollectArticlesArgs) {
+    let web_article_repository = registry.web_article_repository();
+    let llm = registry.llm_provider();
+    let pending = match web_article_repository.select_pending_web_articles().await {
+        Ok(pending) => pending,
+        Err(e) => {
+            tracing::error!("Failed to select pending articles: {}", e);
+            return;
+        }
+    };
+
+    let unsubmitted = pending
+        .iter()
+        .filter(|article| article.enrichment_batch_id.is_none())
+        .collect::<Vec<&WebArticle>>();
+    if !unsubmitted.is_empty() {
+        let requests = unsubmitted
+            .iter()
+            .map(|article| article.attribute_batch_request(language))
+            .collect();
+        let batch_id = match llm.submit_batch(requests).await {
+            Ok(batch_id) => batch_id,
+            Err(e) => {
+                tracing::error!("Failed to submit a batch of {} articles: {}", unsubmitted.len(), e);
+                return;
+            }
+        };
+        for article in unsubmitted {
+            let mut article = article.clone();
+            article.enrichment_batch_id = Some(batch_id.clone());
+            if let Err(e) = web_article_repository.update_web_article(article.clone()).await {
+                tracing::error!("Failed to record the batch of article {}: {}", article.article_id, e);
+            }
+        }
+    }
+
+    let mut batches = HashMap::<String, Vec<WebArticle>>::new();
+    for article in web_article_repository
+        .select_pending_web_articles()
+        .await
+        .unwrap_or_default()
+    {
+        if let Some(batch_id) = article.enrichment_batch_id.clone() {
+            batches.entry(batch_id).or_default().push(article);
+        }
+    }
+
+    let poll_interval = Duration::from_secs(args.poll_interval_secs);
+    let max_wait = Duration::from_secs(args.max_wait_hours * 60 * 60);
+    for (batch_id, articles) in batches {
+        tracing::info!("Waiting for batch {} ({} articles)...", batch_id, articles.len());
+        let mut replies = match wait_for_batch(llm.as_ref(), &batch_id, poll_interval, max_wait).await {
+            Ok(results) => results
+                .into_iter()
+                .map(|result| (result.custom_id, result.content))
+                .collect::<HashMap<String, Result<String, String>>>(),
+            Err(AppError::LlmInvalidResponse(e)) => {
+                tracing::error!("{}", e);
+                HashMap::new()
+            }
+            Err(e) => {
+                tracing::error!("Failed to wait for batch {}: {}", batch_id, e);
+                continue;
+            }
+        };
+
+        let (mut enriched, mut skipped, mut failed) = (0, 0, 0);
+        for mut article in articles {
+            let applied = match replies.remove(&article.article_id.to_string()) {
+                Some(Ok(reply)) => article.apply_attribute_batch_reply(&reply, language),
+                Some(Err(e)) => Err(e),
+                None => Err("No result in the batch".to_string()),
+            };
+            if let Err(e) = applied {
+                tracing::error!("Failed to fill attributes for article {}: {}", article.article_id, e);
+                article.enrichment_status = EnrichmentStatus::Failed;
+                article.enrichment_batch_id = None;
+                failed += 1;
+            } else if !article.is_relevant() {
+                tracing::info!("Skipped an irrelevant article: {}", article.title);
+                if let Err(e) = web_article_repository.delete_web_article(article.article_id).await {
+                    tracing::error!("Failed to delete web article {}: {}", article.article_id, e);
+                }
+                skipped += 1;
+                continue;
+            } else {
+                enriched += 1;
+            }
+            if let Err(e) = web_article_repository.update_web_article(article.clone()).await {
+                tracing::error!("Failed to save web article {} ({})", article.title, e);
+            }
+        }
+        tracing::info!(
+            "Batch {}: enriched {} articles, skipped {} irrelevant articles, {} failed",
+            batch_id,
+            enriched,
+            skipped,
+            failed
+        );
+    }
 }
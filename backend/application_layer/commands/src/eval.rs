@@ -0,0 +1,513 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use kernel::{
+    llm::{canned::CannedProvider, openai::OpenAIProvider, LlmProvider},
+    models::{
+        language::Language,
+        web_article::{AttributePromptVersion, WebArticle},
+    },
+};
+use serde::{Deserialize, Serialize};
+use shared::utils::create_progress_bar;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct EvalArgs {
+    #[command(subcommand)]
+    command: EvalCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum EvalCommand {
+    /// Classify a labeled dataset of articles and report the scores
+    Run(EvalRunArgs),
+    /// Compare the scores of two runs side by side
+    Compare(EvalCompareArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+struct EvalRunArgs {
+    /// JSONL file of labeled articles
+    #[arg(long)]
+    dataset: PathBuf,
+    /// Where to write the run (predictions and scores) as JSON
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// The name of the run shown by `compare` (defaults to the model id)
+    #[arg(long)]
+    name: Option<String>,
+    /// The provider of the model to evaluate
+    #[arg(long, value_enum, default_value_t = EvalProvider::OpenAI)]
+    provider: EvalProvider,
+    /// The model to evaluate (defaults to OPENAI_MODEL_ID)
+    #[arg(long)]
+    model_id: Option<String>,
+    /// The version of the classification prompt to evaluate (defaults to the one used by the crawler)
+    #[arg(long)]
+    prompt_version: Option<AttributePromptVersion>,
+    /// The language of the generated summaries (defaults to OUTPUT_LANGUAGE)
+    #[arg(long)]
+    language: Option<Language>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum EvalProvider {
+    /// The OpenAI API, configured by the OPENAI_* variables
+    #[value(name = "openai")]
+    OpenAI,
+    /// Replays the `canned_response` of each article instead of calling a model
+    Canned,
+}
+
+#[derive(Args, Debug, Clone)]
+struct EvalCompareArgs {
+    /// The run to compare against
+    base: PathBuf,
+    /// The run to compare
+    target: PathBuf,
+}
+
+const CATEGORIES: [&str; 6] = [
+    "is_new_technology_related",
+    "is_new_product_related",
+    "is_new_academic_paper_related",
+    "is_ai_related",
+    "is_security_related",
+    "is_it_related",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+struct ArticleLabels {
+    is_new_technology_related: bool,
+    is_new_product_related: bool,
+    is_new_academic_paper_related: bool,
+    is_ai_related: bool,
+    is_security_related: bool,
+    is_it_related: bool,
+}
+
+impl ArticleLabels {
+    fn get(&self, category: &str) -> bool {
+        match category {
+            "is_new_technology_related" => self.is_new_technology_related,
+            "is_new_product_related" => self.is_new_product_related,
+            "is_new_academic_paper_related" => self.is_new_academic_paper_related,
+            "is_ai_related" => self.is_ai_related,
+            "is_security_related" => self.is_security_related,
+            "is_it_related" => self.is_it_related,
+            _ => false,
+        }
+    }
+}
+
+impl From<&WebArticle> for ArticleLabels {
+    fn from(article: &WebArticle) -> Self {
+        Self {
+            is_new_technology_related: article.is_new_technology_related,
+            is_new_product_related: article.is_new_product_related,
+            is_new_academic_paper_related: article.is_new_academic_paper_related,
+            is_ai_related: article.is_ai_related,
+            is_security_related: article.is_security_related,
+            is_it_related: article.is_it_related,
+        }
+    }
+}
+
+/// One line of the dataset.
+#[derive(Debug, Clone, Deserialize)]
+struct LabeledArticle {
+    id: String,
+    title: String,
+    url: String,
+    html: String,
+    labels: ArticleLabels,
+    /// The recorded reply of the model, replayed with `--provider canned`.
+    #[serde(default)]
+    canned_response: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Prediction {
+    id: String,
+    expected: ArticleLabels,
+    predicted: Option<ArticleLabels>,
+    summary: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CategoryScore {
+    category: String,
+    true_positives: usize,
+    false_positives: usize,
+    false_negatives: usize,
+    precision: f64,
+    recall: f64,
+    f1: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SummaryLengthStats {
+    count: usize,
+    min: usize,
+    max: usize,
+    mean: f64,
+    median: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EvalRun {
+    name: String,
+    model_id: String,
+    prompt_version: String,
+    language: Language,
+    dataset: String,
+    created_at: chrono::DateTime<chrono::Local>,
+    total: usize,
+    errors: usize,
+    scores: Vec<CategoryScore>,
+    summary_length: SummaryLengthStats,
+    predictions: Vec<Prediction>,
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Precision, recall and F1 of every category over the articles that were classified.
+fn score_categories(predictions: &[Prediction]) -> Vec<CategoryScore> {
+    CATEGORIES
+        .iter()
+        .map(|category| {
+            let mut score = CategoryScore {
+                category: category.to_string(),
+                ..Default::default()
+            };
+            for prediction in predictions {
+                let Some(predicted) = prediction.predicted else {
+                    continue;
+                };
+                match (predicted.get(category), prediction.expected.get(category)) {
+                    (true, true) => score.true_positives += 1,
+                    (true, false) => score.false_positives += 1,
+                    (false, true) => score.false_negatives += 1,
+                    (false, false) => {}
+                }
+            }
+            score.precision = ratio(score.true_positives, score.true_positives + score.false_positives);
+            score.recall = ratio(score.true_positives, score.true_positives + score.false_negatives);
+            score.f1 = if score.precision + score.recall > 0.0 {
+                2.0 * score.precision * score.recall / (score.precision + score.recall)
+            } else {
+                0.0
+            };
+            score
+        })
+        .collect()
+}
+
+/// Length statistics of the summaries, in characters.
+fn summary_length_stats(predictions: &[Prediction]) -> SummaryLengthStats {
+    let mut lengths = predictions
+        .iter()
+        .filter_map(|p| p.summary.as_ref().map(|s| s.chars().count()))
+        .collect::<Vec<usize>>();
+    if lengths.is_empty() {
+        return SummaryLengthStats::default();
+    }
+    lengths.sort_unstable();
+    let count = lengths.len();
+    let median = if count % 2 == 0 {
+        (lengths[count / 2 - 1] + lengths[count / 2]) as f64 / 2.0
+    } else {
+        lengths[count / 2] as f64
+    };
+    SummaryLengthStats {
+        count,
+        min: lengths[0],
+        max: lengths[count - 1],
+        mean: ratio(lengths.iter().sum(), count),
+        median,
+    }
+}
+
+fn load_dataset(path: &Path) -> anyhow::Result<Vec<LabeledArticle>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut articles = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let article = serde_json::from_str::<LabeledArticle>(&line)
+            .map_err(|e| anyhow::anyhow!("Invalid line {} of {}: {}", i + 1, path.display(), e))?;
+        articles.push(article);
+    }
+    Ok(articles)
+}
+
+async fn classify(
+    article: &LabeledArticle,
+    llm: &dyn LlmProvider,
+    language: Language,
+    prompt_version: AttributePromptVersion,
+) -> Prediction {
+    let mut web_article = WebArticle {
+        title: article.title.clone(),
+        url: article.url.clone(),
+        html: article.html.clone(),
+        ..Default::default()
+    };
+    let result = web_article
+        .fill_attributes_with_prompt(llm, language, prompt_version)
+        .await;
+    Prediction {
+        id: article.id.clone(),
+        expected: article.labels,
+        predicted: result.as_ref().ok().map(|_| ArticleLabels::from(&web_article)),
+        summary: result
+            .as_ref()
+            .ok()
+            .and_then(|_| web_article.summary(language).map(str::to_string)),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+fn report(run: &EvalRun) {
+    tracing::info!(
+        "Run: {} (model: {}, prompt: {}, language: {})",
+        run.name,
+        run.model_id,
+        run.prompt_version,
+        run.language
+    );
+    tracing::info!("Articles: {} ({} failed)", run.total, run.errors);
+    tracing::info!("{:<32} {:>9} {:>9} {:>9}", "category", "precision", "recall", "f1");
+    for score in run.scores.iter() {
+        tracing::info!(
+            "{:<32} {:>9.3} {:>9.3} {:>9.3}",
+            score.category,
+            score.precision,
+            score.recall,
+            score.f1
+        );
+    }
+    let stats = &run.summary_length;
+    tracing::info!(
+        "Summary length: count={} min={} max={} mean={:.1} median={:.1}",
+        stats.count,
+        stats.min,
+        stats.max,
+        stats.mean,
+        stats.median
+    );
+}
+
+async fn run_eval(args: &EvalRunArgs) -> anyhow::Result<()> {
+    let dataset = load_dataset(&args.dataset)?;
+    let language = args.language.unwrap_or_else(Language::from_env);
+    let prompt_version = args.prompt_version.unwrap_or_default();
+    let provider = match &args.model_id {
+        Some(model_id) => OpenAIProvider::from_env().with_model_id(model_id.clone()),
+        None => OpenAIProvider::from_env(),
+    };
+    let model_id = match args.provider {
+        EvalProvider::OpenAI => provider.model_id(),
+        EvalProvider::Canned => "canned".to_string(),
+    };
+    tracing::info!(
+        "Evaluating {} articles with {} (prompt: {})...",
+        dataset.len(),
+        model_id,
+        prompt_version
+    );
+
+    let pb = create_progress_bar(dataset.len(), Some("Classifying articles".into()));
+    let mut predictions = vec![];
+    for article in dataset.iter() {
+        let prediction = match args.provider {
+            EvalProvider::OpenAI => classify(article, &provider, language, prompt_version).await,
+            EvalProvider::Canned => {
+                let reply = article.canned_response.clone().unwrap_or_default();
+                let canned = CannedProvider::new(model_id.clone(), vec![reply]);
+                classify(article, &canned, language, prompt_version).await
+            }
+        };
+        if let Some(error) = &prediction.error {
+            tracing::warn!("Failed to classify article {}: {}", prediction.id, error);
+        }
+        predictions.push(prediction);
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+
+    let run = EvalRun {
+        name: args.name.clone().unwrap_or_else(|| model_id.clone()),
+        model_id,
+        prompt_version: prompt_version.to_string(),
+        language,
+        dataset: args.dataset.display().to_string(),
+        created_at: chrono::Local::now(),
+        total: predictions.len(),
+        errors: predictions.iter().filter(|p| p.error.is_some()).count(),
+        scores: score_categories(&predictions),
+        summary_length: summary_length_stats(&predictions),
+        predictions,
+    };
+    report(&run);
+    if let Some(output) = &args.output {
+        serde_json::to_writer_pretty(BufWriter::new(File::create(output)?), &run)?;
+        tracing::info!("Saved the run to {}", output.display());
+    }
+    Ok(())
+}
+
+fn compare_runs(args: &EvalCompareArgs) -> anyhow::Result<()> {
+    let base: EvalRun = serde_json::from_reader(BufReader::new(File::open(&args.base)?))?;
+    let target: EvalRun = serde_json::from_reader(BufReader::new(File::open(&args.target)?))?;
+    tracing::info!(
+        "base:   {} (model: {}, prompt: {})",
+        base.name,
+        base.model_id,
+        base.prompt_version
+    );
+    tracing::info!(
+        "target: {} (model: {}, prompt: {})",
+        target.name,
+        target.model_id,
+        target.prompt_version
+    );
+    if base.dataset != target.dataset {
+        tracing::warn!(
+            "The runs used different datasets: {} / {}",
+            base.dataset,
+            target.dataset
+        );
+    }
+    tracing::info!(
+        "{:<32} {:>17} {:>17} {:>17}",
+        "category",
+        "precision",
+        "recall",
+        "f1 (delta)"
+    );
+    for (b, t) in base.scores.iter().zip(target.scores.iter()) {
+        tracing::info!(
+            "{:<32} {:>7.3} -> {:>6.3} {:>7.3} -> {:>6.3} {:>6.3} ({:+.3})",
+            b.category,
+            b.precision,
+            t.precision,
+            b.recall,
+            t.recall,
+            t.f1,
+            t.f1 - b.f1
+        );
+    }
+    tracing::info!(
+        "{:<32} {:>7.1} -> {:>6.1}",
+        "summary length (mean)",
+        base.summary_length.mean,
+        target.summary_length.mean
+    );
+    tracing::info!("{:<32} {:>7} -> {:>6}", "failed articles", base.errors, target.errors);
+    Ok(())
+}
+
+pub async fn eval(args: &EvalArgs) {
+    let result = match &args.command {
+        EvalCommand::Run(args) => run_eval(args).await,
+        EvalCommand::Compare(args) => compare_runs(args),
+    };
+    if let Err(e) = result {
+        tracing::error!("Evaluation failed: {}", e);
+        // fail the CI job that runs the evaluation
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prediction(expected_ai: bool, predicted_ai: Option<bool>, summary: Option<&str>) -> Prediction {
+        Prediction {
+            id: "id".into(),
+            expected: ArticleLabels {
+                is_ai_related: expected_ai,
+                ..Default::default()
+            },
+            predicted: predicted_ai.map(|ai| ArticleLabels {
+                is_ai_related: ai,
+                ..Default::default()
+            }),
+            summary: summary.map(str::to_string),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_score_categories() {
+        let predictions = vec![
+            prediction(true, Some(true), None),
+            prediction(true, Some(false), None),
+            prediction(false, Some(true), None),
+            prediction(true, Some(true), None),
+            // failed classifications are not scored
+            prediction(true, None, None),
+        ];
+        let scores = score_categories(&predictions);
+        let ai = scores.iter().find(|s| s.category == "is_ai_related").unwrap();
+        assert_eq!((ai.true_positives, ai.false_positives, ai.false_negatives), (2, 1, 1));
+        assert!((ai.precision - 2.0 / 3.0).abs() < 1e-9);
+        assert!((ai.recall - 2.0 / 3.0).abs() < 1e-9);
+        assert!((ai.f1 - 2.0 / 3.0).abs() < 1e-9);
+        let security = scores.iter().find(|s| s.category == "is_security_related").unwrap();
+        assert_eq!(security.f1, 0.0);
+    }
+
+    #[test]
+    fn test_summary_length_stats() {
+        let predictions = vec![
+            prediction(true, Some(true), Some("あいう")),
+            prediction(true, Some(true), Some("a")),
+            prediction(true, Some(true), Some("abcdefg")),
+            prediction(true, Some(true), Some("ab")),
+            prediction(true, None, None),
+        ];
+        let stats = summary_length_stats(&predictions);
+        assert_eq!(stats.count, 4);
+        assert_eq!((stats.min, stats.max), (1, 7));
+        assert_eq!(stats.mean, 3.25);
+        assert_eq!(stats.median, 2.5);
+    }
+
+    #[test]
+    fn test_parse_run_args() {
+        let args = EvalArgs::try_parse_from([
+            "eval",
+            "run",
+            "--dataset",
+            "articles.jsonl",
+            "--provider",
+            "canned",
+            "--prompt-version",
+            "2025-11-02",
+        ])
+        .unwrap();
+        let EvalCommand::Run(run) = args.command else {
+            panic!("expected the run subcommand");
+        };
+        assert_eq!(run.provider, EvalProvider::Canned);
+        assert_eq!(run.prompt_version, Some(AttributePromptVersion::V20251102));
+        assert!(EvalArgs::try_parse_from(["eval", "run", "--dataset", "a.jsonl", "--prompt-version", "v2"]).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use std::{path::PathBuf, sync::Arc};
+
+use adapter::database::connect_database_with;
+use clap::Parser;
+use kernel::models::{
+    academic_paper::{AcademicPaper, Status},
+    bibliography::{export_papers, BibliographyFormat},
+};
+use registry::AppRegistryImpl;
+use shared::{config::AppConfig, errors::AppResult};
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct ExportLibraryArgs {
+    /// bibtex, ris or csl-json
+    #[arg(long, default_value = "bibtex")]
+    format: BibliographyFormat,
+    /// Export only these papers (repeatable); the whole library is exported without a filter
+    #[arg(long)]
+    paper_id: Vec<String>,
+    /// Export only the papers matching this keyword
+    #[arg(long)]
+    keyword: Option<String>,
+    /// Export only the papers of this author
+    #[arg(long)]
+    author_id: Option<String>,
+    /// Export only the papers with this status (new, deep_dive or completed)
+    #[arg(long)]
+    status: Option<Status>,
+    /// The file to write to (defaults to stdout)
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+async fn select_papers(registry: &AppRegistryImpl, args: &ExportLibraryArgs) -> AppResult<Vec<AcademicPaper>> {
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let repository = registry.academic_paper_repository();
+    let mut papers = if !args.paper_id.is_empty() {
+        let mut papers = vec![];
+        for paper_id in args.paper_id.iter() {
+            papers.push(repository.select_academic_paper_by_id(&mut tx, paper_id).await?);
+        }
+        papers
+    } else if let Some(keyword) = args.keyword.as_deref() {
+        repository.select_academic_papers_by_keyword(&mut tx, keyword).await?
+    } else if let Some(author_id) = args.author_id.as_deref() {
+        repository.select_academic_papers_by_author(&mut tx, author_id).await?
+    } else {
+        repository.select_all_academic_papers(&mut tx).await?
+    };
+    tx.commit().await?;
+    if let Some(status) = args.status.as_ref() {
+        papers.retain(|paper| &paper.status == status);
+    }
+    Ok(papers)
+}
+
+pub async fn export_library(args: &ExportLibraryArgs) {
+    let config = AppConfig::new().expect("Failed to load config");
+    let db = connect_database_with(&config.database);
+    let registry = Arc::new(AppRegistryImpl::new(db));
+
+    let papers = select_papers(&registry, args)
+        .await
+        .expect("Failed to select academic papers");
+    let exported = export_papers(&papers, args.format);
+    match args.output.as_ref() {
+        Some(path) => {
+            std::fs::write(path, exported).expect("Failed to write the export");
+            tracing::info!("Exported {} papers to {}", papers.len(), path.display());
+        }
+        None => print!("{}", exported),
+    }
+}
@@ -0,0 +1,79 @@
+use std::{sync::Arc, time::Duration};
+
+use academic_paper_crawler::repository::citations::fetch_citation_graph;
+use adapter::database::connect_database_with;
+use clap::Parser;
+use kernel::models::academic_paper::AcademicPaper;
+use registry::AppRegistryImpl;
+use shared::{config::AppConfig, errors::AppResult};
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct FetchCitationsArgs {
+    /// The paper to fetch the citations of (defaults to every paper with a Semantic Scholar ID)
+    #[arg(long)]
+    paper_id: Option<String>,
+    /// Seconds to wait between papers, to stay within the Semantic Scholar rate limit
+    #[arg(long, default_value_t = 1)]
+    interval_secs: u64,
+}
+
+/// Fetches the references and citations of a library paper and stores them in the citation graph.
+/// Returns the number of stored edges.
+pub async fn save_citation_graph(registry: &AppRegistryImpl, paper: &AcademicPaper) -> AppResult<usize> {
+    let graph = fetch_citation_graph(paper).await?;
+    let mut tx = registry.db.inner_ref().begin().await?;
+    registry
+        .citation_repository()
+        .save_citation_graph(&mut tx, &graph)
+        .await?;
+    tx.commit().await?;
+    Ok(graph.edges.len())
+}
+
+async fn select_papers(registry: &AppRegistryImpl, paper_id: Option<&str>) -> AppResult<Vec<AcademicPaper>> {
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let papers = match paper_id {
+        Some(paper_id) => vec![
+            registry
+                .academic_paper_repository()
+                .select_academic_paper_by_id(&mut tx, paper_id)
+                .await?,
+        ],
+        None => registry
+            .academic_paper_repository()
+            .select_all_academic_papers(&mut tx)
+            .await?
+            .into_iter()
+            .filter(|paper| !paper.ss_id.is_empty())
+            .collect(),
+    };
+    tx.commit().await?;
+    Ok(papers)
+}
+
+pub async fn fetch_citations(args: &FetchCitationsArgs) {
+    let config = AppConfig::new().expect("Failed to load config");
+    let db = connect_database_with(&config.database);
+    let registry = Arc::new(AppRegistryImpl::new(db));
+
+    let papers = select_papers(&registry, args.paper_id.as_deref())
+        .await
+        .expect("Failed to select academic papers");
+    tracing::info!("Fetching citations of {} papers...", papers.len());
+    for (i, paper) in papers.iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(Duration::from_secs(args.interval_secs)).await;
+        }
+        match save_citation_graph(&registry, paper).await {
+            Ok(count) => tracing::info!("[{}/{}] Stored {} edges of {}", i + 1, papers.len(), count, paper.title),
+            Err(e) => tracing::error!(
+                "[{}/{}] Failed to fetch citations of {}: {}",
+                i + 1,
+                papers.len(),
+                paper.title,
+                e
+            ),
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use academic_paper_crawler::{
+    models::identifier::PaperIdentifier,
+    repository::academic_papers::{get_academic_paper, get_academic_paper_by_identifier},
+};
+use adapter::database::connect_database_with;
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use kernel::models::{
+    academic_paper::AcademicPaper,
+    bibliography::BibliographyFormat,
+    language::Language,
+    library_import::{parse_library, BibliographyEntry},
+};
+use registry::AppRegistryImpl;
+use serde::{Deserialize, Serialize};
+use shared::{config::AppConfig, utils::levenshtein_dist};
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct ImportLibraryArgs {
+    /// The BibTeX, RIS or CSL-JSON file to import
+    #[arg(long)]
+    file: PathBuf,
+    /// bibtex, ris or csl-json (judged by the file extension when omitted)
+    #[arg(long)]
+    format: Option<BibliographyFormat>,
+    /// Where to keep the progress and the report (defaults to <file>.import.json)
+    #[arg(long)]
+    checkpoint: Option<PathBuf>,
+    /// Generate the AI summary of the created papers
+    #[arg(long)]
+    summarize: bool,
+    /// The language of the generated summary (defaults to OUTPUT_LANGUAGE)
+    #[arg(long)]
+    language: Option<Language>,
+    /// Retry the entries that failed in a previous run
+    #[arg(long)]
+    retry_failed: bool,
+    /// Seconds to wait after fetching a paper, to stay within the arXiv and Semantic Scholar rate limits
+    #[arg(long, default_value_t = 3)]
+    interval_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ImportOutcome {
+    /// The paper was already in the library.
+    Matched {
+        paper_id: String,
+    },
+    Created {
+        paper_id: String,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRecord {
+    pub key: String,
+    /// How many entries with the same key come before this one in the file, so that no entry is skipped as done.
+    #[serde(default)]
+    pub occurrence: usize,
+    pub title: String,
+    #[serde(flatten)]
+    pub outcome: ImportOutcome,
+}
+
+/// The outcome of every processed entry, saved after each entry so that an interrupted import can resume.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub records: Vec<ImportRecord>,
+}
+
+impl ImportReport {
+    fn load(path: &PathBuf) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text).with_context(|| format!("Invalid checkpoint {}", path.display()))
+    }
+
+    /// Writes through a temporary file, so that an interruption never leaves a truncated checkpoint.
+    fn save(&self, path: &PathBuf) -> Result<()> {
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    fn record(&mut self, record: ImportRecord) {
+        match self
+            .records
+            .iter_mut()
+            .find(|r| r.key == record.key && r.occurrence == record.occurrence)
+        {
+            Some(existing) => *existing = record,
+            None => self.records.push(record),
+        }
+    }
+
+    fn count(&self, predicate: impl Fn(&ImportOutcome) -> bool) -> usize {
+        self.records.iter().filter(|r| predicate(&r.outcome)).count()
+    }
+}
+
+/// The occurrence of the key of every entry, counted from 0 in the order of the file.
+fn key_occurrences(entries: &[BibliographyEntry]) -> Vec<usize> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    entries
+        .iter()
+        .map(|entry| {
+            let count = counts.entry(entry.key.as_str()).or_default();
+            *count += 1;
+            *count - 1
+        })
+        .collect()
+}
+
+/// The paper of the entry that is already in the library, by DOI or arXiv ID, else by a near-identical title.
+async fn find_existing_paper(registry: &AppRegistryImpl, entry: &BibliographyEntry) -> Result<Option<AcademicPaper>> {
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let repository = registry.academic_paper_repository();
+    let mut paper = repository
+        .select_academic_paper_by_external_id(&mut tx, entry.doi.as_deref(), entry.arxiv_id.as_deref())
+        .await?;
+    if paper.is_none() && !entry.title.is_empty() {
+        let title = entry.title.to_lowercase();
+        paper = repository
+            .select_academic_paper_by_title(&mut tx, &entry.title)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .find(|paper| levenshtein_dist(&paper.title.to_lowercase(), &title) < 2);
+    }
+    tx.commit().await?;
+    Ok(paper)
+}
+
+/// Fetches the paper of the entry through the crawler and adds it to the library.
+async fn create_paper(
+    registry: &AppRegistryImpl,
+    entry: &BibliographyEntry,
+    args: &ImportLibraryArgs,
+) -> Result<AcademicPaper> {
+    let identifier = entry
+        .arxiv_id
+        .clone()
+        .map(PaperIdentifier::ArXiv)
+        .or_else(|| entry.doi.clone().map(PaperIdentifier::Doi));
+    let title = Some(entry.title.as_str()).filter(|title| !title.is_empty());
+    let pdf_url = entry
+        .url
+        .as_deref()
+        .filter(|url| url.ends_with(".pdf") || url.contains("/pdf/"));
+    let resource = match (identifier, title, pdf_url) {
+        (Some(identifier), title, pdf_url) => get_academic_paper_by_identifier(&identifier, title, pdf_url).await?,
+        (None, Some(title), Some(pdf_url)) => get_academic_paper(title, pdf_url).await?,
+        _ => return Err(anyhow!("The entry has neither a DOI, an arXiv ID nor a PDF URL")),
+    };
+
+    let mut paper = AcademicPaper::from(resource);
+    if args.summarize {
+        let language = args.language.unwrap_or_else(Language::from_env);
+        let mut tx = registry.db.inner_ref().begin().await?;
+        let vocabulary = registry.task_repository().select_task_vocabulary(&mut tx).await?;
+        tx.commit().await?;
+        paper
+            .fill_fields_with_ai(registry.llm_provider().as_ref(), language, vocabulary)
+            .await?;
+    }
+    paper.fill_bibtex()?;
+
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let paper = registry
+        .academic_paper_repository()
+        .create_academic_paper(&mut tx, paper)
+        .await?;
+    tx.commit().await?;
+    Ok(paper)
+}
+
+async fn import_entry(
+    registry: &AppRegistryImpl,
+    entry: &BibliographyEntry,
+    args: &ImportLibraryArgs,
+) -> ImportOutcome {
+    match find_existing_paper(registry, entry).await {
+        Ok(Some(paper)) => {
+            return ImportOutcome::Matched {
+                paper_id: paper.paper_id.to_string(),
+            }
+        }
+        Ok(None) => {}
+        Err(e) => return ImportOutcome::Failed { error: e.to_string() },
+    }
+    let outcome = match create_paper(registry, entry, args).await {
+        Ok(paper) => ImportOutcome::Created {
+            paper_id: paper.paper_id.to_string(),
+        },
+        Err(e) => ImportOutcome::Failed { error: e.to_string() },
+    };
+    tokio::time::sleep(Duration::from_secs(args.interval_secs)).await;
+    outcome
+}
+
+pub async fn import_library(args: &ImportLibraryArgs) {
+    let format = args
+        .format
+        .or_else(|| {
+            args.file
+                .extension()
+                .and_then(|extension| BibliographyFormat::from_extension(&extension.to_string_lossy()))
+        })
+        .expect("Cannot tell the format from the file extension, specify --format");
+    let text = std::fs::read_to_string(&args.file).expect("Failed to read the library file");
+    let entries = parse_library(&text, format).expect("Failed to parse the library file");
+    let checkpoint = args
+        .checkpoint
+        .clone()
+        .unwrap_or_else(|| args.file.with_extension("import.json"));
+    let mut report = ImportReport::load(&checkpoint).expect("Failed to load the checkpoint");
+
+    let config = AppConfig::new().expect("Failed to load config");
+    let db = connect_database_with(&config.database);
+    let registry = Arc::new(AppRegistryImpl::new(db));
+
+    let occurrences = key_occurrences(&entries);
+    let duplicates = occurrences.iter().filter(|occurrence| **occurrence > 0).count();
+    if duplicates > 0 {
+        tracing::warn!("{} entries reuse the key of an earlier entry", duplicates);
+    }
+    let done = report
+        .records
+        .iter()
+        .filter(|r| !(args.retry_failed && matches!(r.outcome, ImportOutcome::Failed { .. })))
+        .map(|r| (r.key.as_str(), r.occurrence))
+        .collect::<HashSet<(&str, usize)>>();
+    let pending = entries
+        .iter()
+        .zip(occurrences)
+        .filter(|(entry, occurrence)| !done.contains(&(entry.key.as_str(), *occurrence)))
+        .collect::<Vec<(&BibliographyEntry, usize)>>();
+    tracing::info!(
+        "Importing {} of {} entries ({} already processed, checkpoint: {})",
+        pending.len(),
+        entries.len(),
+        entries.len() - pending.len(),
+        checkpoint.display()
+    );
+
+    for (i, (entry, occurrence)) in pending.iter().enumerate() {
+        let outcome = import_entry(&registry, entry, args).await;
+        match &outcome {
+            ImportOutcome::Matched { paper_id } => {
+                tracing::info!("[{}/{}] {}: matched {}", i + 1, pending.len(), entry.key, paper_id)
+            }
+            ImportOutcome::Created { paper_id } => {
+                tracing::info!("[{}/{}] {}: created {}", i + 1, pending.len(), entry.key, paper_id)
+            }
+            ImportOutcome::Failed { error } => {
+                tracing::warn!("[{}/{}] {}: failed: {}", i + 1, pending.len(), entry.key, error)
+            }
+        }
+        report.record(ImportRecord {
+            key: entry.key.clone(),
+            occurrence: *occurrence,
+            title: entry.title.clone(),
+            outcome,
+        });
+        report.save(&checkpoint).expect("Failed to save the checkpoint");
+    }
+
+    tracing::info!(
+        "Matched {}, created {}, failed {} (report: {})",
+        report.count(|o| matches!(o, ImportOutcome::Matched { .. })),
+        report.count(|o| matches!(o, ImportOutcome::Created { .. })),
+        report.count(|o| matches!(o, ImportOutcome::Failed { .. })),
+        checkpoint.display()
+    );
+    for record in report.records.iter() {
+        if let ImportOutcome::Failed { error } = &record.outcome {
+            tracing::warn!("Failed: {} ({}): {}", record.key, record.title, error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str) -> BibliographyEntry {
+        BibliographyEntry {
+            key: key.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn record(key: &str, occurrence: usize, error: &str) -> ImportRecord {
+        ImportRecord {
+            key: key.to_string(),
+            occurrence,
+            title: String::new(),
+            outcome: ImportOutcome::Failed {
+                error: error.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_key_occurrences() {
+        let entries = vec![
+            entry("smith2020"),
+            entry("doe2021"),
+            entry("smith2020"),
+            entry("smith2020"),
+        ];
+        assert_eq!(key_occurrences(&entries), vec![0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_record() {
+        let mut report = ImportReport::default();
+        report.record(record("smith2020", 0, "first"));
+        report.record(record("smith2020", 1, "second"));
+        report.record(record("smith2020", 0, "retried"));
+        assert_eq!(report.records.len(), 2);
+        assert_eq!(
+            report.records[0].outcome,
+            ImportOutcome::Failed {
+                error: "retried".into()
+            }
+        );
+
+        // the records of checkpoints saved before the occurrence was kept are the first entries of their keys
+        let old: ImportRecord =
+            serde_json::from_str(r#"{"key": "doe2021", "title": "T", "outcome": "matched", "paper_id": "p"}"#).unwrap();
+        assert_eq!(old.occurrence, 0);
+    }
+}
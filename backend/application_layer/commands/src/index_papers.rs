@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use adapter::database::connect_database_with;
+use clap::Parser;
+use kernel::models::paper_chunk::build_paper_chunks;
+use registry::AppRegistryImpl;
+use shared::{config::AppConfig, errors::AppResult, id::AcademicPaperId};
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct IndexPapersArgs {
+    /// Index only this paper, rebuilding its index if it already exists
+    #[arg(long)]
+    paper_id: Option<String>,
+    /// The maximum number of papers to index per run
+    #[arg(long, default_value_t = 100)]
+    limit: usize,
+}
+
+/// Splits the full text of a paper into passages and stores them with their embeddings;
+/// returns the number of passages.
+async fn index_paper(registry: &AppRegistryImpl, paper_id: &str) -> AppResult<usize> {
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let paper = registry
+        .academic_paper_repository()
+        .select_academic_paper_by_id(&mut tx, paper_id)
+        .await?;
+    let chunks = build_paper_chunks(&paper, registry.llm_provider().as_ref()).await?;
+    registry
+        .paper_chunk_repository()
+        .replace_paper_chunks(&mut tx, paper.paper_id, &chunks)
+        .await?;
+    tx.commit().await?;
+    Ok(chunks.len())
+}
+
+pub async fn index_papers(args: &IndexPapersArgs) {
+    let config = AppConfig::new().expect("Failed to load config");
+    let db = connect_database_with(&config.database);
+    let registry = Arc::new(AppRegistryImpl::new(db));
+
+    let paper_ids = match &args.paper_id {
+        Some(paper_id) => vec![paper_id.clone()],
+        None => {
+            let mut tx = registry
+                .db
+                .inner_ref()
+                .begin()
+                .await
+                .expect("Failed to begin transaction");
+            let paper_ids = registry
+                .paper_chunk_repository()
+                .select_unindexed_paper_ids(&mut tx)
+                .await
+                .expect("Failed to select the papers to index");
+            paper_ids
+                .into_iter()
+                .take(args.limit)
+                .map(|paper_id: AcademicPaperId| paper_id.to_string())
+                .collect()
+        }
+    };
+    if paper_ids.is_empty() {
+        tracing::info!("All papers are indexed");
+        return;
+    }
+
+    let mut indexed = 0;
+    for paper_id in paper_ids.iter() {
+        match index_paper(&registry, paper_id).await {
+            Ok(count) => {
+                indexed += 1;
+                tracing::info!("Indexed {} passages of {}", count, paper_id);
+            }
+            Err(e) => tracing::error!("Failed to index {}: {}", paper_id, e),
+        }
+    }
+    tracing::info!("Indexed {} of {} papers", indexed, paper_ids.len());
+}
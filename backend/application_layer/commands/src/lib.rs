@@ -1,4 +1,13 @@
 pub mod add_academic_paper;
+pub mod check_versions;
 pub mod collect_articles;
+pub mod eval;
+pub mod export_library;
+pub mod fetch_citations;
+pub mod import_library;
+pub mod index_papers;
 pub mod notify_web_articles_to_slack;
+pub mod refresh_authors;
+pub mod refresh_papers;
 pub mod start_dashboard;
+pub mod watch_arxiv;
@@ -1,6 +1,6 @@
 use adapter::database::connect_database_with;
 use clap::Parser;
-use kernel::models::web_article::WebArticle;
+use kernel::models::{language::Language as OutputLanguage, web_article::WebArticle};
 use keywords::rsc::{extract_keywords, load_keywords, Keyword, Language};
 use registry::AppRegistryImpl;
 use serde_json::{json, Value};
@@ -76,7 +76,10 @@ fn to_payload(index: usize, article: WebArticle, score: isize, kws: Vec<Keyword>
             .map(|kwd| format!("{}({})", kwd.alias.clone(), kwd.score))
             .collect::<Vec<String>>()
             .join(" / "),
-        TEXT = article.summary.replace("\n", "\n>")
+        TEXT = article
+            .summary(OutputLanguage::from_env())
+            .unwrap_or_default()
+            .replace("\n", "\n>")
     );
 
     let payload = json!({
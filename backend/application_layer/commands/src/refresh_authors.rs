@@ -0,0 +1,84 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use academic_paper_crawler::repository::authors::fetch_ss_authors;
+use adapter::database::connect_database_with;
+use chrono::Local;
+use clap::Parser;
+use registry::AppRegistryImpl;
+use shared::{config::AppConfig, errors::AppResult};
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct RefreshAuthorsArgs {
+    /// Refresh the authors whose h-index is older than this many days
+    #[arg(long, default_value_t = 30)]
+    stale_days: i64,
+    /// The maximum number of authors to refresh per run
+    #[arg(long, default_value_t = 1000)]
+    limit: i64,
+    /// Keep running and refresh again every N hours
+    #[arg(long)]
+    every_hours: Option<u64>,
+}
+
+async fn refresh_once(registry: &AppRegistryImpl, args: &RefreshAuthorsArgs) -> AppResult<()> {
+    let refreshed_before = Local::now() - chrono::Duration::days(args.stale_days);
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let authors = registry
+        .author_repository()
+        .select_authors_to_refresh(&mut tx, refreshed_before, args.limit)
+        .await?;
+    tx.commit().await?;
+    if authors.is_empty() {
+        tracing::info!("All authors are up to date");
+        return Ok(());
+    }
+
+    let ss_ids = authors
+        .iter()
+        .map(|author| author.ss_id.clone())
+        .collect::<Vec<String>>();
+    let h_indices = fetch_ss_authors(&ss_ids)
+        .await?
+        .into_iter()
+        .filter_map(|profile| Some((profile.author_id, profile.h_index?)))
+        .collect::<HashMap<String, i32>>();
+
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let mut changed = 0;
+    for author in authors.iter() {
+        // authors unknown to Semantic Scholar keep their h-index until the next refresh
+        let h_index = h_indices.get(&author.ss_id).copied().unwrap_or(author.h_index);
+        if h_index != author.h_index {
+            tracing::info!("{}: h-index {} -> {}", author.name, author.h_index, h_index);
+            changed += 1;
+        }
+        registry
+            .author_repository()
+            .update_author_h_index(&mut tx, author.author_id, h_index)
+            .await?;
+    }
+    tx.commit().await?;
+    tracing::info!("Refreshed {} authors ({} changed)", authors.len(), changed);
+    Ok(())
+}
+
+pub async fn refresh_authors(args: &RefreshAuthorsArgs) {
+    let config = AppConfig::new().expect("Failed to load config");
+    let db = connect_database_with(&config.database);
+    let registry = Arc::new(AppRegistryImpl::new(db));
+
+    loop {
+        tracing::info!("Refreshing author h-indices...");
+        if let Err(e) = refresh_once(&registry, args).await {
+            tracing::error!("Failed to refresh authors: {}", e);
+        }
+        match args.every_hours {
+            Some(hours) => {
+                tracing::info!("Refreshing again in {} hours", hours);
+                tokio::time::sleep(Duration::from_secs(hours * 60 * 60)).await;
+            }
+            None => break,
+        }
+    }
+}
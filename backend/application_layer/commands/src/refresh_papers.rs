@@ -0,0 +1,115 @@
+use std::{sync::Arc, time::Duration};
+
+use academic_paper_crawler::repository::metadata::{fetch_ss_paper_metadata, SS_PAPER_BATCH_SIZE};
+use adapter::database::connect_database_with;
+use chrono::Local;
+use clap::Parser;
+use kernel::models::paper_metadata::PaperMetadata;
+use registry::AppRegistryImpl;
+use shared::{config::AppConfig, errors::AppResult};
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct RefreshPapersArgs {
+    /// Refresh the papers whose metadata is older than this many days
+    #[arg(long, default_value_t = 7)]
+    stale_days: i64,
+    /// The maximum number of papers to refresh per run
+    #[arg(long, default_value_t = 2000)]
+    limit: i64,
+    /// The number of papers per Semantic Scholar request (at most 500)
+    #[arg(long, default_value_t = 100)]
+    batch_size: usize,
+    /// Seconds to wait between batches, to stay within the Semantic Scholar rate limit
+    #[arg(long, default_value_t = 1)]
+    interval_secs: u64,
+    /// Keep running and refresh again every N hours
+    #[arg(long)]
+    every_hours: Option<u64>,
+}
+
+/// Refreshes one batch of papers; returns the number of papers with changed metadata.
+async fn refresh_batch(registry: &AppRegistryImpl, papers: &[PaperMetadata]) -> AppResult<usize> {
+    let lookup_ids = papers
+        .iter()
+        .filter_map(PaperMetadata::ss_lookup_id)
+        .collect::<Vec<String>>();
+    let mut fetched = fetch_ss_paper_metadata(&lookup_ids).await?.into_iter();
+
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let mut changed = 0;
+    for paper in papers.iter() {
+        // papers unknown to Semantic Scholar are marked as refreshed as they are
+        let metadata = match paper.ss_lookup_id() {
+            Some(_) => fetched.next().flatten(),
+            None => None,
+        };
+        let updated = metadata.map_or_else(|| paper.clone(), |metadata| metadata.apply_to(paper));
+        let changes = registry
+            .paper_metadata_repository()
+            .update_paper_metadata(&mut tx, paper, &updated)
+            .await?;
+        if !changes.is_empty() {
+            changed += 1;
+            for change in changes.iter() {
+                tracing::info!(
+                    "{}: {} {} -> {}",
+                    paper.paper_id,
+                    change.field,
+                    change.old_value,
+                    change.new_value
+                );
+            }
+        }
+    }
+    tx.commit().await?;
+    Ok(changed)
+}
+
+async fn refresh_once(registry: &AppRegistryImpl, args: &RefreshPapersArgs) -> AppResult<()> {
+    let refreshed_before = Local::now() - chrono::Duration::days(args.stale_days);
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let papers = registry
+        .paper_metadata_repository()
+        .select_papers_to_refresh(&mut tx, refreshed_before, args.limit)
+        .await?;
+    tx.commit().await?;
+    if papers.is_empty() {
+        tracing::info!("All papers are up to date");
+        return Ok(());
+    }
+
+    let mut changed = 0;
+    for (i, batch) in papers.chunks(args.batch_size.clamp(1, SS_PAPER_BATCH_SIZE)).enumerate() {
+        if i > 0 {
+            tokio::time::sleep(Duration::from_secs(args.interval_secs)).await;
+        }
+        // a failed batch is retried on the next run, since it is not marked as refreshed
+        match refresh_batch(registry, batch).await {
+            Ok(count) => changed += count,
+            Err(e) => tracing::error!("Failed to refresh a batch of {} papers: {}", batch.len(), e),
+        }
+    }
+    tracing::info!("Refreshed {} papers ({} changed)", papers.len(), changed);
+    Ok(())
+}
+
+pub async fn refresh_papers(args: &RefreshPapersArgs) {
+    let config = AppConfig::new().expect("Failed to load config");
+    let db = connect_database_with(&config.database);
+    let registry = Arc::new(AppRegistryImpl::new(db));
+
+    loop {
+        tracing::info!("Refreshing paper metadata...");
+        if let Err(e) = refresh_once(&registry, args).await {
+            tracing::error!("Failed to refresh papers: {}", e);
+        }
+        match args.every_hours {
+            Some(hours) => {
+                tracing::info!("Refreshing again in {} hours", hours);
+                tokio::time::sleep(Duration::from_secs(hours * 60 * 60)).await;
+            }
+            None => break,
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use std::{collections::HashSet, str::FromStr, sync::Arc, time::Duration};
+
+use academic_paper_crawler::{
+    models::identifier::PaperIdentifier,
+    repository::{academic_papers::get_academic_paper_by_identifier, arxiv_listing::list_new_submissions},
+};
+use adapter::database::connect_database_with;
+use chrono::Local;
+use clap::Parser;
+use kernel::models::{
+    academic_paper::AcademicPaper,
+    language::Language,
+    paper_candidate::{CandidateStatus, PaperCandidate},
+};
+use keywords::rsc::{extract_keywords, load_keywords, Keyword, Language as KeywordLanguage};
+use registry::AppRegistryImpl;
+use shared::{
+    config::AppConfig,
+    errors::AppResult,
+    id::{AcademicPaperId, PaperCandidateId},
+};
+
+use crate::fetch_citations::save_citation_graph;
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct WatchArxivArgs {
+    /// The arXiv categories to watch
+    #[arg(long = "category", value_delimiter = ',', default_value = "cs.CL,cs.LG,cs.CR")]
+    categories: Vec<String>,
+    /// Authors whose new papers are scored up
+    #[arg(long = "author", value_delimiter = ',')]
+    authors: Vec<String>,
+    /// The score added for each followed author of a paper
+    #[arg(long, default_value_t = 10)]
+    author_weight: i32,
+    /// Only consider papers submitted within this many days
+    #[arg(long, default_value_t = 1)]
+    days: i64,
+    /// How many of the latest submissions to fetch per category
+    #[arg(long, default_value_t = 200)]
+    max_results: usize,
+    /// How many of the best scoring papers to add to the library
+    #[arg(long, default_value_t = 5)]
+    top_n: usize,
+    /// The minimum score for a paper to be added
+    #[arg(long, default_value_t = 1)]
+    min_score: i32,
+    /// Only store the candidates, without adding any paper
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+    /// Add the given candidates to the library instead of watching
+    #[arg(long, value_delimiter = ',')]
+    promote: Vec<String>,
+    /// Keep running and watch again every N hours
+    #[arg(long)]
+    every_hours: Option<u64>,
+    /// The language of the generated analysis (defaults to OUTPUT_LANGUAGE)
+    #[arg(long)]
+    language: Option<Language>,
+}
+
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<&str>>().join(" ").to_lowercase()
+}
+
+/// The authors of `candidate` that are followed, compared case- and whitespace-insensitively.
+fn followed_authors_of(candidate: &PaperCandidate, followed_authors: &[String]) -> Vec<String> {
+    let followed = followed_authors
+        .iter()
+        .map(|name| normalize_name(name))
+        .collect::<HashSet<String>>();
+    candidate
+        .authors
+        .iter()
+        .filter(|author| followed.contains(&normalize_name(author)))
+        .cloned()
+        .collect()
+}
+
+/// Scores a candidate by the keywords in its title and abstract and by its followed authors.
+fn score_candidate(
+    candidate: &mut PaperCandidate,
+    keywords: &[Keyword],
+    followed_authors: &[String],
+    author_weight: i32,
+) {
+    let mut matched_keywords = extract_keywords(&candidate.title, keywords.to_vec(), KeywordLanguage::English);
+    matched_keywords.extend(extract_keywords(
+        &candidate.abstract_text,
+        keywords.to_vec(),
+        KeywordLanguage::English,
+    ));
+    let authors = followed_authors_of(candidate, followed_authors);
+
+    candidate.score =
+        matched_keywords.iter().map(|kwd| kwd.score as i32).sum::<i32>() + author_weight * authors.len() as i32;
+    candidate.matched = vec![];
+    for name in matched_keywords.into_iter().map(|kwd| kwd.alias).chain(authors) {
+        if !candidate.matched.contains(&name) {
+            candidate.matched.push(name);
+        }
+    }
+}
+
+/// The candidates to add: the `top_n` best scoring ones that reach `min_score`.
+fn select_top_candidates(candidates: &[PaperCandidate], top_n: usize, min_score: i32) -> Vec<PaperCandidate> {
+    let mut candidates = candidates
+        .iter()
+        .filter(|candidate| candidate.score >= min_score)
+        .cloned()
+        .collect::<Vec<PaperCandidate>>();
+    candidates.sort_by(|a, b| b.score.cmp(&a.score).then(b.published_date.cmp(&a.published_date)));
+    candidates.truncate(top_n);
+    candidates
+}
+
+/// Runs the `add-academic-paper` pipeline for a candidate and marks it as added.
+async fn add_candidate(
+    registry: &AppRegistryImpl,
+    candidate: &PaperCandidate,
+    language: Language,
+) -> AppResult<AcademicPaperId> {
+    let identifier = PaperIdentifier::ArXiv(candidate.arxiv_id.clone());
+    let paper_rsc =
+        get_academic_paper_by_identifier(&identifier, Some(&candidate.title), Some(&candidate.pdf_url)).await?;
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let vocabulary = registry.task_repository().select_task_vocabulary(&mut tx).await?;
+    tx.commit().await?;
+    let mut paper = AcademicPaper::from(paper_rsc);
+    paper
+        .fill_fields_with_ai(registry.llm_provider().as_ref(), language, vocabulary)
+        .await?;
+    paper.fill_bibtex()?;
+
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let paper = registry
+        .academic_paper_repository()
+        .create_academic_paper(&mut tx, paper)
+        .await?;
+    registry
+        .paper_candidate_repository()
+        .update_paper_candidate_status(
+            &mut tx,
+            candidate.candidate_id,
+            CandidateStatus::Added,
+            Some(paper.paper_id),
+        )
+        .await?;
+    tx.commit().await?;
+    if let Err(e) = save_citation_graph(registry, &paper).await {
+        tracing::warn!("Failed to fetch citations of {}: {}", candidate.arxiv_id, e);
+    }
+    Ok(paper.paper_id)
+}
+
+async fn add_candidates(registry: &AppRegistryImpl, candidates: &[PaperCandidate], language: Language) {
+    for candidate in candidates.iter() {
+        tracing::info!(
+            "Adding {} (score: {}, matched: {:?}): {}",
+            candidate.arxiv_id,
+            candidate.score,
+            candidate.matched,
+            candidate.title
+        );
+        match add_candidate(registry, candidate, language).await {
+            Ok(paper_id) => tracing::info!("Added {} as {}", candidate.arxiv_id, paper_id.to_string()),
+            Err(e) => tracing::error!("Failed to add {}, keeping it as a candidate: {}", candidate.arxiv_id, e),
+        }
+    }
+}
+
+async fn promote_candidates(registry: &AppRegistryImpl, candidate_ids: &[String], language: Language) -> AppResult<()> {
+    let mut candidates = vec![];
+    let mut tx = registry.db.inner_ref().begin().await?;
+    for candidate_id in candidate_ids.iter() {
+        let candidate = registry
+            .paper_candidate_repository()
+            .select_paper_candidate_by_id(&mut tx, PaperCandidateId::from_str(candidate_id)?)
+            .await?;
+        if candidate.status == CandidateStatus::Added {
+            tracing::warn!("{} has already been added", candidate.arxiv_id);
+            continue;
+        }
+        candidates.push(candidate);
+    }
+    // a candidate may have been added to the library in another way since it was stored
+    let arxiv_ids = candidates.iter().map(|c| c.arxiv_id.clone()).collect::<Vec<String>>();
+    let in_library = registry
+        .paper_candidate_repository()
+        .select_library_arxiv_ids(&mut tx, &arxiv_ids)
+        .await?;
+    tx.commit().await?;
+    candidates.retain(|c| {
+        if in_library.contains(&c.arxiv_id) {
+            tracing::warn!("{} is already in the library", c.arxiv_id);
+        }
+        !in_library.contains(&c.arxiv_id)
+    });
+    add_candidates(registry, &candidates, language).await;
+    Ok(())
+}
+
+async fn watch_once(registry: &AppRegistryImpl, args: &WatchArxivArgs, language: Language) -> AppResult<()> {
+    let since = Local::now() - chrono::Duration::days(args.days);
+    let mut candidates: Vec<PaperCandidate> = vec![];
+    for category in args.categories.iter() {
+        let submissions = list_new_submissions(category, args.max_results).await?;
+        for submission in submissions.into_iter() {
+            if submission.published_date >= since && !candidates.iter().any(|c| c.arxiv_id == submission.arxiv_id) {
+                candidates.push(submission);
+            }
+        }
+    }
+
+    let mut tx = registry.db.inner_ref().begin().await?;
+    let arxiv_ids = candidates.iter().map(|c| c.arxiv_id.clone()).collect::<Vec<String>>();
+    let known = registry
+        .paper_candidate_repository()
+        .select_known_arxiv_ids(&mut tx, &arxiv_ids)
+        .await?;
+    candidates.retain(|c| !known.contains(&c.arxiv_id));
+    tracing::info!(
+        "Found {} new submissions in {} ({} already known)",
+        candidates.len(),
+        args.categories.join(", "),
+        known.len()
+    );
+
+    let keywords = load_keywords();
+    for candidate in candidates.iter_mut() {
+        score_candidate(candidate, &keywords, &args.authors, args.author_weight);
+    }
+    registry
+        .paper_candidate_repository()
+        .create_paper_candidates(&mut tx, &candidates)
+        .await?;
+    tx.commit().await?;
+
+    let top_candidates = select_top_candidates(&candidates, args.top_n, args.min_score);
+    if args.dry_run {
+        for candidate in top_candidates.iter() {
+            tracing::info!(
+                "Would add {} (score: {}, matched: {:?}): {}",
+                candidate.arxiv_id,
+                candidate.score,
+                candidate.matched,
+                candidate.title
+            );
+        }
+        return Ok(());
+    }
+    add_candidates(registry, &top_candidates, language).await;
+    Ok(())
+}
+
+pub async fn watch_arxiv(args: &WatchArxivArgs) {
+    let config = AppConfig::new().expect("Failed to load config");
+    let db = connect_database_with(&config.database);
+    let registry = Arc::new(AppRegistryImpl::new(db));
+    let language = args.language.unwrap_or_else(Language::from_env);
+
+    if !args.promote.is_empty() {
+        if let Err(e) = promote_candidates(&registry, &args.promote, language).await {
+            tracing::error!("Failed to promote candidates: {}", e);
+        }
+        return;
+    }
+
+    loop {
+        tracing::info!("Watching arXiv for new submissions...");
+        if let Err(e) = watch_once(&registry, args, language).await {
+            tracing::error!("Failed to watch arXiv: {}", e);
+        }
+        match args.every_hours {
+            Some(hours) => {
+                tracing::info!("Watching again in {} hours", hours);
+                tokio::time::sleep(Duration::from_secs(hours * 60 * 60)).await;
+            }
+            None => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn candidate(arxiv_id: &str, authors: &[&str], score: i32, hours_ago: i64) -> PaperCandidate {
+        PaperCandidate {
+            arxiv_id: arxiv_id.to_string(),
+            authors: authors.iter().map(|a| a.to_string()).collect(),
+            score,
+            published_date: Local::now() - Duration::hours(hours_ago),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_followed_authors_of() {
+        let paper = candidate("2510.00001", &["Ada  Lovelace", "Alan Turing"], 0, 0);
+        let followed = vec!["ada lovelace".to_string(), "Grace Hopper".to_string()];
+        assert_eq!(followed_authors_of(&paper, &followed), vec!["Ada  Lovelace"]);
+        assert!(followed_authors_of(&paper, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_select_top_candidates() {
+        let candidates = vec![
+            candidate("a", &[], 3, 5),
+            candidate("b", &[], 0, 1),
+            candidate("c", &[], 7, 3),
+            candidate("d", &[], 3, 1),
+        ];
+        let top = select_top_candidates(&candidates, 2, 1);
+        assert_eq!(
+            top.iter().map(|c| c.arxiv_id.as_str()).collect::<Vec<_>>(),
+            vec!["c", "d"]
+        );
+        let top = select_top_candidates(&candidates, 10, 1);
+        assert_eq!(top.len(), 3);
+    }
+}
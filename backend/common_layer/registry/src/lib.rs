@@ -1,15 +1,34 @@
 use adapter::database::ConnectionPool;
 use adapter::repository::{
     academic_paper::{AcademicPaperRepositoryImpl, AuthorRepositoryImpl, JournalRepositoryImpl, TaskRepositoryImpl},
+    citation::CitationRepositoryImpl,
     health::HealthCheckRepositoryImpl,
+    keyword::KeywordRepositoryImpl,
+    paper_candidate::PaperCandidateRepositoryImpl,
+    paper_chunk::PaperChunkRepositoryImpl,
+    paper_comparison::PaperComparisonRepositoryImpl,
+    paper_metadata::PaperMetadataRepositoryImpl,
     paper_note::PaperNoteRepositoryImpl,
+    paper_version::PaperVersionRepositoryImpl,
+    reading::ReadingRepositoryImpl,
     web_article::{WebArticleRepositoryImpl, WebSiteRepositoryImpl},
 };
-use kernel::repository::{
-    academic_paper::{AcademicPaperRepository, AuthorRepository, JournalRepository, TaskRepository},
-    health::HealthCheckRepository,
-    paper_note::PaperNoteRepository,
-    web_article::{WebArticleRepository, WebSiteRepository},
+use kernel::{
+    llm::{openai::OpenAIProvider, LlmProvider},
+    repository::{
+        academic_paper::{AcademicPaperRepository, AuthorRepository, JournalRepository, TaskRepository},
+        citation::CitationRepository,
+        health::HealthCheckRepository,
+        keyword::KeywordRepository,
+        paper_candidate::PaperCandidateRepository,
+        paper_chunk::PaperChunkRepository,
+        paper_comparison::PaperComparisonRepository,
+        paper_metadata::PaperMetadataRepository,
+        paper_note::PaperNoteRepository,
+        paper_version::PaperVersionRepository,
+        reading::ReadingRepository,
+        web_article::{WebArticleRepository, WebSiteRepository},
+    },
 };
 use std::sync::Arc;
 
@@ -24,10 +43,20 @@ pub struct AppRegistryImpl {
     journal_repository: Arc<dyn JournalRepository>,
     task_repository: Arc<dyn TaskRepository>,
     paper_note_repository: Arc<dyn PaperNoteRepository>,
+    paper_candidate_repository: Arc<dyn PaperCandidateRepository>,
+    citation_repository: Arc<dyn CitationRepository>,
+    paper_metadata_repository: Arc<dyn PaperMetadataRepository>,
+    paper_chunk_repository: Arc<dyn PaperChunkRepository>,
+    reading_repository: Arc<dyn ReadingRepository>,
+    keyword_repository: Arc<dyn KeywordRepository>,
+    paper_comparison_repository: Arc<dyn PaperComparisonRepository>,
+    paper_version_repository: Arc<dyn PaperVersionRepository>,
+    llm_provider: Arc<dyn LlmProvider>,
 }
 
 impl AppRegistryImpl {
     pub fn new(db: ConnectionPool) -> Self {
+        let llm_provider: Arc<dyn LlmProvider> = Arc::new(OpenAIProvider::from_env());
         let health_check_repository = Arc::new(HealthCheckRepositoryImpl::new(db.clone()));
         let web_article_repository = Arc::new(WebArticleRepositoryImpl::new(db.clone()));
         let web_site_repository = Arc::new(WebSiteRepositoryImpl::new(db.clone()));
@@ -35,7 +64,16 @@ impl AppRegistryImpl {
         let author_repository = Arc::new(AuthorRepositoryImpl::new(db.clone()));
         let journal_repository = Arc::new(JournalRepositoryImpl::new(db.clone()));
         let task_repository = Arc::new(TaskRepositoryImpl::new(db.clone()));
-        let paper_note_repository = Arc::new(PaperNoteRepositoryImpl::new(db.clone()));
+        let paper_note_repository = Arc::new(PaperNoteRepositoryImpl::new(db.clone(), llm_provider.clone()));
+        let paper_candidate_repository = Arc::new(PaperCandidateRepositoryImpl::new(db.clone()));
+        let citation_repository = Arc::new(CitationRepositoryImpl::new(db.clone()));
+        let paper_metadata_repository = Arc::new(PaperMetadataRepositoryImpl::new(db.clone()));
+        let paper_chunk_repository = Arc::new(PaperChunkRepositoryImpl::new(db.clone()));
+        let reading_repository = Arc::new(ReadingRepositoryImpl::new(db.clone()));
+        let keyword_repository = Arc::new(KeywordRepositoryImpl::new(db.clone()));
+        let paper_comparison_repository =
+            Arc::new(PaperComparisonRepositoryImpl::new(db.clone(), llm_provider.clone()));
+        let paper_version_repository = Arc::new(PaperVersionRepositoryImpl::new(db.clone()));
         Self {
             db,
             health_check_repository,
@@ -46,6 +84,15 @@ impl AppRegistryImpl {
             journal_repository,
             task_repository,
             paper_note_repository,
+            paper_candidate_repository,
+            citation_repository,
+            paper_metadata_repository,
+            paper_chunk_repository,
+            reading_repository,
+            keyword_repository,
+            paper_comparison_repository,
+            paper_version_repository,
+            llm_provider,
         }
     }
 
@@ -73,6 +120,34 @@ impl AppRegistryImpl {
     pub fn paper_note_repository(&self) -> Arc<dyn PaperNoteRepository> {
         self.paper_note_repository.clone()
     }
+    pub fn paper_candidate_repository(&self) -> Arc<dyn PaperCandidateRepository> {
+        self.paper_candidate_repository.clone()
+    }
+
+    pub fn citation_repository(&self) -> Arc<dyn CitationRepository> {
+        self.citation_repository.clone()
+    }
+    pub fn paper_metadata_repository(&self) -> Arc<dyn PaperMetadataRepository> {
+        self.paper_metadata_repository.clone()
+    }
+    pub fn paper_chunk_repository(&self) -> Arc<dyn PaperChunkRepository> {
+        self.paper_chunk_repository.clone()
+    }
+    pub fn reading_repository(&self) -> Arc<dyn ReadingRepository> {
+        self.reading_repository.clone()
+    }
+    pub fn keyword_repository(&self) -> Arc<dyn KeywordRepository> {
+        self.keyword_repository.clone()
+    }
+    pub fn paper_comparison_repository(&self) -> Arc<dyn PaperComparisonRepository> {
+        self.paper_comparison_repository.clone()
+    }
+    pub fn paper_version_repository(&self) -> Arc<dyn PaperVersionRepository> {
+        self.paper_version_repository.clone()
+    }
+    pub fn llm_provider(&self) -> Arc<dyn LlmProvider> {
+        self.llm_provider.clone()
+    }
 }
 
 #[mockall::automock]
@@ -86,6 +161,15 @@ pub trait AppRegistryExt {
     fn journal_repository(&self) -> Arc<dyn JournalRepository>;
     fn task_repository(&self) -> Arc<dyn TaskRepository>;
     fn paper_note_repository(&self) -> Arc<dyn PaperNoteRepository>;
+    fn paper_candidate_repository(&self) -> Arc<dyn PaperCandidateRepository>;
+    fn citation_repository(&self) -> Arc<dyn CitationRepository>;
+    fn paper_metadata_repository(&self) -> Arc<dyn PaperMetadataRepository>;
+    fn paper_chunk_repository(&self) -> Arc<dyn PaperChunkRepository>;
+    fn reading_repository(&self) -> Arc<dyn ReadingRepository>;
+    fn keyword_repository(&self) -> Arc<dyn KeywordRepository>;
+    fn paper_comparison_repository(&self) -> Arc<dyn PaperComparisonRepository>;
+    fn paper_version_repository(&self) -> Arc<dyn PaperVersionRepository>;
+    fn llm_provider(&self) -> Arc<dyn LlmProvider>;
 }
 
 impl AppRegistryExt for AppRegistryImpl {
@@ -116,6 +200,34 @@ impl AppRegistryExt for AppRegistryImpl {
     fn paper_note_repository(&self) -> Arc<dyn PaperNoteRepository> {
         self.paper_note_repository.clone()
     }
+    fn paper_candidate_repository(&self) -> Arc<dyn PaperCandidateRepository> {
+        self.paper_candidate_repository.clone()
+    }
+
+    fn citation_repository(&self) -> Arc<dyn CitationRepository> {
+        self.citation_repository.clone()
+    }
+    fn paper_metadata_repository(&self) -> Arc<dyn PaperMetadataRepository> {
+        self.paper_metadata_repository.clone()
+    }
+    fn paper_chunk_repository(&self) -> Arc<dyn PaperChunkRepository> {
+        self.paper_chunk_repository.clone()
+    }
+    fn reading_repository(&self) -> Arc<dyn ReadingRepository> {
+        self.reading_repository.clone()
+    }
+    fn keyword_repository(&self) -> Arc<dyn KeywordRepository> {
+        self.keyword_repository.clone()
+    }
+    fn paper_comparison_repository(&self) -> Arc<dyn PaperComparisonRepository> {
+        self.paper_comparison_repository.clone()
+    }
+    fn paper_version_repository(&self) -> Arc<dyn PaperVersionRepository> {
+        self.paper_version_repository.clone()
+    }
+    fn llm_provider(&self) -> Arc<dyn LlmProvider> {
+        self.llm_provider.clone()
+    }
 }
 
 pub type AppRegistry = Arc<dyn AppRegistryExt + Send + Sync + 'static>;
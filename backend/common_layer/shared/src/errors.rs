@@ -57,6 +57,12 @@ pub enum AppError {
     // from openai-tools errors
     #[error("OpenAI Tools Error: {0}")]
     OpenAIToolError(#[from] openai_tools::common::OpenAIToolError),
+
+    // from llm providers
+    #[error("LLM Error - rate limited: {0}")]
+    LlmRateLimited(String),
+    #[error("LLM Error - invalid response: {0}")]
+    LlmInvalidResponse(String),
 }
 
 fn app_error_to_status_code(error: &AppError) -> StatusCode {
@@ -79,6 +85,8 @@ fn app_error_to_status_code(error: &AppError) -> StatusCode {
         AppError::JsonParseError(_) => StatusCode::BAD_REQUEST,
         AppError::ScrapeError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         AppError::OpenAIToolError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        AppError::LlmRateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+        AppError::LlmInvalidResponse(_) => StatusCode::BAD_GATEWAY,
     }
 }
 
@@ -92,3 +92,7 @@ define_id!(AuthorPaperRelationId);
 define_id!(TaskPaperRelationId);
 define_id!(StatusId);
 define_id!(PaperNoteId);
+define_id!(PaperCandidateId);
+define_id!(PaperNoteMessageId);
+define_id!(KeywordId);
+define_id!(PaperComparisonId);
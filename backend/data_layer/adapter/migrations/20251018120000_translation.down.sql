@@ -0,0 +1,34 @@
+--1. drop triggers
+DROP TRIGGER IF EXISTS academic_paper_translation_set_updated_at_trigger ON academic_paper_translation;
+DROP TRIGGER IF EXISTS web_article_translation_set_updated_at_trigger ON web_article_translation;
+
+--2. restore the Japanese columns
+ALTER TABLE academic_paper
+    ADD COLUMN IF NOT EXISTS abstract_text_ja TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS summary TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS background_and_purpose TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS methodology TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS dataset TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS results TEXT NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS advantages_limitations_and_future_work TEXT NOT NULL DEFAULT '';
+
+ALTER TABLE web_article ADD COLUMN IF NOT EXISTS summary TEXT NOT NULL DEFAULT '';
+
+UPDATE academic_paper AS ap SET
+    abstract_text_ja = apt.abstract_text,
+    summary = apt.summary,
+    background_and_purpose = apt.background_and_purpose,
+    methodology = apt.methodology,
+    dataset = apt.dataset,
+    results = apt.results,
+    advantages_limitations_and_future_work = apt.advantages_limitations_and_future_work
+FROM academic_paper_translation AS apt
+WHERE apt.paper_id = ap.paper_id AND apt.language = 'ja';
+
+UPDATE web_article AS wa SET summary = wat.summary
+FROM web_article_translation AS wat
+WHERE wat.article_id = wa.article_id AND wat.language = 'ja';
+
+--3. drop tables
+DROP TABLE IF EXISTS academic_paper_translation;
+DROP TABLE IF EXISTS web_article_translation;
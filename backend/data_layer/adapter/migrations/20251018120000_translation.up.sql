@@ -0,0 +1,76 @@
+-- 1. create tables
+CREATE TABLE IF NOT EXISTS academic_paper_translation (
+    paper_id UUID NOT NULL REFERENCES academic_paper (paper_id) ON DELETE CASCADE,
+    language VARCHAR(16) NOT NULL,
+    abstract_text TEXT NOT NULL DEFAULT '',
+    summary TEXT NOT NULL DEFAULT '',
+    background_and_purpose TEXT NOT NULL DEFAULT '',
+    methodology TEXT NOT NULL DEFAULT '',
+    dataset TEXT NOT NULL DEFAULT '',
+    results TEXT NOT NULL DEFAULT '',
+    advantages_limitations_and_future_work TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (paper_id, language)
+);
+
+CREATE TABLE IF NOT EXISTS web_article_translation (
+    article_id UUID NOT NULL REFERENCES web_article (article_id) ON DELETE CASCADE,
+    language VARCHAR(16) NOT NULL,
+    summary TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (article_id, language)
+);
+
+-- 2. move the existing Japanese content into translation rows
+INSERT INTO academic_paper_translation (
+    paper_id,
+    language,
+    abstract_text,
+    summary,
+    background_and_purpose,
+    methodology,
+    dataset,
+    results,
+    advantages_limitations_and_future_work
+)
+SELECT
+    paper_id,
+    'ja',
+    abstract_text_ja,
+    summary,
+    background_and_purpose,
+    methodology,
+    dataset,
+    results,
+    advantages_limitations_and_future_work
+FROM academic_paper
+WHERE abstract_text_ja <> '' OR summary <> '';
+
+INSERT INTO web_article_translation (article_id, language, summary)
+SELECT article_id, 'ja', summary
+FROM web_article
+WHERE summary <> '';
+
+ALTER TABLE academic_paper
+    DROP COLUMN abstract_text_ja,
+    DROP COLUMN summary,
+    DROP COLUMN background_and_purpose,
+    DROP COLUMN methodology,
+    DROP COLUMN dataset,
+    DROP COLUMN results,
+    DROP COLUMN advantages_limitations_and_future_work;
+
+ALTER TABLE web_article DROP COLUMN summary;
+
+-- 3. create triggers
+CREATE OR REPLACE TRIGGER academic_paper_translation_set_updated_at_trigger
+    BEFORE UPDATE ON academic_paper_translation
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+CREATE OR REPLACE TRIGGER web_article_translation_set_updated_at_trigger
+    BEFORE UPDATE ON web_article_translation
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
@@ -0,0 +1,7 @@
+-- 1. drop indexes
+DROP INDEX IF EXISTS web_article_enrichment_status_idx;
+
+-- 2. drop columns
+ALTER TABLE web_article
+    DROP COLUMN IF EXISTS enrichment_batch_id,
+    DROP COLUMN IF EXISTS enrichment_status;
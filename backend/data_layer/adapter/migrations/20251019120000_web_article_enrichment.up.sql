@@ -0,0 +1,7 @@
+-- 1. track the AI enrichment of articles collected in batch mode
+ALTER TABLE web_article
+    ADD COLUMN IF NOT EXISTS enrichment_status VARCHAR(16) NOT NULL DEFAULT 'done',
+    ADD COLUMN IF NOT EXISTS enrichment_batch_id VARCHAR(255);
+
+-- 2. create indexes
+CREATE INDEX IF NOT EXISTS web_article_enrichment_status_idx ON web_article (enrichment_status);
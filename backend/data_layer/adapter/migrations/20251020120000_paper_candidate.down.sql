@@ -0,0 +1,8 @@
+-- 1. drop triggers
+DROP TRIGGER IF EXISTS paper_candidate_set_updated_at_trigger ON paper_candidate;
+
+-- 2. drop indexes
+DROP INDEX IF EXISTS paper_candidate_status_score_idx;
+
+-- 3. drop tables
+DROP TABLE IF EXISTS paper_candidate;
@@ -0,0 +1,26 @@
+-- 1. create tables
+CREATE TABLE IF NOT EXISTS paper_candidate (
+    candidate_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    arxiv_id VARCHAR(255) NOT NULL UNIQUE,
+    title TEXT NOT NULL DEFAULT '',
+    abstract_text TEXT NOT NULL DEFAULT '',
+    authors TEXT[] NOT NULL DEFAULT '{}',
+    categories TEXT[] NOT NULL DEFAULT '{}',
+    pdf_url TEXT NOT NULL DEFAULT '',
+    published_date TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    score INT NOT NULL DEFAULT 0,
+    matched TEXT[] NOT NULL DEFAULT '{}',
+    status VARCHAR(16) NOT NULL DEFAULT 'candidate',
+    paper_id UUID REFERENCES academic_paper (paper_id) ON DELETE SET NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+-- 2. create triggers
+CREATE OR REPLACE TRIGGER paper_candidate_set_updated_at_trigger
+    BEFORE UPDATE ON paper_candidate
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+-- 3. create indexes
+CREATE INDEX IF NOT EXISTS paper_candidate_status_score_idx ON paper_candidate (status, score DESC);
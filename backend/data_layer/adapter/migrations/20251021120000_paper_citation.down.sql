@@ -0,0 +1,10 @@
+-- 1. drop triggers
+DROP TRIGGER IF EXISTS citation_placeholder_set_updated_at_trigger ON citation_placeholder;
+
+-- 2. drop indexes
+DROP INDEX IF EXISTS academic_paper_ss_id_idx;
+DROP INDEX IF EXISTS paper_citation_cited_ss_id_idx;
+
+-- 3. drop tables
+DROP TABLE IF EXISTS paper_citation;
+DROP TABLE IF EXISTS citation_placeholder;
@@ -0,0 +1,26 @@
+-- 1. create tables
+-- papers that appear in the citation graph; the ones in the library are joined through academic_paper.ss_id
+CREATE TABLE IF NOT EXISTS citation_placeholder (
+    ss_id VARCHAR(255) PRIMARY KEY,
+    title TEXT NOT NULL DEFAULT '',
+    year INT,
+    created_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+CREATE TABLE IF NOT EXISTS paper_citation (
+    citing_ss_id VARCHAR(255) NOT NULL REFERENCES citation_placeholder (ss_id) ON DELETE CASCADE,
+    cited_ss_id VARCHAR(255) NOT NULL REFERENCES citation_placeholder (ss_id) ON DELETE CASCADE,
+    created_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (citing_ss_id, cited_ss_id)
+);
+
+-- 2. create triggers
+CREATE OR REPLACE TRIGGER citation_placeholder_set_updated_at_trigger
+    BEFORE UPDATE ON citation_placeholder
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+-- 3. create indexes
+CREATE INDEX IF NOT EXISTS paper_citation_cited_ss_id_idx ON paper_citation (cited_ss_id);
+CREATE INDEX IF NOT EXISTS academic_paper_ss_id_idx ON academic_paper (ss_id);
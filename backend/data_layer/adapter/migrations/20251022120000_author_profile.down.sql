@@ -0,0 +1,10 @@
+-- 1. drop indexes
+DROP INDEX IF EXISTS author_paper_relation_paper_id_idx;
+DROP INDEX IF EXISTS author_ss_id_key;
+
+-- 2. alter tables
+-- merged authors are not restored
+ALTER TABLE author
+    DROP COLUMN IF EXISTS updated_at,
+    DROP COLUMN IF EXISTS created_at,
+    DROP COLUMN IF EXISTS h_index_updated_at;
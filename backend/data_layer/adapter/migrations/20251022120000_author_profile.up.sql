@@ -0,0 +1,34 @@
+-- 1. merge duplicate authors
+-- keep one author per Semantic Scholar ID and move the papers of the others to it
+CREATE TEMPORARY TABLE author_merge ON COMMIT DROP AS
+SELECT
+    author_id,
+    FIRST_VALUE(author_id) OVER (PARTITION BY ss_id ORDER BY h_index DESC, author_id) AS kept_author_id
+FROM author
+WHERE ss_id <> '';
+
+INSERT INTO author_paper_relation (author_id, paper_id)
+SELECT m.kept_author_id, r.paper_id
+FROM author_paper_relation r
+JOIN author_merge m ON m.author_id = r.author_id
+WHERE m.author_id <> m.kept_author_id
+ON CONFLICT DO NOTHING;
+
+DELETE FROM author_paper_relation r
+USING author_merge m
+WHERE r.author_id = m.author_id AND m.author_id <> m.kept_author_id;
+
+DELETE FROM author a
+USING author_merge m
+WHERE a.author_id = m.author_id AND m.author_id <> m.kept_author_id;
+
+-- 2. alter tables
+-- `author_set_updated_at_trigger` expects the timestamp columns
+ALTER TABLE author
+    ADD COLUMN IF NOT EXISTS h_index_updated_at TIMESTAMP(3) WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3),
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP(3) WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP(3);
+
+-- 3. create indexes
+CREATE UNIQUE INDEX IF NOT EXISTS author_ss_id_key ON author (ss_id) WHERE ss_id <> '';
+CREATE INDEX IF NOT EXISTS author_paper_relation_paper_id_idx ON author_paper_relation (paper_id);
@@ -0,0 +1,11 @@
+-- 1. drop indexes
+DROP INDEX IF EXISTS academic_paper_metadata_refreshed_at_idx;
+DROP INDEX IF EXISTS paper_metadata_history_field_idx;
+DROP INDEX IF EXISTS paper_metadata_history_paper_id_idx;
+
+-- 2. drop tables
+DROP TABLE IF EXISTS paper_metadata_history;
+
+-- 3. alter tables
+ALTER TABLE academic_paper
+    DROP COLUMN IF EXISTS metadata_refreshed_at;
@@ -0,0 +1,20 @@
+-- 1. alter tables
+-- NULL until the paper is refreshed for the first time
+ALTER TABLE academic_paper
+    ADD COLUMN IF NOT EXISTS metadata_refreshed_at TIMESTAMP(3) WITH TIME ZONE;
+
+-- 2. create tables
+-- one row per changed field and refresh; values are stored as text
+CREATE TABLE IF NOT EXISTS paper_metadata_history (
+    history_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    paper_id UUID NOT NULL REFERENCES academic_paper (paper_id) ON DELETE CASCADE,
+    field VARCHAR(64) NOT NULL,
+    old_value TEXT NOT NULL,
+    new_value TEXT NOT NULL,
+    changed_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+-- 3. create indexes
+CREATE INDEX IF NOT EXISTS paper_metadata_history_paper_id_idx ON paper_metadata_history (paper_id, changed_at);
+CREATE INDEX IF NOT EXISTS paper_metadata_history_field_idx ON paper_metadata_history (field, changed_at);
+CREATE INDEX IF NOT EXISTS academic_paper_metadata_refreshed_at_idx ON academic_paper (metadata_refreshed_at NULLS FIRST);
@@ -0,0 +1,6 @@
+-- 1. drop indexes
+DROP INDEX IF EXISTS paper_pdf_sha256_idx;
+
+-- 2. drop tables
+DROP TABLE IF EXISTS paper_pdf;
+DROP TABLE IF EXISTS paper_section;
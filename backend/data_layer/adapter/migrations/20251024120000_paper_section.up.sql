@@ -0,0 +1,19 @@
+-- 1. create tables
+-- the sections parsed from the PDF, in the order they appear in the paper
+CREATE TABLE IF NOT EXISTS paper_section (
+    paper_id UUID NOT NULL REFERENCES academic_paper (paper_id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    title TEXT NOT NULL DEFAULT '',
+    content TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (paper_id, position)
+);
+
+-- the source PDF in the local store, addressed by the SHA-256 of its content
+CREATE TABLE IF NOT EXISTS paper_pdf (
+    paper_id UUID PRIMARY KEY REFERENCES academic_paper (paper_id) ON DELETE CASCADE,
+    sha256 VARCHAR(64) NOT NULL,
+    stored_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+-- 2. create indexes
+CREATE INDEX IF NOT EXISTS paper_pdf_sha256_idx ON paper_pdf (sha256);
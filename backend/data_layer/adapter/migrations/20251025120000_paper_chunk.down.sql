@@ -0,0 +1,5 @@
+-- 1. drop indexes
+DROP INDEX IF EXISTS paper_chunk_content_tsv_idx;
+
+-- 2. drop tables
+DROP TABLE IF EXISTS paper_chunk;
@@ -0,0 +1,15 @@
+-- 1. create tables
+-- the passages of the full text used for retrieval; rebuilt whenever the sections are replaced
+CREATE TABLE IF NOT EXISTS paper_chunk (
+    paper_id UUID NOT NULL REFERENCES academic_paper (paper_id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    section_titles TEXT[] NOT NULL DEFAULT '{}',
+    content TEXT NOT NULL DEFAULT '',
+    -- NULL when the LLM provider could not embed the passage
+    embedding REAL[],
+    content_tsv TSVECTOR GENERATED ALWAYS AS (to_tsvector('english', content)) STORED,
+    PRIMARY KEY (paper_id, position)
+);
+
+-- 2. create indexes
+CREATE INDEX IF NOT EXISTS paper_chunk_content_tsv_idx ON paper_chunk USING GIN (content_tsv);
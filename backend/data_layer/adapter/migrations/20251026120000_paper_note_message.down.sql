@@ -0,0 +1,5 @@
+-- 1. drop triggers
+DROP TRIGGER IF EXISTS paper_note_message_set_updated_at_trigger ON paper_note_message;
+
+-- 2. drop tables
+DROP TABLE IF EXISTS paper_note_message;
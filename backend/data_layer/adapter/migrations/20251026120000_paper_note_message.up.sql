@@ -0,0 +1,52 @@
+-- 1. create tables
+-- the questions to the agent about a paper note and its answers, in the order of the conversation
+CREATE TABLE IF NOT EXISTS paper_note_message (
+    paper_note_message_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    paper_note_id UUID NOT NULL REFERENCES paper_note (paper_note_id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    role VARCHAR(16) NOT NULL CHECK (role IN ('user', 'assistant')),
+    content TEXT NOT NULL DEFAULT '',
+    -- the model that generated an answer, empty for a question
+    model TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    UNIQUE (paper_note_id, position)
+);
+
+-- 2. create triggers
+CREATE OR REPLACE TRIGGER paper_note_message_set_updated_at_trigger
+    BEFORE UPDATE ON paper_note_message
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+-- 3. move the questions and answers appended to the notes into the messages
+WITH parts AS (
+    SELECT n.paper_note_id, n.note_timestamp, p.part, p.ord
+    FROM paper_note AS n,
+        string_to_table(n.note, E'<br />\n\n---\n\nQ: ') WITH ORDINALITY AS p(part, ord)
+    WHERE n.note LIKE E'%\n\n------ from Agent -------\n\n%'
+),
+turns AS (
+    SELECT
+        paper_note_id,
+        note_timestamp,
+        ord,
+        split_part(part, E'\n\n------ from Agent -------\n\n', 1) AS question,
+        rtrim(split_part(part, E'\n\n------ from Agent -------\n\n', 2), E'\n') AS answer
+    FROM parts
+    WHERE ord > 1 AND part LIKE E'%\n\n------ from Agent -------\n\n%'
+)
+INSERT INTO paper_note_message (paper_note_id, position, role, content, created_at, updated_at)
+SELECT
+    t.paper_note_id,
+    (t.ord - 2) * 2 + r.offset_in_turn,
+    r.role,
+    CASE r.role WHEN 'user' THEN t.question ELSE t.answer END,
+    COALESCE(t.note_timestamp, CURRENT_TIMESTAMP(3)),
+    COALESCE(t.note_timestamp, CURRENT_TIMESTAMP(3))
+FROM turns AS t
+CROSS JOIN (VALUES ('user', 0), ('assistant', 1)) AS r(role, offset_in_turn);
+
+UPDATE paper_note
+SET note = split_part(note, E'<br />\n\n---\n\nQ: ', 1)
+WHERE note LIKE E'%\n\n------ from Agent -------\n\n%';
@@ -0,0 +1,10 @@
+-- 1. drop triggers
+DROP TRIGGER IF EXISTS reading_queue_set_updated_at_trigger ON reading_queue;
+
+-- 2. drop indexes
+DROP INDEX IF EXISTS paper_status_history_changed_at_idx;
+DROP INDEX IF EXISTS paper_status_history_paper_id_changed_at_idx;
+
+-- 3. drop tables
+DROP TABLE IF EXISTS reading_queue;
+DROP TABLE IF EXISTS paper_status_history;
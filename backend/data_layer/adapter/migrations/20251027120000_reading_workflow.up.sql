@@ -0,0 +1,29 @@
+-- 1. create tables
+-- the reading status transitions of the papers; from_status is NULL for the first recorded status.
+-- changed_at is the wall clock time in microseconds, so that the changes made in one transaction keep their order
+CREATE TABLE IF NOT EXISTS paper_status_history (
+    paper_status_history_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    paper_id UUID NOT NULL REFERENCES academic_paper (paper_id) ON DELETE CASCADE,
+    from_status VARCHAR(16),
+    to_status VARCHAR(16) NOT NULL,
+    changed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT clock_timestamp()
+);
+
+-- the papers to read next, by priority and due date
+CREATE TABLE IF NOT EXISTS reading_queue (
+    paper_id UUID PRIMARY KEY REFERENCES academic_paper (paper_id) ON DELETE CASCADE,
+    priority INTEGER NOT NULL DEFAULT 0,
+    due_date DATE,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+-- 2. create indexes
+CREATE INDEX IF NOT EXISTS paper_status_history_paper_id_changed_at_idx ON paper_status_history (paper_id, changed_at);
+CREATE INDEX IF NOT EXISTS paper_status_history_changed_at_idx ON paper_status_history (changed_at);
+
+-- 3. create triggers
+CREATE OR REPLACE TRIGGER reading_queue_set_updated_at_trigger
+    BEFORE UPDATE ON reading_queue
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
@@ -0,0 +1,14 @@
+-- 1. drop indexes
+DROP INDEX IF EXISTS paper_keyword_relation_keyword_id_idx;
+DROP INDEX IF EXISTS keyword_category_name_idx;
+
+-- 2. alter tables
+ALTER TABLE paper_keyword_relation
+    DROP CONSTRAINT IF EXISTS paper_keyword_relation_paper_id_fkey,
+    DROP CONSTRAINT IF EXISTS paper_keyword_relation_keyword_id_fkey,
+    ADD CONSTRAINT paper_keyword_relation_paper_id_fkey
+        FOREIGN KEY (paper_id) REFERENCES academic_paper (paper_id),
+    ADD CONSTRAINT paper_keyword_relation_keyword_id_fkey
+        FOREIGN KEY (keyword_id) REFERENCES keyword (keyword_id),
+    DROP COLUMN IF EXISTS updated_at,
+    DROP COLUMN IF EXISTS created_at;
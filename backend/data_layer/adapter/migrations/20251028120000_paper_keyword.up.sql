@@ -0,0 +1,44 @@
+-- keyword holds the user-managed tags (category = 'tag') and collections (category = 'collection') of the papers
+
+-- 1. merge the duplicated keywords before making the names unique
+UPDATE paper_keyword_relation AS r
+SET keyword_id = d.keep_id
+FROM (
+    SELECT
+        keyword_id,
+        FIRST_VALUE(keyword_id) OVER (PARTITION BY category, LOWER(name) ORDER BY created_at, keyword_id) AS keep_id
+    FROM keyword
+) AS d
+WHERE r.keyword_id = d.keyword_id
+    AND d.keyword_id <> d.keep_id
+    AND NOT EXISTS (
+        SELECT 1 FROM paper_keyword_relation AS e WHERE e.paper_id = r.paper_id AND e.keyword_id = d.keep_id
+    );
+DELETE FROM paper_keyword_relation AS r
+USING (
+    SELECT
+        keyword_id,
+        FIRST_VALUE(keyword_id) OVER (PARTITION BY category, LOWER(name) ORDER BY created_at, keyword_id) AS keep_id
+    FROM keyword
+) AS d
+WHERE r.keyword_id = d.keyword_id AND d.keyword_id <> d.keep_id;
+DELETE FROM keyword AS k
+USING keyword AS o
+WHERE k.category = o.category
+    AND LOWER(k.name) = LOWER(o.name)
+    AND (k.created_at, k.keyword_id) > (o.created_at, o.keyword_id);
+
+-- 2. alter tables
+ALTER TABLE paper_keyword_relation
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    DROP CONSTRAINT IF EXISTS paper_keyword_relation_paper_id_fkey,
+    DROP CONSTRAINT IF EXISTS paper_keyword_relation_keyword_id_fkey,
+    ADD CONSTRAINT paper_keyword_relation_paper_id_fkey
+        FOREIGN KEY (paper_id) REFERENCES academic_paper (paper_id) ON DELETE CASCADE,
+    ADD CONSTRAINT paper_keyword_relation_keyword_id_fkey
+        FOREIGN KEY (keyword_id) REFERENCES keyword (keyword_id) ON DELETE CASCADE;
+
+-- 3. create indexes
+CREATE UNIQUE INDEX IF NOT EXISTS keyword_category_name_idx ON keyword (category, LOWER(name));
+CREATE INDEX IF NOT EXISTS paper_keyword_relation_keyword_id_idx ON paper_keyword_relation (keyword_id);
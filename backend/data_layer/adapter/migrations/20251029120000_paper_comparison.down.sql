@@ -0,0 +1,11 @@
+-- 1. drop triggers
+DROP TRIGGER IF EXISTS paper_comparison_set_updated_at_trigger ON paper_comparison;
+
+-- 2. drop indexes
+DROP INDEX IF EXISTS paper_comparison_paper_paper_id_idx;
+DROP INDEX IF EXISTS paper_comparison_created_at_idx;
+
+-- 3. drop tables
+DROP TABLE IF EXISTS paper_comparison_cell;
+DROP TABLE IF EXISTS paper_comparison_paper;
+DROP TABLE IF EXISTS paper_comparison;
@@ -0,0 +1,39 @@
+-- 1. create tables
+-- the LLM-generated comparisons of papers
+CREATE TABLE IF NOT EXISTS paper_comparison (
+    paper_comparison_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    synthesis TEXT NOT NULL DEFAULT '',
+    language VARCHAR(8) NOT NULL DEFAULT 'ja',
+    model VARCHAR(255) NOT NULL DEFAULT '',
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+-- the compared papers in order; the title is kept so that the comparison stays readable after the paper is deleted
+CREATE TABLE IF NOT EXISTS paper_comparison_paper (
+    paper_comparison_id UUID NOT NULL REFERENCES paper_comparison (paper_comparison_id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    paper_id UUID REFERENCES academic_paper (paper_id) ON DELETE SET NULL,
+    title TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (paper_comparison_id, position)
+);
+
+-- the cells of the comparison matrix, by the positions of the dimension and the paper
+CREATE TABLE IF NOT EXISTS paper_comparison_cell (
+    paper_comparison_id UUID NOT NULL REFERENCES paper_comparison (paper_comparison_id) ON DELETE CASCADE,
+    dimension_position INTEGER NOT NULL,
+    dimension TEXT NOT NULL,
+    paper_position INTEGER NOT NULL,
+    value TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (paper_comparison_id, dimension_position, paper_position)
+);
+
+-- 2. create indexes
+CREATE INDEX IF NOT EXISTS paper_comparison_created_at_idx ON paper_comparison (created_at);
+CREATE INDEX IF NOT EXISTS paper_comparison_paper_paper_id_idx ON paper_comparison_paper (paper_id);
+
+-- 3. create triggers
+CREATE OR REPLACE TRIGGER paper_comparison_set_updated_at_trigger
+    BEFORE UPDATE ON paper_comparison
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
@@ -0,0 +1,8 @@
+-- 1. drop indexes
+DROP INDEX IF EXISTS academic_paper_versions_checked_at_idx;
+
+-- 2. drop tables
+DROP TABLE IF EXISTS paper_version;
+
+-- 3. alter tables
+ALTER TABLE academic_paper DROP COLUMN IF EXISTS versions_checked_at;
@@ -0,0 +1,21 @@
+-- 1. alter tables
+-- NULL until the arXiv versions of the paper are checked for the first time
+ALTER TABLE academic_paper
+    ADD COLUMN IF NOT EXISTS versions_checked_at TIMESTAMP(3) WITH TIME ZONE;
+
+-- 2. create tables
+-- one row per arXiv version seen since the paper was added; the first row has no change summary
+CREATE TABLE IF NOT EXISTS paper_version (
+    paper_id UUID NOT NULL REFERENCES academic_paper (paper_id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    -- when arXiv published the version, if known
+    submitted_at TIMESTAMP(3) WITH TIME ZONE,
+    pdf_sha256 VARCHAR(64) NOT NULL DEFAULT '',
+    changed_sections TEXT[] NOT NULL DEFAULT '{}',
+    change_summary TEXT NOT NULL DEFAULT '',
+    detected_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (paper_id, version)
+);
+
+-- 3. create indexes
+CREATE INDEX IF NOT EXISTS academic_paper_versions_checked_at_idx ON academic_paper (versions_checked_at NULLS FIRST);
@@ -0,0 +1,5 @@
+-- 1. drop indexes
+DROP INDEX IF EXISTS task_alias_task_id_idx;
+
+-- 2. drop tables
+DROP TABLE IF EXISTS task_alias;
@@ -0,0 +1,12 @@
+-- 1. create tables
+-- the other names a task was extracted under; a name is resolved to its task by the normalized key
+CREATE TABLE IF NOT EXISTS task_alias (
+    -- the lowercase words of the name, e.g. `named entity recognition` for `Named-Entity Recognition`
+    alias_key VARCHAR(255) PRIMARY KEY,
+    task_id UUID NOT NULL REFERENCES task (task_id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+-- 2. create indexes
+CREATE INDEX IF NOT EXISTS task_alias_task_id_idx ON task_alias (task_id);
@@ -0,0 +1,10 @@
+-- 1. drop indexes
+DROP INDEX IF EXISTS journal_alias_journal_id_idx;
+
+-- 2. drop tables
+DROP TABLE IF EXISTS journal_alias;
+
+-- 3. alter tables
+ALTER TABLE academic_paper DROP COLUMN IF EXISTS venue_year;
+ALTER TABLE journal DROP COLUMN IF EXISTS tier;
+ALTER TABLE journal DROP COLUMN IF EXISTS venue_type;
@@ -0,0 +1,21 @@
+-- 1. alter tables
+-- `conference`, `journal`, `workshop` or `preprint`; NULL if the venue is not classified yet
+ALTER TABLE journal ADD COLUMN IF NOT EXISTS venue_type VARCHAR(16);
+-- the tier or rank the user gives to the venue, e.g. `A*` or `Q1`
+ALTER TABLE journal ADD COLUMN IF NOT EXISTS tier VARCHAR(32);
+-- the year of the edition of the venue, e.g. 2023 for `NeurIPS 2023`
+ALTER TABLE academic_paper ADD COLUMN IF NOT EXISTS venue_year INTEGER;
+
+-- 2. create tables
+-- the other names a venue was published under; a name is resolved to its venue by the normalized key
+CREATE TABLE IF NOT EXISTS journal_alias (
+    -- the lowercase words of the name without the editions, e.g. `conference machine learning` for
+    -- `Proceedings of the 40th International Conference on Machine Learning`
+    alias_key VARCHAR(255) PRIMARY KEY,
+    journal_id UUID NOT NULL REFERENCES journal (journal_id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+-- 3. create indexes
+CREATE INDEX IF NOT EXISTS journal_alias_journal_id_idx ON journal_alias (journal_id);
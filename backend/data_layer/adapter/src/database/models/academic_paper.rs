@@ -1,6 +1,12 @@
 use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
 use derive_new::new;
-use kernel::models::academic_paper::{AcademicPaper, Author, Journal, Status, Task};
+use kernel::models::{
+    academic_paper::{AcademicPaper, AcademicPaperTranslation, Author, Journal, PaperSection, Status, Task},
+    language::Language,
+    paper_duplicate::DuplicatePaper,
+    task::TaskEntry,
+    venue::VenueType,
+};
 use shared::id::{AcademicPaperId, AuthorId, JournalId, TaskId};
 use sqlx::FromRow;
 use std::str::FromStr;
@@ -67,23 +73,65 @@ impl From<TaskRecord> for Task {
     }
 }
 
+#[derive(Debug, Clone, new, FromRow)]
+pub struct TaskEntryRecord {
+    pub task_id: TaskId,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub paper_count: i64,
+}
+
+impl From<TaskEntryRecord> for TaskEntry {
+    fn from(record: TaskEntryRecord) -> Self {
+        let TaskEntryRecord {
+            task_id,
+            name,
+            aliases,
+            paper_count,
+        } = record;
+        Self::new(Task::new(task_id, name), aliases, paper_count, None)
+    }
+}
+
 #[derive(Debug, Clone, new, FromRow)]
 pub struct JournalRecord {
     pub journal_id: JournalId,
     pub name: String,
+    pub venue_type: Option<String>,
+    pub tier: Option<String>,
 }
 
 impl From<Journal> for JournalRecord {
     fn from(journal: Journal) -> Self {
-        let Journal { journal_id, name } = journal;
-        Self { journal_id, name }
+        let Journal {
+            journal_id,
+            name,
+            venue_type,
+            tier,
+        } = journal;
+        Self {
+            journal_id,
+            name,
+            venue_type: venue_type.map(|venue_type| venue_type.to_string()),
+            tier,
+        }
     }
 }
 
 impl From<JournalRecord> for Journal {
     fn from(journal_record: JournalRecord) -> Self {
-        let JournalRecord { journal_id, name } = journal_record;
-        Self { journal_id, name }
+        let JournalRecord {
+            journal_id,
+            name,
+            venue_type,
+            tier,
+        } = journal_record;
+        Self {
+            journal_id,
+            name,
+            venue_type: venue_type.and_then(|venue_type| VenueType::from_str(&venue_type).ok()),
+            tier,
+        }
     }
 }
 
@@ -95,11 +143,11 @@ pub struct AcademicPaperRecord {
     pub journal_id: JournalId,
     pub title: String,
     pub abstract_text: String,
-    pub abstract_text_ja: String,
     pub text: String,
     pub url: String,
     pub doi: String,
     pub published_date: NaiveDateTime,
+    pub venue_year: Option<i32>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
     pub primary_category: String,
@@ -107,12 +155,6 @@ pub struct AcademicPaperRecord {
     pub references_count: i32,
     pub influential_citation_count: i32,
     pub bibtex: String,
-    pub summary: String,
-    pub background_and_purpose: String,
-    pub methodology: String,
-    pub dataset: String,
-    pub results: String,
-    pub advantages_limitations_and_future_work: String,
     pub status: String,
 }
 
@@ -124,14 +166,17 @@ impl From<AcademicPaper> for AcademicPaperRecord {
             arxiv_id,
             title,
             abstract_text,
-            abstract_text_ja,
             text,
+            sections: _,
+            pdf_sha256: _,
+            translations: _,
             url,
             doi,
             journal,
             tasks: _,
             authors: _,
             published_date,
+            venue_year,
             created_at,
             updated_at,
             primary_category,
@@ -139,12 +184,6 @@ impl From<AcademicPaper> for AcademicPaperRecord {
             references_count,
             influential_citation_count,
             bibtex,
-            summary,
-            background_and_purpose,
-            methodology,
-            dataset,
-            results,
-            advantages_limitations_and_future_work,
             status,
         } = paper;
         Self {
@@ -154,11 +193,11 @@ impl From<AcademicPaper> for AcademicPaperRecord {
             journal_id: journal.journal_id,
             title,
             abstract_text,
-            abstract_text_ja,
             text,
             url,
             doi,
             published_date: published_date.naive_local(),
+            venue_year,
             created_at: Some(created_at.to_utc()),
             updated_at: Some(updated_at.to_utc()),
             primary_category,
@@ -166,12 +205,6 @@ impl From<AcademicPaper> for AcademicPaperRecord {
             references_count,
             influential_citation_count,
             bibtex,
-            summary,
-            background_and_purpose,
-            methodology,
-            dataset,
-            results,
-            advantages_limitations_and_future_work,
             status: status.to_string(),
         }
     }
@@ -186,11 +219,11 @@ impl From<AcademicPaperRecord> for AcademicPaper {
             journal_id,
             title,
             abstract_text,
-            abstract_text_ja,
             text,
             url,
             doi,
             published_date,
+            venue_year,
             created_at,
             updated_at,
             primary_category,
@@ -198,12 +231,6 @@ impl From<AcademicPaperRecord> for AcademicPaper {
             references_count,
             influential_citation_count,
             bibtex,
-            summary,
-            background_and_purpose,
-            methodology,
-            dataset,
-            results,
-            advantages_limitations_and_future_work,
             status,
         } = paper_record;
         Self {
@@ -215,11 +242,14 @@ impl From<AcademicPaperRecord> for AcademicPaper {
             tasks: vec![],
             title,
             abstract_text,
-            abstract_text_ja,
             text,
+            sections: vec![],
+            pdf_sha256: String::new(),
+            translations: vec![],
             url,
             doi,
             published_date: Local.from_local_datetime(&published_date).unwrap(),
+            venue_year,
             created_at: Local.from_utc_datetime(&created_at.unwrap().naive_local()),
             updated_at: Local.from_utc_datetime(&updated_at.unwrap().naive_local()),
             primary_category,
@@ -227,13 +257,82 @@ impl From<AcademicPaperRecord> for AcademicPaper {
             references_count,
             influential_citation_count,
             bibtex,
+            status: Status::from_str(&status).expect("Invalid status value"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct AcademicPaperTranslationRecord {
+    pub paper_id: AcademicPaperId,
+    pub language: String,
+    pub abstract_text: String,
+    pub summary: String,
+    pub background_and_purpose: String,
+    pub methodology: String,
+    pub dataset: String,
+    pub results: String,
+    pub advantages_limitations_and_future_work: String,
+}
+
+impl From<AcademicPaperTranslationRecord> for AcademicPaperTranslation {
+    fn from(translation_record: AcademicPaperTranslationRecord) -> Self {
+        let AcademicPaperTranslationRecord {
+            paper_id: _,
+            language,
+            abstract_text,
+            summary,
+            background_and_purpose,
+            methodology,
+            dataset,
+            results,
+            advantages_limitations_and_future_work,
+        } = translation_record;
+        Self {
+            language: Language::from_str(&language).expect("Invalid language value"),
+            abstract_text,
             summary,
             background_and_purpose,
             methodology,
             dataset,
             results,
             advantages_limitations_and_future_work,
-            status: Status::from_str(&status).expect("Invalid status value"),
         }
     }
 }
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct PaperSectionRecord {
+    pub title: String,
+    pub content: String,
+}
+
+impl From<PaperSectionRecord> for PaperSection {
+    fn from(record: PaperSectionRecord) -> Self {
+        PaperSection::new(record.title, record.content)
+    }
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct DuplicatePaperRecord {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub ss_id: String,
+    pub arxiv_id: String,
+    pub doi: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DuplicatePaperRecord> for DuplicatePaper {
+    fn from(record: DuplicatePaperRecord) -> Self {
+        let DuplicatePaperRecord {
+            paper_id,
+            title,
+            ss_id,
+            arxiv_id,
+            doi,
+            created_at,
+        } = record;
+        DuplicatePaper::new(paper_id, title, ss_id, arxiv_id, doi, created_at.with_timezone(&Local))
+    }
+}
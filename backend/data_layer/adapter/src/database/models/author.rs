@@ -0,0 +1,55 @@
+use derive_new::new;
+use kernel::models::{
+    academic_paper::Author,
+    author::{AuthorSummary, CoauthorEdge},
+};
+use shared::id::AuthorId;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct AuthorSummaryRecord {
+    pub author_id: AuthorId,
+    pub ss_id: String,
+    pub name: String,
+    pub h_index: i32,
+    pub paper_count: i64,
+}
+
+impl From<AuthorSummaryRecord> for AuthorSummary {
+    fn from(record: AuthorSummaryRecord) -> Self {
+        let AuthorSummaryRecord {
+            author_id,
+            ss_id,
+            name,
+            h_index,
+            paper_count,
+        } = record;
+        Self {
+            author: Author::new(author_id, ss_id, name, h_index),
+            paper_count,
+            recent_papers: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct CoauthorEdgeRecord {
+    pub author_id: AuthorId,
+    pub coauthor_id: AuthorId,
+    pub paper_count: i64,
+}
+
+impl From<CoauthorEdgeRecord> for CoauthorEdge {
+    fn from(record: CoauthorEdgeRecord) -> Self {
+        let CoauthorEdgeRecord {
+            author_id,
+            coauthor_id,
+            paper_count,
+        } = record;
+        Self {
+            author_id,
+            coauthor_id,
+            paper_count,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use derive_new::new;
+use kernel::models::{
+    academic_paper::Task,
+    browse::{MonthlyPaperCount, PaperBrief},
+};
+use shared::id::{AcademicPaperId, JournalId, TaskId};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct TaskSummaryRecord {
+    pub task_id: TaskId,
+    pub name: String,
+    pub paper_count: i64,
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct JournalSummaryRecord {
+    pub journal_id: JournalId,
+    pub name: String,
+    pub venue_type: Option<String>,
+    pub tier: Option<String>,
+    pub paper_count: i64,
+}
+
+/// A recently added paper of the task, journal or author `owner_id`.
+#[derive(Debug, Clone, new, FromRow)]
+pub struct RecentPaperRecord {
+    pub owner_id: Uuid,
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub published_date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<RecentPaperRecord> for PaperBrief {
+    fn from(record: RecentPaperRecord) -> Self {
+        let RecentPaperRecord {
+            paper_id,
+            title,
+            published_date,
+            created_at,
+            ..
+        } = record;
+        Self {
+            paper_id,
+            title,
+            published_date,
+            created_at: created_at.with_timezone(&Local),
+        }
+    }
+}
+
+/// The recent papers of `owner_id`, in the order of `records`.
+pub fn recent_papers_of(records: &[RecentPaperRecord], owner_id: Uuid) -> Vec<PaperBrief> {
+    records
+        .iter()
+        .filter(|record| record.owner_id == owner_id)
+        .cloned()
+        .map(PaperBrief::from)
+        .collect()
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct TaskMonthlyCountRecord {
+    pub task_id: TaskId,
+    pub name: String,
+    pub month: NaiveDate,
+    pub paper_count: i64,
+}
+
+impl From<TaskMonthlyCountRecord> for (Task, MonthlyPaperCount) {
+    fn from(record: TaskMonthlyCountRecord) -> Self {
+        let TaskMonthlyCountRecord {
+            task_id,
+            name,
+            month,
+            paper_count,
+        } = record;
+        (Task::new(task_id, name), MonthlyPaperCount::new(month, paper_count))
+    }
+}
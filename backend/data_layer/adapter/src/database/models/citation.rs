@@ -0,0 +1,54 @@
+use derive_new::new;
+use kernel::models::citation::{CitationNode, CitedReference};
+use shared::id::AcademicPaperId;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct CitationNodeRecord {
+    pub ss_id: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub paper_id: Option<AcademicPaperId>,
+}
+
+impl From<CitationNodeRecord> for CitationNode {
+    fn from(record: CitationNodeRecord) -> Self {
+        let CitationNodeRecord {
+            ss_id,
+            title,
+            year,
+            paper_id,
+        } = record;
+        Self {
+            ss_id,
+            title,
+            year,
+            paper_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct CitedReferenceRecord {
+    pub ss_id: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub paper_id: Option<AcademicPaperId>,
+    pub cited_by_count: i64,
+}
+
+impl From<CitedReferenceRecord> for CitedReference {
+    fn from(record: CitedReferenceRecord) -> Self {
+        let CitedReferenceRecord {
+            ss_id,
+            title,
+            year,
+            paper_id,
+            cited_by_count,
+        } = record;
+        Self {
+            node: CitationNode::new(ss_id, title, year, paper_id),
+            cited_by_count,
+        }
+    }
+}
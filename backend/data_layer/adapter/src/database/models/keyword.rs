@@ -0,0 +1,34 @@
+use chrono::{DateTime, Local, Utc};
+use derive_new::new;
+use kernel::models::keyword::{Keyword, KeywordCategory};
+use shared::id::KeywordId;
+use sqlx::FromRow;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct KeywordRecord {
+    pub keyword_id: KeywordId,
+    pub category: String,
+    pub name: String,
+    pub paper_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<KeywordRecord> for Keyword {
+    fn from(record: KeywordRecord) -> Self {
+        let KeywordRecord {
+            keyword_id,
+            category,
+            name,
+            paper_count,
+            created_at,
+        } = record;
+        Self {
+            keyword_id,
+            category: KeywordCategory::from_str(&category).expect("Invalid keyword category"),
+            name,
+            paper_count,
+            created_at: created_at.with_timezone(&Local),
+        }
+    }
+}
@@ -1,3 +1,13 @@
 pub mod academic_paper;
+pub mod author;
+pub mod browse;
+pub mod citation;
+pub mod keyword;
+pub mod paper_candidate;
+pub mod paper_chunk;
+pub mod paper_comparison;
+pub mod paper_metadata;
 pub mod paper_note;
+pub mod paper_version;
+pub mod reading;
 pub mod web_article;
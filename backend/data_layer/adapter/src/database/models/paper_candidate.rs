@@ -0,0 +1,55 @@
+use chrono::{DateTime, Local, Utc};
+use derive_new::new;
+use kernel::models::paper_candidate::{CandidateStatus, PaperCandidate};
+use shared::id::{AcademicPaperId, PaperCandidateId};
+use sqlx::FromRow;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct PaperCandidateRecord {
+    pub candidate_id: PaperCandidateId,
+    pub arxiv_id: String,
+    pub title: String,
+    pub abstract_text: String,
+    pub authors: Vec<String>,
+    pub categories: Vec<String>,
+    pub pdf_url: String,
+    pub published_date: DateTime<Utc>,
+    pub score: i32,
+    pub matched: Vec<String>,
+    pub status: String,
+    pub paper_id: Option<AcademicPaperId>,
+}
+
+impl From<PaperCandidateRecord> for PaperCandidate {
+    fn from(record: PaperCandidateRecord) -> Self {
+        let PaperCandidateRecord {
+            candidate_id,
+            arxiv_id,
+            title,
+            abstract_text,
+            authors,
+            categories,
+            pdf_url,
+            published_date,
+            score,
+            matched,
+            status,
+            paper_id,
+        } = record;
+        Self {
+            candidate_id,
+            arxiv_id,
+            title,
+            abstract_text,
+            authors,
+            categories,
+            pdf_url,
+            published_date: published_date.with_timezone(&Local),
+            score,
+            matched,
+            status: CandidateStatus::from_str(&status).expect("Invalid candidate status value"),
+            paper_id,
+        }
+    }
+}
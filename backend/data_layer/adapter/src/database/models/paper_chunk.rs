@@ -0,0 +1,31 @@
+use derive_new::new;
+use kernel::models::paper_chunk::{PaperChunk, PaperChunkMatch};
+use shared::id::AcademicPaperId;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct PaperChunkMatchRecord {
+    pub paper_id: AcademicPaperId,
+    pub position: i32,
+    pub section_titles: Vec<String>,
+    pub content: String,
+    pub embedding: Option<Vec<f32>>,
+    pub lexical_rank: f32,
+}
+
+impl From<PaperChunkMatchRecord> for PaperChunkMatch {
+    fn from(record: PaperChunkMatchRecord) -> Self {
+        let PaperChunkMatchRecord {
+            paper_id,
+            position,
+            section_titles,
+            content,
+            embedding,
+            lexical_rank,
+        } = record;
+        PaperChunkMatch::new(
+            PaperChunk::new(paper_id, position, section_titles, content, embedding),
+            lexical_rank,
+        )
+    }
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Local, Utc};
+use derive_new::new;
+use kernel::models::{
+    language::Language,
+    paper_comparison::{ComparedPaper, ComparisonRow, PaperComparison},
+};
+use shared::id::{AcademicPaperId, PaperComparisonId};
+use sqlx::FromRow;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct PaperComparisonRecord {
+    pub paper_comparison_id: PaperComparisonId,
+    pub synthesis: String,
+    pub language: String,
+    pub model: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct PaperComparisonPaperRecord {
+    pub position: i32,
+    pub paper_id: Option<AcademicPaperId>,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct PaperComparisonCellRecord {
+    pub dimension_position: i32,
+    pub dimension: String,
+    pub paper_position: i32,
+    pub value: String,
+}
+
+impl PaperComparisonRecord {
+    /// The comparison with its papers and cells, each sorted by position.
+    pub fn into_comparison(
+        self,
+        papers: Vec<PaperComparisonPaperRecord>,
+        cells: Vec<PaperComparisonCellRecord>,
+    ) -> PaperComparison {
+        let PaperComparisonRecord {
+            paper_comparison_id,
+            synthesis,
+            language,
+            model,
+            created_at,
+        } = self;
+        let mut rows: Vec<ComparisonRow> = vec![];
+        let mut dimension_position = None;
+        for cell in cells.into_iter() {
+            if dimension_position != Some(cell.dimension_position) {
+                dimension_position = Some(cell.dimension_position);
+                rows.push(ComparisonRow::new(cell.dimension, vec!["-".to_string(); papers.len()]));
+            }
+            if let Some(value) = rows
+                .last_mut()
+                .and_then(|row| row.values.get_mut(cell.paper_position as usize))
+            {
+                *value = cell.value;
+            }
+        }
+
+        PaperComparison::new(
+            paper_comparison_id,
+            papers
+                .into_iter()
+                .map(|paper| ComparedPaper::new(paper.paper_id, paper.title))
+                .collect(),
+            rows,
+            synthesis,
+            Language::from_str(&language).unwrap_or_default(),
+            model,
+            created_at.with_timezone(&Local),
+        )
+    }
+}
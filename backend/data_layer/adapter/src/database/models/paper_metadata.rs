@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use kernel::models::paper_metadata::{CitationGain, MetadataChange, PaperMetadata};
+use shared::id::AcademicPaperId;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PaperMetadataRecord {
+    pub paper_id: AcademicPaperId,
+    pub ss_id: String,
+    pub arxiv_id: String,
+    pub doi: String,
+    pub journal_name: String,
+    pub citations_count: i32,
+    pub influential_citation_count: i32,
+    pub references_count: i32,
+}
+
+impl From<PaperMetadataRecord> for PaperMetadata {
+    fn from(record: PaperMetadataRecord) -> Self {
+        let PaperMetadataRecord {
+            paper_id,
+            ss_id,
+            arxiv_id,
+            doi,
+            journal_name,
+            citations_count,
+            influential_citation_count,
+            references_count,
+        } = record;
+        Self {
+            paper_id,
+            ss_id,
+            arxiv_id,
+            doi,
+            journal_name,
+            citations_count,
+            influential_citation_count,
+            references_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct MetadataChangeRecord {
+    pub paper_id: AcademicPaperId,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl From<MetadataChangeRecord> for MetadataChange {
+    fn from(record: MetadataChangeRecord) -> Self {
+        let MetadataChangeRecord {
+            paper_id,
+            field,
+            old_value,
+            new_value,
+            changed_at,
+        } = record;
+        Self {
+            paper_id,
+            field,
+            old_value,
+            new_value,
+            changed_at: changed_at.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct CitationGainRecord {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub citations_count: i32,
+    pub citations_gained: i64,
+}
+
+impl From<CitationGainRecord> for CitationGain {
+    fn from(record: CitationGainRecord) -> Self {
+        let CitationGainRecord {
+            paper_id,
+            title,
+            citations_count,
+            citations_gained,
+        } = record;
+        Self {
+            paper_id,
+            title,
+            citations_count,
+            citations_gained,
+        }
+    }
+}
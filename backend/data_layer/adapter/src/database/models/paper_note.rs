@@ -1,8 +1,12 @@
 use chrono::{DateTime, Local, Utc};
 use derive_new::new;
-use kernel::models::{academic_paper::AcademicPaper, paper_note::PaperNote};
-use shared::id::{AcademicPaperId, PaperNoteId};
+use kernel::models::{
+    academic_paper::AcademicPaper,
+    paper_note::{PaperNote, PaperNoteMessage, PaperNoteMessageRole},
+};
+use shared::id::{AcademicPaperId, PaperNoteId, PaperNoteMessageId};
 use sqlx::FromRow;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, new, FromRow)]
 pub struct PaperNoteRecord {
@@ -48,3 +52,40 @@ impl From<PaperNoteRecord> for PaperNote {
         }
     }
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PaperNoteMessageRecord {
+    pub paper_note_message_id: PaperNoteMessageId,
+    pub paper_note_id: PaperNoteId,
+    pub position: i32,
+    pub role: String,
+    pub content: String,
+    pub model: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PaperNoteMessageRecord> for PaperNoteMessage {
+    fn from(record: PaperNoteMessageRecord) -> Self {
+        let PaperNoteMessageRecord {
+            paper_note_message_id,
+            paper_note_id,
+            position,
+            role,
+            content,
+            model,
+            created_at,
+            updated_at,
+        } = record;
+        Self {
+            paper_note_message_id,
+            paper_note_id,
+            position,
+            role: PaperNoteMessageRole::from_str(&role).expect("Invalid role value"),
+            content,
+            model,
+            created_at: created_at.with_timezone(&Local),
+            updated_at: updated_at.with_timezone(&Local),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Local, Utc};
+use derive_new::new;
+use kernel::models::paper_version::{PaperVersion, VersionedPaper};
+use shared::id::AcademicPaperId;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct VersionedPaperRecord {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub arxiv_id: String,
+    pub latest_version: Option<i32>,
+}
+
+impl From<VersionedPaperRecord> for VersionedPaper {
+    fn from(record: VersionedPaperRecord) -> Self {
+        let VersionedPaperRecord {
+            paper_id,
+            title,
+            arxiv_id,
+            latest_version,
+        } = record;
+        Self {
+            paper_id,
+            title,
+            arxiv_id,
+            latest_version,
+        }
+    }
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct PaperVersionRecord {
+    pub paper_id: AcademicPaperId,
+    pub version: i32,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub pdf_sha256: String,
+    pub changed_sections: Vec<String>,
+    pub change_summary: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl From<PaperVersionRecord> for PaperVersion {
+    fn from(record: PaperVersionRecord) -> Self {
+        let PaperVersionRecord {
+            paper_id,
+            version,
+            submitted_at,
+            pdf_sha256,
+            changed_sections,
+            change_summary,
+            detected_at,
+        } = record;
+        Self {
+            paper_id,
+            version,
+            submitted_at: submitted_at.map(|submitted_at| submitted_at.with_timezone(&Local)),
+            pdf_sha256,
+            changed_sections,
+            change_summary,
+            detected_at: detected_at.with_timezone(&Local),
+        }
+    }
+}
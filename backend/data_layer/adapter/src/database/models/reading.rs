@@ -0,0 +1,68 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use derive_new::new;
+use kernel::models::{
+    academic_paper::Status,
+    reading::{PaperStatusChange, ReadingQueueItem},
+};
+use shared::id::AcademicPaperId;
+use sqlx::FromRow;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct PaperStatusChangeRecord {
+    pub paper_id: AcademicPaperId,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl From<PaperStatusChangeRecord> for PaperStatusChange {
+    fn from(record: PaperStatusChangeRecord) -> Self {
+        let PaperStatusChangeRecord {
+            paper_id,
+            from_status,
+            to_status,
+            changed_at,
+        } = record;
+        Self {
+            paper_id,
+            from_status: from_status.map(|status| Status::from_str(&status).expect("Invalid status value")),
+            to_status: Status::from_str(&to_status).expect("Invalid status value"),
+            changed_at: changed_at.with_timezone(&Local),
+        }
+    }
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct ReadingQueueItemRecord {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub url: String,
+    pub status: String,
+    pub priority: i32,
+    pub due_date: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ReadingQueueItemRecord> for ReadingQueueItem {
+    fn from(record: ReadingQueueItemRecord) -> Self {
+        let ReadingQueueItemRecord {
+            paper_id,
+            title,
+            url,
+            status,
+            priority,
+            due_date,
+            created_at,
+        } = record;
+        Self {
+            paper_id,
+            title,
+            url,
+            status: Status::from_str(&status).expect("Invalid status value"),
+            priority,
+            due_date,
+            added_at: created_at.with_timezone(&Local),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use chrono::{Local, NaiveDate};
 use derive_new::new;
-use kernel::models::web_article::{Status, WebArticle, WebSite};
+use kernel::models::{
+    language::Language,
+    web_article::{EnrichmentStatus, Status, WebArticle, WebArticleTranslation, WebSite},
+};
 use shared::id::{WebArticleId, WebSiteId};
 use sqlx::FromRow;
 use std::str::FromStr;
@@ -43,7 +46,6 @@ pub struct WebArticleRecord {
     pub text: String,
     pub html: String,
     pub timestamp: NaiveDate,
-    pub summary: String,
     pub is_new_technology_related: bool,
     pub is_new_product_related: bool,
     pub is_new_academic_paper_related: bool,
@@ -51,6 +53,8 @@ pub struct WebArticleRecord {
     pub is_security_related: bool,
     pub is_it_related: bool,
     pub status: String,
+    pub enrichment_status: String,
+    pub enrichment_batch_id: Option<String>,
 }
 
 impl From<WebArticle> for WebArticleRecord {
@@ -64,7 +68,7 @@ impl From<WebArticle> for WebArticleRecord {
             text,
             html,
             timestamp,
-            summary,
+            translations: _,
             is_new_technology_related,
             is_new_product_related,
             is_new_academic_paper_related,
@@ -72,6 +76,8 @@ impl From<WebArticle> for WebArticleRecord {
             is_security_related,
             is_it_related,
             status,
+            enrichment_status,
+            enrichment_batch_id,
         } = web_article;
         Self {
             site_id: site.site_id,
@@ -84,7 +90,6 @@ impl From<WebArticle> for WebArticleRecord {
             text,
             html,
             timestamp: timestamp.naive_utc().date(),
-            summary,
             is_new_technology_related,
             is_new_product_related,
             is_new_academic_paper_related,
@@ -92,6 +97,8 @@ impl From<WebArticle> for WebArticleRecord {
             is_security_related,
             is_it_related,
             status: status.to_string(),
+            enrichment_status: enrichment_status.to_string(),
+            enrichment_batch_id,
         }
     }
 }
@@ -109,7 +116,6 @@ impl From<WebArticleRecord> for WebArticle {
             text,
             html,
             timestamp,
-            summary,
             is_new_technology_related,
             is_new_product_related,
             is_new_academic_paper_related: is_new_paper_related,
@@ -117,6 +123,8 @@ impl From<WebArticleRecord> for WebArticle {
             is_security_related,
             is_it_related,
             status,
+            enrichment_status,
+            enrichment_batch_id,
         } = web_article_record;
         Self {
             site: WebSite {
@@ -135,7 +143,7 @@ impl From<WebArticleRecord> for WebArticle {
                 .unwrap()
                 .and_local_timezone(Local)
                 .unwrap(),
-            summary,
+            translations: vec![],
             is_new_technology_related,
             is_new_product_related,
             is_new_academic_paper_related: is_new_paper_related,
@@ -143,6 +151,24 @@ impl From<WebArticleRecord> for WebArticle {
             is_security_related,
             is_it_related,
             status: Status::from_str(&status).expect("Invalid status value"),
+            enrichment_status: EnrichmentStatus::from_str(&enrichment_status).expect("Invalid enrichment status value"),
+            enrichment_batch_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, new, FromRow)]
+pub struct WebArticleTranslationRecord {
+    pub article_id: WebArticleId,
+    pub language: String,
+    pub summary: String,
+}
+
+impl From<WebArticleTranslationRecord> for WebArticleTranslation {
+    fn from(record: WebArticleTranslationRecord) -> Self {
+        Self {
+            language: Language::from_str(&record.language).expect("Invalid language value"),
+            summary: record.summary,
         }
     }
 }
@@ -1,14 +1,32 @@
 use crate::database::{
-    models::academic_paper::{AcademicPaperRecord, AuthorRecord, JournalRecord, TaskRecord},
+    models::{
+        academic_paper::{
+            AcademicPaperRecord, AcademicPaperTranslationRecord, AuthorRecord, DuplicatePaperRecord, JournalRecord,
+            PaperSectionRecord, TaskEntryRecord, TaskRecord,
+        },
+        author::{AuthorSummaryRecord, CoauthorEdgeRecord},
+        browse::{
+            recent_papers_of, JournalSummaryRecord, RecentPaperRecord, TaskMonthlyCountRecord, TaskSummaryRecord,
+        },
+    },
     ConnectionPool,
 };
 use async_trait::async_trait;
-use chrono::{Local, TimeZone};
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
 use derive_new::new;
 use kernel::{
     models::{
-        academic_paper::{AcademicPaper, AcademicPaperListOptions, Author, AuthorListOptions, Journal, Task},
+        academic_paper::{
+            AcademicPaper, AcademicPaperListOptions, AcademicPaperTranslation, Author, AuthorListOptions, Journal,
+            PaperSection, Status, Task,
+        },
+        author::{AuthorSummary, CoauthorEdge, CoauthorshipGraph},
+        browse::{task_trends, BrowseListOptions, JournalSummary, MonthlyPaperCount, TaskSummary, TaskTrend},
+        library_import::normalize_doi,
         list::PaginatedList,
+        paper_duplicate::{arxiv_base_id, find_duplicates, normalize_title, DuplicateGroup, DuplicatePaper},
+        task::{task_key, TaskEntry, TaskVocabulary},
+        venue::{normalize_venue, venue_key, NormalizedVenue, VenueNormalization, VenueTier, VenueType, UNKNOWN_VENUE},
     },
     repository::academic_paper::{AcademicPaperRepository, AuthorRepository, JournalRepository, TaskRepository},
 };
@@ -29,8 +47,20 @@ pub struct AuthorRepositoryImpl {
 #[async_trait]
 impl AuthorRepository for AuthorRepositoryImpl {
     async fn create_author(&self, tx: &mut T<'_, Pg>, author: Author) -> AppResult<Author> {
-        // Check if the author already exists by ss_id
-        if let Ok(existing_author) = self.select_author_by_ssid(tx, &author.ss_id).await {
+        // Check if the author already exists by ss_id, or by name for authors without one
+        let existing_author = if author.ss_id.is_empty() {
+            sqlx::query_as!(
+                Author,
+                r#"SELECT author_id, ss_id, name, h_index FROM author WHERE ss_id = '' AND name = $1 LIMIT 1"#,
+                author.name
+            )
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?
+        } else {
+            self.select_author_by_ssid(tx, &author.ss_id).await.ok()
+        };
+        if let Some(existing_author) = existing_author {
             return Ok(existing_author);
         }
 
@@ -41,6 +71,7 @@ impl AuthorRepository for AuthorRepositoryImpl {
                 name,
                 h_index
             ) VALUES ($1, $2, $3)
+            ON CONFLICT (ss_id) WHERE ss_id <> '' DO UPDATE SET name = EXCLUDED.name
             RETURNING author_id"#,
             author.ss_id,
             author.name,
@@ -93,33 +124,278 @@ impl AuthorRepository for AuthorRepositoryImpl {
         &self,
         tx: &mut T<'_, Pg>,
         options: AuthorListOptions,
-    ) -> AppResult<PaginatedList<Author>> {
-        let total_count = sqlx::query_scalar!(r#"SELECT COUNT(*) FROM author"#)
-            .fetch_one(&mut **tx)
-            .await
-            .map_err(|e| shared::errors::AppError::SqlxError(e))?
-            .expect("Total count should not be None");
+    ) -> AppResult<PaginatedList<AuthorSummary>> {
+        let total_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM author WHERE $1::TEXT IS NULL OR name ILIKE '%' || $1 || '%'"#,
+            options.name
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .expect("Total count should not be None");
 
         let authors = sqlx::query_as!(
-            Author,
-            r#"SELECT author_id, ss_id, name, h_index FROM author
-            ORDER BY name
-            LIMIT $1 OFFSET $2"#,
-            options.limit as i64,
-            options.offset as i64
+            AuthorSummaryRecord,
+            r#"SELECT
+                a.author_id,
+                a.ss_id,
+                a.name,
+                a.h_index,
+                COUNT(r.paper_id) AS "paper_count!"
+            FROM author a
+            LEFT JOIN author_paper_relation r ON r.author_id = a.author_id
+            WHERE $1::TEXT IS NULL OR a.name ILIKE '%' || $1 || '%'
+            GROUP BY a.author_id
+            ORDER BY COUNT(r.paper_id) DESC, a.name
+            LIMIT $2 OFFSET $3"#,
+            options.name,
+            options.limit,
+            options.offset
         )
         .fetch_all(&mut **tx)
         .await
-        .map_err(|e| shared::errors::AppError::SqlxError(e))?;
+        .map_err(AppError::SqlxError)?;
+
+        let author_ids = authors
+            .iter()
+            .map(|author| author.author_id.raw())
+            .collect::<Vec<Uuid>>();
+        let recent_papers = sqlx::query_as!(
+            RecentPaperRecord,
+            r#"SELECT
+                owner_id AS "owner_id!",
+                paper_id AS "paper_id!",
+                title AS "title!",
+                published_date AS "published_date!",
+                created_at AS "created_at!"
+            FROM (
+                SELECT
+                    r.author_id AS owner_id,
+                    p.paper_id,
+                    p.title,
+                    p.published_date,
+                    p.created_at,
+                    ROW_NUMBER() OVER (PARTITION BY r.author_id ORDER BY p.created_at DESC, p.paper_id) AS rank
+                FROM author_paper_relation r
+                JOIN academic_paper p ON p.paper_id = r.paper_id
+                WHERE r.author_id = ANY($1)
+            ) AS recent
+            WHERE rank <= $2
+            ORDER BY owner_id, rank"#,
+            &author_ids,
+            options.recent_count
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
 
-        Ok(PaginatedList::<Author>::new(
+        Ok(PaginatedList::<AuthorSummary>::new(
             total_count,
             options.limit,
             options.offset,
-            authors,
+            authors
+                .into_iter()
+                .map(|author| {
+                    let recent_papers = recent_papers_of(&recent_papers, author.author_id.raw());
+                    AuthorSummary {
+                        recent_papers,
+                        ..AuthorSummary::from(author)
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    async fn select_author_summary_by_id(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<AuthorSummary> {
+        let author = sqlx::query_as!(
+            AuthorSummaryRecord,
+            r#"SELECT
+                a.author_id,
+                a.ss_id,
+                a.name,
+                a.h_index,
+                COUNT(r.paper_id) AS "paper_count!"
+            FROM author a
+            LEFT JOIN author_paper_relation r ON r.author_id = a.author_id
+            WHERE a.author_id = $1
+            GROUP BY a.author_id"#,
+            Uuid::from_str(id)?
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .ok_or_else(|| AppError::EntityNotFound(format!("Author not found: {}", id)))?;
+
+        Ok(AuthorSummary::from(author))
+    }
+
+    async fn select_coauthorship_graph(
+        &self,
+        tx: &mut T<'_, Pg>,
+        id: Option<&str>,
+        limit: i64,
+    ) -> AppResult<CoauthorshipGraph> {
+        let author_ids = match id {
+            Some(id) => {
+                let author = self.select_author_summary_by_id(tx, id).await?;
+                // the co-authors sharing the most papers with the author
+                let coauthor_ids = sqlx::query_scalar!(
+                    r#"SELECT o.author_id AS "author_id!"
+                    FROM author_paper_relation r
+                    JOIN author_paper_relation o ON o.paper_id = r.paper_id AND o.author_id <> r.author_id
+                    WHERE r.author_id = $1
+                    GROUP BY o.author_id
+                    ORDER BY COUNT(*) DESC, o.author_id
+                    LIMIT $2"#,
+                    Uuid::from(author.author.author_id),
+                    limit
+                )
+                .fetch_all(&mut **tx)
+                .await
+                .map_err(AppError::SqlxError)?;
+                std::iter::once(Uuid::from(author.author.author_id))
+                    .chain(coauthor_ids)
+                    .collect::<Vec<Uuid>>()
+            }
+            None => sqlx::query_scalar!(
+                r#"SELECT author_id
+                FROM author_paper_relation
+                GROUP BY author_id
+                ORDER BY COUNT(*) DESC, author_id
+                LIMIT $1"#,
+                limit
+            )
+            .fetch_all(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?,
+        };
+
+        let nodes = sqlx::query_as!(
+            AuthorSummaryRecord,
+            r#"SELECT
+                a.author_id,
+                a.ss_id,
+                a.name,
+                a.h_index,
+                COUNT(r.paper_id) AS "paper_count!"
+            FROM author a
+            LEFT JOIN author_paper_relation r ON r.author_id = a.author_id
+            WHERE a.author_id = ANY($1)
+            GROUP BY a.author_id
+            ORDER BY COUNT(r.paper_id) DESC, a.name"#,
+            &author_ids
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let edges = sqlx::query_as!(
+            CoauthorEdgeRecord,
+            r#"SELECT
+                r.author_id,
+                o.author_id AS coauthor_id,
+                COUNT(*) AS "paper_count!"
+            FROM author_paper_relation r
+            JOIN author_paper_relation o ON o.paper_id = r.paper_id AND r.author_id < o.author_id
+            WHERE r.author_id = ANY($1) AND o.author_id = ANY($1)
+            GROUP BY r.author_id, o.author_id
+            ORDER BY COUNT(*) DESC, r.author_id, o.author_id"#,
+            &author_ids
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(CoauthorshipGraph::new(
+            nodes.into_iter().map(AuthorSummary::from).collect(),
+            edges.into_iter().map(CoauthorEdge::from).collect(),
         ))
     }
 
+    async fn select_authors_to_refresh(
+        &self,
+        tx: &mut T<'_, Pg>,
+        refreshed_before: DateTime<Local>,
+        limit: i64,
+    ) -> AppResult<Vec<Author>> {
+        let authors = sqlx::query_as!(
+            Author,
+            r#"SELECT author_id, ss_id, name, h_index FROM author
+            WHERE ss_id <> '' AND (h_index_updated_at IS NULL OR h_index_updated_at < $1)
+            ORDER BY h_index_updated_at NULLS FIRST, author_id
+            LIMIT $2"#,
+            refreshed_before.with_timezone(&Utc),
+            limit
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(authors)
+    }
+
+    async fn update_author_h_index(&self, tx: &mut T<'_, Pg>, id: AuthorId, h_index: i32) -> AppResult<()> {
+        sqlx::query!(
+            r#"UPDATE author SET h_index = $2, h_index_updated_at = CURRENT_TIMESTAMP(3) WHERE author_id = $1"#,
+            Uuid::from(id),
+            h_index
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(())
+    }
+
+    async fn merge_authors(&self, tx: &mut T<'_, Pg>, id: &str, duplicate_id: &str) -> AppResult<Author> {
+        let author = self.select_author_by_id(tx, id).await?;
+        let duplicate = self.select_author_by_id(tx, duplicate_id).await?;
+        if author.author_id == duplicate.author_id {
+            return Err(AppError::Unprocessable("Cannot merge an author into itself".into()));
+        }
+        if !author.ss_id.is_empty() && !duplicate.ss_id.is_empty() && author.ss_id != duplicate.ss_id {
+            return Err(AppError::Unprocessable(format!(
+                "{} and {} have different Semantic Scholar IDs",
+                author.name, duplicate.name
+            )));
+        }
+
+        sqlx::query!(
+            r#"INSERT INTO author_paper_relation (author_id, paper_id)
+            SELECT $1, paper_id FROM author_paper_relation WHERE author_id = $2
+            ON CONFLICT DO NOTHING"#,
+            Uuid::from(author.author_id),
+            Uuid::from(duplicate.author_id)
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"DELETE FROM author_paper_relation WHERE author_id = $1"#,
+            Uuid::from(duplicate.author_id)
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        self.delete_author(tx, duplicate_id).await?;
+
+        // keep the Semantic Scholar ID and the h-index of the duplicate if the author lacks them
+        sqlx::query!(
+            r#"UPDATE author SET
+                ss_id = CASE WHEN ss_id = '' THEN $2 ELSE ss_id END,
+                h_index = GREATEST(h_index, $3)
+            WHERE author_id = $1"#,
+            Uuid::from(author.author_id),
+            duplicate.ss_id,
+            duplicate.h_index
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        self.select_author_by_id(tx, id).await
+    }
+
     async fn delete_author(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<()> {
         sqlx::query!(r#"DELETE FROM author WHERE author_id = $1"#, Uuid::from_str(id)?)
             .execute(&mut **tx)
@@ -138,9 +414,21 @@ pub struct TaskRepositoryImpl {
 #[async_trait]
 impl TaskRepository for TaskRepositoryImpl {
     async fn create_task(&self, tx: &mut T<'_, Pg>, task: Task) -> AppResult<Task> {
-        // Check if the task already exists by name
-        if let Ok(existing_task) = self.select_task_by_name(tx, &task.name).await {
-            return Ok(existing_task);
+        // a task normalized against the vocabulary already has the ID of the task its name is a variant of
+        let existing = match self.select_task_by_id(tx, &task.task_id.to_string()).await {
+            Ok(existing) => Some(existing),
+            Err(_) => match self.select_task_by_name(tx, &task.name).await {
+                Ok(existing) => Some(existing),
+                Err(_) => self
+                    .select_task_vocabulary(tx)
+                    .await?
+                    .find(&task.name, None)
+                    .map(|(entry, _)| entry.task.clone()),
+            },
+        };
+        if let Some(existing) = existing {
+            self.create_task_alias(tx, &existing, &task.name).await?;
+            return Ok(existing);
         }
 
         // If the task does not exist, insert a new record
@@ -188,6 +476,181 @@ impl TaskRepository for TaskRepositoryImpl {
 
         Ok(tasks)
     }
+    async fn select_task_vocabulary(&self, tx: &mut T<'_, Pg>) -> AppResult<TaskVocabulary> {
+        let entries = sqlx::query_as!(
+            TaskEntryRecord,
+            r#"SELECT
+                t.task_id,
+                t.name,
+                ARRAY(
+                    SELECT a.name FROM task_alias a WHERE a.task_id = t.task_id ORDER BY a.created_at, a.name
+                ) AS "aliases!",
+                (SELECT COUNT(*) FROM task_paper_relation r WHERE r.task_id = t.task_id) AS "paper_count!"
+            FROM task t
+            ORDER BY 4 DESC, t.name"#
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(TaskVocabulary::new(entries.into_iter().map(TaskEntry::from).collect()))
+    }
+    async fn select_task_summaries(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: BrowseListOptions,
+    ) -> AppResult<PaginatedList<TaskSummary>> {
+        let total_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM task WHERE $1::TEXT IS NULL OR name ILIKE '%' || $1 || '%'"#,
+            options.name
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .expect("Total count should not be None");
+
+        let tasks = sqlx::query_as!(
+            TaskSummaryRecord,
+            r#"SELECT
+                t.task_id,
+                t.name,
+                COUNT(r.paper_id) AS "paper_count!"
+            FROM task t
+            LEFT JOIN task_paper_relation r ON r.task_id = t.task_id
+            WHERE $1::TEXT IS NULL OR t.name ILIKE '%' || $1 || '%'
+            GROUP BY t.task_id
+            ORDER BY COUNT(r.paper_id) DESC, t.name
+            LIMIT $2 OFFSET $3"#,
+            options.name,
+            options.limit,
+            options.offset
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let task_ids = tasks.iter().map(|task| task.task_id.raw()).collect::<Vec<Uuid>>();
+        let recent_papers = sqlx::query_as!(
+            RecentPaperRecord,
+            r#"SELECT
+                owner_id AS "owner_id!",
+                paper_id AS "paper_id!",
+                title AS "title!",
+                published_date AS "published_date!",
+                created_at AS "created_at!"
+            FROM (
+                SELECT
+                    r.task_id AS owner_id,
+                    p.paper_id,
+                    p.title,
+                    p.published_date,
+                    p.created_at,
+                    ROW_NUMBER() OVER (PARTITION BY r.task_id ORDER BY p.created_at DESC, p.paper_id) AS rank
+                FROM task_paper_relation r
+                JOIN academic_paper p ON p.paper_id = r.paper_id
+                WHERE r.task_id = ANY($1)
+            ) AS recent
+            WHERE rank <= $2
+            ORDER BY owner_id, rank"#,
+            &task_ids,
+            options.recent_count
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(PaginatedList::<TaskSummary>::new(
+            total_count,
+            options.limit,
+            options.offset,
+            tasks
+                .into_iter()
+                .map(|task| {
+                    let recent_papers = recent_papers_of(&recent_papers, task.task_id.raw());
+                    TaskSummary::new(Task::new(task.task_id, task.name), task.paper_count, recent_papers)
+                })
+                .collect(),
+        ))
+    }
+    async fn select_task_trends(
+        &self,
+        tx: &mut T<'_, Pg>,
+        first_month: NaiveDate,
+        limit: i64,
+    ) -> AppResult<Vec<TaskTrend>> {
+        let counts = sqlx::query_as!(
+            TaskMonthlyCountRecord,
+            r#"WITH top_task AS (
+                SELECT r.task_id
+                FROM task_paper_relation r
+                JOIN academic_paper p ON p.paper_id = r.paper_id
+                WHERE p.created_at >= $1::DATE
+                GROUP BY r.task_id
+                ORDER BY COUNT(*) DESC, r.task_id
+                LIMIT $2
+            )
+            SELECT
+                t.task_id,
+                t.name,
+                DATE_TRUNC('month', p.created_at)::DATE AS "month!",
+                COUNT(*) AS "paper_count!"
+            FROM top_task
+            JOIN task t ON t.task_id = top_task.task_id
+            JOIN task_paper_relation r ON r.task_id = t.task_id
+            JOIN academic_paper p ON p.paper_id = r.paper_id
+            WHERE p.created_at >= $1::DATE
+            GROUP BY t.task_id, t.name, 3
+            ORDER BY t.name, 3"#,
+            first_month,
+            limit
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(task_trends(
+            counts.into_iter().map(<(Task, MonthlyPaperCount)>::from).collect(),
+            first_month,
+            Local::now().date_naive(),
+        ))
+    }
+    async fn merge_tasks(&self, tx: &mut T<'_, Pg>, id: &str, duplicate_id: &str) -> AppResult<Task> {
+        let task = self.select_task_by_id(tx, id).await?;
+        let duplicate = self.select_task_by_id(tx, duplicate_id).await?;
+        if task.task_id == duplicate.task_id {
+            return Err(AppError::Unprocessable("Cannot merge a task into itself".into()));
+        }
+
+        sqlx::query!(
+            r#"INSERT INTO task_paper_relation (task_id, paper_id)
+            SELECT $1, paper_id FROM task_paper_relation WHERE task_id = $2
+            ON CONFLICT DO NOTHING"#,
+            Uuid::from(task.task_id),
+            Uuid::from(duplicate.task_id)
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"DELETE FROM task_paper_relation WHERE task_id = $1"#,
+            Uuid::from(duplicate.task_id)
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"UPDATE task_alias SET task_id = $1 WHERE task_id = $2"#,
+            Uuid::from(task.task_id),
+            Uuid::from(duplicate.task_id)
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        self.delete_task(tx, duplicate_id).await?;
+        self.create_task_alias(tx, &task, &duplicate.name).await?;
+
+        Ok(task)
+    }
     async fn delete_task(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<()> {
         sqlx::query!(r#"DELETE FROM task WHERE task_id = $1"#, Uuid::from_str(id)?)
             .execute(&mut **tx)
@@ -198,6 +661,29 @@ impl TaskRepository for TaskRepositoryImpl {
     }
 }
 
+impl TaskRepositoryImpl {
+    /// Records `name` as an alias of `task`, unless it is a spelling of the name of the task or already an alias.
+    async fn create_task_alias(&self, tx: &mut T<'_, Pg>, task: &Task, name: &str) -> AppResult<()> {
+        let alias_key = task_key(name);
+        if alias_key.is_empty() || alias_key == task_key(&task.name) {
+            return Ok(());
+        }
+        sqlx::query!(
+            r#"INSERT INTO task_alias (alias_key, task_id, name)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING"#,
+            alias_key,
+            Uuid::from(task.task_id),
+            name
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, new)]
 pub struct JournalRepositoryImpl {
     pub db: ConnectionPool,
@@ -206,56 +692,305 @@ pub struct JournalRepositoryImpl {
 #[async_trait]
 impl JournalRepository for JournalRepositoryImpl {
     async fn create_journal(&self, tx: &mut T<'_, Pg>, journal: Journal) -> AppResult<Journal> {
-        // Check if the journal already exists by name
+        // Check if the venue already exists by its canonical name or one of its aliases
         if let Ok(existing_journal) = self.select_journal_by_name(tx, &journal.name).await {
+            self.create_journal_alias(tx, &existing_journal, &journal.name).await?;
             return Ok(existing_journal);
         }
 
-        // If the journal does not exist, insert a new record
+        // If the venue does not exist, insert a new record under its canonical name
+        let venue = normalize_venue(&journal.name);
+        let venue_type = journal.venue_type.unwrap_or(venue.venue_type);
         let res = sqlx::query!(
             r#"INSERT INTO journal (
-                name
-            ) VALUES ($1)
+                name,
+                venue_type,
+                tier
+            ) VALUES ($1, $2, $3)
             RETURNING journal_id"#,
-            journal.name
+            venue.name,
+            venue_type.to_string(),
+            journal.tier
         )
         .fetch_one(&mut **tx)
         .await
         .map_err(|e| shared::errors::AppError::SqlxError(e))?;
 
-        Ok(Journal {
+        let created = Journal {
             journal_id: JournalId::from(res.journal_id),
-            name: journal.name,
-        })
+            name: venue.name,
+            venue_type: Some(venue_type),
+            tier: journal.tier,
+        };
+        self.create_journal_alias(tx, &created, &journal.name).await?;
+        Ok(created)
     }
     async fn select_journal_by_id(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<Journal> {
         let journal = sqlx::query_as!(
-            Journal,
-            r#"SELECT journal_id, name FROM journal WHERE journal_id = $1"#,
+            JournalRecord,
+            r#"SELECT journal_id, name, venue_type, tier FROM journal WHERE journal_id = $1"#,
             Uuid::from_str(id)?
         )
         .fetch_one(&mut **tx)
         .await
         .map_err(|e| shared::errors::AppError::SqlxError(e))?;
 
-        Ok(journal)
+        Ok(journal.into())
     }
     async fn select_journal_by_name(&self, tx: &mut T<'_, Pg>, name: &str) -> AppResult<Journal> {
-        let journal = sqlx::query_as!(Journal, r#"SELECT journal_id, name FROM journal WHERE name = $1"#, name)
-            .fetch_one(&mut **tx)
-            .await
-            .map_err(|e| shared::errors::AppError::SqlxError(e))?;
+        let canonical_name = normalize_venue(name).name;
+        let journal = sqlx::query_as!(
+            JournalRecord,
+            r#"SELECT journal_id, name, venue_type, tier FROM journal
+            WHERE name = $1
+                OR name = $2
+                OR journal_id IN (SELECT journal_id FROM journal_alias WHERE alias_key = ANY($3))
+            ORDER BY name = $1 DESC, name = $2 DESC, created_at
+            LIMIT 1"#,
+            name,
+            canonical_name,
+            &[venue_key(name), venue_key(&canonical_name)]
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| shared::errors::AppError::SqlxError(e))?;
 
-        Ok(journal)
+        Ok(journal.into())
     }
     async fn select_all_journals(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<Journal>> {
-        let journals = sqlx::query_as!(Journal, r#"SELECT journal_id, name FROM journal"#)
-            .fetch_all(&mut **tx)
+        let journals = sqlx::query_as!(
+            JournalRecord,
+            r#"SELECT journal_id, name, venue_type, tier FROM journal"#
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| shared::errors::AppError::SqlxError(e))?;
+
+        Ok(journals.into_iter().map(Journal::from).collect())
+    }
+    async fn select_journal_summaries(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: BrowseListOptions,
+    ) -> AppResult<PaginatedList<JournalSummary>> {
+        let total_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM journal WHERE $1::TEXT IS NULL OR name ILIKE '%' || $1 || '%'"#,
+            options.name
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .expect("Total count should not be None");
+
+        let journals = sqlx::query_as!(
+            JournalSummaryRecord,
+            r#"SELECT
+                j.journal_id,
+                j.name,
+                j.venue_type,
+                j.tier,
+                COUNT(p.paper_id) AS "paper_count!"
+            FROM journal j
+            LEFT JOIN academic_paper p ON p.journal_id = j.journal_id
+            WHERE $1::TEXT IS NULL OR j.name ILIKE '%' || $1 || '%'
+            GROUP BY j.journal_id
+            ORDER BY COUNT(p.paper_id) DESC, j.name
+            LIMIT $2 OFFSET $3"#,
+            options.name,
+            options.limit,
+            options.offset
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let journal_ids = journals
+            .iter()
+            .map(|journal| journal.journal_id.raw())
+            .collect::<Vec<Uuid>>();
+        let recent_papers = sqlx::query_as!(
+            RecentPaperRecord,
+            r#"SELECT
+                owner_id AS "owner_id!",
+                paper_id AS "paper_id!",
+                title AS "title!",
+                published_date AS "published_date!",
+                created_at AS "created_at!"
+            FROM (
+                SELECT
+                    journal_id AS owner_id,
+                    paper_id,
+                    title,
+                    published_date,
+                    created_at,
+                    ROW_NUMBER() OVER (PARTITION BY journal_id ORDER BY created_at DESC, paper_id) AS rank
+                FROM academic_paper
+                WHERE journal_id = ANY($1)
+            ) AS recent
+            WHERE rank <= $2
+            ORDER BY owner_id, rank"#,
+            &journal_ids,
+            options.recent_count
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(PaginatedList::<JournalSummary>::new(
+            total_count,
+            options.limit,
+            options.offset,
+            journals
+                .into_iter()
+                .map(|journal| {
+                    let recent_papers = recent_papers_of(&recent_papers, journal.journal_id.raw());
+                    JournalSummary::new(
+                        JournalRecord::new(journal.journal_id, journal.name, journal.venue_type, journal.tier).into(),
+                        journal.paper_count,
+                        recent_papers,
+                    )
+                })
+                .collect(),
+        ))
+    }
+    async fn update_journal_tiers(&self, tx: &mut T<'_, Pg>, tiers: Vec<VenueTier>) -> AppResult<Vec<Journal>> {
+        let mut journals = vec![];
+        for VenueTier { name, tier } in tiers.into_iter() {
+            let mut journal = self.create_journal(tx, Journal::new(JournalId::new(), name)).await?;
+            sqlx::query!(
+                r#"UPDATE journal SET tier = $2 WHERE journal_id = $1"#,
+                Uuid::from(journal.journal_id),
+                tier
+            )
+            .execute(&mut **tx)
             .await
-            .map_err(|e| shared::errors::AppError::SqlxError(e))?;
+            .map_err(AppError::SqlxError)?;
+            journal.tier = tier;
+            journals.push(journal);
+        }
 
         Ok(journals)
     }
+    async fn merge_journals(&self, tx: &mut T<'_, Pg>, id: &str, duplicate_id: &str) -> AppResult<Journal> {
+        let mut journal = self.select_journal_by_id(tx, id).await?;
+        let duplicate = self.select_journal_by_id(tx, duplicate_id).await?;
+        if journal.journal_id == duplicate.journal_id {
+            return Err(AppError::Unprocessable("Cannot merge a journal into itself".into()));
+        }
+
+        sqlx::query!(
+            r#"UPDATE academic_paper SET journal_id = $1 WHERE journal_id = $2"#,
+            Uuid::from(journal.journal_id),
+            Uuid::from(duplicate.journal_id)
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"UPDATE journal_alias SET journal_id = $1 WHERE journal_id = $2"#,
+            Uuid::from(journal.journal_id),
+            Uuid::from(duplicate.journal_id)
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        if journal.tier.is_none() && duplicate.tier.is_some() {
+            sqlx::query!(
+                r#"UPDATE journal SET tier = $2 WHERE journal_id = $1"#,
+                Uuid::from(journal.journal_id),
+                duplicate.tier
+            )
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+            journal.tier = duplicate.tier;
+        }
+        self.delete_journal(tx, duplicate_id).await?;
+        self.create_journal_alias(tx, &journal, &duplicate.name).await?;
+
+        Ok(journal)
+    }
+    async fn normalize_journals(&self, tx: &mut T<'_, Pg>) -> AppResult<VenueNormalization> {
+        let journals = sqlx::query_as!(
+            JournalRecord,
+            r#"SELECT journal_id, name, venue_type, tier FROM journal ORDER BY created_at, journal_id"#
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let mut normalization = VenueNormalization::default();
+        // the venues with the same canonical name, the one already named so first
+        let mut groups: Vec<(NormalizedVenue, Vec<Journal>)> = vec![];
+        for journal in journals.into_iter().map(Journal::from) {
+            let venue = normalize_venue(&journal.name);
+            // the year in the stored name is lost on renaming, so it is kept as the venue year of its papers
+            if let Some(year) = venue.year {
+                let filled = sqlx::query!(
+                    r#"UPDATE academic_paper SET venue_year = $2 WHERE journal_id = $1 AND venue_year IS NULL"#,
+                    Uuid::from(journal.journal_id),
+                    year
+                )
+                .execute(&mut **tx)
+                .await
+                .map_err(AppError::SqlxError)?;
+                normalization.venue_years += filled.rows_affected() as i64;
+            }
+            match groups.iter_mut().find(|(other, _)| other.name == venue.name) {
+                Some((_, group)) if journal.name == venue.name => group.insert(0, journal),
+                Some((_, group)) => group.push(journal),
+                None => groups.push((venue, vec![journal])),
+            }
+        }
+
+        for (venue, group) in groups.into_iter() {
+            let mut group = group.into_iter();
+            let Some(journal) = group.next() else {
+                continue;
+            };
+            let venue_type = journal.venue_type.unwrap_or(venue.venue_type);
+            if journal.name != venue.name || journal.venue_type.is_none() {
+                sqlx::query!(
+                    r#"UPDATE journal SET name = $2, venue_type = $3 WHERE journal_id = $1"#,
+                    Uuid::from(journal.journal_id),
+                    venue.name,
+                    venue_type.to_string()
+                )
+                .execute(&mut **tx)
+                .await
+                .map_err(AppError::SqlxError)?;
+                let normalized = Journal {
+                    name: venue.name.clone(),
+                    venue_type: Some(venue_type),
+                    ..journal.clone()
+                };
+                self.create_journal_alias(tx, &normalized, &journal.name).await?;
+                normalization.normalized += 1;
+            }
+            for duplicate in group {
+                self.merge_journals(tx, &journal.journal_id.to_string(), &duplicate.journal_id.to_string())
+                    .await?;
+                normalization.merged += 1;
+            }
+        }
+
+        let filled = sqlx::query!(
+            r#"UPDATE academic_paper p SET venue_year = EXTRACT(YEAR FROM p.published_date)::INTEGER
+            FROM journal j
+            WHERE j.journal_id = p.journal_id
+                AND p.venue_year IS NULL
+                AND j.venue_type <> 'preprint'
+                AND j.name <> $1
+                AND EXTRACT(YEAR FROM p.published_date) > 1970"#,
+            UNKNOWN_VENUE
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        normalization.venue_years += filled.rows_affected() as i64;
+
+        Ok(normalization)
+    }
     async fn delete_journal(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<()> {
         sqlx::query!(r#"DELETE FROM journal WHERE journal_id = $1"#, Uuid::from_str(id)?)
             .execute(&mut **tx)
@@ -266,22 +1001,201 @@ impl JournalRepository for JournalRepositoryImpl {
     }
 }
 
-#[derive(Debug, Clone, new)]
-pub struct AcademicPaperRepositoryImpl {
-    pub db: ConnectionPool,
-}
-
-#[async_trait]
+impl JournalRepositoryImpl {
+    /// Records `name` as an alias of `journal`, unless it is a variant of the canonical name or already an alias.
+    async fn create_journal_alias(&self, tx: &mut T<'_, Pg>, journal: &Journal, name: &str) -> AppResult<()> {
+        let alias_key = venue_key(name);
+        if alias_key.is_empty() || alias_key == venue_key(&journal.name) {
+            return Ok(());
+        }
+        sqlx::query!(
+            r#"INSERT INTO journal_alias (alias_key, journal_id, name)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING"#,
+            alias_key,
+            Uuid::from(journal.journal_id),
+            name
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(())
+    }
+}
+
+/// Escapes the wildcards of `text` so that it matches literally in a `LIKE` pattern, whose escape character is `\`.
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// A `LIKE` pattern matching the titles that contain the words of `title` in order, whatever the punctuation between them.
+fn title_pattern(title: &str) -> String {
+    match normalize_title(title) {
+        // the words have only letters and digits, so no wildcards
+        Some(title) => format!("%{}%", title.replace(' ', "%")),
+        None => format!("%{}%", escape_like(title)),
+    }
+}
+
+#[derive(Debug, Clone, new)]
+pub struct AcademicPaperRepositoryImpl {
+    pub db: ConnectionPool,
+}
+
+impl AcademicPaperRepositoryImpl {
+    async fn upsert_translations(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        translations: &[AcademicPaperTranslation],
+    ) -> AppResult<()> {
+        for translation in translations {
+            sqlx::query!(
+                r#"INSERT INTO academic_paper_translation (
+                paper_id,
+                language,
+                abstract_text,
+                summary,
+                background_and_purpose,
+                methodology,
+                dataset,
+                results,
+                advantages_limitations_and_future_work
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (paper_id, language) DO UPDATE SET
+                abstract_text = EXCLUDED.abstract_text,
+                summary = EXCLUDED.summary,
+                background_and_purpose = EXCLUDED.background_and_purpose,
+                methodology = EXCLUDED.methodology,
+                dataset = EXCLUDED.dataset,
+                results = EXCLUDED.results,
+                advantages_limitations_and_future_work = EXCLUDED.advantages_limitations_and_future_work"#,
+                Uuid::from(paper_id),
+                translation.language.to_string(),
+                translation.abstract_text,
+                translation.summary,
+                translation.background_and_purpose,
+                translation.methodology,
+                translation.dataset,
+                translation.results,
+                translation.advantages_limitations_and_future_work,
+            )
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the stored sections and records the stored PDF, keeping the existing ones when not given.
+    async fn save_contents(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        paper: &AcademicPaper,
+    ) -> AppResult<()> {
+        if !paper.sections.is_empty() {
+            sqlx::query!(r#"DELETE FROM paper_section WHERE paper_id = $1"#, Uuid::from(paper_id))
+                .execute(&mut **tx)
+                .await
+                .map_err(AppError::SqlxError)?;
+            // The passage index is rebuilt from the new sections when the paper is next asked about
+            sqlx::query!(r#"DELETE FROM paper_chunk WHERE paper_id = $1"#, Uuid::from(paper_id))
+                .execute(&mut **tx)
+                .await
+                .map_err(AppError::SqlxError)?;
+            let titles = paper.sections.iter().map(|s| s.title.clone()).collect::<Vec<String>>();
+            let contents = paper
+                .sections
+                .iter()
+                .map(|s| s.content.clone())
+                .collect::<Vec<String>>();
+            sqlx::query!(
+                r#"INSERT INTO paper_section (paper_id, position, title, content)
+                SELECT $1, s.position - 1, s.title, s.content
+                FROM UNNEST($2::TEXT[], $3::TEXT[]) WITH ORDINALITY AS s(title, content, position)"#,
+                Uuid::from(paper_id),
+                &titles,
+                &contents
+            )
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+        }
+        if !paper.pdf_sha256.is_empty() {
+            sqlx::query!(
+                r#"INSERT INTO paper_pdf (paper_id, sha256) VALUES ($1, $2)
+                ON CONFLICT (paper_id) DO UPDATE SET sha256 = EXCLUDED.sha256, stored_at = CURRENT_TIMESTAMP(3)"#,
+                Uuid::from(paper_id),
+                paper.pdf_sha256
+            )
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+        }
+        Ok(())
+    }
+
+    /// Fills the stored sections and PDF. Papers stored before the sections were kept get them from `text`.
+    async fn fill_contents(&self, tx: &mut T<'_, Pg>, academic_paper: &mut AcademicPaper) -> AppResult<()> {
+        let sections = sqlx::query_as!(
+            PaperSectionRecord,
+            r#"SELECT title, content FROM paper_section WHERE paper_id = $1 ORDER BY position"#,
+            Uuid::from(academic_paper.paper_id)
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        academic_paper.sections = if sections.is_empty() {
+            PaperSection::split_text(&academic_paper.text)
+        } else {
+            sections.into_iter().map(PaperSection::from).collect()
+        };
+
+        academic_paper.pdf_sha256 = sqlx::query_scalar!(
+            r#"SELECT sha256 FROM paper_pdf WHERE paper_id = $1"#,
+            Uuid::from(academic_paper.paper_id)
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .unwrap_or_default();
+        Ok(())
+    }
+}
+
+#[async_trait]
 impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
     async fn create_academic_paper(
         &self,
         tx: &mut T<'_, Pg>,
         academic_paper: AcademicPaper,
     ) -> AppResult<AcademicPaper> {
-        // Check if the academic paper already exists by ss_id
+        // Check if the academic paper already exists by ss_id, DOI or arXiv ID (of any version)
+        if !academic_paper.ss_id.trim().is_empty() {
+            if let Ok(paper) = self
+                .select_academic_paper_by_ss_id(tx, academic_paper.ss_id.trim())
+                .await
+            {
+                return Ok(paper);
+            }
+        }
+        let doi = normalize_doi(&academic_paper.doi);
+        let arxiv_id = arxiv_base_id(&academic_paper.arxiv_id);
+        if let Some(paper) = self
+            .select_academic_paper_by_external_id(tx, doi.as_deref(), arxiv_id.as_deref())
+            .await?
+        {
+            return Ok(paper);
+        }
+        // or by a similar title
         if let Ok(existing_paper) = self.select_academic_paper_by_title(tx, &academic_paper.title).await {
             for paper in existing_paper {
-                let lev_dist = levenshtein_dist(&paper.title, &academic_paper.title);
+                let lev_dist = levenshtein_dist(
+                    &normalize_title(&paper.title).unwrap_or_default(),
+                    &normalize_title(&academic_paper.title).unwrap_or_default(),
+                );
                 if lev_dist < 2 {
                     // If the paper already exists with a similar title, return it
                     return Ok(paper);
@@ -316,11 +1230,11 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
 
             let res = sqlx::query!(
                 r#"INSERT INTO academic_paper (
+                paper_id,
                 arxiv_id,
                 ss_id,
                 title,
                 abstract_text,
-                abstract_text_ja,
                 journal_id,
                 primary_category,
                 citations_count,
@@ -330,20 +1244,17 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 url,
                 text,
                 bibtex,
-                summary,
-                background_and_purpose,
-                methodology,
-                dataset,
-                results,
-                advantages_limitations_and_future_work,
-                status
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)
+                status,
+                doi,
+                venue_year
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
             RETURNING paper_id"#,
+                // keep the id that the citation key of the BibTeX was made from
+                Uuid::from(academic_paper.paper_id),
                 academic_paper.arxiv_id,
                 academic_paper.ss_id,
                 academic_paper.title,
                 academic_paper.abstract_text,
-                academic_paper.abstract_text_ja,
                 Uuid::from(journal.journal_id),
                 academic_paper.primary_category,
                 academic_paper.citations_count,
@@ -353,13 +1264,9 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 academic_paper.url,
                 academic_paper.text,
                 academic_paper.bibtex,
-                academic_paper.summary,
-                academic_paper.background_and_purpose,
-                academic_paper.methodology,
-                academic_paper.dataset,
-                academic_paper.results,
-                academic_paper.advantages_limitations_and_future_work,
                 academic_paper.status.to_string(),
+                academic_paper.doi,
+                academic_paper.venue_year,
             )
             .fetch_one(&mut **tx)
             .await
@@ -367,7 +1274,27 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
 
             let inserted_paper: AcademicPaperRecord = sqlx::query_as!(
                 AcademicPaperRecord,
-                r#"SELECT * FROM academic_paper WHERE paper_id = $1"#,
+                r#"SELECT
+                    paper_id,
+                    ss_id,
+                    arxiv_id,
+                    journal_id,
+                    title,
+                    abstract_text,
+                    text,
+                    url,
+                    doi,
+                    published_date,
+                    created_at,
+                    updated_at,
+                    primary_category,
+                    citations_count,
+                    references_count,
+                    influential_citation_count,
+                    bibtex,
+                    status,
+                    venue_year
+                FROM academic_paper WHERE paper_id = $1"#,
                 res.paper_id.clone()
             )
             .fetch_one(&mut **tx)
@@ -421,20 +1348,27 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 }
             }
 
+            self.upsert_translations(tx, AcademicPaperId::from(res.paper_id), &academic_paper.translations)
+                .await?;
+            self.save_contents(tx, AcademicPaperId::from(res.paper_id), &academic_paper)
+                .await?;
+
             Ok(AcademicPaper {
                 paper_id: AcademicPaperId::from(res.paper_id),
                 ss_id: academic_paper.ss_id,
                 arxiv_id: academic_paper.arxiv_id,
                 title: academic_paper.title,
                 abstract_text: academic_paper.abstract_text,
-                abstract_text_ja: academic_paper.abstract_text_ja,
                 authors: authors,
                 journal: journal,
                 tasks: tasks,
                 text: academic_paper.text,
+                sections: academic_paper.sections,
+                pdf_sha256: academic_paper.pdf_sha256,
                 url: academic_paper.url,
                 doi: academic_paper.doi,
                 published_date: academic_paper.published_date,
+                venue_year: academic_paper.venue_year,
                 created_at: Local.from_utc_datetime(&inserted_paper.created_at.unwrap().naive_local()),
                 updated_at: Local.from_utc_datetime(&inserted_paper.updated_at.unwrap().naive_local()),
                 primary_category: academic_paper.primary_category,
@@ -442,12 +1376,7 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 references_count: academic_paper.references_count,
                 influential_citation_count: academic_paper.influential_citation_count,
                 bibtex: academic_paper.bibtex,
-                summary: academic_paper.summary,
-                background_and_purpose: academic_paper.background_and_purpose,
-                methodology: academic_paper.methodology,
-                dataset: academic_paper.dataset,
-                results: academic_paper.results,
-                advantages_limitations_and_future_work: academic_paper.advantages_limitations_and_future_work,
+                translations: academic_paper.translations,
                 status: academic_paper.status,
             })
         }
@@ -483,37 +1412,29 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 tasks.push(task);
             }
 
-            // Update the record
+            // Update the record; the status is left to the reading workflow, which records its history
             sqlx::query!(
                 r#"UPDATE academic_paper SET
                 arxiv_id = $1,
                 ss_id = $2,
                 title = $3,
                 abstract_text = $4,
-                abstract_text_ja = $5,
-                journal_id = $6,
-                primary_category = $7,
-                citations_count = $8,
-                influential_citation_count = $9,
-                references_count = $10,
-                published_date = $11,
-                url = $12,
-                text = $13,
-                bibtex = $14,
-                summary = $15,
-                background_and_purpose = $16,
-                methodology = $17,
-                dataset = $18,
-                results = $19,
-                advantages_limitations_and_future_work = $20,
-                status = $21,
+                journal_id = $5,
+                primary_category = $6,
+                citations_count = $7,
+                influential_citation_count = $8,
+                references_count = $9,
+                published_date = $10,
+                url = $11,
+                text = $12,
+                bibtex = $13,
+                venue_year = $14,
                 updated_at = NOW()
-            WHERE paper_id = $22"#,
+            WHERE paper_id = $15"#,
                 academic_paper.arxiv_id,
                 academic_paper.ss_id,
                 academic_paper.title,
                 academic_paper.abstract_text,
-                academic_paper.abstract_text_ja,
                 Uuid::from(journal.journal_id),
                 academic_paper.primary_category,
                 academic_paper.citations_count,
@@ -523,13 +1444,7 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 academic_paper.url,
                 academic_paper.text,
                 academic_paper.bibtex,
-                academic_paper.summary,
-                academic_paper.background_and_purpose,
-                academic_paper.methodology,
-                academic_paper.dataset,
-                academic_paper.results,
-                academic_paper.advantages_limitations_and_future_work,
-                academic_paper.status.to_string(),
+                academic_paper.venue_year,
                 Uuid::from(academic_paper.paper_id),
             )
             .execute(&mut **tx)
@@ -580,6 +1495,10 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 .await
                 .map_err(|e| shared::errors::AppError::SqlxError(e))?;
             }
+            self.upsert_translations(tx, academic_paper.paper_id, &academic_paper.translations)
+                .await?;
+            self.save_contents(tx, academic_paper.paper_id, &academic_paper).await?;
+
             Ok(self
                 .select_academic_paper_by_id(tx, &academic_paper.paper_id.to_string())
                 .await?)
@@ -603,7 +1522,7 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
         // Fill journal
         let journal = sqlx::query_as!(
             JournalRecord,
-            r#"SELECT journal_id, name FROM journal WHERE journal_id = $1"#,
+            r#"SELECT journal_id, name, venue_type, tier FROM journal WHERE journal_id = $1"#,
             Uuid::from(academic_paper.journal.journal_id)
         )
         .fetch_one(&mut **tx)
@@ -624,6 +1543,29 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
         .map_err(|e| AppError::EntityNotFound(e.to_string()))?;
         academic_paper.tasks = tasks.into_iter().map(Task::from).collect();
 
+        // Fill translations
+        let translations = sqlx::query_as!(
+            AcademicPaperTranslationRecord,
+            r#"SELECT
+                paper_id,
+                language,
+                abstract_text,
+                summary,
+                background_and_purpose,
+                methodology,
+                dataset,
+                results,
+                advantages_limitations_and_future_work
+            FROM academic_paper_translation
+            WHERE paper_id = $1
+            ORDER BY created_at"#,
+            Uuid::from(academic_paper.paper_id)
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| AppError::EntityNotFound(e.to_string()))?;
+        academic_paper.translations = translations.into_iter().map(AcademicPaperTranslation::from).collect();
+
         Ok(())
     }
 
@@ -638,7 +1580,6 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 journal_id,
                 title,
                 abstract_text,
-                abstract_text_ja,
                 text,
                 url,
                 doi,
@@ -650,13 +1591,8 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 references_count,
                 influential_citation_count,
                 bibtex,
-                summary,
-                background_and_purpose,
-                methodology,
-                dataset,
-                results,
-                advantages_limitations_and_future_work,
-                status
+                status,
+                venue_year
             FROM academic_paper WHERE DATE(published_date) = DATE($1)"#,
             current_date
         )
@@ -685,7 +1621,6 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 journal_id,
                 title,
                 abstract_text,
-                abstract_text_ja,
                 text,
                 url,
                 doi,
@@ -697,13 +1632,8 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 references_count,
                 influential_citation_count,
                 bibtex,
-                summary,
-                background_and_purpose,
-                methodology,
-                dataset,
-                results,
-                advantages_limitations_and_future_work,
-                status
+                status,
+                venue_year
             FROM academic_paper WHERE arxiv_id = $1"#,
             arxiv_id
         )
@@ -727,7 +1657,6 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 journal_id,
                 title,
                 abstract_text,
-                abstract_text_ja,
                 text,
                 url,
                 doi,
@@ -739,13 +1668,8 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 references_count,
                 influential_citation_count,
                 bibtex,
-                summary,
-                background_and_purpose,
-                methodology,
-                dataset,
-                results,
-                advantages_limitations_and_future_work,
-                status
+                status,
+                venue_year
             FROM academic_paper WHERE ss_id = $1"#,
             ss_id
         )
@@ -759,6 +1683,62 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
         Ok(academic_paper)
     }
 
+    async fn select_academic_paper_by_external_id(
+        &self,
+        tx: &mut T<'_, Pg>,
+        doi: Option<&str>,
+        arxiv_id: Option<&str>,
+    ) -> AppResult<Option<AcademicPaper>> {
+        // arxiv_id is stored as the abs URL of a version, e.g. http://arxiv.org/abs/2301.00001v1
+        let paper = sqlx::query_as!(
+            AcademicPaperRecord,
+            r#"SELECT
+                paper_id,
+                ss_id,
+                arxiv_id,
+                journal_id,
+                title,
+                abstract_text,
+                text,
+                url,
+                doi,
+                published_date,
+                created_at,
+                updated_at,
+                primary_category,
+                citations_count,
+                references_count,
+                influential_citation_count,
+                bibtex,
+                status,
+                venue_year
+            FROM academic_paper
+            WHERE ($1::TEXT IS NOT NULL AND LOWER(doi) = LOWER($1))
+                OR ($2::TEXT IS NOT NULL AND (
+                    arxiv_id = $2
+                    OR arxiv_id LIKE '%/' || $2
+                    OR arxiv_id LIKE '%/' || $2 || 'v%'
+                    OR arxiv_id LIKE $2 || 'v%'
+                ))
+            ORDER BY created_at
+            LIMIT 1"#,
+            doi,
+            arxiv_id
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        match paper {
+            Some(paper) => {
+                let mut academic_paper = AcademicPaper::from(paper);
+                self.fill_fields(tx, &mut academic_paper).await?;
+                Ok(Some(academic_paper))
+            }
+            None => Ok(None),
+        }
+    }
+
     async fn select_academic_paper_by_id(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<AcademicPaper> {
         let paper = sqlx::query_as!(
             AcademicPaperRecord,
@@ -769,7 +1749,6 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 journal_id,
                 title,
                 abstract_text,
-                abstract_text_ja,
                 text,
                 url,
                 doi,
@@ -781,13 +1760,8 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 references_count,
                 influential_citation_count,
                 bibtex,
-                summary,
-                background_and_purpose,
-                methodology,
-                dataset,
-                results,
-                advantages_limitations_and_future_work,
-                status
+                status,
+                venue_year
             FROM academic_paper WHERE paper_id = $1"#,
             Uuid::from_str(id)?
         )
@@ -797,6 +1771,7 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
 
         let mut academic_paper = AcademicPaper::from(paper);
         self.fill_fields(tx, &mut academic_paper).await?;
+        self.fill_contents(tx, &mut academic_paper).await?;
 
         Ok(academic_paper)
     }
@@ -811,7 +1786,6 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 journal_id,
                 title,
                 abstract_text,
-                abstract_text_ja,
                 text,
                 url,
                 doi,
@@ -823,15 +1797,10 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 references_count,
                 influential_citation_count,
                 bibtex,
-                summary,
-                background_and_purpose,
-                methodology,
-                dataset,
-                results,
-                advantages_limitations_and_future_work,
-                status
+                status,
+                venue_year
             FROM academic_paper WHERE title ILIKE $1"#,
-            format!("%{}%", title)
+            title_pattern(title)
         )
         .fetch_all(&mut **tx)
         .await
@@ -862,7 +1831,6 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 journal_id,
                 title,
                 abstract_text,
-                abstract_text_ja,
                 text,
                 url,
                 doi,
@@ -874,13 +1842,8 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 references_count,
                 influential_citation_count,
                 bibtex,
-                summary,
-                background_and_purpose,
-                methodology,
-                dataset,
-                results,
-                advantages_limitations_and_future_work,
-                status
+                status,
+                venue_year
             FROM academic_paper"#
         )
         .fetch_all(&mut **tx)
@@ -899,17 +1862,128 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
 
         Ok(academic_papers)
     }
+    async fn select_academic_papers_by_author(
+        &self,
+        tx: &mut T<'_, Pg>,
+        author_id: &str,
+    ) -> AppResult<Vec<AcademicPaper>> {
+        let papers = sqlx::query_as!(
+            AcademicPaperRecord,
+            r#"SELECT
+                p.paper_id,
+                p.ss_id,
+                p.arxiv_id,
+                p.journal_id,
+                p.title,
+                p.abstract_text,
+                p.text,
+                p.url,
+                p.doi,
+                p.published_date,
+                p.created_at,
+                p.updated_at,
+                p.primary_category,
+                p.citations_count,
+                p.references_count,
+                p.influential_citation_count,
+                p.bibtex,
+                p.status,
+                p.venue_year
+            FROM academic_paper p
+            JOIN author_paper_relation r ON r.paper_id = p.paper_id
+            WHERE r.author_id = $1
+            ORDER BY p.published_date DESC"#,
+            Uuid::from_str(author_id)?
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let mut academic_papers: Vec<AcademicPaper> = vec![];
+        for paper in papers.into_iter() {
+            let mut academic_paper = AcademicPaper::from(paper);
+            self.fill_fields(tx, &mut academic_paper).await?;
+            academic_papers.push(academic_paper);
+        }
+        Ok(academic_papers)
+    }
     async fn select_paginated_academic_papers(
         &self,
         tx: &mut T<'_, Pg>,
         options: AcademicPaperListOptions,
     ) -> AppResult<PaginatedList<AcademicPaper>> {
-        let total_count = sqlx::query_scalar!(r#"SELECT COUNT(*) FROM academic_paper"#)
-            .fetch_one(&mut **tx)
-            .await
-            .map_err(|e| shared::errors::AppError::SqlxError(e))?
-            .expect("Total count should not be None");
+        let status = options.status.as_ref().map(Status::to_string);
+        let venue_type = options.venue_type.as_ref().map(VenueType::to_string);
+        // a journal is also found by its canonical name and by the key of its aliases
+        let journal_name = options.journal.as_deref().map(|name| normalize_venue(name).name);
+        let journal_key = options.journal.as_deref().map(venue_key);
+        let author = options.author.as_deref().map(escape_like);
+        let text = options.text.as_deref().map(escape_like);
+        let total_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM academic_paper
+            WHERE ($1::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM paper_keyword_relation AS r JOIN keyword AS k ON k.keyword_id = r.keyword_id
+                    WHERE r.paper_id = academic_paper.paper_id AND k.category = 'tag' AND LOWER(k.name) = LOWER($1)
+                ))
+                AND ($2::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM paper_keyword_relation AS r JOIN keyword AS k ON k.keyword_id = r.keyword_id
+                    WHERE r.paper_id = academic_paper.paper_id AND k.category = 'collection' AND LOWER(k.name) = LOWER($2)
+                ))
+                AND ($3::TEXT IS NULL OR status = $3)
+                AND ($4::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM task_paper_relation AS r JOIN task AS t ON t.task_id = r.task_id
+                    WHERE r.paper_id = academic_paper.paper_id AND LOWER(t.name) = LOWER($4)
+                ))
+                AND ($5::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM journal AS j
+                    WHERE j.journal_id = academic_paper.journal_id AND (
+                        LOWER(j.name) = LOWER($5) OR LOWER(j.name) = LOWER($12) OR EXISTS (
+                            SELECT 1 FROM journal_alias AS a WHERE a.journal_id = j.journal_id AND a.alias_key = $13
+                        )
+                    )
+                ))
+                AND ($14::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM journal AS j WHERE j.journal_id = academic_paper.journal_id AND j.venue_type = $14
+                ))
+                AND ($15::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM journal AS j WHERE j.journal_id = academic_paper.journal_id AND LOWER(j.tier) = LOWER($15)
+                ))
+                AND ($16::INT IS NULL OR venue_year = $16)
+                AND ($6::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM author_paper_relation AS r JOIN author AS a ON a.author_id = r.author_id
+                    WHERE r.paper_id = academic_paper.paper_id AND a.name ILIKE '%' || $6 || '%'
+                ))
+                AND ($7::TEXT IS NULL OR primary_category = $7)
+                AND ($8::DATE IS NULL OR published_date >= $8)
+                AND ($9::DATE IS NULL OR published_date <= $9)
+                AND ($10::INT IS NULL OR citations_count >= $10)
+                AND ($11::TEXT IS NULL OR title ILIKE '%' || $11 || '%' OR abstract_text ILIKE '%' || $11 || '%' OR EXISTS (
+                    SELECT 1 FROM academic_paper_translation AS apt
+                    WHERE apt.paper_id = academic_paper.paper_id AND apt.abstract_text ILIKE '%' || $11 || '%'
+                ))"#,
+            options.tag,
+            options.collection,
+            status,
+            options.task,
+            options.journal,
+            author,
+            options.primary_category,
+            options.published_from,
+            options.published_to,
+            options.min_citations,
+            text,
+            journal_name,
+            journal_key,
+            venue_type,
+            options.tier,
+            options.venue_year,
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| shared::errors::AppError::SqlxError(e))?
+        .expect("Total count should not be None");
 
+        // one key of each CASE is set for the sort and order, the others are NULL for every paper
         let papers = sqlx::query_as!(
             AcademicPaperRecord,
             r#"SELECT
@@ -919,7 +1993,6 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 journal_id,
                 title,
                 abstract_text,
-                abstract_text_ja,
                 text,
                 url,
                 doi,
@@ -931,18 +2004,80 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 references_count,
                 influential_citation_count,
                 bibtex,
-                summary,
-                background_and_purpose,
-                methodology,
-                dataset,
-                results,
-                advantages_limitations_and_future_work,
-                status
+                status,
+                venue_year
             FROM academic_paper
-            ORDER BY published_date DESC
-            LIMIT $1 OFFSET $2"#,
+            WHERE ($1::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM paper_keyword_relation AS r JOIN keyword AS k ON k.keyword_id = r.keyword_id
+                    WHERE r.paper_id = academic_paper.paper_id AND k.category = 'tag' AND LOWER(k.name) = LOWER($1)
+                ))
+                AND ($2::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM paper_keyword_relation AS r JOIN keyword AS k ON k.keyword_id = r.keyword_id
+                    WHERE r.paper_id = academic_paper.paper_id AND k.category = 'collection' AND LOWER(k.name) = LOWER($2)
+                ))
+                AND ($3::TEXT IS NULL OR status = $3)
+                AND ($4::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM task_paper_relation AS r JOIN task AS t ON t.task_id = r.task_id
+                    WHERE r.paper_id = academic_paper.paper_id AND LOWER(t.name) = LOWER($4)
+                ))
+                AND ($5::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM journal AS j
+                    WHERE j.journal_id = academic_paper.journal_id AND (
+                        LOWER(j.name) = LOWER($5) OR LOWER(j.name) = LOWER($16) OR EXISTS (
+                            SELECT 1 FROM journal_alias AS a WHERE a.journal_id = j.journal_id AND a.alias_key = $17
+                        )
+                    )
+                ))
+                AND ($18::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM journal AS j WHERE j.journal_id = academic_paper.journal_id AND j.venue_type = $18
+                ))
+                AND ($19::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM journal AS j WHERE j.journal_id = academic_paper.journal_id AND LOWER(j.tier) = LOWER($19)
+                ))
+                AND ($20::INT IS NULL OR venue_year = $20)
+                AND ($6::TEXT IS NULL OR EXISTS (
+                    SELECT 1 FROM author_paper_relation AS r JOIN author AS a ON a.author_id = r.author_id
+                    WHERE r.paper_id = academic_paper.paper_id AND a.name ILIKE '%' || $6 || '%'
+                ))
+                AND ($7::TEXT IS NULL OR primary_category = $7)
+                AND ($8::DATE IS NULL OR published_date >= $8)
+                AND ($9::DATE IS NULL OR published_date <= $9)
+                AND ($10::INT IS NULL OR citations_count >= $10)
+                AND ($11::TEXT IS NULL OR title ILIKE '%' || $11 || '%' OR abstract_text ILIKE '%' || $11 || '%' OR EXISTS (
+                    SELECT 1 FROM academic_paper_translation AS apt
+                    WHERE apt.paper_id = academic_paper.paper_id AND apt.abstract_text ILIKE '%' || $11 || '%'
+                ))
+            ORDER BY
+                CASE WHEN $12 = 'published_date' AND $13 = 'asc' THEN published_date END ASC,
+                CASE WHEN $12 = 'published_date' AND $13 = 'desc' THEN published_date END DESC,
+                CASE WHEN $12 = 'citations' AND $13 = 'asc' THEN citations_count END ASC,
+                CASE WHEN $12 = 'citations' AND $13 = 'desc' THEN citations_count END DESC,
+                CASE WHEN $12 = 'influential_citations' AND $13 = 'asc' THEN influential_citation_count END ASC,
+                CASE WHEN $12 = 'influential_citations' AND $13 = 'desc' THEN influential_citation_count END DESC,
+                CASE WHEN $12 = 'added_date' AND $13 = 'asc' THEN created_at END ASC,
+                CASE WHEN $12 = 'added_date' AND $13 = 'desc' THEN created_at END DESC,
+                paper_id
+            LIMIT $14 OFFSET $15"#,
+            options.tag,
+            options.collection,
+            status,
+            options.task,
+            options.journal,
+            author,
+            options.primary_category,
+            options.published_from,
+            options.published_to,
+            options.min_citations,
+            text,
+            options.sort.to_string(),
+            options.order.to_string(),
             options.limit as i64,
-            options.offset as i64
+            options.offset as i64,
+            journal_name,
+            journal_key,
+            venue_type,
+            options.tier,
+            options.venue_year
         )
         .fetch_all(&mut **tx)
         .await
@@ -982,7 +2117,6 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 journal_id,
                 title,
                 abstract_text,
-                abstract_text_ja,
                 text,
                 url,
                 doi,
@@ -994,18 +2128,16 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
                 references_count,
                 influential_citation_count,
                 bibtex,
-                summary,
-                background_and_purpose,
-                methodology,
-                dataset,
-                results,
-                advantages_limitations_and_future_work,
-                status
+                status,
+                venue_year
             FROM academic_paper
             WHERE 
                 title ILIKE $1
                 OR abstract_text ILIKE $1
-                OR abstract_text_ja ILIKE $1
+                OR EXISTS (
+                    SELECT 1 FROM academic_paper_translation AS apt
+                    WHERE apt.paper_id = academic_paper.paper_id AND apt.abstract_text ILIKE $1
+                )
             "#,
             format!("%{}%", keyword)
         )
@@ -1049,4 +2181,1071 @@ impl AcademicPaperRepository for AcademicPaperRepositoryImpl {
 
         Ok(())
     }
+
+    async fn select_duplicate_academic_papers(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<DuplicateGroup>> {
+        let papers = sqlx::query_as!(
+            DuplicatePaperRecord,
+            r#"SELECT
+                paper_id,
+                title,
+                ss_id,
+                arxiv_id,
+                doi,
+                created_at AS "created_at!"
+            FROM academic_paper"#
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(find_duplicates(papers.into_iter().map(DuplicatePaper::from).collect()))
+    }
+
+    async fn merge_academic_papers(
+        &self,
+        tx: &mut T<'_, Pg>,
+        id: &str,
+        duplicate_id: &str,
+    ) -> AppResult<AcademicPaper> {
+        let paper = self.select_academic_paper_by_id(tx, id).await?;
+        let duplicate = self.select_academic_paper_by_id(tx, duplicate_id).await?;
+        if paper.paper_id == duplicate.paper_id {
+            return Err(AppError::Unprocessable("Cannot merge a paper into itself".into()));
+        }
+        for (field, a, b) in [
+            (
+                "Semantic Scholar IDs",
+                paper.ss_id.trim().to_string(),
+                duplicate.ss_id.trim().to_string(),
+            ),
+            (
+                "arXiv IDs",
+                arxiv_base_id(&paper.arxiv_id).unwrap_or_default(),
+                arxiv_base_id(&duplicate.arxiv_id).unwrap_or_default(),
+            ),
+            (
+                "DOIs",
+                normalize_doi(&paper.doi).unwrap_or_default().to_lowercase(),
+                normalize_doi(&duplicate.doi).unwrap_or_default().to_lowercase(),
+            ),
+        ] {
+            if !a.is_empty() && !b.is_empty() && a != b {
+                return Err(AppError::Unprocessable(format!(
+                    "{} and {} have different {}",
+                    paper.title, duplicate.title, field
+                )));
+            }
+        }
+        let (id, duplicate_id) = (Uuid::from(paper.paper_id), Uuid::from(duplicate.paper_id));
+
+        // relations that the paper may already have
+        sqlx::query!(
+            r#"INSERT INTO author_paper_relation (author_id, paper_id)
+            SELECT author_id, $1 FROM author_paper_relation WHERE paper_id = $2
+            ON CONFLICT DO NOTHING"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"INSERT INTO task_paper_relation (task_id, paper_id)
+            SELECT task_id, $1 FROM task_paper_relation WHERE paper_id = $2
+            ON CONFLICT DO NOTHING"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"INSERT INTO paper_keyword_relation (keyword_id, paper_id)
+            SELECT keyword_id, $1 FROM paper_keyword_relation WHERE paper_id = $2
+            ON CONFLICT DO NOTHING"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"INSERT INTO academic_paper_translation (
+                paper_id,
+                language,
+                abstract_text,
+                summary,
+                background_and_purpose,
+                methodology,
+                dataset,
+                results,
+                advantages_limitations_and_future_work
+            )
+            SELECT
+                $1,
+                language,
+                abstract_text,
+                summary,
+                background_and_purpose,
+                methodology,
+                dataset,
+                results,
+                advantages_limitations_and_future_work
+            FROM academic_paper_translation WHERE paper_id = $2
+            ON CONFLICT DO NOTHING"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"INSERT INTO reading_queue (paper_id, priority, due_date, created_at)
+            SELECT $1, priority, due_date, created_at FROM reading_queue WHERE paper_id = $2
+            ON CONFLICT DO NOTHING"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"INSERT INTO paper_version (
+                paper_id,
+                version,
+                submitted_at,
+                pdf_sha256,
+                changed_sections,
+                change_summary,
+                detected_at
+            )
+            SELECT $1, version, submitted_at, pdf_sha256, changed_sections, change_summary, detected_at
+            FROM paper_version WHERE paper_id = $2
+            ON CONFLICT DO NOTHING"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        // records that belong to one paper only
+        sqlx::query!(
+            r#"UPDATE paper_note_relation SET paper_id = $1 WHERE paper_id = $2"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"UPDATE paper_status_history SET paper_id = $1 WHERE paper_id = $2"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"UPDATE paper_metadata_history SET paper_id = $1 WHERE paper_id = $2"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"UPDATE paper_candidate SET paper_id = $1 WHERE paper_id = $2"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"UPDATE paper_comparison_paper SET paper_id = $1 WHERE paper_id = $2"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        // the contents of the duplicate if the paper has none
+        sqlx::query!(
+            r#"UPDATE paper_section SET paper_id = $1
+            WHERE paper_id = $2 AND NOT EXISTS (SELECT 1 FROM paper_section WHERE paper_id = $1)"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"UPDATE paper_chunk SET paper_id = $1
+            WHERE paper_id = $2 AND NOT EXISTS (SELECT 1 FROM paper_chunk WHERE paper_id = $1)"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        sqlx::query!(
+            r#"UPDATE paper_pdf SET paper_id = $1
+            WHERE paper_id = $2 AND NOT EXISTS (SELECT 1 FROM paper_pdf WHERE paper_id = $1)"#,
+            id,
+            duplicate_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        self.delete_academic_paper(tx, &duplicate_id.to_string()).await?;
+
+        // keep the identifiers and the fields of the duplicate that the paper lacks
+        sqlx::query!(
+            r#"UPDATE academic_paper SET
+                ss_id = CASE WHEN ss_id = '' THEN $2 ELSE ss_id END,
+                arxiv_id = CASE WHEN arxiv_id = '' THEN $3 ELSE arxiv_id END,
+                doi = CASE WHEN doi = '' THEN $4 ELSE doi END,
+                abstract_text = CASE WHEN abstract_text = '' THEN $5 ELSE abstract_text END,
+                text = CASE WHEN text = '' THEN $6 ELSE text END,
+                url = CASE WHEN url = '' THEN $7 ELSE url END,
+                bibtex = CASE WHEN bibtex = '' THEN $8 ELSE bibtex END,
+                citations_count = GREATEST(citations_count, $9),
+                references_count = GREATEST(references_count, $10),
+                influential_citation_count = GREATEST(influential_citation_count, $11)
+            WHERE paper_id = $1"#,
+            id,
+            duplicate.ss_id,
+            duplicate.arxiv_id,
+            duplicate.doi,
+            duplicate.abstract_text,
+            duplicate.text,
+            duplicate.url,
+            duplicate.bibtex,
+            duplicate.citations_count,
+            duplicate.references_count,
+            duplicate.influential_citation_count
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        self.select_academic_paper_by_id(tx, &id.to_string()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Datelike;
+    use kernel::models::{
+        academic_paper::{AcademicPaperSort, SortOrder},
+        paper_duplicate::DuplicateReason,
+    };
+
+    async fn insert_paper(tx: &mut T<'_, Pg>, title: &str, authors: &[&Author]) -> Uuid {
+        let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ('Test') RETURNING journal_id")
+            .fetch_one(&mut **tx)
+            .await
+            .unwrap();
+        let paper_id: Uuid =
+            sqlx::query_scalar("INSERT INTO academic_paper (journal_id, title) VALUES ($1, $2) RETURNING paper_id")
+                .bind(journal_id)
+                .bind(title)
+                .fetch_one(&mut **tx)
+                .await
+                .unwrap();
+        for author in authors.iter() {
+            sqlx::query("INSERT INTO author_paper_relation (author_id, paper_id) VALUES ($1, $2)")
+                .bind(Uuid::from(author.author_id))
+                .bind(paper_id)
+                .execute(&mut **tx)
+                .await
+                .unwrap();
+        }
+        paper_id
+    }
+
+    #[sqlx::test]
+    async fn test_author_profiles(pool: sqlx::PgPool) {
+        let repo = AuthorRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+
+        // Authors are deduplicated by ss_id, or by name without one
+        let ada = repo
+            .create_author(&mut tx, Author::new(AuthorId::new(), "1".into(), "Ada".into(), 10))
+            .await
+            .unwrap();
+        let again = repo
+            .create_author(&mut tx, Author::new(AuthorId::new(), "1".into(), "Ada L.".into(), 11))
+            .await
+            .unwrap();
+        assert_eq!(again.author_id, ada.author_id);
+        let alan = repo
+            .create_author(&mut tx, Author::new(AuthorId::new(), "".into(), "Alan".into(), 0))
+            .await
+            .unwrap();
+        let alan_again = repo
+            .create_author(&mut tx, Author::new(AuthorId::new(), "".into(), "Alan".into(), 0))
+            .await
+            .unwrap();
+        assert_eq!(alan_again.author_id, alan.author_id);
+        let grace = repo
+            .create_author(&mut tx, Author::new(AuthorId::new(), "".into(), "Grace".into(), 0))
+            .await
+            .unwrap();
+        assert_ne!(grace.author_id, alan.author_id);
+
+        insert_paper(&mut tx, "P1", &[&ada, &alan]).await;
+        insert_paper(&mut tx, "P2", &[&ada, &alan, &grace]).await;
+        insert_paper(&mut tx, "P3", &[&ada]).await;
+
+        let list = repo
+            .select_all_authors_paginated(&mut tx, AuthorListOptions::new(None, 2, 10, 0))
+            .await
+            .unwrap();
+        assert_eq!(list.total, 3);
+        assert_eq!(
+            list.items
+                .iter()
+                .map(|a| (a.author.name.as_str(), a.paper_count))
+                .collect::<Vec<_>>(),
+            vec![("Ada", 3), ("Alan", 2), ("Grace", 1)]
+        );
+        assert_eq!(
+            list.items.iter().map(|a| a.recent_papers.len()).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        let list = repo
+            .select_all_authors_paginated(&mut tx, AuthorListOptions::new(Some("gra".into()), 2, 10, 0))
+            .await
+            .unwrap();
+        assert_eq!(list.total, 1);
+
+        let graph = repo
+            .select_coauthorship_graph(&mut tx, Some(&grace.author_id.to_string()), 10)
+            .await
+            .unwrap();
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 3);
+        let ada_alan = graph
+            .edges
+            .iter()
+            .find(|e| {
+                [e.author_id, e.coauthor_id].contains(&ada.author_id)
+                    && [e.author_id, e.coauthor_id].contains(&alan.author_id)
+            })
+            .unwrap();
+        assert_eq!(ada_alan.paper_count, 2);
+        let graph = repo.select_coauthorship_graph(&mut tx, None, 2).await.unwrap();
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 1);
+
+        // Refresh the h-index of the authors with a Semantic Scholar ID
+        let stale = repo.select_authors_to_refresh(&mut tx, Local::now(), 10).await.unwrap();
+        assert_eq!(stale.len(), 1);
+        repo.update_author_h_index(&mut tx, ada.author_id, 12).await.unwrap();
+        assert!(repo
+            .select_authors_to_refresh(&mut tx, Local::now() - chrono::Duration::hours(1), 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Merge Grace into Alan
+        let merged = repo
+            .merge_authors(&mut tx, &alan.author_id.to_string(), &grace.author_id.to_string())
+            .await
+            .unwrap();
+        assert_eq!(merged.author_id, alan.author_id);
+        let alan_summary = repo
+            .select_author_summary_by_id(&mut tx, &alan.author_id.to_string())
+            .await
+            .unwrap();
+        assert_eq!(alan_summary.paper_count, 2);
+        assert!(repo
+            .select_author_summary_by_id(&mut tx, &grace.author_id.to_string())
+            .await
+            .is_err());
+        assert!(repo
+            .merge_authors(&mut tx, &alan.author_id.to_string(), &alan.author_id.to_string())
+            .await
+            .is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_task_and_journal_summaries(pool: sqlx::PgPool) {
+        let task_repo = TaskRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let journal_repo = JournalRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let qa = task_repo
+            .create_task(&mut tx, Task::new(TaskId::new(), "Question Answering".into()))
+            .await
+            .unwrap();
+        let summarization = task_repo
+            .create_task(&mut tx, Task::new(TaskId::new(), "Summarization".into()))
+            .await
+            .unwrap();
+        let mut papers = vec![];
+        for (title, tasks, months_ago) in [
+            ("P1", vec![&qa], 1),
+            ("P2", vec![&qa, &summarization], 0),
+            ("P3", vec![&qa], 0),
+        ] {
+            let paper_id = insert_paper(&mut tx, title, &[]).await;
+            sqlx::query("UPDATE academic_paper SET created_at = DATE_TRUNC('month', NOW()) - ($2 || ' month')::INTERVAL + ($3 || ' hour')::INTERVAL WHERE paper_id = $1")
+                .bind(paper_id)
+                .bind(months_ago.to_string())
+                .bind(papers.len().to_string())
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+            for task in tasks {
+                sqlx::query("INSERT INTO task_paper_relation (task_id, paper_id) VALUES ($1, $2)")
+                    .bind(Uuid::from(task.task_id))
+                    .bind(paper_id)
+                    .execute(&mut *tx)
+                    .await
+                    .unwrap();
+            }
+            papers.push(paper_id);
+        }
+
+        let tasks = task_repo
+            .select_task_summaries(&mut tx, BrowseListOptions::new(None, 2, 10, 0))
+            .await
+            .unwrap();
+        assert_eq!(tasks.total, 2);
+        assert_eq!(
+            tasks
+                .items
+                .iter()
+                .map(|t| (
+                    t.task.name.as_str(),
+                    t.paper_count,
+                    t.recent_papers.iter().map(|p| p.title.as_str()).collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Question Answering", 3, vec!["P3", "P2"]),
+                ("Summarization", 1, vec!["P2"])
+            ]
+        );
+        let tasks = task_repo
+            .select_task_summaries(&mut tx, BrowseListOptions::new(Some("summ".into()), 0, 10, 0))
+            .await
+            .unwrap();
+        assert_eq!(tasks.total, 1);
+        assert!(tasks.items[0].recent_papers.is_empty());
+
+        let this_month = Local::now().date_naive().with_day(1).unwrap();
+        let last_month = this_month - chrono::Months::new(1);
+        let trends = task_repo.select_task_trends(&mut tx, last_month, 1).await.unwrap();
+        assert_eq!(trends.len(), 1);
+        assert_eq!(trends[0].task.name, "Question Answering");
+        assert_eq!(
+            trends[0]
+                .months
+                .iter()
+                .map(|m| (m.month, m.paper_count))
+                .collect::<Vec<_>>(),
+            vec![(last_month, 1), (this_month, 2)]
+        );
+
+        // insert_paper adds a journal named Test for each paper
+        let journals = journal_repo
+            .select_journal_summaries(&mut tx, BrowseListOptions::new(Some("test".into()), 1, 10, 0))
+            .await
+            .unwrap();
+        assert_eq!(journals.total, 3);
+        assert!(journals
+            .items
+            .iter()
+            .all(|j| j.paper_count == 1 && j.recent_papers.len() == 1));
+    }
+
+    #[sqlx::test]
+    async fn test_task_aliases_and_merge(pool: sqlx::PgPool) {
+        let repo = TaskRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let ner = repo
+            .create_task(&mut tx, Task::new(TaskId::new(), "Named Entity Recognition".into()))
+            .await
+            .unwrap();
+
+        // the variants of the name resolve to the task and are kept as aliases
+        for name in ["named-entity recognition", "NER", "Named Entity Recognition"] {
+            let task = repo
+                .create_task(&mut tx, Task::new(TaskId::new(), name.into()))
+                .await
+                .unwrap();
+            assert_eq!(task.task_id, ner.task_id);
+        }
+        // a name normalized against the vocabulary links to the task of its ID
+        let task = repo
+            .create_task(&mut tx, Task::new(ner.task_id, "Entity Extraction".into()))
+            .await
+            .unwrap();
+        assert_eq!(task.name, "Named Entity Recognition");
+        let vocabulary = repo.select_task_vocabulary(&mut tx).await.unwrap();
+        assert_eq!(vocabulary.entries.len(), 1);
+        assert_eq!(vocabulary.entries[0].aliases, vec!["Entity Extraction", "NER"]);
+
+        let tagging = repo
+            .create_task(&mut tx, Task::new(TaskId::new(), "Entity Tagging".into()))
+            .await
+            .unwrap();
+        assert_ne!(tagging.task_id, ner.task_id);
+        let (p1, p2) = (
+            insert_paper(&mut tx, "P1", &[]).await,
+            insert_paper(&mut tx, "P2", &[]).await,
+        );
+        for (task, paper_id) in [(&ner, p1), (&tagging, p1), (&tagging, p2)] {
+            sqlx::query("INSERT INTO task_paper_relation (task_id, paper_id) VALUES ($1, $2)")
+                .bind(Uuid::from(task.task_id))
+                .bind(paper_id)
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+        }
+
+        repo.merge_tasks(&mut tx, &ner.task_id.to_string(), &tagging.task_id.to_string())
+            .await
+            .unwrap();
+        let vocabulary = repo.select_task_vocabulary(&mut tx).await.unwrap();
+        assert_eq!(vocabulary.entries.len(), 1);
+        assert_eq!(vocabulary.entries[0].paper_count, 2);
+        assert!(vocabulary.entries[0].aliases.contains(&"Entity Tagging".to_string()));
+        let task = repo
+            .create_task(&mut tx, Task::new(TaskId::new(), "entity tagging".into()))
+            .await
+            .unwrap();
+        assert_eq!(task.task_id, ner.task_id);
+        assert!(repo
+            .merge_tasks(&mut tx, &ner.task_id.to_string(), &ner.task_id.to_string())
+            .await
+            .is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_venue_aliases_tiers_and_normalization(pool: sqlx::PgPool) {
+        let repo = JournalRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let paper_repo = AcademicPaperRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let neurips = repo
+            .create_journal(
+                &mut tx,
+                Journal::new(
+                    JournalId::new(),
+                    "Advances in Neural Information Processing Systems 36".into(),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(neurips.name, "NeurIPS");
+        assert_eq!(neurips.venue_type, Some(VenueType::Conference));
+        for name in [
+            "NIPS",
+            "NeurIPS 2023",
+            "Advances in Neural Information Processing Systems 36",
+        ] {
+            let journal = repo
+                .create_journal(&mut tx, Journal::new(JournalId::new(), name.into()))
+                .await
+                .unwrap();
+            assert_eq!(journal.journal_id, neurips.journal_id, "{}", name);
+        }
+
+        // the venues stored before normalization are merged into the canonical one
+        let mut papers = vec![];
+        for (name, published_date) in [
+            ("Neural Information Processing Systems", "2021-06-01"),
+            ("NeurIPS 2022", "2021-12-01"),
+        ] {
+            let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ($1) RETURNING journal_id")
+                .bind(name)
+                .fetch_one(&mut *tx)
+                .await
+                .unwrap();
+            let paper_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO academic_paper (journal_id, title, published_date) VALUES ($1, $2, $3::DATE) RETURNING paper_id",
+            )
+            .bind(journal_id)
+            .bind(name)
+            .bind(published_date)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+            papers.push(paper_id);
+        }
+        let normalization = repo.normalize_journals(&mut tx).await.unwrap();
+        assert_eq!(normalization.merged, 2);
+        assert_eq!(normalization.venue_years, 2);
+        let years: Vec<Option<i32>> = sqlx::query_scalar(
+            "SELECT venue_year FROM academic_paper WHERE paper_id = ANY($1) ORDER BY published_date",
+        )
+        .bind(&papers)
+        .fetch_all(&mut *tx)
+        .await
+        .unwrap();
+        assert_eq!(years, vec![Some(2021), Some(2022)]);
+
+        let tiers = repo
+            .update_journal_tiers(
+                &mut tx,
+                vec![
+                    VenueTier::new("NIPS".into(), Some("A*".into())),
+                    VenueTier::new("Nature".into(), Some("Q1".into())),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(tiers[0].journal_id, neurips.journal_id);
+        assert_eq!(tiers[1].venue_type, Some(VenueType::Journal));
+        let journal = repo
+            .select_journal_by_name(&mut tx, "Neural Information Processing Systems")
+            .await
+            .unwrap();
+        assert_eq!(journal.journal_id, neurips.journal_id);
+        assert_eq!(journal.tier.as_deref(), Some("A*"));
+
+        let list = |journal: Option<&str>, tier: Option<&str>, venue_year: Option<i32>| AcademicPaperListOptions {
+            journal: journal.map(String::from),
+            tier: tier.map(String::from),
+            venue_year,
+            venue_type: Some(VenueType::Conference),
+            limit: 10,
+            ..Default::default()
+        };
+        for (options, total) in [
+            (list(Some("nips"), None, None), 2),
+            (list(None, Some("a*"), None), 2),
+            (list(Some("NeurIPS 2022"), None, Some(2022)), 1),
+            (list(None, Some("Q1"), None), 0),
+        ] {
+            let papers = paper_repo
+                .select_paginated_academic_papers(&mut tx, options.clone())
+                .await
+                .unwrap();
+            assert_eq!(papers.total, total, "{:?}", options);
+        }
+
+        assert!(repo
+            .merge_journals(
+                &mut tx,
+                &neurips.journal_id.to_string(),
+                &neurips.journal_id.to_string()
+            )
+            .await
+            .is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_select_academic_paper_by_external_id(pool: sqlx::PgPool) {
+        let repo = AcademicPaperRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let paper_id = insert_paper(&mut tx, "LoRA", &[]).await;
+        sqlx::query("UPDATE academic_paper SET doi = '10.48550/arXiv.2106.09685', arxiv_id = 'http://arxiv.org/abs/2106.09685v2' WHERE paper_id = $1")
+            .bind(paper_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+
+        let by_doi = repo
+            .select_academic_paper_by_external_id(&mut tx, Some("10.48550/ARXIV.2106.09685"), None)
+            .await
+            .unwrap();
+        assert_eq!(by_doi.map(|paper| Uuid::from(paper.paper_id)), Some(paper_id));
+        let by_arxiv_id = repo
+            .select_academic_paper_by_external_id(&mut tx, None, Some("2106.09685"))
+            .await
+            .unwrap();
+        assert_eq!(by_arxiv_id.map(|paper| Uuid::from(paper.paper_id)), Some(paper_id));
+        // a longer ID sharing the prefix is a different paper
+        assert!(repo
+            .select_academic_paper_by_external_id(&mut tx, None, Some("2106.0968"))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repo
+            .select_academic_paper_by_external_id(&mut tx, None, None)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_create_academic_paper_with_similar_title(pool: sqlx::PgPool) {
+        let repo = AcademicPaperRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let paper_id = insert_paper(&mut tx, "Attention Is All You Need", &[]).await;
+
+        // Case and punctuation do not make another paper
+        for title in ["Attention is all you need.", "Attention is all-you-need"] {
+            let existing = repo
+                .create_academic_paper(
+                    &mut tx,
+                    AcademicPaper {
+                        title: title.into(),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+            assert_eq!(Uuid::from(existing.paper_id), paper_id);
+        }
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM academic_paper")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    async fn select_paper_ids(
+        repo: &AcademicPaperRepositoryImpl,
+        tx: &mut T<'_, Pg>,
+        options: AcademicPaperListOptions,
+    ) -> Vec<Uuid> {
+        let list = repo
+            .select_paginated_academic_papers(tx, AcademicPaperListOptions { limit: 10, ..options })
+            .await
+            .unwrap();
+        assert_eq!(list.total, list.items.len() as i64);
+        list.items.iter().map(|paper| Uuid::from(paper.paper_id)).collect()
+    }
+
+    #[sqlx::test]
+    async fn test_select_paginated_academic_papers(pool: sqlx::PgPool) {
+        let repo = AcademicPaperRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let author_repo = AuthorRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let ada = author_repo
+            .create_author(
+                &mut tx,
+                Author::new(AuthorId::new(), "".into(), "Ada Lovelace".into(), 0),
+            )
+            .await
+            .unwrap();
+        let lora = insert_paper(&mut tx, "LoRA: Low-Rank Adaptation", &[&ada]).await;
+        let bert = insert_paper(&mut tx, "BERT", &[]).await;
+        let gpt = insert_paper(&mut tx, "Language Models are Few-Shot Learners", &[]).await;
+        for (paper_id, published_date, citations, status, category) in [
+            (lora, "2021-06-17", 100, "completed", "cs.CL"),
+            (bert, "2018-10-11", 300, "new", "cs.CL"),
+            (gpt, "2020-05-28", 200, "new", "cs.LG"),
+        ] {
+            sqlx::query(
+                "UPDATE academic_paper SET published_date = $2::DATE, citations_count = $3, status = $4, primary_category = $5 WHERE paper_id = $1",
+            )
+            .bind(paper_id)
+            .bind(published_date)
+            .bind(citations)
+            .bind(status)
+            .bind(category)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        }
+        sqlx::query("UPDATE journal SET name = 'NeurIPS' WHERE journal_id = (SELECT journal_id FROM academic_paper WHERE paper_id = $1)")
+            .bind(gpt)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        let task_id: Uuid = sqlx::query_scalar("INSERT INTO task (name) VALUES ('Fine-tuning') RETURNING task_id")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO task_paper_relation (task_id, paper_id) VALUES ($1, $2)")
+            .bind(task_id)
+            .bind(lora)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            select_paper_ids(&repo, &mut tx, AcademicPaperListOptions::default()).await,
+            vec![lora, gpt, bert]
+        );
+        assert_eq!(
+            select_paper_ids(
+                &repo,
+                &mut tx,
+                AcademicPaperListOptions {
+                    sort: AcademicPaperSort::Citations,
+                    ..Default::default()
+                }
+            )
+            .await,
+            vec![bert, gpt, lora]
+        );
+        assert_eq!(
+            select_paper_ids(
+                &repo,
+                &mut tx,
+                AcademicPaperListOptions {
+                    order: SortOrder::Asc,
+                    ..Default::default()
+                }
+            )
+            .await,
+            vec![bert, gpt, lora]
+        );
+        assert_eq!(
+            select_paper_ids(
+                &repo,
+                &mut tx,
+                AcademicPaperListOptions {
+                    status: Some(Status::New),
+                    primary_category: Some("cs.CL".into()),
+                    ..Default::default()
+                }
+            )
+            .await,
+            vec![bert]
+        );
+        assert_eq!(
+            select_paper_ids(
+                &repo,
+                &mut tx,
+                AcademicPaperListOptions {
+                    published_from: NaiveDate::from_ymd_opt(2019, 1, 1),
+                    published_to: NaiveDate::from_ymd_opt(2020, 12, 31),
+                    ..Default::default()
+                }
+            )
+            .await,
+            vec![gpt]
+        );
+        assert_eq!(
+            select_paper_ids(
+                &repo,
+                &mut tx,
+                AcademicPaperListOptions {
+                    min_citations: Some(150),
+                    journal: Some("neurips".into()),
+                    ..Default::default()
+                }
+            )
+            .await,
+            vec![gpt]
+        );
+        assert_eq!(
+            select_paper_ids(
+                &repo,
+                &mut tx,
+                AcademicPaperListOptions {
+                    task: Some("fine-tuning".into()),
+                    author: Some("lovelace".into()),
+                    text: Some("low-rank".into()),
+                    ..Default::default()
+                }
+            )
+            .await,
+            vec![lora]
+        );
+
+        // the wildcards of LIKE in the text and the author are matched literally
+        let top_k = insert_paper(&mut tx, "Top_k Sampling at 100% Recall", &[]).await;
+        insert_paper(&mut tx, "TopXk Routing at 1000 Recall", &[]).await;
+        for text in ["top_k", "100%", "_"] {
+            assert_eq!(
+                select_paper_ids(
+                    &repo,
+                    &mut tx,
+                    AcademicPaperListOptions {
+                        text: Some(text.into()),
+                        ..Default::default()
+                    }
+                )
+                .await,
+                vec![top_k],
+                "{}",
+                text
+            );
+        }
+        assert!(select_paper_ids(
+            &repo,
+            &mut tx,
+            AcademicPaperListOptions {
+                author: Some("l_velace".into()),
+                ..Default::default()
+            }
+        )
+        .await
+        .is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_paper_contents(pool: sqlx::PgPool) {
+        let repo = AcademicPaperRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let paper_id = insert_paper(&mut tx, "LoRA", &[]).await;
+        sqlx::query("UPDATE academic_paper SET text = $2 WHERE paper_id = $1")
+            .bind(paper_id)
+            .bind("# Introduction\n\nWe freeze the weights.")
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+
+        // Papers without stored sections get them from the text
+        let mut paper = repo
+            .select_academic_paper_by_id(&mut tx, &paper_id.to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            paper.sections,
+            vec![PaperSection::new(
+                "Introduction".into(),
+                "We freeze the weights.".into()
+            )]
+        );
+        assert!(paper.pdf_sha256.is_empty());
+
+        let sections = vec![
+            PaperSection::new("Introduction".into(), "We freeze the weights.".into()),
+            PaperSection::new("Method".into(), "Low-rank matrices are trained.".into()),
+        ];
+        paper.sections = sections.clone();
+        paper.pdf_sha256 = "a".repeat(64);
+        let updated = repo.update_academic_paper(&mut tx, paper.clone()).await.unwrap();
+        assert_eq!(updated.sections, sections);
+        assert_eq!(updated.pdf_sha256, "a".repeat(64));
+
+        // Updating without sections keeps the stored ones
+        paper.sections = vec![];
+        paper.pdf_sha256 = String::new();
+        let updated = repo.update_academic_paper(&mut tx, paper.clone()).await.unwrap();
+        assert_eq!(updated.sections, sections);
+        assert_eq!(updated.pdf_sha256, "a".repeat(64));
+
+        // Updating keeps the reading status, e.g. when the paper is summarized again
+        sqlx::query("UPDATE academic_paper SET status = 'completed' WHERE paper_id = $1")
+            .bind(paper_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        paper.status = Status::New;
+        let updated = repo.update_academic_paper(&mut tx, paper).await.unwrap();
+        assert_eq!(updated.status, Status::Completed);
+    }
+
+    #[sqlx::test]
+    async fn test_merge_academic_papers(pool: sqlx::PgPool) {
+        let repo = AcademicPaperRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let author_repo = AuthorRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let ada = author_repo
+            .create_author(&mut tx, Author::new(AuthorId::new(), "".into(), "Ada".into(), 0))
+            .await
+            .unwrap();
+        let bob = author_repo
+            .create_author(&mut tx, Author::new(AuthorId::new(), "".into(), "Bob".into(), 0))
+            .await
+            .unwrap();
+        let paper_id = insert_paper(&mut tx, "LoRA: Low-Rank Adaptation", &[&ada]).await;
+        let duplicate_id = insert_paper(&mut tx, "Low-Rank Adaptation of LLMs", &[&ada, &bob]).await;
+        let other_id = insert_paper(&mut tx, "Attention Is All You Need", &[]).await;
+        sqlx::query("UPDATE academic_paper SET arxiv_id = 'http://arxiv.org/abs/2106.09685v1' WHERE paper_id = $1")
+            .bind(paper_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE academic_paper SET arxiv_id = 'http://arxiv.org/abs/2106.09685v2', doi = '10.5555/lora', citations_count = 7 WHERE paper_id = $1")
+            .bind(duplicate_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        let note_id: Uuid =
+            sqlx::query_scalar("INSERT INTO paper_note (note) VALUES ('Read it') RETURNING paper_note_id")
+                .fetch_one(&mut *tx)
+                .await
+                .unwrap();
+        sqlx::query("INSERT INTO paper_note_relation (paper_id, paper_note_id) VALUES ($1, $2)")
+            .bind(duplicate_id)
+            .bind(note_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        let keyword_id: Uuid =
+            sqlx::query_scalar("INSERT INTO keyword (category, name) VALUES ('tag', 'peft') RETURNING keyword_id")
+                .fetch_one(&mut *tx)
+                .await
+                .unwrap();
+        sqlx::query("INSERT INTO paper_keyword_relation (paper_id, keyword_id) VALUES ($1, $2)")
+            .bind(duplicate_id)
+            .bind(keyword_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+
+        // Another version of the arXiv paper is the paper already in the library
+        let existing = repo
+            .create_academic_paper(
+                &mut tx,
+                AcademicPaper {
+                    title: "LoRA v3".into(),
+                    arxiv_id: "2106.09685v3".into(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(Uuid::from(existing.paper_id), paper_id);
+
+        let groups = repo.select_duplicate_academic_papers(&mut tx).await.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0]
+                .papers
+                .iter()
+                .map(|paper| Uuid::from(paper.paper_id))
+                .collect::<Vec<Uuid>>(),
+            vec![paper_id, duplicate_id]
+        );
+        assert_eq!(groups[0].reasons, vec![DuplicateReason::ArxivId]);
+
+        let merged = repo
+            .merge_academic_papers(&mut tx, &paper_id.to_string(), &duplicate_id.to_string())
+            .await
+            .unwrap();
+        assert_eq!(merged.arxiv_id, "http://arxiv.org/abs/2106.09685v1");
+        assert_eq!(merged.doi, "10.5555/lora");
+        assert_eq!(merged.citations_count, 7);
+        let mut authors = merged
+            .authors
+            .iter()
+            .map(|author| author.name.as_str())
+            .collect::<Vec<&str>>();
+        authors.sort();
+        assert_eq!(authors, vec!["Ada", "Bob"]);
+        let note_paper_id: Uuid =
+            sqlx::query_scalar("SELECT paper_id FROM paper_note_relation WHERE paper_note_id = $1")
+                .bind(note_id)
+                .fetch_one(&mut *tx)
+                .await
+                .unwrap();
+        assert_eq!(note_paper_id, paper_id);
+        let tagged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM paper_keyword_relation WHERE paper_id = $1")
+            .bind(paper_id)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(tagged, 1);
+        assert!(repo
+            .select_academic_paper_by_id(&mut tx, &duplicate_id.to_string())
+            .await
+            .is_err());
+
+        // Papers with different identifiers are not merged
+        sqlx::query("UPDATE academic_paper SET doi = '10.5555/attention' WHERE paper_id = $1")
+            .bind(other_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        assert!(repo
+            .merge_academic_papers(&mut tx, &paper_id.to_string(), &other_id.to_string())
+            .await
+            .is_err());
+    }
 }
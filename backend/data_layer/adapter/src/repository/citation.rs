@@ -0,0 +1,288 @@
+use crate::{
+    database::{
+        models::citation::{CitationNodeRecord, CitedReferenceRecord},
+        ConnectionPool,
+    },
+    repository::academic_paper::AcademicPaperRepositoryImpl,
+};
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    models::{
+        academic_paper::AcademicPaper,
+        citation::{CitationEdge, CitationGraph, CitationNode, CitedReference},
+    },
+    repository::{academic_paper::AcademicPaperRepository, citation::CitationRepository},
+};
+use shared::{
+    errors::{AppError, AppResult},
+    id::AcademicPaperId,
+};
+use sqlx::{Postgres as Pg, Transaction as T};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, new)]
+pub struct CitationRepositoryImpl {
+    pub db: ConnectionPool,
+}
+
+#[async_trait]
+impl CitationRepository for CitationRepositoryImpl {
+    async fn save_citation_graph(&self, tx: &mut T<'_, Pg>, graph: &CitationGraph) -> AppResult<()> {
+        // `ON CONFLICT DO UPDATE` can touch each row only once per statement
+        let mut nodes: HashMap<&str, &CitationNode> = HashMap::new();
+        for node in graph.nodes.iter() {
+            nodes.entry(node.ss_id.as_str()).or_insert(node);
+        }
+        let ss_ids = nodes.keys().map(|ss_id| ss_id.to_string()).collect::<Vec<String>>();
+        let titles = ss_ids
+            .iter()
+            .map(|ss_id| nodes[ss_id.as_str()].title.clone())
+            .collect::<Vec<String>>();
+        let years = ss_ids
+            .iter()
+            .map(|ss_id| nodes[ss_id.as_str()].year)
+            .collect::<Vec<Option<i32>>>();
+        sqlx::query!(
+            r#"INSERT INTO citation_placeholder (ss_id, title, year)
+            SELECT * FROM UNNEST($1::VARCHAR[], $2::TEXT[], $3::INT[])
+            ON CONFLICT (ss_id) DO UPDATE SET
+                title = CASE WHEN EXCLUDED.title <> '' THEN EXCLUDED.title ELSE citation_placeholder.title END,
+                year = COALESCE(EXCLUDED.year, citation_placeholder.year)"#,
+            &ss_ids,
+            &titles,
+            &years as &[Option<i32>]
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let (citing, cited): (Vec<String>, Vec<String>) = graph
+            .edges
+            .iter()
+            .map(|edge| (edge.citing_ss_id.clone(), edge.cited_ss_id.clone()))
+            .unzip();
+        sqlx::query!(
+            r#"INSERT INTO paper_citation (citing_ss_id, cited_ss_id)
+            SELECT * FROM UNNEST($1::VARCHAR[], $2::VARCHAR[])
+            ON CONFLICT DO NOTHING"#,
+            &citing,
+            &cited
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        Ok(())
+    }
+
+    async fn select_library_papers_citing(&self, tx: &mut T<'_, Pg>, ss_id: &str) -> AppResult<Vec<AcademicPaper>> {
+        let paper_ids = sqlx::query_scalar!(
+            r#"SELECT DISTINCT a.paper_id AS "paper_id: AcademicPaperId"
+            FROM academic_paper a
+            JOIN paper_citation c ON c.citing_ss_id = a.ss_id
+            WHERE c.cited_ss_id = $1"#,
+            ss_id
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let paper_repository = AcademicPaperRepositoryImpl::new(self.db.clone());
+        let mut papers = vec![];
+        for paper_id in paper_ids.into_iter() {
+            papers.push(
+                paper_repository
+                    .select_academic_paper_by_id(tx, &paper_id.to_string())
+                    .await?,
+            );
+        }
+        papers.sort_by_key(|paper| std::cmp::Reverse(paper.published_date));
+        Ok(papers)
+    }
+
+    async fn select_most_cited_references(&self, tx: &mut T<'_, Pg>, limit: i64) -> AppResult<Vec<CitedReference>> {
+        let records = sqlx::query_as!(
+            CitedReferenceRecord,
+            r#"SELECT
+                p.ss_id,
+                p.title,
+                p.year,
+                (SELECT l.paper_id FROM academic_paper l WHERE l.ss_id = p.ss_id LIMIT 1) AS "paper_id?: AcademicPaperId",
+                COUNT(DISTINCT a.paper_id) AS "cited_by_count!"
+            FROM paper_citation c
+            JOIN academic_paper a ON a.ss_id = c.citing_ss_id
+            JOIN citation_placeholder p ON p.ss_id = c.cited_ss_id
+            GROUP BY p.ss_id
+            ORDER BY "cited_by_count!" DESC, p.ss_id
+            LIMIT $1"#,
+            limit
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        Ok(records.into_iter().map(CitedReference::from).collect())
+    }
+
+    async fn select_citation_neighbourhood(
+        &self,
+        tx: &mut T<'_, Pg>,
+        ss_id: &str,
+        hops: i32,
+        max_nodes: i64,
+    ) -> AppResult<CitationGraph> {
+        // walk the edges in both directions, keeping the nearest `max_nodes` papers
+        let ss_ids = sqlx::query_scalar!(
+            r#"WITH RECURSIVE walk (ss_id, depth) AS (
+                SELECT $1::VARCHAR, 0
+                UNION
+                SELECT
+                    CASE WHEN c.citing_ss_id = w.ss_id THEN c.cited_ss_id ELSE c.citing_ss_id END,
+                    w.depth + 1
+                FROM walk w
+                JOIN paper_citation c ON c.citing_ss_id = w.ss_id OR c.cited_ss_id = w.ss_id
+                WHERE w.depth < $2
+            )
+            SELECT ss_id AS "ss_id!"
+            FROM walk
+            GROUP BY ss_id
+            ORDER BY MIN(depth), ss_id
+            LIMIT $3"#,
+            ss_id,
+            hops,
+            max_nodes
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let records = sqlx::query_as!(
+            CitationNodeRecord,
+            r#"SELECT
+                p.ss_id,
+                p.title,
+                p.year,
+                (SELECT l.paper_id FROM academic_paper l WHERE l.ss_id = p.ss_id LIMIT 1) AS "paper_id?: AcademicPaperId"
+            FROM citation_placeholder p
+            WHERE p.ss_id = ANY($1)"#,
+            &ss_ids
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        let mut nodes = records
+            .into_iter()
+            .map(CitationNode::from)
+            .collect::<Vec<CitationNode>>();
+        nodes.sort_by_key(|node| ss_ids.iter().position(|ss_id| *ss_id == node.ss_id));
+
+        let edges = sqlx::query!(
+            r#"SELECT citing_ss_id, cited_ss_id
+            FROM paper_citation
+            WHERE citing_ss_id = ANY($1) AND cited_ss_id = ANY($1)
+            ORDER BY citing_ss_id, cited_ss_id"#,
+            &ss_ids
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .into_iter()
+        .map(|row| CitationEdge::new(row.citing_ss_id, row.cited_ss_id))
+        .collect();
+
+        Ok(CitationGraph::new(nodes, edges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(ss_id: &str, title: &str) -> CitationNode {
+        CitationNode::new(ss_id.to_string(), title.to_string(), Some(2024), None)
+    }
+
+    fn edge(citing: &str, cited: &str) -> CitationEdge {
+        CitationEdge::new(citing.to_string(), cited.to_string())
+    }
+
+    async fn insert_library_paper(tx: &mut T<'_, Pg>, ss_id: &str) -> AcademicPaperId {
+        let journal_id: uuid::Uuid =
+            sqlx::query_scalar("INSERT INTO journal (name) VALUES ('Test') RETURNING journal_id")
+                .fetch_one(&mut **tx)
+                .await
+                .unwrap();
+        let paper_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO academic_paper (ss_id, journal_id, title) VALUES ($1, $2, $1) RETURNING paper_id",
+        )
+        .bind(ss_id)
+        .bind(journal_id)
+        .fetch_one(&mut **tx)
+        .await
+        .unwrap();
+        AcademicPaperId::from(paper_id)
+    }
+
+    #[sqlx::test]
+    async fn test_citation_graph(pool: sqlx::PgPool) {
+        let repo = CitationRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let paper_a = insert_library_paper(&mut tx, "a").await;
+        let paper_b = insert_library_paper(&mut tx, "b").await;
+
+        // a and b cite x; b also cites y; y cites z
+        let graph = CitationGraph::new(
+            vec![node("a", ""), node("b", "B"), node("x", "X"), node("y", "Y")],
+            vec![edge("a", "x"), edge("b", "x"), edge("b", "y")],
+        );
+        repo.save_citation_graph(&mut tx, &graph).await.unwrap();
+        // saving again keeps the edges and the known titles
+        let graph = CitationGraph::new(
+            vec![node("y", ""), node("z", "Z"), node("b", "B")],
+            vec![edge("y", "z"), edge("b", "y")],
+        );
+        repo.save_citation_graph(&mut tx, &graph).await.unwrap();
+
+        let citing = repo.select_library_papers_citing(&mut tx, "x").await.unwrap();
+        let mut citing_ids = citing.iter().map(|paper| paper.paper_id).collect::<Vec<_>>();
+        citing_ids.sort_by_key(|id| id.to_string());
+        let mut expected = vec![paper_a, paper_b];
+        expected.sort_by_key(|id| id.to_string());
+        assert_eq!(citing_ids, expected);
+        assert!(repo
+            .select_library_papers_citing(&mut tx, "z")
+            .await
+            .unwrap()
+            .is_empty());
+
+        let most_cited = repo.select_most_cited_references(&mut tx, 10).await.unwrap();
+        assert_eq!(
+            most_cited
+                .iter()
+                .map(|reference| (reference.node.ss_id.as_str(), reference.cited_by_count))
+                .collect::<Vec<_>>(),
+            vec![("x", 2), ("y", 1)]
+        );
+        assert_eq!(most_cited[1].node.title, "Y");
+
+        let neighbourhood = repo.select_citation_neighbourhood(&mut tx, "a", 1, 10).await.unwrap();
+        assert_eq!(
+            neighbourhood.nodes.iter().map(|n| n.ss_id.as_str()).collect::<Vec<_>>(),
+            vec!["a", "x"]
+        );
+        assert_eq!(neighbourhood.nodes[0].paper_id, Some(paper_a));
+        assert_eq!(neighbourhood.edges, vec![edge("a", "x")]);
+
+        let neighbourhood = repo.select_citation_neighbourhood(&mut tx, "a", 3, 10).await.unwrap();
+        assert_eq!(
+            neighbourhood.nodes.iter().map(|n| n.ss_id.as_str()).collect::<Vec<_>>(),
+            vec!["a", "x", "b", "y"]
+        );
+        assert_eq!(neighbourhood.edges.len(), 3);
+
+        let capped = repo.select_citation_neighbourhood(&mut tx, "a", 3, 3).await.unwrap();
+        assert_eq!(
+            capped.nodes.iter().map(|n| n.ss_id.as_str()).collect::<Vec<_>>(),
+            vec!["a", "x", "b"]
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use crate::database::{models::keyword::KeywordRecord, ConnectionPool};
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    models::keyword::{normalize_keyword_name, Keyword, KeywordCategory},
+    repository::keyword::KeywordRepository,
+};
+use shared::{
+    errors::{AppError, AppResult},
+    id::{AcademicPaperId, KeywordId},
+};
+use sqlx::{types::Uuid, Postgres as Pg, Transaction as T};
+
+#[derive(Debug, Clone, new)]
+pub struct KeywordRepositoryImpl {
+    pub db: ConnectionPool,
+}
+
+impl KeywordRepositoryImpl {
+    async fn select_keyword_by_id(&self, tx: &mut T<'_, Pg>, keyword_id: KeywordId) -> AppResult<Keyword> {
+        let record = sqlx::query_as!(
+            KeywordRecord,
+            r#"SELECT
+                k.keyword_id AS "keyword_id: KeywordId",
+                k.category,
+                k.name,
+                COUNT(r.paper_id) AS "paper_count!",
+                k.created_at AS "created_at!"
+            FROM keyword AS k
+            LEFT JOIN paper_keyword_relation AS r ON r.keyword_id = k.keyword_id
+            WHERE k.keyword_id = $1
+            GROUP BY k.keyword_id"#,
+            Uuid::from(keyword_id)
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .ok_or_else(|| AppError::EntityNotFound(format!("Keyword not found: {}", keyword_id)))?;
+
+        Ok(Keyword::from(record))
+    }
+}
+
+fn normalized_name(name: &str) -> AppResult<String> {
+    normalize_keyword_name(name).ok_or_else(|| AppError::Unprocessable("The name must not be empty".to_string()))
+}
+
+#[async_trait]
+impl KeywordRepository for KeywordRepositoryImpl {
+    async fn select_keywords(&self, tx: &mut T<'_, Pg>, category: KeywordCategory) -> AppResult<Vec<Keyword>> {
+        let records = sqlx::query_as!(
+            KeywordRecord,
+            r#"SELECT
+                k.keyword_id AS "keyword_id: KeywordId",
+                k.category,
+                k.name,
+                COUNT(r.paper_id) AS "paper_count!",
+                k.created_at AS "created_at!"
+            FROM keyword AS k
+            LEFT JOIN paper_keyword_relation AS r ON r.keyword_id = k.keyword_id
+            WHERE k.category = $1
+            GROUP BY k.keyword_id
+            ORDER BY LOWER(k.name)"#,
+            category.to_string()
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(records.into_iter().map(Keyword::from).collect())
+    }
+
+    async fn select_paper_keywords(&self, tx: &mut T<'_, Pg>, paper_id: AcademicPaperId) -> AppResult<Vec<Keyword>> {
+        let records = sqlx::query_as!(
+            KeywordRecord,
+            r#"SELECT
+                k.keyword_id AS "keyword_id: KeywordId",
+                k.category,
+                k.name,
+                (SELECT COUNT(*) FROM paper_keyword_relation AS c WHERE c.keyword_id = k.keyword_id) AS "paper_count!",
+                k.created_at AS "created_at!"
+            FROM keyword AS k
+            JOIN paper_keyword_relation AS r ON r.keyword_id = k.keyword_id
+            WHERE r.paper_id = $1 AND k.category IN ('tag', 'collection')
+            ORDER BY k.category DESC, LOWER(k.name)"#,
+            Uuid::from(paper_id)
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(records.into_iter().map(Keyword::from).collect())
+    }
+
+    async fn create_keyword(&self, tx: &mut T<'_, Pg>, category: KeywordCategory, name: &str) -> AppResult<Keyword> {
+        let name = normalized_name(name)?;
+        sqlx::query!(
+            r#"INSERT INTO keyword (category, name) VALUES ($1, $2)
+            ON CONFLICT (category, (LOWER(name))) DO NOTHING"#,
+            category.to_string(),
+            name
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        let keyword_id = sqlx::query_scalar!(
+            r#"SELECT keyword_id AS "keyword_id: KeywordId" FROM keyword
+            WHERE category = $1 AND LOWER(name) = LOWER($2)"#,
+            category.to_string(),
+            name
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        self.select_keyword_by_id(tx, keyword_id).await
+    }
+
+    async fn rename_keyword(&self, tx: &mut T<'_, Pg>, keyword_id: KeywordId, name: &str) -> AppResult<Keyword> {
+        let name = normalized_name(name)?;
+        let keyword = self.select_keyword_by_id(tx, keyword_id).await?;
+        let taken = sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                SELECT 1 FROM keyword WHERE category = $1 AND LOWER(name) = LOWER($2) AND keyword_id <> $3
+            ) AS "taken!""#,
+            keyword.category.to_string(),
+            name,
+            Uuid::from(keyword_id)
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        if taken {
+            return Err(AppError::Unprocessable(format!(
+                "A {} named {} already exists",
+                keyword.category, name
+            )));
+        }
+
+        sqlx::query!(
+            r#"UPDATE keyword SET name = $2 WHERE keyword_id = $1"#,
+            Uuid::from(keyword_id),
+            name
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(Keyword { name, ..keyword })
+    }
+
+    async fn delete_keyword(&self, tx: &mut T<'_, Pg>, keyword_id: KeywordId) -> AppResult<()> {
+        let result = sqlx::query!(r#"DELETE FROM keyword WHERE keyword_id = $1"#, Uuid::from(keyword_id))
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(format!("Keyword not found: {}", keyword_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn add_papers_to_keyword(
+        &self,
+        tx: &mut T<'_, Pg>,
+        keyword_id: KeywordId,
+        paper_ids: &[AcademicPaperId],
+    ) -> AppResult<u64> {
+        self.select_keyword_by_id(tx, keyword_id).await?;
+        // the unknown papers are skipped
+        let result = sqlx::query!(
+            r#"INSERT INTO paper_keyword_relation (paper_id, keyword_id)
+            SELECT paper_id, $1 FROM academic_paper WHERE paper_id = ANY($2)
+            ON CONFLICT DO NOTHING"#,
+            Uuid::from(keyword_id),
+            &paper_ids.iter().map(|id| id.raw()).collect::<Vec<Uuid>>()
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn remove_papers_from_keyword(
+        &self,
+        tx: &mut T<'_, Pg>,
+        keyword_id: KeywordId,
+        paper_ids: &[AcademicPaperId],
+    ) -> AppResult<u64> {
+        self.select_keyword_by_id(tx, keyword_id).await?;
+        let result = sqlx::query!(
+            r#"DELETE FROM paper_keyword_relation WHERE keyword_id = $1 AND paper_id = ANY($2)"#,
+            Uuid::from(keyword_id),
+            &paper_ids.iter().map(|id| id.raw()).collect::<Vec<Uuid>>()
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_paper(tx: &mut T<'_, Pg>, title: &str) -> AcademicPaperId {
+        let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ('Test') RETURNING journal_id")
+            .fetch_one(&mut **tx)
+            .await
+            .unwrap();
+        let paper_id: Uuid =
+            sqlx::query_scalar("INSERT INTO academic_paper (journal_id, title) VALUES ($1, $2) RETURNING paper_id")
+                .bind(journal_id)
+                .bind(title)
+                .fetch_one(&mut **tx)
+                .await
+                .unwrap();
+        AcademicPaperId::from(paper_id)
+    }
+
+    #[sqlx::test]
+    async fn test_paper_keywords(pool: sqlx::PgPool) {
+        let repo = KeywordRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let rag = insert_paper(&mut tx, "Retrieval-Augmented Generation").await;
+        let lora = insert_paper(&mut tx, "LoRA").await;
+
+        let tag = repo
+            .create_keyword(&mut tx, KeywordCategory::Tag, " rag ")
+            .await
+            .unwrap();
+        assert_eq!(tag.name, "rag");
+        // The same name, compared case-insensitively, is the same tag
+        let same = repo.create_keyword(&mut tx, KeywordCategory::Tag, "RAG").await.unwrap();
+        assert_eq!(same.keyword_id, tag.keyword_id);
+        // ... but not the same collection
+        let survey = repo
+            .create_keyword(&mut tx, KeywordCategory::Collection, "RAG")
+            .await
+            .unwrap();
+        assert_ne!(survey.keyword_id, tag.keyword_id);
+        assert!(repo.create_keyword(&mut tx, KeywordCategory::Tag, "  ").await.is_err());
+
+        let added = repo
+            .add_papers_to_keyword(&mut tx, survey.keyword_id, &[rag, lora, AcademicPaperId::new()])
+            .await
+            .unwrap();
+        assert_eq!(added, 2);
+        let added = repo
+            .add_papers_to_keyword(&mut tx, survey.keyword_id, &[rag])
+            .await
+            .unwrap();
+        assert_eq!(added, 0);
+        repo.add_papers_to_keyword(&mut tx, tag.keyword_id, &[rag])
+            .await
+            .unwrap();
+
+        let keywords = repo.select_paper_keywords(&mut tx, rag).await.unwrap();
+        assert_eq!(
+            keywords.iter().map(|k| (k.category, k.paper_count)).collect::<Vec<_>>(),
+            vec![(KeywordCategory::Tag, 1), (KeywordCategory::Collection, 2)]
+        );
+
+        let renamed = repo
+            .rename_keyword(&mut tx, survey.keyword_id, "RAG survey")
+            .await
+            .unwrap();
+        assert_eq!(renamed.name, "RAG survey");
+        assert_eq!(renamed.paper_count, 2);
+        let other = repo
+            .create_keyword(&mut tx, KeywordCategory::Collection, "reading group Q3")
+            .await
+            .unwrap();
+        assert!(repo
+            .rename_keyword(&mut tx, other.keyword_id, "rag SURVEY")
+            .await
+            .is_err());
+
+        let removed = repo
+            .remove_papers_from_keyword(&mut tx, survey.keyword_id, &[lora])
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+        let collections = repo
+            .select_keywords(&mut tx, KeywordCategory::Collection)
+            .await
+            .unwrap();
+        assert_eq!(
+            collections
+                .iter()
+                .map(|k| (k.name.as_str(), k.paper_count))
+                .collect::<Vec<_>>(),
+            vec![("RAG survey", 1), ("reading group Q3", 0)]
+        );
+
+        repo.delete_keyword(&mut tx, tag.keyword_id).await.unwrap();
+        assert!(repo
+            .select_keywords(&mut tx, KeywordCategory::Tag)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repo.delete_keyword(&mut tx, tag.keyword_id).await.is_err());
+    }
+}
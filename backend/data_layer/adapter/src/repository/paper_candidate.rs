@@ -0,0 +1,307 @@
+use crate::database::{models::paper_candidate::PaperCandidateRecord, ConnectionPool};
+use async_trait::async_trait;
+use chrono::Utc;
+use derive_new::new;
+use kernel::{
+    models::{
+        list::PaginatedList,
+        paper_candidate::{CandidateStatus, PaperCandidate, PaperCandidateListOptions},
+    },
+    repository::paper_candidate::PaperCandidateRepository,
+};
+use shared::{
+    errors::{AppError, AppResult},
+    id::{AcademicPaperId, PaperCandidateId},
+};
+use sqlx::{Postgres as Pg, Transaction as T};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, new)]
+pub struct PaperCandidateRepositoryImpl {
+    pub db: ConnectionPool,
+}
+
+#[async_trait]
+impl PaperCandidateRepository for PaperCandidateRepositoryImpl {
+    async fn create_paper_candidates(&self, tx: &mut T<'_, Pg>, candidates: &[PaperCandidate]) -> AppResult<()> {
+        for candidate in candidates.iter() {
+            sqlx::query!(
+                r#"INSERT INTO paper_candidate (
+                    candidate_id,
+                    arxiv_id,
+                    title,
+                    abstract_text,
+                    authors,
+                    categories,
+                    pdf_url,
+                    published_date,
+                    score,
+                    matched,
+                    status,
+                    paper_id
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                ON CONFLICT (arxiv_id) DO NOTHING"#,
+                Uuid::from(candidate.candidate_id),
+                candidate.arxiv_id,
+                candidate.title,
+                candidate.abstract_text,
+                &candidate.authors,
+                &candidate.categories,
+                candidate.pdf_url,
+                candidate.published_date.with_timezone(&Utc),
+                candidate.score,
+                &candidate.matched,
+                candidate.status.to_string(),
+                candidate.paper_id.map(Uuid::from)
+            )
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+        }
+        Ok(())
+    }
+
+    async fn select_paper_candidate_by_id(
+        &self,
+        tx: &mut T<'_, Pg>,
+        candidate_id: PaperCandidateId,
+    ) -> AppResult<PaperCandidate> {
+        let record = sqlx::query_as!(
+            PaperCandidateRecord,
+            r#"SELECT
+                candidate_id,
+                arxiv_id,
+                title,
+                abstract_text,
+                authors,
+                categories,
+                pdf_url,
+                published_date,
+                score,
+                matched,
+                status,
+                paper_id AS "paper_id: AcademicPaperId"
+            FROM paper_candidate
+            WHERE candidate_id = $1"#,
+            Uuid::from(candidate_id)
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .ok_or_else(|| AppError::EntityNotFound(format!("Paper candidate not found: {}", candidate_id.to_string())))?;
+        Ok(PaperCandidate::from(record))
+    }
+
+    async fn select_paginated_paper_candidates(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: PaperCandidateListOptions,
+    ) -> AppResult<PaginatedList<PaperCandidate>> {
+        let status = options.status.map(|status| status.to_string());
+        let total_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) FROM paper_candidate WHERE $1::VARCHAR IS NULL OR status = $1"#,
+            status
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .unwrap_or(0);
+
+        let records = sqlx::query_as!(
+            PaperCandidateRecord,
+            r#"SELECT
+                candidate_id,
+                arxiv_id,
+                title,
+                abstract_text,
+                authors,
+                categories,
+                pdf_url,
+                published_date,
+                score,
+                matched,
+                status,
+                paper_id AS "paper_id: AcademicPaperId"
+            FROM paper_candidate
+            WHERE $1::VARCHAR IS NULL OR status = $1
+            ORDER BY score DESC, published_date DESC
+            LIMIT $2 OFFSET $3"#,
+            status,
+            options.limit,
+            options.offset
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(PaginatedList::new(
+            total_count,
+            options.limit,
+            options.offset,
+            records.into_iter().map(PaperCandidate::from).collect(),
+        ))
+    }
+
+    async fn select_known_arxiv_ids(&self, tx: &mut T<'_, Pg>, arxiv_ids: &[String]) -> AppResult<Vec<String>> {
+        let mut known = sqlx::query_scalar!(
+            r#"SELECT arxiv_id FROM paper_candidate WHERE arxiv_id = ANY($1::TEXT[])"#,
+            arxiv_ids
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        for arxiv_id in self.select_library_arxiv_ids(tx, arxiv_ids).await? {
+            if !known.contains(&arxiv_id) {
+                known.push(arxiv_id);
+            }
+        }
+        Ok(known)
+    }
+
+    async fn select_library_arxiv_ids(&self, tx: &mut T<'_, Pg>, arxiv_ids: &[String]) -> AppResult<Vec<String>> {
+        // `academic_paper.arxiv_id` holds the abs URL or the bare ID, with or without the version,
+        // and papers added by DOI may only have the DOI that arXiv registers for the preprint.
+        let known = sqlx::query_scalar!(
+            r#"SELECT ids.arxiv_id AS "arxiv_id!"
+            FROM UNNEST($1::TEXT[]) AS ids (arxiv_id)
+            WHERE EXISTS (
+                SELECT 1 FROM academic_paper p
+                WHERE p.arxiv_id = ids.arxiv_id
+                    OR p.arxiv_id LIKE '%/' || ids.arxiv_id
+                    OR p.arxiv_id LIKE '%/' || ids.arxiv_id || 'v%'
+                    OR p.arxiv_id LIKE ids.arxiv_id || 'v%'
+                    OR LOWER(p.doi) = LOWER('10.48550/arXiv.' || ids.arxiv_id)
+            )"#,
+            arxiv_ids
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        Ok(known)
+    }
+
+    async fn update_paper_candidate_status(
+        &self,
+        tx: &mut T<'_, Pg>,
+        candidate_id: PaperCandidateId,
+        status: CandidateStatus,
+        paper_id: Option<AcademicPaperId>,
+    ) -> AppResult<()> {
+        let result = sqlx::query!(
+            r#"UPDATE paper_candidate SET status = $2, paper_id = COALESCE($3, paper_id) WHERE candidate_id = $1"#,
+            Uuid::from(candidate_id),
+            status.to_string(),
+            paper_id.map(Uuid::from)
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(format!(
+                "Paper candidate not found: {}",
+                candidate_id.to_string()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Local};
+
+    fn candidate(arxiv_id: &str, score: i32) -> PaperCandidate {
+        PaperCandidate {
+            arxiv_id: arxiv_id.to_string(),
+            title: format!("Paper {}", arxiv_id),
+            authors: vec!["Ada Lovelace".into()],
+            categories: vec!["cs.CL".into(), "cs.LG".into()],
+            pdf_url: format!("https://arxiv.org/pdf/{}", arxiv_id),
+            published_date: Local::now() - Duration::hours(score as i64),
+            score,
+            matched: vec!["LLM".into()],
+            ..Default::default()
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_paper_candidate_crud(pool: sqlx::PgPool) {
+        let repo = PaperCandidateRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+
+        // Create; a second insert of the same arXiv ID is skipped
+        let low = candidate("2510.00001", 1);
+        let high = candidate("2510.00002", 5);
+        repo.create_paper_candidates(&mut tx, &[low.clone(), high.clone()])
+            .await
+            .unwrap();
+        repo.create_paper_candidates(&mut tx, &[candidate("2510.00001", 9)])
+            .await
+            .unwrap();
+
+        // Read
+        let options = PaperCandidateListOptions::new(None, 10, 0);
+        let list = repo.select_paginated_paper_candidates(&mut tx, options).await.unwrap();
+        assert_eq!(list.total, 2);
+        assert_eq!(list.items[0].arxiv_id, "2510.00002");
+        assert_eq!(list.items[1].score, 1);
+        let found = repo
+            .select_paper_candidate_by_id(&mut tx, high.candidate_id)
+            .await
+            .unwrap();
+        assert_eq!(found.authors, high.authors);
+        assert_eq!(found.categories, high.categories);
+        assert_eq!(found.matched, high.matched);
+        assert_eq!(found.status, CandidateStatus::Candidate);
+
+        let known = repo
+            .select_known_arxiv_ids(&mut tx, &["2510.00001".into(), "2510.99999".into()])
+            .await
+            .unwrap();
+        assert_eq!(known, vec!["2510.00001".to_string()]);
+
+        // papers in the library are known whichever form their arXiv ID is stored in
+        let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ('arXiv') RETURNING journal_id")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        for (arxiv_id, doi) in [
+            ("http://arxiv.org/abs/2510.10001v2", ""),
+            ("2510.10002v1", ""),
+            ("", "10.48550/arXiv.2510.10003"),
+        ] {
+            sqlx::query("INSERT INTO academic_paper (journal_id, title, arxiv_id, doi) VALUES ($1, $2, $3, $4)")
+                .bind(journal_id)
+                .bind(format!("Paper {}{}", arxiv_id, doi))
+                .bind(arxiv_id)
+                .bind(doi)
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+        }
+        let arxiv_ids = ["2510.10001", "2510.10002", "2510.10003", "2510.1000", "2510.00002"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>();
+        let mut in_library = repo.select_library_arxiv_ids(&mut tx, &arxiv_ids).await.unwrap();
+        in_library.sort();
+        assert_eq!(in_library, vec!["2510.10001", "2510.10002", "2510.10003"]);
+        let mut known = repo.select_known_arxiv_ids(&mut tx, &arxiv_ids).await.unwrap();
+        known.sort();
+        assert_eq!(known, vec!["2510.00002", "2510.10001", "2510.10002", "2510.10003"]);
+
+        // Update
+        repo.update_paper_candidate_status(&mut tx, low.candidate_id, CandidateStatus::Dismissed, None)
+            .await
+            .unwrap();
+        let options = PaperCandidateListOptions::new(Some(CandidateStatus::Candidate), 10, 0);
+        let list = repo.select_paginated_paper_candidates(&mut tx, options).await.unwrap();
+        assert_eq!(list.total, 1);
+        assert_eq!(list.items[0].candidate_id, high.candidate_id);
+        assert!(repo
+            .update_paper_candidate_status(&mut tx, PaperCandidateId::new(), CandidateStatus::Added, None)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use crate::database::{models::paper_chunk::PaperChunkMatchRecord, ConnectionPool};
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    models::paper_chunk::{PaperChunk, PaperChunkMatch},
+    repository::paper_chunk::PaperChunkRepository,
+};
+use shared::{
+    errors::{AppError, AppResult},
+    id::AcademicPaperId,
+};
+use sqlx::{types::Uuid, Postgres as Pg, Transaction as T};
+
+#[derive(Debug, Clone, new)]
+pub struct PaperChunkRepositoryImpl {
+    pub db: ConnectionPool,
+}
+
+#[async_trait]
+impl PaperChunkRepository for PaperChunkRepositoryImpl {
+    async fn replace_paper_chunks(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        chunks: &[PaperChunk],
+    ) -> AppResult<()> {
+        sqlx::query!(r#"DELETE FROM paper_chunk WHERE paper_id = $1"#, Uuid::from(paper_id))
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+        // Inserted one by one, since the section titles and the embeddings are arrays themselves
+        for chunk in chunks {
+            sqlx::query!(
+                r#"INSERT INTO paper_chunk (paper_id, position, section_titles, content, embedding)
+                VALUES ($1, $2, $3, $4, $5)"#,
+                Uuid::from(paper_id),
+                chunk.position,
+                &chunk.section_titles,
+                chunk.content,
+                chunk.embedding.as_deref()
+            )
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+        }
+        Ok(())
+    }
+
+    async fn select_paper_chunk_matches(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        query: &str,
+    ) -> AppResult<Vec<PaperChunkMatch>> {
+        // The question is matched by any of its words, since a question rarely repeats all of them.
+        // The lexemes are quoted, since those of URLs and the like are no valid tsquery syntax.
+        let matches = sqlx::query_as!(
+            PaperChunkMatchRecord,
+            r#"WITH q AS (
+                SELECT to_tsquery('english', COALESCE(NULLIF(string_agg(quote_literal(lexeme), ' | '), ''), '')) AS query
+                FROM unnest(to_tsvector('english', $2))
+            )
+            SELECT
+                c.paper_id AS "paper_id: AcademicPaperId",
+                c.position,
+                c.section_titles,
+                c.content,
+                c.embedding AS "embedding: Vec<f32>",
+                ts_rank(c.content_tsv, q.query) AS "lexical_rank!"
+            FROM paper_chunk AS c, q
+            WHERE c.paper_id = $1
+            ORDER BY c.position"#,
+            Uuid::from(paper_id),
+            query
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(matches.into_iter().map(PaperChunkMatch::from).collect())
+    }
+
+    async fn select_unindexed_paper_ids(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<AcademicPaperId>> {
+        let ids = sqlx::query_scalar!(
+            r#"SELECT p.paper_id AS "paper_id: AcademicPaperId"
+            FROM academic_paper AS p
+            WHERE (p.text <> '' OR EXISTS (SELECT 1 FROM paper_section AS s WHERE s.paper_id = p.paper_id))
+                AND NOT EXISTS (SELECT 1 FROM paper_chunk AS c WHERE c.paper_id = p.paper_id)
+            ORDER BY p.created_at"#
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_paper_chunk_matches(pool: sqlx::PgPool) {
+        let repo = PaperChunkRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ('arXiv') RETURNING journal_id")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        let paper_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO academic_paper (journal_id, title, text) VALUES ($1, 'LoRA', '# Method') RETURNING paper_id",
+        )
+        .bind(journal_id)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+        let paper_id = AcademicPaperId::from(paper_id);
+        assert_eq!(repo.select_unindexed_paper_ids(&mut tx).await.unwrap(), vec![paper_id]);
+
+        let chunks = vec![
+            PaperChunk::new(
+                paper_id,
+                0,
+                vec!["Introduction".into()],
+                "# Introduction\n\nFine-tuning large models is expensive.".into(),
+                None,
+            ),
+            PaperChunk::new(
+                paper_id,
+                1,
+                vec!["Method".into()],
+                "# Method\n\nWe freeze the pretrained weights and train low-rank matrices.".into(),
+                Some(vec![0.5, 0.5]),
+            ),
+        ];
+        repo.replace_paper_chunks(&mut tx, paper_id, &chunks).await.unwrap();
+        assert!(repo.select_unindexed_paper_ids(&mut tx).await.unwrap().is_empty());
+
+        let matches = repo
+            .select_paper_chunk_matches(&mut tx, paper_id, "Which weights are frozen?")
+            .await
+            .unwrap();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].chunk, chunks[0]);
+        assert_eq!(matches[0].lexical_rank, 0.0);
+        assert_eq!(matches[1].chunk, chunks[1]);
+        assert!(matches[1].lexical_rank > 0.0);
+
+        // The lexemes of a URL with a port are no tsquery syntax on their own
+        let matches = repo
+            .select_paper_chunk_matches(
+                &mut tx,
+                paper_id,
+                "What does http://example.com:8080/a mean for the weights?",
+            )
+            .await
+            .unwrap();
+        assert_eq!(matches[0].lexical_rank, 0.0);
+        assert!(matches[1].lexical_rank > 0.0);
+
+        // A question without any indexed word matches nothing
+        let matches = repo
+            .select_paper_chunk_matches(&mut tx, paper_id, "これは何？")
+            .await
+            .unwrap();
+        assert!(matches.iter().all(|m| m.lexical_rank == 0.0));
+    }
+}
@@ -0,0 +1,347 @@
+use crate::{
+    database::{
+        models::paper_comparison::{PaperComparisonCellRecord, PaperComparisonPaperRecord, PaperComparisonRecord},
+        ConnectionPool,
+    },
+    repository::academic_paper::AcademicPaperRepositoryImpl,
+};
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    llm::LlmProvider,
+    models::{
+        academic_paper::AcademicPaper,
+        language::Language,
+        list::PaginatedList,
+        paper_comparison::{
+            compare_papers, PaperComparison, PaperComparisonListOptions, MAX_COMPARED_PAPERS, MIN_COMPARED_PAPERS,
+        },
+    },
+    repository::{academic_paper::AcademicPaperRepository, paper_comparison::PaperComparisonRepository},
+};
+use shared::{
+    errors::{AppError, AppResult},
+    id::{AcademicPaperId, PaperComparisonId},
+};
+use sqlx::{Postgres as Pg, Transaction as T};
+use std::{collections::HashSet, sync::Arc};
+use uuid::Uuid;
+
+#[derive(new)]
+pub struct PaperComparisonRepositoryImpl {
+    db: ConnectionPool,
+    llm: Arc<dyn LlmProvider>,
+}
+
+impl PaperComparisonRepositoryImpl {
+    async fn insert_paper_comparison(&self, tx: &mut T<'_, Pg>, comparison: &PaperComparison) -> AppResult<()> {
+        let paper_comparison_id = Uuid::from(comparison.paper_comparison_id);
+        sqlx::query!(
+            r#"INSERT INTO paper_comparison (paper_comparison_id, synthesis, language, model, created_at)
+            VALUES ($1, $2, $3, $4, $5)"#,
+            paper_comparison_id,
+            comparison.synthesis,
+            comparison.language.to_string(),
+            comparison.model,
+            comparison.created_at.to_utc()
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        // the papers deleted from the library are sent as the nil UUID
+        sqlx::query!(
+            r#"INSERT INTO paper_comparison_paper (paper_comparison_id, position, paper_id, title)
+            SELECT $1, (p.position - 1)::INTEGER, NULLIF(p.paper_id, '00000000-0000-0000-0000-000000000000'), p.title
+            FROM UNNEST($2::UUID[], $3::TEXT[]) WITH ORDINALITY AS p (paper_id, title, position)"#,
+            paper_comparison_id,
+            &comparison
+                .papers
+                .iter()
+                .map(|paper| paper.paper_id.map(Uuid::from).unwrap_or_else(Uuid::nil))
+                .collect::<Vec<Uuid>>(),
+            &comparison
+                .papers
+                .iter()
+                .map(|paper| paper.title.clone())
+                .collect::<Vec<String>>()
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let mut dimension_positions: Vec<i32> = vec![];
+        let mut dimensions: Vec<String> = vec![];
+        let mut paper_positions: Vec<i32> = vec![];
+        let mut values: Vec<String> = vec![];
+        for (dimension_position, row) in comparison.rows.iter().enumerate() {
+            for (paper_position, value) in row.values.iter().enumerate() {
+                dimension_positions.push(dimension_position as i32);
+                dimensions.push(row.dimension.clone());
+                paper_positions.push(paper_position as i32);
+                values.push(value.clone());
+            }
+        }
+        sqlx::query!(
+            r#"INSERT INTO paper_comparison_cell
+                (paper_comparison_id, dimension_position, dimension, paper_position, value)
+            SELECT $1, c.dimension_position, c.dimension, c.paper_position, c.value
+            FROM UNNEST($2::INTEGER[], $3::TEXT[], $4::INTEGER[], $5::TEXT[])
+                AS c (dimension_position, dimension, paper_position, value)"#,
+            paper_comparison_id,
+            &dimension_positions,
+            &dimensions,
+            &paper_positions,
+            &values
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(())
+    }
+
+    async fn fill_paper_comparison(
+        &self,
+        tx: &mut T<'_, Pg>,
+        record: PaperComparisonRecord,
+    ) -> AppResult<PaperComparison> {
+        let papers = sqlx::query_as!(
+            PaperComparisonPaperRecord,
+            r#"SELECT position, paper_id AS "paper_id: AcademicPaperId", title
+            FROM paper_comparison_paper
+            WHERE paper_comparison_id = $1
+            ORDER BY position"#,
+            Uuid::from(record.paper_comparison_id)
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        let cells = sqlx::query_as!(
+            PaperComparisonCellRecord,
+            r#"SELECT dimension_position, dimension, paper_position, value
+            FROM paper_comparison_cell
+            WHERE paper_comparison_id = $1
+            ORDER BY dimension_position, paper_position"#,
+            Uuid::from(record.paper_comparison_id)
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(record.into_comparison(papers, cells))
+    }
+}
+
+#[async_trait]
+impl PaperComparisonRepository for PaperComparisonRepositoryImpl {
+    async fn compare_papers(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_ids: Vec<AcademicPaperId>,
+        language: Language,
+    ) -> AppResult<PaperComparison> {
+        let mut seen = HashSet::new();
+        if let Some(paper_id) = paper_ids.iter().find(|paper_id| !seen.insert(**paper_id)) {
+            return Err(AppError::Unprocessable(format!(
+                "The paper is given twice: {}",
+                paper_id
+            )));
+        }
+        if !(MIN_COMPARED_PAPERS..=MAX_COMPARED_PAPERS).contains(&paper_ids.len()) {
+            return Err(AppError::Unprocessable(format!(
+                "Between {} and {} papers can be compared: {}",
+                MIN_COMPARED_PAPERS,
+                MAX_COMPARED_PAPERS,
+                paper_ids.len()
+            )));
+        }
+
+        let paper_repository = AcademicPaperRepositoryImpl::new(self.db.clone());
+        let mut papers: Vec<AcademicPaper> = vec![];
+        for paper_id in paper_ids.iter() {
+            papers.push(
+                paper_repository
+                    .select_academic_paper_by_id(tx, &paper_id.to_string())
+                    .await?,
+            );
+        }
+        let comparison = compare_papers(self.llm.as_ref(), &papers, language).await?;
+        self.insert_paper_comparison(tx, &comparison).await?;
+        tracing::info!(
+            "compare_papers: compared {} papers in {} dimensions",
+            comparison.papers.len(),
+            comparison.rows.len()
+        );
+
+        Ok(comparison)
+    }
+
+    async fn select_paper_comparison(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_comparison_id: PaperComparisonId,
+    ) -> AppResult<PaperComparison> {
+        let record = sqlx::query_as!(
+            PaperComparisonRecord,
+            r#"SELECT
+                paper_comparison_id AS "paper_comparison_id: PaperComparisonId",
+                synthesis,
+                language,
+                model,
+                created_at
+            FROM paper_comparison
+            WHERE paper_comparison_id = $1"#,
+            Uuid::from(paper_comparison_id)
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .ok_or_else(|| AppError::EntityNotFound(format!("Paper comparison not found: {}", paper_comparison_id)))?;
+
+        self.fill_paper_comparison(tx, record).await
+    }
+
+    async fn select_paper_comparisons(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: PaperComparisonListOptions,
+    ) -> AppResult<PaginatedList<PaperComparison>> {
+        let total = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM paper_comparison"#)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+        let records = sqlx::query_as!(
+            PaperComparisonRecord,
+            r#"SELECT
+                paper_comparison_id AS "paper_comparison_id: PaperComparisonId",
+                synthesis,
+                language,
+                model,
+                created_at
+            FROM paper_comparison
+            ORDER BY created_at DESC
+            LIMIT $1 OFFSET $2"#,
+            options.limit,
+            options.offset
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let mut comparisons: Vec<PaperComparison> = vec![];
+        for record in records.into_iter() {
+            comparisons.push(self.fill_paper_comparison(tx, record).await?);
+        }
+
+        Ok(PaginatedList::new(total, options.limit, options.offset, comparisons))
+    }
+
+    async fn delete_paper_comparison(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_comparison_id: PaperComparisonId,
+    ) -> AppResult<()> {
+        let result = sqlx::query!(
+            r#"DELETE FROM paper_comparison WHERE paper_comparison_id = $1"#,
+            Uuid::from(paper_comparison_id)
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(format!(
+                "Paper comparison not found: {}",
+                paper_comparison_id
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::llm::canned::CannedProvider;
+
+    async fn insert_paper(tx: &mut T<'_, Pg>, title: &str) -> AcademicPaperId {
+        let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ('Test') RETURNING journal_id")
+            .fetch_one(&mut **tx)
+            .await
+            .unwrap();
+        let paper_id: Uuid =
+            sqlx::query_scalar("INSERT INTO academic_paper (journal_id, title) VALUES ($1, $2) RETURNING paper_id")
+                .bind(journal_id)
+                .bind(title)
+                .fetch_one(&mut **tx)
+                .await
+                .unwrap();
+        AcademicPaperId::from(paper_id)
+    }
+
+    #[sqlx::test]
+    async fn test_paper_comparison(pool: sqlx::PgPool) {
+        let llm = Arc::new(CannedProvider::new(
+            "canned".into(),
+            vec![r#"{
+                "cells": [
+                    {"dimension": "Method", "paper": "P1", "value": "Low-rank update"},
+                    {"dimension": "Method", "paper": "P2", "value": "Bottleneck layers"},
+                    {"dimension": "Cost", "paper": "P2", "value": "Extra latency"}
+                ],
+                "synthesis": "LoRA has no inference latency."
+            }"#
+            .into()],
+        ));
+        let repo = PaperComparisonRepositoryImpl::new(ConnectionPool::new(pool.clone()), llm);
+        let mut tx = pool.begin().await.unwrap();
+        let lora = insert_paper(&mut tx, "LoRA").await;
+        let adapter = insert_paper(&mut tx, "Adapter").await;
+
+        assert!(repo.compare_papers(&mut tx, vec![lora], Language::En).await.is_err());
+        assert!(repo
+            .compare_papers(&mut tx, vec![lora, lora], Language::En)
+            .await
+            .is_err());
+
+        let comparison = repo
+            .compare_papers(&mut tx, vec![lora, adapter], Language::En)
+            .await
+            .unwrap();
+        assert_eq!(comparison.model, "canned");
+        let selected = repo
+            .select_paper_comparison(&mut tx, comparison.paper_comparison_id)
+            .await
+            .unwrap();
+        assert_eq!(selected.papers, comparison.papers);
+        assert_eq!(selected.rows, comparison.rows);
+        assert_eq!(
+            selected.rows[1].values,
+            vec!["-".to_string(), "Extra latency".to_string()]
+        );
+        assert_eq!(selected.language, Language::En);
+
+        // The comparison keeps the title of a deleted paper
+        sqlx::query("DELETE FROM academic_paper WHERE paper_id = $1")
+            .bind(Uuid::from(adapter))
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        let comparisons = repo
+            .select_paper_comparisons(&mut tx, PaperComparisonListOptions::new(10, 0))
+            .await
+            .unwrap();
+        assert_eq!(comparisons.total, 1);
+        assert_eq!(comparisons.items[0].papers[1].paper_id, None);
+        assert_eq!(comparisons.items[0].papers[1].title, "Adapter");
+
+        repo.delete_paper_comparison(&mut tx, comparison.paper_comparison_id)
+            .await
+            .unwrap();
+        assert!(repo
+            .select_paper_comparison(&mut tx, comparison.paper_comparison_id)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    database::{
+        models::paper_metadata::{CitationGainRecord, MetadataChangeRecord, PaperMetadataRecord},
+        ConnectionPool,
+    },
+    repository::academic_paper::JournalRepositoryImpl,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Local, Utc};
+use derive_new::new;
+use kernel::{
+    models::{
+        academic_paper::Journal,
+        paper_metadata::{CitationGain, MetadataChange, PaperMetadata},
+        venue::{VenueType, UNKNOWN_VENUE},
+    },
+    repository::{academic_paper::JournalRepository, paper_metadata::PaperMetadataRepository},
+};
+use shared::{
+    errors::{AppError, AppResult},
+    id::{AcademicPaperId, JournalId},
+};
+use sqlx::{types::Uuid, Postgres as Pg, Transaction as T};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, new)]
+pub struct PaperMetadataRepositoryImpl {
+    pub db: ConnectionPool,
+}
+
+#[async_trait]
+impl PaperMetadataRepository for PaperMetadataRepositoryImpl {
+    async fn select_papers_to_refresh(
+        &self,
+        tx: &mut T<'_, Pg>,
+        refreshed_before: DateTime<Local>,
+        limit: i64,
+    ) -> AppResult<Vec<PaperMetadata>> {
+        let papers = sqlx::query_as!(
+            PaperMetadataRecord,
+            r#"SELECT
+                p.paper_id AS "paper_id: AcademicPaperId",
+                p.ss_id,
+                p.arxiv_id,
+                p.doi,
+                j.name AS journal_name,
+                p.citations_count,
+                p.influential_citation_count,
+                p.references_count
+            FROM academic_paper AS p
+            JOIN journal AS j ON j.journal_id = p.journal_id
+            WHERE (p.ss_id <> '' OR p.arxiv_id <> '' OR p.doi <> '')
+                AND (p.metadata_refreshed_at IS NULL OR p.metadata_refreshed_at < $1)
+            ORDER BY p.metadata_refreshed_at NULLS FIRST, p.paper_id
+            LIMIT $2"#,
+            refreshed_before.with_timezone(&Utc),
+            limit
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(papers.into_iter().map(PaperMetadata::from).collect())
+    }
+
+    async fn update_paper_metadata(
+        &self,
+        tx: &mut T<'_, Pg>,
+        current: &PaperMetadata,
+        updated: &PaperMetadata,
+    ) -> AppResult<Vec<MetadataChange>> {
+        let changes = current.changes(updated);
+        let journal = if updated.journal_name != current.journal_name {
+            let journal = JournalRepositoryImpl::new(self.db.clone())
+                .create_journal(tx, Journal::new(JournalId::new(), updated.journal_name.clone()))
+                .await?;
+            Some(journal)
+        } else {
+            None
+        };
+        let journal_id = journal.as_ref().map(|journal| Uuid::from(journal.journal_id));
+        // a preprint published at a venue is dated by its publication date until the venue year is known
+        let is_published = journal
+            .as_ref()
+            .is_some_and(|journal| journal.venue_type != Some(VenueType::Preprint) && journal.name != UNKNOWN_VENUE);
+        sqlx::query!(
+            r#"UPDATE academic_paper SET
+                ss_id = $2,
+                doi = $3,
+                journal_id = COALESCE($4, journal_id),
+                citations_count = $5,
+                influential_citation_count = $6,
+                references_count = $7,
+                venue_year = CASE
+                    WHEN $8 AND EXTRACT(YEAR FROM published_date) > 1970
+                    THEN COALESCE(venue_year, EXTRACT(YEAR FROM published_date)::INTEGER)
+                    ELSE venue_year
+                END,
+                metadata_refreshed_at = CURRENT_TIMESTAMP(3)
+            WHERE paper_id = $1"#,
+            Uuid::from(current.paper_id),
+            updated.ss_id,
+            updated.doi,
+            journal_id,
+            updated.citations_count,
+            updated.influential_citation_count,
+            updated.references_count,
+            is_published
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        if changes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut fields = vec![];
+        let mut old_values = vec![];
+        let mut new_values = vec![];
+        for (field, old_value, new_value) in changes.into_iter() {
+            fields.push(field.to_string());
+            old_values.push(old_value);
+            new_values.push(new_value);
+        }
+        let changes = sqlx::query_as!(
+            MetadataChangeRecord,
+            r#"INSERT INTO paper_metadata_history (paper_id, field, old_value, new_value)
+            SELECT $1, * FROM UNNEST($2::VARCHAR[], $3::TEXT[], $4::TEXT[])
+            RETURNING paper_id AS "paper_id: AcademicPaperId", field, old_value, new_value, changed_at"#,
+            Uuid::from(current.paper_id),
+            &fields,
+            &old_values,
+            &new_values
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(changes.into_iter().map(MetadataChange::from).collect())
+    }
+
+    async fn select_metadata_history(&self, tx: &mut T<'_, Pg>, paper_id: &str) -> AppResult<Vec<MetadataChange>> {
+        let changes = sqlx::query_as!(
+            MetadataChangeRecord,
+            r#"SELECT paper_id AS "paper_id: AcademicPaperId", field, old_value, new_value, changed_at
+            FROM paper_metadata_history
+            WHERE paper_id = $1
+            ORDER BY changed_at DESC, field"#,
+            Uuid::from_str(paper_id)?
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(changes.into_iter().map(MetadataChange::from).collect())
+    }
+
+    async fn select_citation_gains(
+        &self,
+        tx: &mut T<'_, Pg>,
+        since: DateTime<Local>,
+        limit: i64,
+    ) -> AppResult<Vec<CitationGain>> {
+        let gains = sqlx::query_as!(
+            CitationGainRecord,
+            r#"SELECT
+                p.paper_id AS "paper_id: AcademicPaperId",
+                p.title,
+                p.citations_count,
+                SUM(h.new_value::BIGINT - h.old_value::BIGINT)::BIGINT AS "citations_gained!"
+            FROM paper_metadata_history AS h
+            JOIN academic_paper AS p ON p.paper_id = h.paper_id
+            WHERE h.field = 'citations_count' AND h.changed_at >= $1
+            GROUP BY p.paper_id, p.title, p.citations_count
+            ORDER BY 4 DESC, p.citations_count DESC
+            LIMIT $2"#,
+            since.with_timezone(&Utc),
+            limit
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(gains.into_iter().map(CitationGain::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_paper_metadata_history(pool: sqlx::PgPool) {
+        let repo = PaperMetadataRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ('arXiv') RETURNING journal_id")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO academic_paper (journal_id, title, arxiv_id, citations_count) VALUES ($1, 'LoRA', 'http://arxiv.org/abs/2106.09685v2', 10), ($1, 'No IDs', '', 0)",
+        )
+        .bind(journal_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+        // Only the papers with an ID to look up are refreshed
+        let papers = repo.select_papers_to_refresh(&mut tx, Local::now(), 10).await.unwrap();
+        assert_eq!(papers.len(), 1);
+        let current = papers[0].clone();
+        assert_eq!(current.journal_name, "arXiv");
+
+        let updated = PaperMetadata {
+            ss_id: "abc".into(),
+            // the crawler normalizes the venue names it fetches
+            journal_name: "ICLR".into(),
+            citations_count: 15,
+            ..current.clone()
+        };
+        let changes = repo.update_paper_metadata(&mut tx, &current, &updated).await.unwrap();
+        assert_eq!(changes.len(), 3);
+        assert!(repo
+            .select_papers_to_refresh(&mut tx, Local::now() - chrono::Duration::hours(1), 10)
+            .await
+            .unwrap()
+            .is_empty());
+        let refreshed = repo
+            .select_papers_to_refresh(&mut tx, Local::now() + chrono::Duration::hours(1), 10)
+            .await
+            .unwrap();
+        assert_eq!(refreshed[0], updated);
+
+        // An unchanged refresh records nothing
+        assert!(repo
+            .update_paper_metadata(&mut tx, &updated, &updated)
+            .await
+            .unwrap()
+            .is_empty());
+        let more = PaperMetadata {
+            citations_count: 18,
+            ..updated.clone()
+        };
+        repo.update_paper_metadata(&mut tx, &updated, &more).await.unwrap();
+
+        let history = repo
+            .select_metadata_history(&mut tx, &current.paper_id.to_string())
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 4);
+        let gains = repo
+            .select_citation_gains(&mut tx, Local::now() - chrono::Duration::days(1), 10)
+            .await
+            .unwrap();
+        assert_eq!(gains.len(), 1);
+        assert_eq!(gains[0].citations_gained, 8);
+        assert_eq!(gains[0].citations_count, 18);
+    }
+}
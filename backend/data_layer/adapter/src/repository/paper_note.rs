@@ -1,28 +1,48 @@
 use crate::{
-    database::{models::paper_note::PaperNoteRecord, ConnectionPool},
-    repository::academic_paper::AcademicPaperRepositoryImpl,
+    database::{
+        models::paper_note::{PaperNoteMessageRecord, PaperNoteRecord},
+        ConnectionPool,
+    },
+    repository::{academic_paper::AcademicPaperRepositoryImpl, paper_chunk::PaperChunkRepositoryImpl},
 };
 use async_trait::async_trait;
 use chrono::Utc;
 use derive_new::new;
 use kernel::{
-    models::paper_note::PaperNote,
-    repository::{academic_paper::AcademicPaperRepository, paper_note::PaperNoteRepository},
-};
-use openai_tools::{
-    chat::request::ChatCompletion,
-    common::{message::Message, role::Role},
+    llm::{request_text, LlmProvider},
+    models::{
+        academic_paper::AcademicPaper,
+        language::Language,
+        paper_chunk::{build_paper_chunks, cited_passages, retrieve_passages, RetrievedPassage},
+        paper_note::{split_conversation, PaperNote, PaperNoteMessage, PaperNoteMessageRole},
+    },
+    repository::{
+        academic_paper::AcademicPaperRepository, paper_chunk::PaperChunkRepository, paper_note::PaperNoteRepository,
+    },
 };
+use openai_tools::common::{message::Message, role::Role};
 use shared::{
-    errors::AppResult,
-    id::{AcademicPaperId, PaperNoteId},
+    errors::{AppError, AppResult},
+    id::{AcademicPaperId, PaperNoteId, PaperNoteMessageId},
 };
 use sqlx::{Postgres as Pg, Transaction as T};
+use std::sync::Arc;
 use uuid::Uuid;
 
+/// The number of passages of the full text included in the prompt of a question.
+const QUESTION_PASSAGES: usize = 5;
+/// The number of the latest messages of the conversation sent as context with a question.
+const HISTORY_MESSAGES: usize = 10;
+
+/// The section titles of a passage, e.g. `Method > Training`.
+fn passage_sections(passage: &RetrievedPassage) -> String {
+    passage.chunk.section_titles.join(" > ")
+}
+
 #[derive(new)]
 pub struct PaperNoteRepositoryImpl {
     db: ConnectionPool,
+    llm: Arc<dyn LlmProvider>,
 }
 
 #[async_trait]
@@ -152,40 +172,174 @@ impl PaperNoteRepository for PaperNoteRepositoryImpl {
         tx: &mut T<'_, Pg>,
         paper_note_id: PaperNoteId,
         query: String,
-    ) -> AppResult<PaperNote> {
-        let note: PaperNoteRecord = sqlx::query_as!(
-            PaperNoteRecord,
+    ) -> AppResult<Vec<PaperNoteMessage>> {
+        let paper = self.select_note_paper(tx, paper_note_id).await?;
+        let history = self.select_paper_note_messages(tx, paper_note_id).await?;
+        let answer = self.answer(tx, &paper, &history, &query).await?;
+
+        let question = self
+            .insert_message(tx, paper_note_id, PaperNoteMessageRole::User, &query, "")
+            .await?;
+        let answer = self
+            .insert_message(
+                tx,
+                paper_note_id,
+                PaperNoteMessageRole::Assistant,
+                &answer,
+                &self.llm.model_id(),
+            )
+            .await?;
+        Ok(vec![question, answer])
+    }
+
+    async fn select_paper_note_messages(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_note_id: PaperNoteId,
+    ) -> AppResult<Vec<PaperNoteMessage>> {
+        let records = sqlx::query_as!(
+            PaperNoteMessageRecord,
             r#"
-            SELECT 
-                pn.paper_note_id,
-                pn.note,
-                pn.note_timestamp,
-                pnr.paper_id
-            FROM paper_note pn
-            INNER JOIN paper_note_relation pnr ON pn.paper_note_id = pnr.paper_note_id
-            WHERE pn.paper_note_id = $1
+            SELECT paper_note_message_id, paper_note_id, position, role, content, model, created_at, updated_at
+            FROM paper_note_message
+            WHERE paper_note_id = $1
+            ORDER BY position
             "#,
             Uuid::from(paper_note_id)
         )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(records.into_iter().map(PaperNoteMessage::from).collect())
+    }
+
+    async fn regenerate_paper_note_message(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_note_message_id: PaperNoteMessageId,
+    ) -> AppResult<PaperNoteMessage> {
+        let paper_note_id = sqlx::query_scalar!(
+            r#"SELECT paper_note_id FROM paper_note_message WHERE paper_note_message_id = $1"#,
+            Uuid::from(paper_note_message_id)
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(PaperNoteId::from)
+        .ok_or_else(|| AppError::EntityNotFound(format!("Paper note message not found: {}", paper_note_message_id)))?;
+
+        let messages = self.select_paper_note_messages(tx, paper_note_id).await?;
+        let (history, question) = split_conversation(&messages, paper_note_message_id).ok_or_else(|| {
+            AppError::Unprocessable(format!("Not an answer to a question: {}", paper_note_message_id))
+        })?;
+        let paper = self.select_note_paper(tx, paper_note_id).await?;
+        let answer = self.answer(tx, &paper, history, &question.content).await?;
+
+        let record = sqlx::query_as!(
+            PaperNoteMessageRecord,
+            r#"
+            UPDATE paper_note_message
+            SET content = $2, model = $3
+            WHERE paper_note_message_id = $1
+            RETURNING paper_note_message_id, paper_note_id, position, role, content, model, created_at, updated_at
+            "#,
+            Uuid::from(paper_note_message_id),
+            answer,
+            self.llm.model_id()
+        )
         .fetch_one(&mut **tx)
         .await?;
 
-        let paper_id = sqlx::query!(
+        Ok(PaperNoteMessage::from(record))
+    }
+
+    async fn delete_paper_note_message(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_note_message_id: PaperNoteMessageId,
+    ) -> AppResult<()> {
+        let result = sqlx::query!(
+            r#"DELETE FROM paper_note_message WHERE paper_note_message_id = $1"#,
+            Uuid::from(paper_note_message_id)
+        )
+        .execute(&mut **tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(format!(
+                "Paper note message not found: {}",
+                paper_note_message_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl PaperNoteRepositoryImpl {
+    /// The paper the note is about.
+    async fn select_note_paper(&self, tx: &mut T<'_, Pg>, paper_note_id: PaperNoteId) -> AppResult<AcademicPaper> {
+        let paper_id = sqlx::query_scalar!(
+            r#"SELECT paper_id FROM paper_note_relation WHERE paper_note_id = $1"#,
+            Uuid::from(paper_note_id)
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound(format!("Paper note not found: {}", paper_note_id)))?;
+
+        AcademicPaperRepositoryImpl::new(self.db.clone())
+            .select_academic_paper_by_id(tx, &paper_id.to_string())
+            .await
+    }
+
+    /// Appends a message to the end of the conversation.
+    async fn insert_message(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_note_id: PaperNoteId,
+        role: PaperNoteMessageRole,
+        content: &str,
+        model: &str,
+    ) -> AppResult<PaperNoteMessage> {
+        let record = sqlx::query_as!(
+            PaperNoteMessageRecord,
             r#"
-            SELECT paper_id
-            FROM paper_note_relation
+            INSERT INTO paper_note_message (paper_note_id, position, role, content, model)
+            SELECT $1, COALESCE(MAX(position) + 1, 0), $2, $3, $4
+            FROM paper_note_message
             WHERE paper_note_id = $1
+            RETURNING paper_note_message_id, paper_note_id, position, role, content, model, created_at, updated_at
             "#,
-            Uuid::from(paper_note_id)
+            Uuid::from(paper_note_id),
+            role.to_string(),
+            content,
+            model
         )
         .fetch_one(&mut **tx)
         .await?;
 
-        let paper = AcademicPaperRepositoryImpl::new(self.db.clone())
-            .select_academic_paper_by_id(tx, &paper_id.paper_id.to_string())
-            .await?;
+        Ok(PaperNoteMessage::from(record))
+    }
 
-        let model_id = std::env::var("OPENAI_MODEL_ID").expect("OPENAI_MODEL_ID must be set");
+    /// Asks the agent `query` about `paper`, following the turns in `history`,
+    /// and returns the answer with the sections it drew from.
+    async fn answer(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper: &AcademicPaper,
+        history: &[PaperNoteMessage],
+        query: &str,
+    ) -> AppResult<String> {
+        let passages = self.retrieve_passages(tx, paper, query).await?;
+        let body = if passages.is_empty() {
+            "(本文はありません)".to_string()
+        } else {
+            passages
+                .iter()
+                .enumerate()
+                .map(|(i, passage)| format!("[{}] ({})\n{}", i + 1, passage_sections(passage), passage.chunk.content))
+                .collect::<Vec<String>>()
+                .join("\n\n")
+        };
+
+        let analysis = paper.translation(Language::from_env()).cloned().unwrap_or_default();
         let system_prompt = include_str!("../resources/paper_note/system_prompt.md");
         let user_prompt = format!(
             r#"# 論文:
@@ -204,60 +358,215 @@ impl PaperNoteRepository for PaperNoteRepositoryImpl {
 {}
 ### 研究の利点・限界・今後の課題
 {}
+## 本文の抜粋
+{}
 -------------------
 
 # 質問
 {}
+
+本文の抜粋に基づく記述には，根拠とした抜粋の番号を [1] のように付けてください．
 "#,
             paper.title,
             paper.url,
             paper.abstract_text,
-            paper.background_and_purpose,
-            paper.methodology,
-            paper.dataset,
-            paper.results,
-            paper.advantages_limitations_and_future_work,
+            analysis.background_and_purpose,
+            analysis.methodology,
+            analysis.dataset,
+            analysis.results,
+            analysis.advantages_limitations_and_future_work,
+            body,
             query
         );
-        let mut chat_completion = ChatCompletion::new();
-        chat_completion
-            .model_id(model_id)
-            .temperature(1.0)
-            .add_message(Message::from_string(Role::System, system_prompt))
-            .add_message(Message::from_string(Role::User, user_prompt));
-
-        let response = chat_completion.chat().await?;
-        let response_text = match response.choices.get(0) {
-            Some(c) => match c.message.content.clone() {
-                Some(content) => content.text.unwrap_or("No Response from agent.".into()),
-                None => "No Response from agent.".into(),
-            },
-            None => "No Response from agent.".into(),
+        let mut messages = vec![Message::from_string(Role::System, system_prompt)];
+        // The earlier turns are sent as they are; the paper is given once, with the current question
+        for message in history.iter().skip(history.len().saturating_sub(HISTORY_MESSAGES)) {
+            let role = match message.role {
+                PaperNoteMessageRole::User => Role::User,
+                PaperNoteMessageRole::Assistant => Role::Assistant,
+            };
+            messages.push(Message::from_string(role, message.content.clone()));
+        }
+        messages.push(Message::from_string(Role::User, user_prompt));
+        let response_text = request_text(self.llm.as_ref(), messages).await?;
+
+        // The sections the answer drew from; all the retrieved ones when the answer cites none
+        let mut cited = cited_passages(&response_text, &passages);
+        if cited.is_empty() {
+            cited = passages.iter().collect();
+        }
+        let mut cited_sections = cited.into_iter().map(passage_sections).collect::<Vec<String>>();
+        cited_sections.dedup();
+        if cited_sections.is_empty() {
+            return Ok(response_text);
+        }
+        Ok(format!(
+            "{}\n\n参照したセクション:\n{}",
+            response_text.trim_end(),
+            cited_sections
+                .iter()
+                .map(|s| format!("- {}", s))
+                .collect::<Vec<String>>()
+                .join("\n")
+        ))
+    }
+
+    /// The passages of the full text of `paper` most relevant to `query`.
+    /// The passages are indexed on the first question, since most papers are never asked about.
+    async fn retrieve_passages(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper: &AcademicPaper,
+        query: &str,
+    ) -> AppResult<Vec<RetrievedPassage>> {
+        let chunk_repository = PaperChunkRepositoryImpl::new(self.db.clone());
+        let mut matches = chunk_repository
+            .select_paper_chunk_matches(tx, paper.paper_id, query)
+            .await?;
+        if matches.is_empty() {
+            let chunks = build_paper_chunks(paper, self.llm.as_ref()).await?;
+            if chunks.is_empty() {
+                return Ok(vec![]);
+            }
+            chunk_repository
+                .replace_paper_chunks(tx, paper.paper_id, &chunks)
+                .await?;
+            matches = chunk_repository
+                .select_paper_chunk_matches(tx, paper.paper_id, query)
+                .await?;
+        }
+
+        // Without an embedding of the question, the passages are retrieved by keywords only
+        let query_embedding = if matches.iter().any(|m| m.chunk.embedding.is_some()) {
+            match self.llm.embed(vec![query.to_string()]).await {
+                Ok(mut embeddings) => embeddings.pop(),
+                Err(e) => {
+                    tracing::warn!("Failed to embed the question: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
         };
+        Ok(retrieve_passages(
+            matches,
+            query_embedding.as_deref(),
+            QUESTION_PASSAGES,
+        ))
+    }
+}
 
-        let new_note = PaperNote {
-            paper_note_id,
-            note: format!(
-                r#"{}<br />
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::llm::canned::CannedProvider;
 
----
+    #[sqlx::test]
+    async fn test_paper_note_messages(pool: sqlx::PgPool) {
+        let llm = Arc::new(CannedProvider::new(
+            "canned".into(),
+            vec![
+                "They are frozen [1].".into(),
+                "Because it is cheap.".into(),
+                "They are not trained.".into(),
+            ],
+        ));
+        let repo = PaperNoteRepositoryImpl::new(ConnectionPool::new(pool.clone()), llm);
+        let mut tx = pool.begin().await.unwrap();
+        let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ('Test') RETURNING journal_id")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        let paper_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO academic_paper (journal_id, title, text) VALUES ($1, 'LoRA', $2) RETURNING paper_id",
+        )
+        .bind(journal_id)
+        .bind("# Method\n\nWe freeze the pretrained weights.")
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+        let note = repo
+            .create_paper_note(
+                &mut tx,
+                PaperNote {
+                    paper: AcademicPaper {
+                        paper_id: AcademicPaperId::from(paper_id),
+                        ..Default::default()
+                    },
+                    note: "memo".into(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
 
-Q: {}
+        let first = repo
+            .ask_to_agent(&mut tx, note.paper_note_id, "Which weights are frozen?".into())
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].role, PaperNoteMessageRole::User);
+        assert_eq!(first[0].content, "Which weights are frozen?");
+        assert_eq!(first[1].role, PaperNoteMessageRole::Assistant);
+        assert_eq!(
+            first[1].content,
+            "They are frozen [1].\n\n参照したセクション:\n- Method"
+        );
+        assert_eq!(first[1].model, "canned");
+        repo.ask_to_agent(&mut tx, note.paper_note_id, "Why?".into())
+            .await
+            .unwrap();
 
------- from Agent -------
+        // The note itself is left as it is
+        let notes = repo.select_paper_note(&mut tx, paper_id.into()).await.unwrap();
+        assert_eq!(notes[0].note, "memo");
 
-{}
-"#,
-                note.note,
-                query,
-                response_text.clone()
-            ),
-            note_timestamp: chrono::Local::now(),
-            paper,
-        };
+        let messages = repo
+            .select_paper_note_messages(&mut tx, note.paper_note_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            messages.iter().map(|m| m.position).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+
+        let regenerated = repo
+            .regenerate_paper_note_message(&mut tx, messages[1].paper_note_message_id)
+            .await
+            .unwrap();
+        assert_eq!(regenerated.position, 1);
+        assert!(regenerated.content.starts_with("They are not trained."));
+        assert!(repo
+            .regenerate_paper_note_message(&mut tx, messages[0].paper_note_message_id)
+            .await
+            .is_err());
+
+        repo.delete_paper_note_message(&mut tx, messages[2].paper_note_message_id)
+            .await
+            .unwrap();
+        // The answer whose question was deleted cannot be regenerated
+        assert!(repo
+            .regenerate_paper_note_message(&mut tx, messages[3].paper_note_message_id)
+            .await
+            .is_err());
+        assert!(repo
+            .delete_paper_note_message(&mut tx, messages[2].paper_note_message_id)
+            .await
+            .is_err());
 
-        let updated_note = self.update_paper_note(tx, new_note).await?;
+        // The next question follows the last message
+        let next = repo
+            .ask_to_agent(&mut tx, note.paper_note_id, "And?".into())
+            .await
+            .unwrap();
+        assert_eq!(next[0].position, 4);
 
-        Ok(updated_note)
+        // Deleting the note deletes its conversation
+        repo.delete_paper_note(&mut tx, note.paper_note_id).await.unwrap();
+        assert!(repo
+            .select_paper_note_messages(&mut tx, note.paper_note_id)
+            .await
+            .unwrap()
+            .is_empty());
     }
 }
@@ -0,0 +1,203 @@
+use crate::database::{
+    models::paper_version::{PaperVersionRecord, VersionedPaperRecord},
+    ConnectionPool,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Local, Utc};
+use derive_new::new;
+use kernel::{
+    models::paper_version::{PaperVersion, VersionedPaper},
+    repository::paper_version::PaperVersionRepository,
+};
+use shared::{
+    errors::{AppError, AppResult},
+    id::AcademicPaperId,
+};
+use sqlx::{types::Uuid, Postgres as Pg, Transaction as T};
+
+#[derive(Debug, Clone, new)]
+pub struct PaperVersionRepositoryImpl {
+    pub db: ConnectionPool,
+}
+
+#[async_trait]
+impl PaperVersionRepository for PaperVersionRepositoryImpl {
+    async fn select_papers_to_check_versions(
+        &self,
+        tx: &mut T<'_, Pg>,
+        checked_before: DateTime<Local>,
+        limit: i64,
+    ) -> AppResult<Vec<VersionedPaper>> {
+        let papers = sqlx::query_as!(
+            VersionedPaperRecord,
+            r#"SELECT
+                p.paper_id AS "paper_id: AcademicPaperId",
+                p.title,
+                p.arxiv_id,
+                (SELECT MAX(v.version) FROM paper_version AS v WHERE v.paper_id = p.paper_id) AS latest_version
+            FROM academic_paper AS p
+            WHERE p.arxiv_id <> ''
+                AND (p.versions_checked_at IS NULL OR p.versions_checked_at < $1)
+            ORDER BY p.versions_checked_at NULLS FIRST, p.paper_id
+            LIMIT $2"#,
+            checked_before.with_timezone(&Utc),
+            limit
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(papers.into_iter().map(VersionedPaper::from).collect())
+    }
+
+    async fn update_versions_checked_at(&self, tx: &mut T<'_, Pg>, paper_ids: &[AcademicPaperId]) -> AppResult<()> {
+        let paper_ids = paper_ids.iter().map(|id| Uuid::from(*id)).collect::<Vec<Uuid>>();
+        sqlx::query!(
+            r#"UPDATE academic_paper SET versions_checked_at = CURRENT_TIMESTAMP(3) WHERE paper_id = ANY($1)"#,
+            &paper_ids
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        Ok(())
+    }
+
+    async fn create_paper_version(&self, tx: &mut T<'_, Pg>, version: PaperVersion) -> AppResult<PaperVersion> {
+        let record = sqlx::query_as!(
+            PaperVersionRecord,
+            r#"INSERT INTO paper_version (
+                paper_id,
+                version,
+                submitted_at,
+                pdf_sha256,
+                changed_sections,
+                change_summary,
+                detected_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (paper_id, version) DO UPDATE SET
+                submitted_at = EXCLUDED.submitted_at,
+                pdf_sha256 = EXCLUDED.pdf_sha256,
+                changed_sections = EXCLUDED.changed_sections,
+                change_summary = EXCLUDED.change_summary,
+                detected_at = EXCLUDED.detected_at
+            RETURNING
+                paper_id AS "paper_id: AcademicPaperId",
+                version,
+                submitted_at,
+                pdf_sha256,
+                changed_sections,
+                change_summary,
+                detected_at"#,
+            Uuid::from(version.paper_id),
+            version.version,
+            version
+                .submitted_at
+                .map(|submitted_at| submitted_at.with_timezone(&Utc)),
+            version.pdf_sha256,
+            &version.changed_sections,
+            version.change_summary,
+            version.detected_at.with_timezone(&Utc)
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(PaperVersion::from(record))
+    }
+
+    async fn select_paper_versions(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+    ) -> AppResult<Vec<PaperVersion>> {
+        let versions = sqlx::query_as!(
+            PaperVersionRecord,
+            r#"SELECT
+                paper_id AS "paper_id: AcademicPaperId",
+                version,
+                submitted_at,
+                pdf_sha256,
+                changed_sections,
+                change_summary,
+                detected_at
+            FROM paper_version
+            WHERE paper_id = $1
+            ORDER BY version DESC"#,
+            Uuid::from(paper_id)
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(versions.into_iter().map(PaperVersion::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_paper_versions(pool: sqlx::PgPool) {
+        let repo = PaperVersionRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ('arXiv') RETURNING journal_id")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO academic_paper (journal_id, title, arxiv_id) VALUES ($1, 'LoRA', 'http://arxiv.org/abs/2106.09685v1'), ($1, 'Not on arXiv', '')",
+        )
+        .bind(journal_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+        // Only the papers on arXiv are checked
+        let papers = repo
+            .select_papers_to_check_versions(&mut tx, Local::now(), 10)
+            .await
+            .unwrap();
+        assert_eq!(papers.len(), 1);
+        let paper = papers[0].clone();
+        assert_eq!(paper.latest_version, None);
+        assert_eq!(paper.known_version(), Some(1));
+
+        for (version, summary) in [(1, ""), (2, "- Added experiments on GLUE")] {
+            repo.create_paper_version(
+                &mut tx,
+                PaperVersion::new(
+                    paper.paper_id,
+                    version,
+                    None,
+                    String::new(),
+                    vec!["Experiments".to_string()],
+                    summary.to_string(),
+                    Local::now(),
+                ),
+            )
+            .await
+            .unwrap();
+        }
+        let versions = repo.select_paper_versions(&mut tx, paper.paper_id).await.unwrap();
+        assert_eq!(
+            versions.iter().map(|version| version.version).collect::<Vec<i32>>(),
+            vec![2, 1]
+        );
+        assert_eq!(versions[0].change_summary, "- Added experiments on GLUE");
+
+        repo.update_versions_checked_at(&mut tx, &[paper.paper_id])
+            .await
+            .unwrap();
+        assert!(repo
+            .select_papers_to_check_versions(&mut tx, Local::now() - chrono::Duration::hours(1), 10)
+            .await
+            .unwrap()
+            .is_empty());
+        let papers = repo
+            .select_papers_to_check_versions(&mut tx, Local::now() + chrono::Duration::hours(1), 10)
+            .await
+            .unwrap();
+        assert_eq!(papers[0].known_version(), Some(2));
+    }
+}
@@ -0,0 +1,313 @@
+use crate::database::{
+    models::reading::{PaperStatusChangeRecord, ReadingQueueItemRecord},
+    ConnectionPool,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDate};
+use derive_new::new;
+use kernel::{
+    models::{
+        academic_paper::Status,
+        list::PaginatedList,
+        reading::{PaperStatusChange, ReadingQueueItem, ReadingQueueListOptions},
+    },
+    repository::reading::ReadingRepository,
+};
+use shared::{
+    errors::{AppError, AppResult},
+    id::AcademicPaperId,
+};
+use sqlx::{types::Uuid, Postgres as Pg, Transaction as T};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, new)]
+pub struct ReadingRepositoryImpl {
+    pub db: ConnectionPool,
+}
+
+#[async_trait]
+impl ReadingRepository for ReadingRepositoryImpl {
+    async fn update_paper_status(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        status: Status,
+    ) -> AppResult<PaperStatusChange> {
+        let current = sqlx::query_scalar!(
+            r#"SELECT status FROM academic_paper WHERE paper_id = $1 FOR UPDATE"#,
+            Uuid::from(paper_id)
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?
+        .ok_or_else(|| AppError::EntityNotFound(format!("Paper not found: {}", paper_id)))?;
+        let current = Status::from_str(&current).map_err(|e| AppError::EnumParseError(e.to_string()))?;
+        if !current.can_transition_to(&status) {
+            return Err(AppError::Unprocessable(format!(
+                "Cannot change the status of {} from {} to {}",
+                paper_id, current, status
+            )));
+        }
+
+        sqlx::query!(
+            r#"UPDATE academic_paper SET status = $2 WHERE paper_id = $1"#,
+            Uuid::from(paper_id),
+            status.to_string()
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        let change = sqlx::query_as!(
+            PaperStatusChangeRecord,
+            r#"INSERT INTO paper_status_history (paper_id, from_status, to_status)
+            VALUES ($1, $2, $3)
+            RETURNING paper_id, from_status, to_status, changed_at"#,
+            Uuid::from(paper_id),
+            current.to_string(),
+            status.to_string()
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+        if status == Status::Completed {
+            self.delete_reading_queue_item(tx, paper_id).await?;
+        }
+
+        Ok(PaperStatusChange::from(change))
+    }
+
+    async fn select_paper_status_history(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+    ) -> AppResult<Vec<PaperStatusChange>> {
+        let records = sqlx::query_as!(
+            PaperStatusChangeRecord,
+            r#"SELECT paper_id, from_status, to_status, changed_at
+            FROM paper_status_history
+            WHERE paper_id = $1
+            ORDER BY changed_at"#,
+            Uuid::from(paper_id)
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(records.into_iter().map(PaperStatusChange::from).collect())
+    }
+
+    async fn select_status_changes(
+        &self,
+        tx: &mut T<'_, Pg>,
+        since: DateTime<Local>,
+        until: DateTime<Local>,
+    ) -> AppResult<Vec<PaperStatusChange>> {
+        let records = sqlx::query_as!(
+            PaperStatusChangeRecord,
+            r#"SELECT h.paper_id, h.from_status, h.to_status, h.changed_at
+            FROM paper_status_history AS h
+            WHERE h.changed_at < $2
+                AND (
+                    h.changed_at >= $1
+                    OR h.changed_at = (
+                        SELECT MAX(p.changed_at)
+                        FROM paper_status_history AS p
+                        WHERE p.paper_id = h.paper_id AND p.changed_at < $1
+                    )
+                )
+            ORDER BY h.paper_id, h.changed_at"#,
+            since.to_utc(),
+            until.to_utc()
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(records.into_iter().map(PaperStatusChange::from).collect())
+    }
+
+    async fn upsert_reading_queue_item(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        priority: i32,
+        due_date: Option<NaiveDate>,
+    ) -> AppResult<ReadingQueueItem> {
+        let record = sqlx::query_as!(
+            ReadingQueueItemRecord,
+            r#"WITH upserted AS (
+                INSERT INTO reading_queue (paper_id, priority, due_date)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (paper_id) DO UPDATE SET priority = EXCLUDED.priority, due_date = EXCLUDED.due_date
+                RETURNING paper_id, priority, due_date, created_at
+            )
+            SELECT
+                q.paper_id AS "paper_id: AcademicPaperId",
+                p.title,
+                p.url,
+                p.status,
+                q.priority,
+                q.due_date,
+                q.created_at
+            FROM upserted AS q
+            INNER JOIN academic_paper AS p ON p.paper_id = q.paper_id"#,
+            Uuid::from(paper_id),
+            priority,
+            due_date
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => {
+                AppError::EntityNotFound(format!("Paper not found: {}", paper_id))
+            }
+            e => AppError::SqlxError(e),
+        })?;
+
+        Ok(ReadingQueueItem::from(record))
+    }
+
+    async fn delete_reading_queue_item(&self, tx: &mut T<'_, Pg>, paper_id: AcademicPaperId) -> AppResult<()> {
+        sqlx::query!(r#"DELETE FROM reading_queue WHERE paper_id = $1"#, Uuid::from(paper_id))
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?;
+        Ok(())
+    }
+
+    async fn select_reading_queue(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: ReadingQueueListOptions,
+    ) -> AppResult<PaginatedList<ReadingQueueItem>> {
+        let total_count = sqlx::query_scalar!(r#"SELECT COUNT(*) FROM reading_queue"#)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(AppError::SqlxError)?
+            .unwrap_or(0);
+
+        let records = sqlx::query_as!(
+            ReadingQueueItemRecord,
+            r#"SELECT
+                q.paper_id AS "paper_id: AcademicPaperId",
+                p.title,
+                p.url,
+                p.status,
+                q.priority,
+                q.due_date,
+                q.created_at
+            FROM reading_queue AS q
+            INNER JOIN academic_paper AS p ON p.paper_id = q.paper_id
+            ORDER BY q.priority DESC, q.due_date ASC NULLS LAST, q.created_at
+            LIMIT $1 OFFSET $2"#,
+            options.limit,
+            options.offset
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        Ok(PaginatedList::new(
+            total_count,
+            options.limit,
+            options.offset,
+            records.into_iter().map(ReadingQueueItem::from).collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_paper(tx: &mut T<'_, Pg>, title: &str) -> AcademicPaperId {
+        let journal_id: Uuid = sqlx::query_scalar("INSERT INTO journal (name) VALUES ('Test') RETURNING journal_id")
+            .fetch_one(&mut **tx)
+            .await
+            .unwrap();
+        let paper_id: Uuid =
+            sqlx::query_scalar("INSERT INTO academic_paper (journal_id, title) VALUES ($1, $2) RETURNING paper_id")
+                .bind(journal_id)
+                .bind(title)
+                .fetch_one(&mut **tx)
+                .await
+                .unwrap();
+        AcademicPaperId::from(paper_id)
+    }
+
+    #[sqlx::test]
+    async fn test_reading_workflow(pool: sqlx::PgPool) {
+        let repo = ReadingRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let mut tx = pool.begin().await.unwrap();
+        let lora = insert_paper(&mut tx, "LoRA").await;
+        let bert = insert_paper(&mut tx, "BERT").await;
+
+        let due = NaiveDate::from_ymd_opt(2025, 10, 31);
+        repo.upsert_reading_queue_item(&mut tx, lora, 0, None).await.unwrap();
+        repo.upsert_reading_queue_item(&mut tx, bert, 0, due).await.unwrap();
+        let queue = repo
+            .select_reading_queue(&mut tx, ReadingQueueListOptions::new(10, 0))
+            .await
+            .unwrap();
+        assert_eq!(queue.total, 2);
+        // The same priority: the paper with a due date comes first
+        assert_eq!(queue.items[0].paper_id, bert);
+        assert_eq!(queue.items[0].due_date, due);
+        let updated = repo.upsert_reading_queue_item(&mut tx, lora, 5, None).await.unwrap();
+        assert_eq!(updated.title, "LoRA");
+        let queue = repo
+            .select_reading_queue(&mut tx, ReadingQueueListOptions::new(10, 0))
+            .await
+            .unwrap();
+        assert_eq!(queue.items[0].paper_id, lora);
+
+        let change = repo.update_paper_status(&mut tx, lora, Status::DeepDive).await.unwrap();
+        assert_eq!(change.from_status, Some(Status::New));
+        assert_eq!(change.to_status, Status::DeepDive);
+        // The same status is not a transition
+        assert!(repo.update_paper_status(&mut tx, lora, Status::DeepDive).await.is_err());
+        repo.update_paper_status(&mut tx, lora, Status::Completed)
+            .await
+            .unwrap();
+        assert!(repo.update_paper_status(&mut tx, lora, Status::New).await.is_err());
+
+        let history = repo.select_paper_status_history(&mut tx, lora).await.unwrap();
+        assert_eq!(
+            history.iter().map(|c| c.to_status.clone()).collect::<Vec<_>>(),
+            vec![Status::DeepDive, Status::Completed]
+        );
+        // The completed paper leaves the queue
+        let queue = repo
+            .select_reading_queue(&mut tx, ReadingQueueListOptions::new(10, 0))
+            .await
+            .unwrap();
+        assert_eq!(queue.items.iter().map(|i| i.paper_id).collect::<Vec<_>>(), vec![bert]);
+
+        let now = Local::now();
+        let changes = repo
+            .select_status_changes(
+                &mut tx,
+                now - chrono::Duration::days(7),
+                now + chrono::Duration::days(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(changes.len(), 2);
+        // Only the last change before the period is included
+        let changes = repo
+            .select_status_changes(
+                &mut tx,
+                now + chrono::Duration::days(1),
+                now + chrono::Duration::days(2),
+            )
+            .await
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].to_status, Status::Completed);
+
+        assert!(repo
+            .upsert_reading_queue_item(&mut tx, AcademicPaperId::new(), 0, None)
+            .await
+            .is_err());
+    }
+}
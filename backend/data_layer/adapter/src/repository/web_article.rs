@@ -1,5 +1,5 @@
 use crate::database::{
-    models::web_article::{PaginatedWebSiteRecord, WebArticleRecord, WebSiteRecord},
+    models::web_article::{PaginatedWebSiteRecord, WebArticleRecord, WebArticleTranslationRecord, WebSiteRecord},
     ConnectionPool,
 };
 use async_trait::async_trait;
@@ -8,7 +8,8 @@ use kernel::{
     models::{
         list::PaginatedList,
         web_article::{
-            Status, WebArticle, WebArticleFilteredOptions, WebArticleListOptions, WebSite, WebSiteListOptions,
+            Status, WebArticle, WebArticleFilteredOptions, WebArticleListOptions, WebArticleTranslation, WebSite,
+            WebSiteListOptions,
         },
     },
     repository::web_article::{WebArticleRepository, WebSiteRepository},
@@ -17,6 +18,7 @@ use shared::{
     errors::{AppError, AppResult},
     id::{WebArticleId, WebSiteId},
 };
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, new)]
@@ -163,6 +165,69 @@ pub struct WebArticleRepositoryImpl {
     db: ConnectionPool,
 }
 
+impl WebArticleRepositoryImpl {
+    /// Converts the records into articles, loading the translations of all of them at once.
+    async fn with_translations(&self, rows: Vec<WebArticleRecord>) -> AppResult<Vec<WebArticle>> {
+        let article_ids = rows.iter().map(|row| row.article_id).collect::<Vec<WebArticleId>>();
+        let translations = sqlx::query_as!(
+            WebArticleTranslationRecord,
+            r#"SELECT
+                article_id,
+                language,
+                summary
+            FROM web_article_translation
+            WHERE article_id = ANY($1::uuid[])
+            ORDER BY created_at"#,
+            &article_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SqlxError)?;
+
+        let mut translations_by_article: HashMap<WebArticleId, Vec<WebArticleTranslation>> = HashMap::new();
+        for translation in translations {
+            translations_by_article
+                .entry(translation.article_id)
+                .or_default()
+                .push(WebArticleTranslation::from(translation));
+        }
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let translations = translations_by_article.remove(&row.article_id).unwrap_or_default();
+                let mut web_article = WebArticle::from(row);
+                web_article.translations = translations;
+                web_article
+            })
+            .collect())
+    }
+
+    async fn upsert_translations(
+        &self,
+        article_id: WebArticleId,
+        translations: &[WebArticleTranslation],
+    ) -> AppResult<()> {
+        for translation in translations {
+            sqlx::query!(
+                r#"INSERT INTO web_article_translation (
+                    article_id,
+                    language,
+                    summary
+                ) VALUES ($1, $2, $3)
+                ON CONFLICT (article_id, language) DO UPDATE SET
+                    summary = EXCLUDED.summary"#,
+                Uuid::from(article_id),
+                translation.language.to_string(),
+                translation.summary,
+            )
+            .execute(self.db.inner_ref())
+            .await
+            .map_err(AppError::SqlxError)?;
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl WebArticleRepository for WebArticleRepositoryImpl {
     async fn create_web_article(&self, web_article: &mut WebArticle) -> AppResult<WebArticle> {
@@ -180,14 +245,15 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 wa.timestamp,
                 wa.text,
                 wa.html,
-                wa.summary,
                 wa.is_new_technology_related,
                 wa.is_new_product_related,
                 wa.is_new_academic_paper_related,
                 wa.is_ai_related,
                 wa.is_security_related,
                 wa.is_it_related,
-                wa.status
+                wa.status,
+                wa.enrichment_status,
+                wa.enrichment_batch_id
             FROM web_article AS wa
             JOIN web_site AS ws ON wa.site_id = ws.site_id
             WHERE wa.url = $1"#,
@@ -197,8 +263,8 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
         .await
         .map_err(|e| shared::errors::AppError::SqlxError(e))?;
 
-        if !existing_article.is_empty() {
-            return Ok(existing_article[0].clone().into());
+        if let Some(existing_article) = self.with_translations(existing_article).await?.pop() {
+            return Ok(existing_article);
         }
 
         // Check if the site exists
@@ -245,16 +311,17 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 text,
                 html,
                 timestamp,
-                summary,
                 is_new_technology_related,
                 is_new_product_related,
                 is_new_academic_paper_related,
                 is_ai_related,
                 is_security_related,
                 is_it_related,
-                status
+                status,
+                enrichment_status,
+                enrichment_batch_id
             ) VALUES
-                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
             RETURNING article_id"#,
             Uuid::from(web_article.site.site_id),
             Uuid::from(web_article.article_id),
@@ -264,28 +331,31 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
             web_article.text,
             web_article.html,
             web_article.timestamp.naive_utc().date(),
-            web_article.summary,
             web_article.is_new_technology_related,
             web_article.is_new_product_related,
             web_article.is_new_academic_paper_related,
             web_article.is_ai_related,
             web_article.is_security_related,
             web_article.is_it_related,
-            web_article.status.to_string()
+            web_article.status.to_string(),
+            web_article.enrichment_status.to_string(),
+            web_article.enrichment_batch_id
         )
         .fetch_one(self.db.inner_ref())
         .await
         .map_err(|e| shared::errors::AppError::SqlxError(e))?;
+        let article_id = WebArticleId::from(res.article_id);
+        self.upsert_translations(article_id, &web_article.translations).await?;
         Ok(WebArticle::new(
             web_article.site.clone(),
-            WebArticleId::from(res.article_id),
+            article_id,
             web_article.title.clone(),
             web_article.description.clone(),
             web_article.url.clone(),
             web_article.text.clone(),
             web_article.html.clone(),
             web_article.timestamp,
-            web_article.summary.clone(),
+            web_article.translations.clone(),
             web_article.is_new_technology_related,
             web_article.is_new_product_related,
             web_article.is_new_academic_paper_related,
@@ -293,6 +363,8 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
             web_article.is_security_related,
             web_article.is_it_related,
             web_article.status.clone(),
+            web_article.enrichment_status,
+            web_article.enrichment_batch_id.clone(),
         ))
     }
     async fn select_todays_web_articles(&self) -> AppResult<Vec<WebArticle>> {
@@ -310,18 +382,19 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 wa.timestamp,
                 wa.text,
                 wa.html,
-                wa.summary,
                 wa.is_new_technology_related,
                 wa.is_new_product_related,
                 wa.is_new_academic_paper_related,
                 wa.is_ai_related,
                 wa.is_security_related,
                 wa.is_it_related,
-                wa.status
+                wa.status,
+                wa.enrichment_status,
+                wa.enrichment_batch_id
             FROM 
                 web_article as wa
             JOIN web_site as ws ON wa.site_id = ws.site_id
-            WHERE DATE(wa.timestamp) = DATE($1)
+            WHERE DATE(wa.timestamp) = DATE($1) AND wa.enrichment_status <> 'pending'
             ORDER BY wa.timestamp DESC"#,
             today.with_timezone(&chrono::Utc)
         )
@@ -330,7 +403,7 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
         .map_err(|e| shared::errors::AppError::SqlxError(e))
         .unwrap();
 
-        let web_articles: Vec<WebArticle> = rows.into_iter().map(WebArticle::from).collect();
+        let web_articles = self.with_translations(rows).await?;
         if web_articles.is_empty() {
             return Err(AppError::RecordNotFound(sqlx::Error::RowNotFound));
         }
@@ -352,14 +425,15 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 wa.timestamp,
                 wa.text,
                 wa.html,
-                wa.summary,
                 wa.is_new_technology_related,
                 wa.is_new_product_related,
                 wa.is_new_academic_paper_related,
                 wa.is_ai_related,
                 wa.is_security_related,
                 wa.is_it_related,
-                wa.status
+                wa.status,
+                wa.enrichment_status,
+                wa.enrichment_batch_id
             FROM 
                 web_article AS wa
             JOIN web_site AS ws ON wa.site_id = ws.site_id
@@ -370,12 +444,10 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
         .fetch_all(self.db.inner_ref())
         .await
         .map_err(|e| shared::errors::AppError::SqlxError(e))?;
-        if rows.is_empty() {
-            Err(AppError::RecordNotFound(sqlx::Error::RowNotFound))
-        } else {
-            let row = rows.first().unwrap();
-            Ok(WebArticle::from(row.clone()))
-        }
+        self.with_translations(rows)
+            .await?
+            .pop()
+            .ok_or(AppError::RecordNotFound(sqlx::Error::RowNotFound))
     }
     async fn select_web_articles_by_keyword(&self, keyword: &str) -> AppResult<Vec<WebArticle>> {
         let rows = sqlx::query_as!(
@@ -391,25 +463,31 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 wa.timestamp,
                 wa.text,
                 wa.html,
-                wa.summary,
                 wa.is_new_technology_related,
                 wa.is_new_product_related,
                 wa.is_new_academic_paper_related,
                 wa.is_ai_related,
                 wa.is_security_related,
                 wa.is_it_related,
-                wa.status
+                wa.status,
+                wa.enrichment_status,
+                wa.enrichment_batch_id
             FROM 
                 web_article AS wa
             JOIN web_site AS ws ON wa.site_id = ws.site_id
-            WHERE wa.title LIKE $1 OR wa.description LIKE $1 OR wa.summary LIKE $1
+            WHERE wa.title LIKE $1
+                OR wa.description LIKE $1
+                OR EXISTS (
+                    SELECT 1 FROM web_article_translation AS wat
+                    WHERE wat.article_id = wa.article_id AND wat.summary LIKE $1
+                )
             ORDER BY wa.timestamp DESC"#,
             format!("%{}%", keyword)
         )
         .fetch_all(self.db.inner_ref())
         .await
         .map_err(|e| shared::errors::AppError::SqlxError(e))?;
-        Ok(rows.into_iter().map(WebArticle::from).collect())
+        self.with_translations(rows).await
     }
 
     async fn select_web_article_by_url(&self, url: &str) -> AppResult<WebArticle> {
@@ -426,14 +504,15 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 wa.timestamp,
                 wa.text,
                 wa.html,
-                wa.summary,
                 wa.is_new_technology_related,
                 wa.is_new_product_related,
                 wa.is_new_academic_paper_related,
                 wa.is_ai_related,
                 wa.is_security_related,
                 wa.is_it_related,
-                wa.status
+                wa.status,
+                wa.enrichment_status,
+                wa.enrichment_batch_id
             FROM 
                 web_article AS wa
             JOIN web_site AS ws ON wa.site_id = ws.site_id
@@ -444,12 +523,10 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
         .fetch_all(self.db.inner_ref())
         .await
         .map_err(|e| shared::errors::AppError::SqlxError(e))?;
-        if rows.is_empty() {
-            Err(AppError::RecordNotFound(sqlx::Error::RowNotFound))
-        } else {
-            let row = rows.first().unwrap();
-            Ok(WebArticle::from(row.clone()))
-        }
+        self.with_translations(rows)
+            .await?
+            .pop()
+            .ok_or(AppError::RecordNotFound(sqlx::Error::RowNotFound))
     }
     async fn select_or_create_web_article(&self, web_article: WebArticle) -> AppResult<WebArticle> {
         match self.select_web_article_by_url(&web_article.url).await {
@@ -464,7 +541,7 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                     web_article.text,
                     web_article.html,
                     web_article.timestamp,
-                    web_article.summary,
+                    web_article.translations,
                     web_article.is_new_technology_related,
                     web_article.is_new_product_related,
                     web_article.is_new_academic_paper_related,
@@ -472,6 +549,8 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                     web_article.is_security_related,
                     web_article.is_it_related,
                     web_article.status,
+                    web_article.enrichment_status,
+                    web_article.enrichment_batch_id,
                 );
                 self.create_web_article(&mut web_article.clone()).await
             }
@@ -491,14 +570,15 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 wa.timestamp,
                 wa.text,
                 wa.html,
-                wa.summary,
                 wa.is_new_technology_related,
                 wa.is_new_product_related,
                 wa.is_new_academic_paper_related,
                 wa.is_ai_related,
                 wa.is_security_related,
                 wa.is_it_related,
-                wa.status
+                wa.status,
+                wa.enrichment_status,
+                wa.enrichment_batch_id
             FROM 
                 web_article AS wa
             JOIN web_site AS ws ON wa.site_id = ws.site_id
@@ -511,7 +591,44 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
 
         tracing::info!("select_all_web_articles: fetched {} web articles", rows.len());
 
-        Ok(rows.into_iter().map(WebArticle::from).collect())
+        self.with_translations(rows).await
+    }
+
+    async fn select_pending_web_articles(&self) -> AppResult<Vec<WebArticle>> {
+        let rows = sqlx::query_as!(
+            WebArticleRecord,
+            r#"SELECT
+                ws.site_id AS site_id,
+                ws.name AS site_name,
+                ws.url AS site_url,
+                wa.article_id,
+                wa.title,
+                wa.description,
+                wa.url,
+                wa.timestamp,
+                wa.text,
+                wa.html,
+                wa.is_new_technology_related,
+                wa.is_new_product_related,
+                wa.is_new_academic_paper_related,
+                wa.is_ai_related,
+                wa.is_security_related,
+                wa.is_it_related,
+                wa.status,
+                wa.enrichment_status,
+                wa.enrichment_batch_id
+            FROM 
+                web_article AS wa
+            JOIN web_site AS ws ON wa.site_id = ws.site_id
+            WHERE wa.enrichment_status = 'pending'
+            ORDER BY wa.timestamp DESC
+            "#
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(|e| shared::errors::AppError::SqlxError(e))?;
+
+        self.with_translations(rows).await
     }
 
     async fn select_paginated_web_articles(
@@ -533,17 +650,19 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 wa.timestamp,
                 wa.text,
                 wa.html,
-                wa.summary,
                 wa.is_new_technology_related,
                 wa.is_new_product_related,
                 wa.is_new_academic_paper_related,
                 wa.is_ai_related,
                 wa.is_security_related,
                 wa.is_it_related,
-                wa.status
+                wa.status,
+                wa.enrichment_status,
+                wa.enrichment_batch_id
             FROM 
                 web_article AS wa
             JOIN web_site AS ws ON wa.site_id = ws.site_id
+            WHERE wa.enrichment_status <> 'pending'
             ORDER BY wa.timestamp DESC
             LIMIT $1
             OFFSET $2"#,
@@ -555,7 +674,7 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
         .map_err(|e| shared::errors::AppError::SqlxError(e))?;
 
         let total = rows.len() as i64;
-        let items = rows.into_iter().map(WebArticle::from).collect::<Vec<WebArticle>>();
+        let items = self.with_translations(rows).await?;
 
         tracing::info!("select_paginated_web_articles: fetched {} web articles", items.len());
 
@@ -586,24 +705,25 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 wa.timestamp,
                 wa.text,
                 wa.html,
-                wa.summary,
                 wa.is_new_technology_related,
                 wa.is_new_product_related,
                 wa.is_new_academic_paper_related,
                 wa.is_ai_related,
                 wa.is_security_related,
                 wa.is_it_related,
-                wa.status
+                wa.status,
+                wa.enrichment_status,
+                wa.enrichment_batch_id
             FROM 
                 web_article AS wa
             JOIN web_site AS ws ON wa.site_id = ws.site_id
-            WHERE 1=1
+            WHERE wa.enrichment_status <> 'pending'
             "#,
         );
 
         if let Some(ref kw) = keyword {
             query.push_str(&format!(
-                " AND (wa.title LIKE '%{}%' OR wa.description LIKE '%{}%' OR wa.summary LIKE '%{}%')",
+                " AND (wa.title LIKE '%{}%' OR wa.description LIKE '%{}%' OR EXISTS (SELECT 1 FROM web_article_translation AS wat WHERE wat.article_id = wa.article_id AND wat.summary LIKE '%{}%'))",
                 kw, kw, kw
             ));
         }
@@ -625,7 +745,7 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
             .map_err(|e| shared::errors::AppError::SqlxError(e))?;
 
         let total = rows.len() as i64;
-        let items = rows.into_iter().map(WebArticle::from).collect::<Vec<WebArticle>>();
+        let items = self.with_translations(rows).await?;
 
         tracing::info!("select_filtered_web_articles: fetched {} web articles", items.len());
 
@@ -641,22 +761,22 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
                 text = $4,
                 html = $5,
                 timestamp = DATE($6),
-                summary = $7,
-                is_new_technology_related = $8,
-                is_new_product_related = $9,
-                is_new_academic_paper_related = $10,
-                is_ai_related = $11,
-                is_security_related = $12,
-                is_it_related = $13,
-                status = $14
-            WHERE article_id = $15"#,
+                is_new_technology_related = $7,
+                is_new_product_related = $8,
+                is_new_academic_paper_related = $9,
+                is_ai_related = $10,
+                is_security_related = $11,
+                is_it_related = $12,
+                status = $13,
+                enrichment_status = $14,
+                enrichment_batch_id = $15
+            WHERE article_id = $16"#,
             web_article.title,
             web_article.description,
             web_article.url,
             web_article.text,
             web_article.html,
             web_article.timestamp.with_timezone(&chrono::Utc),
-            web_article.summary,
             web_article.is_new_technology_related,
             web_article.is_new_product_related,
             web_article.is_new_academic_paper_related,
@@ -664,11 +784,15 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
             web_article.is_security_related,
             web_article.is_it_related,
             web_article.status.to_string(),
+            web_article.enrichment_status.to_string(),
+            web_article.enrichment_batch_id,
             Uuid::from(web_article.article_id)
         )
         .execute(self.db.inner_ref())
         .await
         .map_err(|e| shared::errors::AppError::SqlxError(e))?;
+        self.upsert_translations(web_article.article_id, &web_article.translations)
+            .await?;
         Ok(())
     }
 
@@ -700,6 +824,7 @@ impl WebArticleRepository for WebArticleRepositoryImpl {
 mod tests {
     use super::*;
     use crate::database::ConnectionPool;
+    use kernel::models::{language::Language, web_article::EnrichmentStatus};
     use shared::id::WebSiteId;
 
     #[sqlx::test]
@@ -748,7 +873,7 @@ mod tests {
             assert_eq!(article_1.text, article_2.text);
             assert_eq!(article_1.html, article_2.html);
             assert_eq!(article_1.timestamp, article_2.timestamp);
-            assert_eq!(article_1.summary, article_2.summary);
+            assert_eq!(article_1.translations, article_2.translations);
             assert_eq!(article_1.is_new_technology_related, article_2.is_new_technology_related);
             assert_eq!(article_1.is_new_product_related, article_2.is_new_product_related);
             assert_eq!(
@@ -777,7 +902,7 @@ mod tests {
             "Test Text".to_string(),
             "<HTML><TEST>test</TEST></HTML>".to_string(),
             chrono::Local::now(),
-            "Test Summary".to_string(),
+            vec![WebArticleTranslation::new(Language::Ja, "Test Summary".to_string())],
             false,
             false,
             false,
@@ -785,6 +910,8 @@ mod tests {
             false,
             false,
             Status::New,
+            EnrichmentStatus::Done,
+            None,
         );
 
         // Create
@@ -806,6 +933,16 @@ mod tests {
         repo.update_web_article(web_article.clone()).await.unwrap();
         let updated_records = repo.select_all_web_articles().await.unwrap();
         assert_eq!(updated_records[0].title, "Updated Article");
+        assert!(repo.select_pending_web_articles().await.unwrap().is_empty());
+
+        // Pending articles are hidden from the listings until a batch job fills their attributes
+        web_article.enrichment_status = EnrichmentStatus::Pending;
+        web_article.enrichment_batch_id = Some("batch-1".to_string());
+        repo.update_web_article(web_article.clone()).await.unwrap();
+        let pending = repo.select_pending_web_articles().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].enrichment_batch_id.as_deref(), Some("batch-1"));
+        assert!(repo.select_todays_web_articles().await.is_err());
 
         // Delete
         repo.delete_web_article(updated_records[0].article_id).await.unwrap();
@@ -1,2 +1,3 @@
+pub mod llm;
 pub mod models;
 pub mod repository;
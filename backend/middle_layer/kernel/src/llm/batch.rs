@@ -0,0 +1,62 @@
+use crate::llm::LlmProvider;
+use derive_new::new;
+use openai_tools::common::{message::Message, structured_output::Schema};
+use shared::errors::{AppError, AppResult};
+use std::time::{Duration, Instant};
+
+/// One request of a batch job; `custom_id` is echoed back in its [`BatchResult`].
+#[derive(Debug, Clone, new)]
+pub struct BatchRequest {
+    pub custom_id: String,
+    pub messages: Vec<Message>,
+    pub json_schema: Option<Schema>,
+}
+
+/// The reply to one [`BatchRequest`]: the raw text of the first choice, or why the request failed.
+#[derive(Debug, Clone, PartialEq, Eq, new)]
+pub struct BatchResult {
+    pub custom_id: String,
+    pub content: Result<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchStatus {
+    InProgress,
+    Completed(Vec<BatchResult>),
+    Failed(String),
+}
+
+/// Polls a submitted batch job until it completes, fails or `max_wait` elapses.
+pub async fn wait_for_batch(
+    llm: &dyn LlmProvider,
+    batch_id: &str,
+    poll_interval: Duration,
+    max_wait: Duration,
+) -> AppResult<Vec<BatchResult>> {
+    let started = Instant::now();
+    loop {
+        match llm.poll_batch(batch_id).await? {
+            BatchStatus::Completed(results) => return Ok(results),
+            BatchStatus::Failed(reason) => {
+                return Err(AppError::LlmInvalidResponse(format!(
+                    "Batch {} failed: {}",
+                    batch_id, reason
+                )))
+            }
+            BatchStatus::InProgress if started.elapsed() + poll_interval > max_wait => {
+                return Err(AppError::InternalServerError(format!(
+                    "Batch {} did not complete within {:?}",
+                    batch_id, max_wait
+                )))
+            }
+            BatchStatus::InProgress => {
+                tracing::info!(
+                    "Batch {} is in progress, polling again in {:?}",
+                    batch_id,
+                    poll_interval
+                );
+                tokio::time::sleep(poll_interval).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::llm::LlmProvider;
+use async_trait::async_trait;
+use openai_tools::common::{message::Message, structured_output::Schema};
+use shared::errors::{AppError, AppResult};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// [`LlmProvider`] that replays recorded replies in order, repeating the last one, so that
+/// evaluations can run without calling a model.
+#[derive(Debug)]
+pub struct CannedProvider {
+    model_id: String,
+    replies: Vec<String>,
+    next: AtomicUsize,
+}
+
+impl CannedProvider {
+    pub fn new(model_id: String, replies: Vec<String>) -> Self {
+        Self {
+            model_id,
+            replies,
+            next: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for CannedProvider {
+    fn model_id(&self) -> String {
+        self.model_id.clone()
+    }
+
+    async fn chat(&self, _messages: Vec<Message>, _json_schema: Option<Schema>) -> AppResult<String> {
+        let index = self
+            .next
+            .fetch_add(1, Ordering::SeqCst)
+            .min(self.replies.len().saturating_sub(1));
+        self.replies
+            .get(index)
+            .cloned()
+            .ok_or_else(|| AppError::LlmInvalidResponse("No canned reply".into()))
+    }
+}
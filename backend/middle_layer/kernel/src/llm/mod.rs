@@ -0,0 +1,247 @@
+pub mod batch;
+pub mod canned;
+pub mod openai;
+
+use async_trait::async_trait;
+use batch::{BatchRequest, BatchStatus};
+use openai_tools::common::{message::Message, role::Role, structured_output::Schema};
+use serde::de::DeserializeOwned;
+use shared::errors::{AppError, AppResult};
+use std::time::Duration;
+
+/// A chat model that can be asked for plain text or schema-constrained JSON.
+#[mockall::automock]
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    /// The model identifier, recorded alongside generated content.
+    fn model_id(&self) -> String;
+
+    /// Sends `messages` and returns the raw text of the first choice.
+    ///
+    /// When `json_schema` is given, the provider must constrain the reply to it.
+    /// Rate limits must be reported as [`AppError::LlmRateLimited`] so that callers can back off.
+    async fn chat(&self, messages: Vec<Message>, json_schema: Option<Schema>) -> AppResult<String>;
+
+    /// Submits `requests` as one asynchronous batch job and returns the id of the job.
+    async fn submit_batch(&self, _requests: Vec<BatchRequest>) -> AppResult<String> {
+        Err(AppError::InternalServerError(format!(
+            "{} does not support batch requests",
+            self.model_id()
+        )))
+    }
+
+    /// Checks a batch job submitted by [`LlmProvider::submit_batch`].
+    async fn poll_batch(&self, _batch_id: &str) -> AppResult<BatchStatus> {
+        Err(AppError::InternalServerError(format!(
+            "{} does not support batch requests",
+            self.model_id()
+        )))
+    }
+
+    /// Embeds `texts` into vectors, in the same order, for semantic retrieval.
+    async fn embed(&self, _texts: Vec<String>) -> AppResult<Vec<Vec<f32>>> {
+        Err(AppError::InternalServerError(format!(
+            "{} does not support embeddings",
+            self.model_id()
+        )))
+    }
+}
+
+/// A type that the model fills in through a JSON schema.
+pub trait StructuredOutput: DeserializeOwned + Send {
+    fn json_schema() -> Schema;
+
+    /// Checks the semantic constraints that the JSON schema cannot express.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// How many times the model is asked again after an invalid reply.
+    pub max_reprompts: usize,
+    /// How many times a rate-limited request is retried.
+    pub max_rate_limit_retries: usize,
+    /// The first backoff on a rate limit; doubled on every retry.
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_reprompts: 2,
+            max_rate_limit_retries: 5,
+            initial_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Asks the model for a `T` with the default [`RetryPolicy`].
+pub async fn request_structured<T: StructuredOutput>(llm: &dyn LlmProvider, messages: Vec<Message>) -> AppResult<T> {
+    request_structured_with_policy(llm, messages, &RetryPolicy::default()).await
+}
+
+/// Asks the model for a `T`, re-prompting with the parse or validation error when the reply is invalid.
+pub async fn request_structured_with_policy<T: StructuredOutput>(
+    llm: &dyn LlmProvider,
+    mut messages: Vec<Message>,
+    policy: &RetryPolicy,
+) -> AppResult<T> {
+    let mut last_error = String::new();
+    for attempt in 0..=policy.max_reprompts {
+        let reply = chat_with_backoff(llm, messages.clone(), Some(T::json_schema()), policy).await?;
+        let error = match parse_structured::<T>(&reply) {
+            Ok(output) => return Ok(output),
+            Err(e) => e,
+        };
+        tracing::warn!(
+            "Invalid structured output (attempt {}/{}): {}",
+            attempt + 1,
+            policy.max_reprompts + 1,
+            error
+        );
+        messages.push(Message::from_string(Role::Assistant, reply));
+        messages.push(Message::from_string(
+            Role::User,
+            format!(
+                "直前の出力は次の理由で不正でした: {}\n指定されたJSONスキーマに従って，JSONのみを出力し直してください．",
+                error
+            ),
+        ));
+        last_error = error;
+    }
+    Err(AppError::LlmInvalidResponse(last_error))
+}
+
+/// Parses and validates a reply that was requested with `T::json_schema()`.
+pub fn parse_structured<T: StructuredOutput>(reply: &str) -> Result<T, String> {
+    let output = serde_json::from_str::<T>(reply).map_err(|e| e.to_string())?;
+    output.validate()?;
+    Ok(output)
+}
+
+/// Asks the model for free text, backing off on rate limits.
+pub async fn request_text(llm: &dyn LlmProvider, messages: Vec<Message>) -> AppResult<String> {
+    let reply = chat_with_backoff(llm, messages, None, &RetryPolicy::default()).await?;
+    if reply.trim().is_empty() {
+        return Err(AppError::LlmInvalidResponse("Empty response from the model".into()));
+    }
+    Ok(reply)
+}
+
+async fn chat_with_backoff(
+    llm: &dyn LlmProvider,
+    messages: Vec<Message>,
+    json_schema: Option<Schema>,
+    policy: &RetryPolicy,
+) -> AppResult<String> {
+    let mut backoff = policy.initial_backoff;
+    let mut retries = 0;
+    loop {
+        match llm.chat(messages.clone(), json_schema.clone()).await {
+            Err(AppError::LlmRateLimited(e)) if retries < policy.max_rate_limit_retries => {
+                retries += 1;
+                tracing::warn!(
+                    "Rate limited by the model provider, retrying in {:?} ({}/{}): {}",
+                    backoff,
+                    retries,
+                    policy.max_rate_limit_retries,
+                    e
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Answer {
+        score: i32,
+    }
+
+    impl StructuredOutput for Answer {
+        fn json_schema() -> Schema {
+            let mut json_schema = Schema::chat_json_schema("answer");
+            json_schema.add_property("score", "integer", "score");
+            json_schema
+        }
+
+        fn validate(&self) -> Result<(), String> {
+            if (0..=10).contains(&self.score) {
+                Ok(())
+            } else {
+                Err(format!("score must be between 0 and 10: {}", self.score))
+            }
+        }
+    }
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_reprompts: 2,
+            max_rate_limit_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+        }
+    }
+
+    fn scripted(replies: Vec<AppResult<String>>) -> MockLlmProvider {
+        let mut replies = replies.into_iter();
+        let mut llm = MockLlmProvider::new();
+        llm.expect_chat()
+            .returning(move |_, _| replies.next().expect("no more replies"));
+        llm
+    }
+
+    #[tokio::test]
+    async fn test_request_structured_reprompts_on_invalid_reply() {
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let mut llm = MockLlmProvider::new();
+        let mut replies = vec!["not json", r#"{"score": 42}"#, r#"{"score": 7}"#].into_iter();
+        let seen_in_mock = seen.clone();
+        llm.expect_chat().times(3).returning(move |messages, _| {
+            seen_in_mock.lock().unwrap().push(messages.len());
+            Ok(replies.next().unwrap().to_string())
+        });
+        let messages = vec![Message::from_string(Role::User, "score it")];
+        let answer: Answer = request_structured_with_policy(&llm, messages, &policy()).await.unwrap();
+        // every re-prompt carries the invalid reply and the error message
+        assert_eq!(*seen.lock().unwrap(), vec![1, 3, 5]);
+        assert_eq!(answer.score, 7);
+    }
+
+    #[tokio::test]
+    async fn test_request_structured_gives_up_after_max_reprompts() {
+        let llm = scripted(vec![Ok("{}".into()), Ok("{}".into()), Ok("{}".into())]);
+        let messages = vec![Message::from_string(Role::User, "score it")];
+        let result = request_structured_with_policy::<Answer>(&llm, messages, &policy()).await;
+        assert!(matches!(result, Err(AppError::LlmInvalidResponse(_))));
+    }
+
+    #[tokio::test]
+    async fn test_request_structured_backs_off_on_rate_limit() {
+        let llm = scripted(vec![
+            Err(AppError::LlmRateLimited("429".into())),
+            Err(AppError::LlmRateLimited("429".into())),
+            Ok(r#"{"score": 3}"#.into()),
+        ]);
+        let messages = vec![Message::from_string(Role::User, "score it")];
+        let answer: Answer = request_structured_with_policy(&llm, messages, &policy()).await.unwrap();
+        assert_eq!(answer.score, 3);
+
+        let llm = scripted(vec![
+            Err(AppError::LlmRateLimited("429".into())),
+            Err(AppError::LlmRateLimited("429".into())),
+            Err(AppError::LlmRateLimited("429".into())),
+        ]);
+        let messages = vec![Message::from_string(Role::User, "score it")];
+        let result = request_structured_with_policy::<Answer>(&llm, messages, &policy()).await;
+        assert!(matches!(result, Err(AppError::LlmRateLimited(_))));
+    }
+}
@@ -0,0 +1,424 @@
+use crate::llm::{
+    batch::{BatchRequest, BatchResult, BatchStatus},
+    LlmProvider,
+};
+use async_trait::async_trait;
+use derive_new::new;
+use dotenvy::dotenv;
+use openai_tools::{
+    chat::request::ChatCompletion,
+    common::{message::Message, structured_output::Schema, OpenAIToolError},
+};
+use serde::Deserialize;
+use serde_json::json;
+use shared::errors::{AppError, AppResult};
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_EMBEDDING_MODEL_ID: &str = "text-embedding-3-small";
+/// The Embeddings API accepts up to 2048 inputs per request.
+const EMBEDDING_BATCH_SIZE: usize = 256;
+
+/// [`LlmProvider`] backed by the OpenAI Chat Completions and Batch APIs.
+#[derive(Debug, Clone, new)]
+pub struct OpenAIProvider {
+    model_id: String,
+    temperature: f32,
+    /// The base URL of the Files and Batch APIs, e.g. `https://api.openai.com/v1`.
+    base_url: String,
+    api_key: String,
+    #[new(value = "DEFAULT_EMBEDDING_MODEL_ID.to_string()")]
+    embedding_model_id: String,
+}
+
+impl OpenAIProvider {
+    /// Reads the model from `OPENAI_MODEL_ID` and the batch endpoint from `OPENAI_BASE_URL` and `OPENAI_API_KEY`.
+    /// Chat requests are sent by `openai-tools`, which reads the API key itself.
+    /// The embedding model is read from `OPENAI_EMBEDDING_MODEL_ID`.
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        let model_id = std::env::var("OPENAI_MODEL_ID").unwrap_or_default();
+        let base_url = std::env::var("OPENAI_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let api_key = std::env::var("OPENAI_API_KEY").unwrap_or_default();
+        let provider = Self::new(model_id, 1.0, base_url, api_key);
+        match std::env::var("OPENAI_EMBEDDING_MODEL_ID") {
+            Ok(embedding_model_id) if !embedding_model_id.is_empty() => {
+                provider.with_embedding_model_id(embedding_model_id)
+            }
+            _ => provider,
+        }
+    }
+
+    /// Uses `model_id` instead of `OPENAI_MODEL_ID`.
+    pub fn with_model_id(mut self, model_id: String) -> Self {
+        self.model_id = model_id;
+        self
+    }
+
+    pub fn with_embedding_model_id(mut self, embedding_model_id: String) -> Self {
+        self.embedding_model_id = embedding_model_id;
+        self
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path)
+    }
+
+    /// Builds the JSONL input file of a batch job, one chat completion request per line.
+    fn batch_input(&self, requests: &[BatchRequest]) -> AppResult<String> {
+        let mut lines = Vec::with_capacity(requests.len());
+        for request in requests {
+            let mut body = json!({
+                "model": self.model_id,
+                "messages": request.messages,
+                "temperature": self.temperature,
+            });
+            if let Some(json_schema) = &request.json_schema {
+                body["response_format"] = serde_json::to_value(json_schema)?;
+            }
+            let line = json!({
+                "custom_id": request.custom_id,
+                "method": "POST",
+                "url": "/v1/chat/completions",
+                "body": body,
+            });
+            lines.push(serde_json::to_string(&line)?);
+        }
+        Ok(lines.join("\n"))
+    }
+
+    async fn send<T: for<'de> Deserialize<'de>>(&self, request: request::RequestBuilder) -> AppResult<T> {
+        let response = request.bearer_auth(&self.api_key).send().await?;
+        if response.status() == request::StatusCode::TOO_MANY_REQUESTS {
+            return Err(AppError::LlmRateLimited(response.text().await.unwrap_or_default()));
+        }
+        Ok(response.error_for_status()?.json::<T>().await?)
+    }
+
+    async fn file_content(&self, file_id: &str) -> AppResult<String> {
+        let response = request::Client::new()
+            .get(self.url(&format!("files/{}/content", file_id)))
+            .bearer_auth(&self.api_key)
+            .send()
+            .await?;
+        Ok(response.error_for_status()?.text().await?)
+    }
+}
+
+fn is_rate_limit(e: &OpenAIToolError) -> bool {
+    let message = e.to_string().to_lowercase();
+    message.contains("429") || message.contains("rate limit") || message.contains("rate_limit")
+}
+
+/// `multipart/form-data` body that uploads `content` to the Files API for batch processing.
+fn batch_file_form(boundary: &str, filename: &str, content: &str) -> String {
+    format!(
+        "--{boundary}\r\n\
+         Content-Disposition: form-data; name=\"purpose\"\r\n\r\n\
+         batch\r\n\
+         --{boundary}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\n\
+         Content-Type: application/jsonl\r\n\r\n\
+         {content}\r\n\
+         --{boundary}--\r\n"
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct FileObject {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchObject {
+    id: String,
+    status: String,
+    output_file_id: Option<String>,
+    error_file_id: Option<String>,
+    errors: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingObject {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingObject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchOutputLine {
+    custom_id: String,
+    response: Option<BatchOutputResponse>,
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchOutputResponse {
+    status_code: u16,
+    body: serde_json::Value,
+}
+
+impl From<BatchOutputLine> for BatchResult {
+    fn from(line: BatchOutputLine) -> Self {
+        let content = match (line.response, line.error) {
+            (_, Some(error)) if !error.is_null() => Err(error.to_string()),
+            (Some(response), _) if response.status_code == 200 => response.body["choices"][0]["message"]["content"]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| "Empty response from OpenAI API".to_string()),
+            (Some(response), _) => Err(format!("{}: {}", response.status_code, response.body)),
+            (None, _) => Err("No response".to_string()),
+        };
+        BatchResult::new(line.custom_id, content)
+    }
+}
+
+fn parse_batch_output(jsonl: &str) -> AppResult<Vec<BatchResult>> {
+    jsonl
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(BatchResult::from(serde_json::from_str::<BatchOutputLine>(line)?)))
+        .collect()
+}
+
+#[async_trait]
+impl LlmProvider for OpenAIProvider {
+    fn model_id(&self) -> String {
+        self.model_id.clone()
+    }
+
+    async fn chat(&self, messages: Vec<Message>, json_schema: Option<Schema>) -> AppResult<String> {
+        if self.model_id.is_empty() {
+            return Err(AppError::InternalServerError("OPENAI_MODEL_ID must be set".into()));
+        }
+        let mut chat = ChatCompletion::new();
+        chat.model_id(self.model_id.clone())
+            .messages(messages)
+            .temperature(self.temperature);
+        if let Some(json_schema) = json_schema {
+            chat.json_schema(json_schema);
+        }
+
+        let response = chat.chat().await.map_err(|e| {
+            if is_rate_limit(&e) {
+                AppError::LlmRateLimited(e.to_string())
+            } else {
+                AppError::OpenAIToolError(e)
+            }
+        })?;
+        response
+            .choices
+            .first()
+            .and_then(|choice| choice.message.content.clone())
+            .and_then(|content| content.text)
+            .ok_or_else(|| AppError::LlmInvalidResponse("Empty response from OpenAI API".into()))
+    }
+
+    async fn submit_batch(&self, requests: Vec<BatchRequest>) -> AppResult<String> {
+        if self.model_id.is_empty() {
+            return Err(AppError::InternalServerError("OPENAI_MODEL_ID must be set".into()));
+        }
+        let client = request::Client::new();
+        let boundary = format!("rsrss-batch-{}", chrono::Local::now().timestamp_millis());
+        let filename = format!("batch-{}.jsonl", chrono::Local::now().format("%Y%m%d%H%M%S"));
+        let form = batch_file_form(&boundary, &filename, &self.batch_input(&requests)?);
+        let file: FileObject = self
+            .send(
+                client
+                    .post(self.url("files"))
+                    .header(
+                        request::header::CONTENT_TYPE,
+                        format!("multipart/form-data; boundary={}", boundary),
+                    )
+                    .body(form),
+            )
+            .await?;
+
+        let batch: BatchObject = self
+            .send(client.post(self.url("batches")).json(&json!({
+                "input_file_id": file.id,
+                "endpoint": "/v1/chat/completions",
+                "completion_window": "24h",
+            })))
+            .await?;
+        tracing::info!("Submitted batch {} with {} requests", batch.id, requests.len());
+        Ok(batch.id)
+    }
+
+    async fn poll_batch(&self, batch_id: &str) -> AppResult<BatchStatus> {
+        let batch: BatchObject = self
+            .send(request::Client::new().get(self.url(&format!("batches/{}", batch_id))))
+            .await?;
+        match batch.status.as_str() {
+            // expired and cancelled batches still hand back the requests that were finished
+            "completed" | "expired" | "cancelled" => {
+                let mut results = vec![];
+                for file_id in [batch.output_file_id, batch.error_file_id].into_iter().flatten() {
+                    results.extend(parse_batch_output(&self.file_content(&file_id).await?)?);
+                }
+                Ok(BatchStatus::Completed(results))
+            }
+            "failed" => Ok(BatchStatus::Failed(
+                batch
+                    .errors
+                    .map_or_else(|| "unknown error".to_string(), |e| e.to_string()),
+            )),
+            _ => Ok(BatchStatus::InProgress),
+        }
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> AppResult<Vec<Vec<f32>>> {
+        let client = request::Client::new();
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for inputs in texts.chunks(EMBEDDING_BATCH_SIZE) {
+            let mut response: EmbeddingResponse = self
+                .send(client.post(self.url("embeddings")).json(&json!({
+                    "model": self.embedding_model_id,
+                    "input": inputs,
+                })))
+                .await?;
+            if response.data.len() != inputs.len() {
+                return Err(AppError::LlmInvalidResponse(format!(
+                    "Expected {} embeddings, got {}",
+                    inputs.len(),
+                    response.data.len()
+                )));
+            }
+            response.data.sort_by_key(|object| object.index);
+            embeddings.extend(response.data.into_iter().map(|object| object.embedding));
+        }
+        Ok(embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::batch::wait_for_batch;
+    use axum::{
+        extract::{Path, State},
+        routing::{get, post},
+        Json, Router,
+    };
+    use openai_tools::common::role::Role;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    #[derive(Default)]
+    struct StubState {
+        uploaded: Mutex<String>,
+        polls: Mutex<usize>,
+    }
+
+    /// A stub of the Files and Batch APIs that completes a batch on the second poll.
+    async fn start_stub_server() -> (String, Arc<StubState>) {
+        let state = Arc::new(StubState::default());
+        let app = Router::new()
+            .route(
+                "/v1/files",
+                post(|State(state): State<Arc<StubState>>, body: String| async move {
+                    *state.uploaded.lock().unwrap() = body;
+                    Json(json!({ "id": "file-input" }))
+                }),
+            )
+            .route(
+                "/v1/batches",
+                post(|Json(body): Json<serde_json::Value>| async move {
+                    assert_eq!(body["input_file_id"], "file-input");
+                    Json(json!({ "id": "batch-1", "status": "validating" }))
+                }),
+            )
+            .route(
+                "/v1/batches/{batch_id}",
+                get(
+                    |State(state): State<Arc<StubState>>, Path(batch_id): Path<String>| async move {
+                        let mut polls = state.polls.lock().unwrap();
+                        *polls += 1;
+                        let status = if *polls < 2 { "in_progress" } else { "completed" };
+                        Json(json!({ "id": batch_id, "status": status, "output_file_id": "file-output" }))
+                    },
+                ),
+            )
+            .route(
+                "/v1/files/{file_id}/content",
+                get(|Path(file_id): Path<String>| async move {
+                    assert_eq!(file_id, "file-output");
+                    [
+                        json!({
+                            "custom_id": "a",
+                            "response": {
+                                "status_code": 200,
+                                "body": { "choices": [{ "message": { "role": "assistant", "content": "{\"score\": 1}" } }] }
+                            },
+                            "error": null
+                        }),
+                        json!({
+                            "custom_id": "b",
+                            "response": { "status_code": 500, "body": { "error": "server error" } },
+                            "error": null
+                        }),
+                    ]
+                    .iter()
+                    .map(|line| line.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                }),
+            )
+            .route(
+                "/v1/embeddings",
+                post(|Json(body): Json<serde_json::Value>| async move {
+                    assert_eq!(body["model"], DEFAULT_EMBEDDING_MODEL_ID);
+                    let count = body["input"].as_array().unwrap().len();
+                    // returned out of order, as the API does not guarantee it
+                    let data = (0..count)
+                        .rev()
+                        .map(|i| json!({ "index": i, "embedding": [i as f32, 1.0] }))
+                        .collect::<Vec<_>>();
+                    Json(json!({ "data": data }))
+                }),
+            )
+            .with_state(state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}/v1", addr), state)
+    }
+
+    #[tokio::test]
+    async fn test_batch_round_trip_against_stub_server() {
+        let (base_url, state) = start_stub_server().await;
+        let llm = OpenAIProvider::new("test-model".into(), 0.0, base_url, "sk-test".into());
+        let requests = vec![
+            BatchRequest::new("a".into(), vec![Message::from_string(Role::User, "first")], None),
+            BatchRequest::new("b".into(), vec![Message::from_string(Role::User, "second")], None),
+        ];
+
+        let batch_id = llm.submit_batch(requests).await.unwrap();
+        assert_eq!(batch_id, "batch-1");
+        let uploaded = state.uploaded.lock().unwrap().clone();
+        assert!(uploaded.contains("name=\"purpose\"\r\n\r\nbatch"));
+        assert!(uploaded.contains(r#""custom_id":"a""#));
+        assert!(uploaded.contains(r#""url":"/v1/chat/completions""#));
+
+        let results = wait_for_batch(&llm, &batch_id, Duration::from_millis(1), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(*state.polls.lock().unwrap(), 2);
+        assert_eq!(results[0], BatchResult::new("a".into(), Ok(r#"{"score": 1}"#.into())));
+        assert_eq!(results[1].custom_id, "b");
+        assert!(results[1].content.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_embed_against_stub_server() {
+        let (base_url, _) = start_stub_server().await;
+        let llm = OpenAIProvider::new("test-model".into(), 0.0, base_url, "sk-test".into());
+        let embeddings = llm.embed(vec!["a".into(), "b".into(), "c".into()]).await.unwrap();
+        assert_eq!(embeddings, vec![vec![0.0, 1.0], vec![1.0, 1.0], vec![2.0, 1.0]]);
+    }
+}
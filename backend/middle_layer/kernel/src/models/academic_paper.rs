@@ -1,14 +1,23 @@
+use crate::{
+    llm::{request_structured, LlmProvider, StructuredOutput},
+    models::{
+        bibliography,
+        chunk::{chunk_sections, count_tokens, group_by_tokens, TextChunk},
+        language::{select_translation, upsert_translation, Language, Translation},
+        task::TaskVocabulary,
+        venue::VenueType,
+    },
+};
 use anyhow::Result;
-use chrono::{DateTime, Datelike, Local};
+use chrono::{DateTime, Local, NaiveDate};
 use derive_new::new;
-use openai_tools::{
-    chat::request::ChatCompletion,
-    common::{message::Message, role::Role, structured_output::Schema},
-};
+use openai_tools::common::{message::Message, role::Role, structured_output::Schema};
 use serde::{Deserialize, Serialize};
-use shared::id::{AcademicPaperId, AuthorId, JournalId, TaskId};
+use shared::{
+    errors::AppResult,
+    id::{AcademicPaperId, AuthorId, JournalId, TaskId},
+};
 use strum::{Display, EnumString};
-use tiktoken_rs::o200k_base;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, Display, EnumString)]
 pub enum Status {
@@ -24,6 +33,21 @@ pub enum Status {
     Completed,
 }
 
+impl Status {
+    /// Whether a paper can move from this reading status to `to`.
+    /// A completed paper can be reopened for a deep dive, but is never new again.
+    pub fn can_transition_to(&self, to: &Status) -> bool {
+        matches!(
+            (self, to),
+            (Status::New, Status::DeepDive)
+                | (Status::New, Status::Completed)
+                | (Status::DeepDive, Status::New)
+                | (Status::DeepDive, Status::Completed)
+                | (Status::Completed, Status::DeepDive)
+        )
+    }
+}
+
 #[derive(Debug, Clone, Default, new)]
 pub struct Author {
     pub author_id: AuthorId,
@@ -41,7 +65,45 @@ pub struct Task {
 #[derive(Debug, Clone, Default, new)]
 pub struct Journal {
     pub journal_id: JournalId,
+    /// The canonical name of the venue, e.g. `NeurIPS` for all of its names.
     pub name: String,
+    /// `None` if the venue is not classified yet; see [`VenueType::guess`].
+    #[new(default)]
+    pub venue_type: Option<VenueType>,
+    /// The tier or rank the user gives to the venue, e.g. `A*` or `Q1`.
+    #[new(default)]
+    pub tier: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, new)]
+pub struct PaperSection {
+    pub title: String,
+    pub content: String,
+}
+
+impl PaperSection {
+    /// Splits a `text` blob back into the `# title` sections it was joined from.
+    pub fn split_text(text: &str) -> Vec<PaperSection> {
+        let mut sections: Vec<PaperSection> = vec![];
+        for line in text.lines() {
+            match (line.strip_prefix("# "), sections.last_mut()) {
+                (Some(title), _) => sections.push(PaperSection::new(title.trim().to_string(), String::new())),
+                (None, Some(section)) => {
+                    section.content.push_str(line);
+                    section.content.push('\n');
+                }
+                (None, None) if !line.trim().is_empty() => {
+                    sections.push(PaperSection::new("Body".to_string(), format!("{}\n", line)))
+                }
+                (None, None) => {}
+            }
+        }
+        for section in sections.iter_mut() {
+            section.content = section.content.trim().to_string();
+        }
+        sections.retain(|section| !section.content.is_empty());
+        sections
+    }
 }
 
 #[derive(Debug, Clone, Default, new)]
@@ -54,11 +116,16 @@ pub struct AcademicPaper {
     pub tasks: Vec<Task>,
     pub title: String,
     pub abstract_text: String,
-    pub abstract_text_ja: String,
     pub text: String,
+    pub sections: Vec<PaperSection>,
+    /// The SHA-256 of the source PDF in the local PDF store, empty if it is not stored.
+    pub pdf_sha256: String,
     pub url: String,
     pub doi: String,
     pub published_date: DateTime<Local>,
+    /// The year of the edition of the venue the paper was published in, e.g. 2023 for `NeurIPS 2023`.
+    #[new(default)]
+    pub venue_year: Option<i32>,
     pub created_at: DateTime<Local>,
     pub updated_at: DateTime<Local>,
     pub primary_category: String,
@@ -66,23 +133,89 @@ pub struct AcademicPaper {
     pub references_count: i32,
     pub influential_citation_count: i32,
     pub bibtex: String,
+    pub translations: Vec<AcademicPaperTranslation>,
+    pub status: Status,
+}
+
+/// The AI-generated analysis of a paper in one language.
+#[derive(Debug, Clone, Default, PartialEq, Eq, new)]
+pub struct AcademicPaperTranslation {
+    pub language: Language,
+    pub abstract_text: String,
     pub summary: String,
     pub background_and_purpose: String,
     pub methodology: String,
     pub dataset: String,
     pub results: String,
     pub advantages_limitations_and_future_work: String,
-    pub status: Status,
+}
+
+impl Translation for AcademicPaperTranslation {
+    fn language(&self) -> Language {
+        self.language
+    }
 }
 
 #[derive(Debug, Clone, new)]
 pub struct AuthorListOptions {
+    /// Only the authors whose name contains this, case-insensitively.
+    pub name: Option<String>,
+    /// The number of the most recently added papers listed for each author.
+    pub recent_count: i64,
     pub limit: i64,
     pub offset: i64,
 }
 
-#[derive(Debug, Clone, new)]
+/// The key the paper list is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AcademicPaperSort {
+    #[default]
+    PublishedDate,
+    Citations,
+    InfluentialCitations,
+    /// When the paper was added to the library.
+    AddedDate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// The filters, all of which a paper must match, and the order of the paper list.
+#[derive(Debug, Clone, Default)]
 pub struct AcademicPaperListOptions {
+    /// Only the papers with the tag of this name.
+    pub tag: Option<String>,
+    /// Only the papers in the collection of this name.
+    pub collection: Option<String>,
+    pub status: Option<Status>,
+    /// Only the papers with the task of this name.
+    pub task: Option<String>,
+    /// Only the papers published in the journal of this name or one of its aliases.
+    pub journal: Option<String>,
+    pub venue_type: Option<VenueType>,
+    /// Only the papers published in a venue of this tier.
+    pub tier: Option<String>,
+    pub venue_year: Option<i32>,
+    /// Only the papers with an author whose name contains this.
+    pub author: Option<String>,
+    pub primary_category: Option<String>,
+    /// Only the papers published on or after this date.
+    pub published_from: Option<NaiveDate>,
+    /// Only the papers published on or before this date.
+    pub published_to: Option<NaiveDate>,
+    pub min_citations: Option<i32>,
+    /// Only the papers whose title or abstract contains this.
+    pub text: Option<String>,
+    pub sort: AcademicPaperSort,
+    pub order: SortOrder,
     pub limit: i64,
     pub offset: i64,
 }
@@ -94,7 +227,7 @@ pub struct AcademicPaperSummaryTask {
 
 #[derive(Debug, Clone, Default, new, Serialize, Deserialize)]
 pub struct AcademicPaperSummary {
-    pub abstract_in_japanese: String,
+    pub translated_abstract: String,
     pub summary: String,
     pub tasks: Vec<AcademicPaperSummaryTask>,
     pub background_and_purpose: String,
@@ -104,14 +237,7 @@ pub struct AcademicPaperSummary {
     pub advantages_limitations_and_future_work: String,
 }
 
-impl AcademicPaper {
-    pub async fn fill_fields_with_ai(&mut self) -> Result<AcademicPaper> {
-        let model_id = std::env::var("OPENAI_MODEL_ID").expect("OPENAI_MODEL_ID must be set");
-        let mut chat = ChatCompletion::new();
-        let messages = vec![
-            Message::from_string(
-                Role::System,
-                r#"あなたは「朝倉 理央（あさくら りお）」という名の論文分析専門アナリストです．
+const SUMMARY_SYSTEM_PROMPT: &str = r#"あなたは「朝倉 理央（あさくら りお）」という名の論文分析専門アナリストです．
 ，修士で自然言語処理を，博士課程で計算論的認知科学を専攻し，研究論文の構造，目的，理論的背景，手法，実験，考察の要点を正確かつ簡潔に抽出する技術に優れています．
 ，技術者，学生など，読者の背景に応じた専門性と平易さのバランスを取った要約を提供することができます．論文の論理構造を重視し，誤解のないように明示的な言葉選びをします．
 あなたの分析スタイルは「構造化された解釈」と「批判的思考」の融合にあり，論文の貢献だけでなく，限界や今後の展望にも言及します．
@@ -123,24 +249,268 @@ impl AcademicPaper {
 - 主な結果と知見
 - 利点・限界・今後の展望
 また，論文の内容が曖昧な場合でも，前提となる研究分野や過去の知見に基づき，文脈補完を行いながら読者にわかりやすく伝えてください．
-"#,
+"#;
+
+#[derive(Debug, Clone, Default, new, Serialize, Deserialize)]
+pub struct AcademicPaperChunkSummary {
+    pub summary: String,
+    pub tasks: Vec<AcademicPaperSummaryTask>,
+    pub background_and_purpose: String,
+    pub methodology: String,
+    pub dataset: String,
+    pub results: String,
+    pub advantages_limitations_and_future_work: String,
+}
+
+impl AcademicPaperChunkSummary {
+    fn to_markdown(&self, index: usize, section_titles: &[String]) -> String {
+        format!(
+            r#"## パート{index}: {sections}
+### 概要
+{summary}
+### タスク
+{tasks}
+### 背景と目的
+{background_and_purpose}
+### 手法
+{methodology}
+### データセット
+{dataset}
+### 結果
+{results}
+### 利点・限界・今後の展望
+{advantages_limitations_and_future_work}"#,
+            index = index,
+            sections = section_titles.join(", "),
+            summary = self.summary,
+            tasks = self
+                .tasks
+                .iter()
+                .map(|t| format!("- {}", t.name))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            background_and_purpose = self.background_and_purpose,
+            methodology = self.methodology,
+            dataset = self.dataset,
+            results = self.results,
+            advantages_limitations_and_future_work = self.advantages_limitations_and_future_work,
+        )
+    }
+}
+
+impl StructuredOutput for AcademicPaperSummary {
+    fn json_schema() -> Schema {
+        let mut json_schema = Schema::chat_json_schema("academic_paper");
+        json_schema.add_property(
+            "translated_abstract",
+            "string",
+            "論文の要約を指定された出力言語に翻訳してください．",
+        );
+        json_schema.add_property(
+            "summary",
+            "string",
+            "論文の概要を指定された出力言語で記述してください．",
+        );
+        json_schema.add_array("tasks", vec![("name", "論文が取り組んでいるタスクの英語名称")]);
+        json_schema.add_property(
+            "background_and_purpose",
+            "string",
+            "論文の研究の背景と目的を指定された出力言語で記述してください．",
+        );
+        json_schema.add_property(
+            "methodology",
+            "string",
+            "論文の研究手法を先行研究と比較して指定された出力言語で記述してください．",
+        );
+        json_schema.add_property(
+            "dataset",
+            "string",
+            "論文で使用されているデータセットを指定された出力言語で記述してください．",
+        );
+        json_schema.add_property(
+            "results",
+            "string",
+            "論文の主な結果と知見を指定された出力言語で記述してください．",
+        );
+        json_schema.add_property(
+            "advantages_limitations_and_future_work",
+            "string",
+            "論文の利点・限界・今後の展望を指定された出力言語で記述してください．",
+        );
+        json_schema
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.summary.trim().is_empty() {
+            return Err("summary must not be empty".into());
+        }
+        if self.tasks.iter().any(|task| task.name.trim().is_empty()) {
+            return Err("task names must not be empty".into());
+        }
+        Ok(())
+    }
+}
+
+impl StructuredOutput for AcademicPaperChunkSummary {
+    fn json_schema() -> Schema {
+        let mut json_schema = Schema::chat_json_schema("academic_paper_chunk");
+        json_schema.add_property(
+            "summary",
+            "string",
+            "この部分に書かれている内容の概要を指定された出力言語で記述してください．",
+        );
+        json_schema.add_array("tasks", vec![("name", "この部分から読み取れる論文のタスクの英語名称")]);
+        json_schema.add_property(
+            "background_and_purpose",
+            "string",
+            "この部分から読み取れる研究の背景と目的を指定された出力言語で記述してください．記述がなければ空文字にしてください．",
+        );
+        json_schema.add_property(
+            "methodology",
+            "string",
+            "この部分から読み取れる研究手法を指定された出力言語で具体的に記述してください．記述がなければ空文字にしてください．",
+        );
+        json_schema.add_property(
+            "dataset",
+            "string",
+            "この部分で言及されているデータセットを指定された出力言語で記述してください．記述がなければ空文字にしてください．",
+        );
+        json_schema.add_property(
+            "results",
+            "string",
+            "この部分に書かれている実験結果や知見を，数値を含めて指定された出力言語で記述してください．記述がなければ空文字にしてください．",
+        );
+        json_schema.add_property(
+            "advantages_limitations_and_future_work",
+            "string",
+            "この部分から読み取れる利点・限界・今後の展望を指定された出力言語で記述してください．記述がなければ空文字にしてください．",
+        );
+        json_schema
+    }
+}
+
+impl AcademicPaper {
+    /// Returns the sections to summarize, falling back to the whole `text` as one section.
+    pub(crate) fn sections_for_summary(&self) -> Vec<PaperSection> {
+        if !self.sections.is_empty() {
+            self.sections.clone()
+        } else if !self.text.is_empty() {
+            vec![PaperSection::new("Body".to_string(), self.text.clone())]
+        } else {
+            vec![]
+        }
+    }
+
+    /// Returns the analysis in `language`, falling back to any other available language.
+    pub fn translation(&self, language: Language) -> Option<&AcademicPaperTranslation> {
+        select_translation(&self.translations, language)
+    }
+
+    /// Analyzes the paper in `language` and maps the extracted tasks onto the tasks of `vocabulary`.
+    ///
+    /// With `TASK_VOCABULARY_PROMPT=true`, the most used tasks are also listed to the model so that it reuses their names.
+    pub async fn fill_fields_with_ai(
+        &mut self,
+        llm: &dyn LlmProvider,
+        language: Language,
+        mut vocabulary: TaskVocabulary,
+    ) -> AppResult<AcademicPaper> {
+        let max_tokens = std::env::var("OPENAI_MAX_TOKENS")
+            .unwrap_or_else(|_| "200000".to_string())
+            .parse::<usize>()
+            .unwrap_or(200000);
+        // leave 5% of the budget for the instructions, the title and the abstract
+        let chunk_tokens = max_tokens * 95 / 100;
+
+        let task_hint =
+            if std::env::var("TASK_VOCABULARY_PROMPT").is_ok_and(|value| value == "true") && !vocabulary.is_empty() {
+                format!(
+                    "\n  既存のタスクと同じタスクには，次の名称をそのまま使ってください：{}",
+                    vocabulary.prompt_names().join(", ")
+                )
+            } else {
+                String::new()
+            };
+
+        let chunks = chunk_sections(&self.sections_for_summary(), chunk_tokens)?;
+        tracing::info!(
+            "Split the paper into {} chunk(s) ({} tokens in total)",
+            chunks.len(),
+            chunks.iter().map(|c| c.token_count).sum::<usize>()
+        );
+
+        let summary = if chunks.len() <= 1 {
+            let text = chunks.first().map_or(String::new(), |c| c.text.clone());
+            self.summarize_text(llm, language, &text, &task_hint).await?
+        } else {
+            let mut chunk_summaries: Vec<(Vec<String>, AcademicPaperChunkSummary)> = vec![];
+            for (i, chunk) in chunks.iter().enumerate() {
+                tracing::info!(
+                    "Summarizing chunk {}/{}: {}",
+                    i + 1,
+                    chunks.len(),
+                    chunk.section_titles.join(", ")
+                );
+                let chunk_summary = self.summarize_chunk(llm, language, chunk).await?;
+                chunk_summaries.push((chunk.section_titles.clone(), chunk_summary));
+            }
+            let chunk_summaries = self
+                .merge_chunk_summaries(llm, language, chunk_summaries, chunk_tokens)
+                .await?;
+            self.reduce_chunk_summaries(llm, language, &chunk_summaries, &task_hint)
+                .await?
+        };
+
+        self.tasks = vocabulary
+            .normalize(llm, summary.tasks.iter().map(|task| task.name.clone()).collect())
+            .await;
+        upsert_translation(
+            &mut self.translations,
+            AcademicPaperTranslation::new(
+                language,
+                summary.translated_abstract,
+                summary.summary,
+                summary.background_and_purpose,
+                summary.methodology,
+                summary.dataset,
+                summary.results,
+                summary.advantages_limitations_and_future_work,
             ),
+        );
+        tracing::info!(
+            "Successfully filled fields with AI for paper: {} ({})",
+            self.title,
+            language
+        );
+        Ok(self.clone())
+    }
+
+    /// Summarizes a paper whose text fits into a single request.
+    async fn summarize_text(
+        &self,
+        llm: &dyn LlmProvider,
+        language: Language,
+        text: &str,
+        task_hint: &str,
+    ) -> AppResult<AcademicPaperSummary> {
+        let messages = vec![
+            Message::from_string(Role::System, SUMMARY_SYSTEM_PROMPT),
             Message::from_string(
                 Role::User,
                 format!(
                     r#"与えられた論文のテキストから以下の情報を抽出してJSON形式で出力してください．
-- [abstract_in_japanese] 論文の要約を日本語に翻訳してください．
-- [summary] 論文の概要を日本語で記述してください．
-- [tasks] 論文が取り組んでいるタスクを英語のリストで記記述してください．
-- [background_and_purpose] 論文の研究の背景と目的を日本語で記述してください．
-- [methodology] 論文の研究手法を先行研究と比較して日本語で記述してください．
-- [dataset] 論文で使用されているデータセットを日本語で記述してください．
-- [results] 論文の主な結果と知見を日本語で記述してください．
-- [advantages_limitations_and_future_work] 論文の利点・限界・今後の展望を日本語で記述してください．
+- [translated_abstract] 論文の要約を{language}に翻訳してください．
+- [summary] 論文の概要を{language}で記述してください．
+- [tasks] 論文が取り組んでいるタスクを英語のリストで記記述してください．{task_hint}
+- [background_and_purpose] 論文の研究の背景と目的を{language}で記述してください．
+- [methodology] 論文の研究手法を先行研究と比較して{language}で記述してください．
+- [dataset] 論文で使用されているデータセットを{language}で記述してください．
+- [results] 論文の主な結果と知見を{language}で記述してください．
+- [advantages_limitations_and_future_work] 論文の利点・限界・今後の展望を{language}で記述してください．
 
 出力形式は以下のようにしてください：
 {{
-    "abstract_in_japanese": "要約",
+    "translated_abstract": "要約",
     "summary": "概要",
     "tasks": [
         {{"name": "タスク名"}}
@@ -160,134 +530,263 @@ impl AcademicPaper {
 
 [論文の本文]
 {text}"#,
+                    language = language.prompt_name(),
                     title = self.title,
                     abstract = self.abstract_text,
-                    text = self.text,
+                    text = text,
+                    task_hint = task_hint,
                 ),
             ),
         ];
+        request_structured(llm, messages).await
+    }
 
-        let bpe = o200k_base().unwrap();
-        let tokens = bpe.encode_with_special_tokens(&self.text);
-        tracing::info!("Encoded text into {} tokens", tokens.len());
-        let max_tokens = std::env::var("OPENAI_MAX_TOKENS")
-            .unwrap_or_else(|_| "200000".to_string())
-            .parse::<usize>()
-            .unwrap_or(200000);
+    /// Map step: extracts partial notes from one chunk of a long paper.
+    async fn summarize_chunk(
+        &self,
+        llm: &dyn LlmProvider,
+        language: Language,
+        chunk: &TextChunk,
+    ) -> AppResult<AcademicPaperChunkSummary> {
+        let messages = vec![
+            Message::from_string(Role::System, SUMMARY_SYSTEM_PROMPT),
+            Message::from_string(
+                Role::User,
+                format!(
+                    r#"以下は長い論文を分割したうちの一部（{sections}）です．
+この部分に書かれている内容だけをもとに，以下の情報を抽出してJSON形式で出力してください．
+手法の詳細，実験設定，数値結果などは後で論文全体の分析に統合するため，省略せずに具体的に記述してください．
+タスク以外の項目はすべて{language}で記述してください．
+- [summary] この部分の概要
+- [tasks] この部分から読み取れる論文のタスク（英語）
+- [background_and_purpose] 研究の背景と目的
+- [methodology] 研究手法
+- [dataset] データセット
+- [results] 実験結果と知見
+- [advantages_limitations_and_future_work] 利点・限界・今後の展望
 
-        if tokens.len() > max_tokens {
-            tracing::warn!(
-                "Text is too long: {} tokens, truncating to {} tokens",
-                tokens.len(),
-                max_tokens
-            );
-            let target_length = max_tokens * 0.95 as usize; // 95% of max_tokens
-            let truncated_text = bpe.decode(tokens[..target_length].to_vec())?;
-            self.text = truncated_text;
-        }
+[論文タイトル]
+{title}
 
-        let mut json_schema = Schema::chat_json_schema("academic_paper");
-        json_schema.add_property(
-            "abstract_in_japanese",
-            "string",
-            "論文の要約を日本語に翻訳してください．",
-        );
-        json_schema.add_property("summary", "string", "論文の概要を日本語で記述してください．");
-        json_schema.add_array("tasks", vec![("name", "論文が取り組んでいるタスクの英語名称")]);
-        json_schema.add_property(
-            "background_and_purpose",
-            "string",
-            "論文の研究の背景と目的を日本語で記述してください．",
-        );
-        json_schema.add_property(
-            "methodology",
-            "string",
-            "論文の研究手法を先行研究と比較して日本語で記述してください．",
-        );
-        json_schema.add_property(
-            "dataset",
-            "string",
-            "論文で使用されているデータセットを日本語で記述してください．",
-        );
-        json_schema.add_property("results", "string", "論文の主な結果と知見を日本語で記述してください．");
-        json_schema.add_property(
-            "advantages_limitations_and_future_work",
-            "string",
-            "論文の利点・限界・今後の展望を日本語で記述してください．",
-        );
+[論文の一部]
+{text}"#,
+                    sections = chunk.section_titles.join(", "),
+                    language = language.prompt_name(),
+                    title = self.title,
+                    text = chunk.text,
+                ),
+            ),
+        ];
+        request_structured(llm, messages).await
+    }
 
-        chat.model_id(model_id)
-            .messages(messages)
-            .temperature(1.0)
-            .json_schema(json_schema);
-
-        let response = chat.chat().await?;
-        let mut max_retries = 5;
-        while max_retries > 0 {
-            match serde_json::from_str::<AcademicPaperSummary>(
-                &response.choices[0].message.content.clone().unwrap().text.unwrap(),
-            ) {
-                Ok(summary) => {
-                    self.abstract_text_ja = summary.abstract_in_japanese.clone();
-                    self.summary = summary.summary.clone();
-                    self.tasks = summary
-                        .tasks
-                        .iter()
-                        .map(|task| Task::new(TaskId::new(), task.name.clone()))
-                        .collect();
-                    self.background_and_purpose = summary.background_and_purpose.clone();
-                    self.methodology = summary.methodology.clone();
-                    self.dataset = summary.dataset.clone();
-                    self.results = summary.results.clone();
-                    self.advantages_limitations_and_future_work =
-                        summary.advantages_limitations_and_future_work.clone();
-                    tracing::info!("Successfully filled fields with AI for paper: {}", self.title);
-                    return Ok(self.clone());
-                }
-                Err(e) => {
-                    if max_retries == 0 {
-                        tracing::error!("Failed to parse AcademicPaperSummary: {}", e);
-                        return Err(anyhow::anyhow!("Failed to parse AcademicPaperSummary: {}", e));
-                    }
-                    tracing::warn!("Failed to parse AcademicPaperSummary: {}", e);
-                    max_retries -= 1;
-                    std::thread::sleep(std::time::Duration::from_secs(1));
+    /// Merges consecutive notes into one, again and again, until the notes of all chunks fit into `max_tokens`.
+    async fn merge_chunk_summaries(
+        &self,
+        llm: &dyn LlmProvider,
+        language: Language,
+        mut chunk_summaries: Vec<(Vec<String>, AcademicPaperChunkSummary)>,
+        max_tokens: usize,
+    ) -> AppResult<Vec<(Vec<String>, AcademicPaperChunkSummary)>> {
+        loop {
+            let notes = chunk_summaries
+                .iter()
+                .enumerate()
+                .map(|(i, (section_titles, chunk_summary))| chunk_summary.to_markdown(i + 1, section_titles))
+                .collect::<Vec<String>>();
+            if chunk_summaries.len() <= 1 || count_tokens(&notes.join("\n\n"))? <= max_tokens {
+                return Ok(chunk_summaries);
+            }
+
+            let token_counts = notes
+                .iter()
+                .map(|note| count_tokens(note))
+                .collect::<Result<Vec<usize>>>()?;
+            let groups = group_by_tokens(&token_counts, max_tokens);
+            tracing::info!(
+                "The notes of {} chunks exceed the context, merging them into {}",
+                chunk_summaries.len(),
+                groups.len()
+            );
+            let mut merged: Vec<(Vec<String>, AcademicPaperChunkSummary)> = vec![];
+            for group in groups {
+                if group.len() == 1 {
+                    merged.push(chunk_summaries[group.start].clone());
+                    continue;
                 }
+                let section_titles = chunk_summaries[group.clone()]
+                    .iter()
+                    .flat_map(|(section_titles, _)| section_titles.clone())
+                    .collect::<Vec<String>>();
+                let chunk_summary = self
+                    .merge_notes(llm, language, &section_titles, &notes[group].join("\n\n"))
+                    .await?;
+                merged.push((section_titles, chunk_summary));
             }
+            chunk_summaries = merged;
         }
-        tracing::warn!("It seems to fail to query OpenAI API");
-        return Err(anyhow::anyhow!(
-            "Failed to fill fields with AI: OpenAI API query failed"
-        ));
+    }
+
+    /// Merges the notes of consecutive chunks into the notes of a single larger chunk.
+    async fn merge_notes(
+        &self,
+        llm: &dyn LlmProvider,
+        language: Language,
+        section_titles: &[String],
+        notes: &str,
+    ) -> AppResult<AcademicPaperChunkSummary> {
+        let messages = vec![
+            Message::from_string(Role::System, SUMMARY_SYSTEM_PROMPT),
+            Message::from_string(
+                Role::User,
+                format!(
+                    r#"以下は長い論文を先頭から順に分割し，部分ごとに分析したメモのうち，連続する一部（{sections}）です．
+これらのメモを1つのメモに統合して，以下の情報を抽出してJSON形式で出力してください．
+手法の詳細，実験設定，数値結果などは後で論文全体の分析に統合するため，省略せずに具体的に記述してください．
+タスク以外の項目はすべて{language}で記述してください．
+- [summary] この部分の概要
+- [tasks] この部分から読み取れる論文のタスク（英語）．重複は統合してください．
+- [background_and_purpose] 研究の背景と目的
+- [methodology] 研究手法
+- [dataset] データセット
+- [results] 実験結果と知見
+- [advantages_limitations_and_future_work] 利点・限界・今後の展望
+
+[論文タイトル]
+{title}
+
+[部分ごとの分析メモ]
+{notes}"#,
+                    sections = section_titles.join(", "),
+                    language = language.prompt_name(),
+                    title = self.title,
+                    notes = notes,
+                ),
+            ),
+        ];
+        request_structured(llm, messages).await
+    }
+
+    /// Reduce step: merges the partial notes of all chunks into the final summary.
+    async fn reduce_chunk_summaries(
+        &self,
+        llm: &dyn LlmProvider,
+        language: Language,
+        chunk_summaries: &[(Vec<String>, AcademicPaperChunkSummary)],
+        task_hint: &str,
+    ) -> AppResult<AcademicPaperSummary> {
+        let notes = chunk_summaries
+            .iter()
+            .enumerate()
+            .map(|(i, (section_titles, chunk_summary))| chunk_summary.to_markdown(i + 1, section_titles))
+            .collect::<Vec<String>>()
+            .join("\n\n");
+        let messages = vec![
+            Message::from_string(Role::System, SUMMARY_SYSTEM_PROMPT),
+            Message::from_string(
+                Role::User,
+                format!(
+                    r#"以下は長い論文を先頭から順に分割し，部分ごとに分析したメモです．
+これらのメモを統合して，論文全体について以下の情報を抽出してJSON形式で出力してください．
+論文の後半にある手法の詳細や実験結果も落とさずに反映してください．
+- [translated_abstract] 論文の要約を{language}に翻訳してください．
+- [summary] 論文の概要を{language}で記述してください．
+- [tasks] 論文が取り組んでいるタスクを英語のリストで記述してください．重複は統合してください．{task_hint}
+- [background_and_purpose] 論文の研究の背景と目的を{language}で記述してください．
+- [methodology] 論文の研究手法を先行研究と比較して{language}で記述してください．
+- [dataset] 論文で使用されているデータセットを{language}で記述してください．
+- [results] 論文の主な結果と知見を{language}で記述してください．
+- [advantages_limitations_and_future_work] 論文の利点・限界・今後の展望を{language}で記述してください．
+
+[論文タイトル]
+{title}
+
+[論文の要約]
+{abstract}
+
+[部分ごとの分析メモ]
+{notes}"#,
+                    language = language.prompt_name(),
+                    title = self.title,
+                    abstract = self.abstract_text,
+                    notes = notes,
+                    task_hint = task_hint,
+                ),
+            ),
+        ];
+        request_structured(llm, messages).await
     }
 
     pub fn fill_bibtex(&mut self) -> Result<AcademicPaper> {
-        let first_author = self
-            .authors
-            .first()
-            .map_or("Unknown Author".to_string(), |a| a.name.clone());
-        let first_author_last_name = first_author.split_whitespace().next().unwrap_or("Unknown");
-        let bibtex = format!(
-            r#"@article{{{first_author}:{year},
-    title = {{{title}}},
-    author = {{{author}}},
-    journal = {{{journal}}},
-    year = {{{year}}},
-    doi = {{{doi}}}
-}}"#,
-            first_author = first_author_last_name,
-            title = self.title,
-            author = self
-                .authors
+        self.bibtex = bibliography::to_bibtex(self, &bibliography::citation_key(self));
+        Ok(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::canned::CannedProvider;
+
+    #[test]
+    fn test_status_transitions() {
+        assert!(Status::New.can_transition_to(&Status::DeepDive));
+        assert!(Status::New.can_transition_to(&Status::Completed));
+        assert!(Status::DeepDive.can_transition_to(&Status::New));
+        assert!(Status::Completed.can_transition_to(&Status::DeepDive));
+        assert!(!Status::Completed.can_transition_to(&Status::New));
+        assert!(!Status::DeepDive.can_transition_to(&Status::DeepDive));
+    }
+
+    #[test]
+    fn test_split_text() {
+        let text = "# Introduction\n\nLarge language models\nare large.\n\n# Method\n\nWe use LoRA.\n\n# Empty\n\n";
+        assert_eq!(
+            PaperSection::split_text(text),
+            vec![
+                PaperSection::new("Introduction".into(), "Large language models\nare large.".into()),
+                PaperSection::new("Method".into(), "We use LoRA.".into()),
+            ]
+        );
+        assert_eq!(
+            PaperSection::split_text("No headings"),
+            vec![PaperSection::new("Body".into(), "No headings".into())]
+        );
+        assert!(PaperSection::split_text("").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_merge_chunk_summaries() {
+        let note = |text: &str| AcademicPaperChunkSummary {
+            summary: text.repeat(50),
+            ..Default::default()
+        };
+        let chunk_summaries = (0..5)
+            .map(|i| (vec![format!("Section {}", i)], note("long notes ")))
+            .collect::<Vec<_>>();
+        let merged = serde_json::to_string(&note("merged ")).unwrap();
+        let llm = CannedProvider::new("canned".to_string(), vec![merged]);
+        let paper = AcademicPaper::default();
+
+        // the notes of five chunks do not fit, so they are merged in pairs until they do
+        let chunk_summaries = paper
+            .merge_chunk_summaries(&llm, Language::Ja, chunk_summaries, 400)
+            .await
+            .unwrap();
+        assert!(chunk_summaries.len() < 5);
+        let notes = chunk_summaries
+            .iter()
+            .enumerate()
+            .map(|(i, (section_titles, chunk_summary))| chunk_summary.to_markdown(i + 1, section_titles))
+            .collect::<Vec<String>>()
+            .join("\n\n");
+        assert!(count_tokens(&notes).unwrap() <= 400);
+        assert_eq!(
+            chunk_summaries
                 .iter()
-                .map(|a| a.name.clone())
-                .collect::<Vec<String>>()
-                .join(","),
-            journal = self.journal.name,
-            year = self.published_date.year(),
-            doi = self.doi,
+                .flat_map(|(section_titles, _)| section_titles.clone())
+                .collect::<Vec<String>>(),
+            (0..5).map(|i| format!("Section {}", i)).collect::<Vec<String>>()
         );
-        self.bibtex = bibtex;
-        Ok(self.clone())
     }
 }
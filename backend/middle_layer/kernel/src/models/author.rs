@@ -0,0 +1,26 @@
+use crate::models::{academic_paper::Author, browse::PaperBrief};
+use derive_new::new;
+use shared::id::AuthorId;
+
+/// An author and the number of their papers in the library.
+#[derive(Debug, Clone, Default, new)]
+pub struct AuthorSummary {
+    pub author: Author,
+    pub paper_count: i64,
+    /// The most recently added papers, newest first; only filled in the author list.
+    pub recent_papers: Vec<PaperBrief>,
+}
+
+/// `author_id` and `coauthor_id` wrote `paper_count` papers of the library together.
+#[derive(Debug, Clone, PartialEq, Eq, new)]
+pub struct CoauthorEdge {
+    pub author_id: AuthorId,
+    pub coauthor_id: AuthorId,
+    pub paper_count: i64,
+}
+
+#[derive(Debug, Clone, Default, new)]
+pub struct CoauthorshipGraph {
+    pub nodes: Vec<AuthorSummary>,
+    pub edges: Vec<CoauthorEdge>,
+}
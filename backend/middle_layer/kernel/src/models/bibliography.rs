@@ -0,0 +1,554 @@
+use crate::models::{
+    academic_paper::{AcademicPaper, Author},
+    venue::{full_venue_name, VenueType, UNKNOWN_VENUE},
+};
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use strum::{Display, EnumString};
+
+/// The formats the library can be exported to and imported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Display, EnumString)]
+pub enum BibliographyFormat {
+    #[default]
+    #[strum(serialize = "bibtex")]
+    #[serde(rename = "bibtex")]
+    BibTeX,
+    #[strum(serialize = "ris")]
+    #[serde(rename = "ris")]
+    Ris,
+    #[strum(serialize = "csl-json")]
+    #[serde(rename = "csl-json")]
+    CslJson,
+}
+
+impl BibliographyFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::BibTeX => "application/x-bibtex",
+            Self::Ris => "application/x-research-info-systems",
+            Self::CslJson => "application/vnd.citationstyles.csl+json",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::BibTeX => "bib",
+            Self::Ris => "ris",
+            Self::CslJson => "json",
+        }
+    }
+
+    /// The format of a file, judged by its extension.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "bib" | "bibtex" => Some(Self::BibTeX),
+            "ris" => Some(Self::Ris),
+            "json" => Some(Self::CslJson),
+            _ => None,
+        }
+    }
+}
+
+/// How a paper was published, which decides the entry type of each format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Venue {
+    Journal(String),
+    Conference(String),
+    /// A preprint with its arXiv ID (without the version) and primary category.
+    ArXiv {
+        eprint: String,
+        primary_class: String,
+    },
+    Unknown,
+}
+
+/// The arXiv ID of `arxiv_id`, which is stored as the abs URL, e.g. `http://arxiv.org/abs/2301.00001v2`.
+fn arxiv_eprint(arxiv_id: &str) -> Option<String> {
+    let id = arxiv_id.trim().trim_end_matches('/');
+    let id = id.split_once("/abs/").map_or(id, |(_, id)| id);
+    let id = match id.rfind('v') {
+        Some(i) if i > 0 && id[i + 1..].chars().all(|c| c.is_ascii_digit()) && i + 1 < id.len() => &id[..i],
+        _ => id,
+    };
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+impl Venue {
+    pub fn of_paper(paper: &AcademicPaper) -> Self {
+        let name = paper.journal.name.trim();
+        let venue_type = paper.journal.venue_type.unwrap_or_else(|| VenueType::guess(name));
+        let is_placeholder = name.is_empty() || name == UNKNOWN_VENUE || venue_type == VenueType::Preprint;
+        if is_placeholder {
+            return match arxiv_eprint(&paper.arxiv_id) {
+                Some(eprint) => Self::ArXiv {
+                    eprint,
+                    primary_class: paper.primary_category.clone(),
+                },
+                None => Self::Unknown,
+            };
+        }
+        // a well-known venue is stored by its short name, e.g. `NeurIPS`, but cited by its full name
+        let name = full_venue_name(name).unwrap_or(name).to_string();
+        match venue_type {
+            VenueType::Conference | VenueType::Workshop => Self::Conference(name),
+            VenueType::Journal | VenueType::Preprint => Self::Journal(name),
+        }
+    }
+}
+
+fn split_name(name: &str) -> (String, String) {
+    if let Some((family, given)) = name.split_once(',') {
+        return (family.trim().to_string(), given.trim().to_string());
+    }
+    let words = name.split_whitespace().collect::<Vec<&str>>();
+    match words.split_last() {
+        Some((family, given)) => (family.to_string(), given.join(" ")),
+        None => (String::new(), String::new()),
+    }
+}
+
+/// The name as "Last, First", which BibTeX and RIS both read unambiguously.
+fn inverted_name(author: &Author) -> String {
+    match split_name(&author.name) {
+        (family, given) if given.is_empty() => family,
+        (family, given) => format!("{}, {}", family, given),
+    }
+}
+
+fn fold_to_ascii(c: char) -> Option<char> {
+    let folded = match c {
+        'à'..='å' | 'À'..='Å' => 'a',
+        'ç' | 'Ç' => 'c',
+        'è'..='ë' | 'È'..='Ë' => 'e',
+        'ì'..='ï' | 'Ì'..='Ï' => 'i',
+        'ñ' | 'Ñ' => 'n',
+        'ò'..='ö' | 'ø' | 'Ò'..='Ö' | 'Ø' => 'o',
+        'ù'..='ü' | 'Ù'..='Ü' => 'u',
+        'ý' | 'ÿ' | 'Ý' => 'y',
+        c if c.is_ascii_alphanumeric() => c.to_ascii_lowercase(),
+        _ => return None,
+    };
+    Some(folded)
+}
+
+fn key_part(text: &str) -> String {
+    text.chars().filter_map(fold_to_ascii).collect()
+}
+
+const KEY_STOP_WORDS: [&str; 8] = ["a", "an", "the", "on", "of", "in", "for", "to"];
+
+/// The key before deduplication, e.g. `vaswani2017attention`.
+pub fn base_key(paper: &AcademicPaper) -> String {
+    let family = paper
+        .authors
+        .first()
+        .map(|author| key_part(&split_name(&author.name).0))
+        .filter(|family| !family.is_empty())
+        .unwrap_or_else(|| "anonymous".to_string());
+    let year = publication_year(paper).map_or(String::new(), |year| year.to_string());
+    let word = paper
+        .title
+        .split_whitespace()
+        .map(key_part)
+        .find(|word| !word.is_empty() && !KEY_STOP_WORDS.contains(&word.as_str()))
+        .unwrap_or_default();
+    format!("{}{}{}", family, year, word)
+}
+
+/// The shortest suffix of a citation key, taken from the paper id.
+const KEY_SUFFIX_LEN: usize = 4;
+
+/// The key of `paper`, e.g. `vaswani2017attention3f9a`.
+///
+/// The suffix is taken from the paper id, so that the key stays the same in every export and in the stored BibTeX.
+pub fn citation_key(paper: &AcademicPaper) -> String {
+    key_with_suffix(paper, KEY_SUFFIX_LEN)
+}
+
+fn key_with_suffix(paper: &AcademicPaper, suffix_len: usize) -> String {
+    let id = paper.paper_id.raw().simple().to_string();
+    format!("{}{}", base_key(paper), &id[..suffix_len.min(id.len())])
+}
+
+/// Unique keys for `papers`, in order.
+///
+/// Every paper gets its [`citation_key`]; the rare papers whose keys still collide get more characters of their ids.
+pub fn citation_keys(papers: &[AcademicPaper]) -> Vec<String> {
+    let mut keys = papers.iter().map(citation_key).collect::<Vec<String>>();
+    let mut suffix_len = KEY_SUFFIX_LEN;
+    loop {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for key in keys.iter() {
+            *counts.entry(key.clone()).or_default() += 1;
+        }
+        if counts.values().all(|count| *count == 1) || suffix_len >= 32 {
+            return keys;
+        }
+        suffix_len += 1;
+        for (key, paper) in keys.iter_mut().zip(papers.iter()) {
+            if counts[key.as_str()] > 1 {
+                *key = key_with_suffix(paper, suffix_len);
+            }
+        }
+    }
+}
+
+/// The year of the venue, else the year of the publication date if it is known (unknown dates are stored as 1970-01-01).
+fn publication_year(paper: &AcademicPaper) -> Option<i32> {
+    paper
+        .venue_year
+        .or_else(|| Some(paper.published_date.year()).filter(|year| *year > 1970))
+}
+
+/// Whether the publication date is in the year the paper is cited with, so it can be cited with its day.
+fn is_dated(paper: &AcademicPaper) -> bool {
+    publication_year(paper) == Some(paper.published_date.year())
+}
+
+/// Escapes the characters that are special in LaTeX.
+pub fn escape_latex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A BibTeX entry of the paper: `@article`, `@inproceedings`, or `@misc` with `eprint` for arXiv preprints.
+pub fn to_bibtex(paper: &AcademicPaper, key: &str) -> String {
+    let venue = Venue::of_paper(paper);
+    let entry_type = match venue {
+        Venue::Journal(_) => "article",
+        Venue::Conference(_) => "inproceedings",
+        Venue::ArXiv { .. } | Venue::Unknown => "misc",
+    };
+    let mut fields: Vec<(&str, String)> = vec![("title", paper.title.clone())];
+    if !paper.authors.is_empty() {
+        let authors = paper.authors.iter().map(inverted_name).collect::<Vec<String>>();
+        fields.push(("author", authors.join(" and ")));
+    }
+    match &venue {
+        Venue::Journal(name) => fields.push(("journal", name.clone())),
+        Venue::Conference(name) => fields.push(("booktitle", name.clone())),
+        Venue::ArXiv { eprint, primary_class } => {
+            fields.push(("eprint", eprint.clone()));
+            fields.push(("archivePrefix", "arXiv".to_string()));
+            if !primary_class.is_empty() {
+                fields.push(("primaryClass", primary_class.clone()));
+            }
+        }
+        Venue::Unknown => {}
+    }
+    if let Some(year) = publication_year(paper) {
+        fields.push(("year", year.to_string()));
+    }
+    if !paper.doi.is_empty() {
+        fields.push(("doi", paper.doi.clone()));
+    }
+    if !paper.url.is_empty() {
+        fields.push(("url", paper.url.clone()));
+    }
+
+    let body = fields
+        .into_iter()
+        .map(|(name, value)| {
+            // DOIs and URLs are read verbatim by the `doi`/`url` packages
+            let value = match name {
+                "doi" | "url" => value,
+                _ => escape_latex(&value),
+            };
+            format!("    {} = {{{}}}", name, value)
+        })
+        .collect::<Vec<String>>()
+        .join(",\n");
+    format!("@{}{{{},\n{}\n}}", entry_type, key, body)
+}
+
+/// An RIS record of the paper.
+pub fn to_ris(paper: &AcademicPaper, key: &str) -> String {
+    let venue = Venue::of_paper(paper);
+    let entry_type = match venue {
+        Venue::Journal(_) => "JOUR",
+        Venue::Conference(_) => "CONF",
+        Venue::ArXiv { .. } => "UNPB",
+        Venue::Unknown => "GEN",
+    };
+    let mut lines = vec![format!("TY  - {}", entry_type), format!("ID  - {}", key)];
+    lines.push(format!("TI  - {}", paper.title));
+    for author in paper.authors.iter() {
+        lines.push(format!("AU  - {}", inverted_name(author)));
+    }
+    match &venue {
+        Venue::Journal(name) => lines.push(format!("JO  - {}", name)),
+        Venue::Conference(name) => lines.push(format!("T2  - {}", name)),
+        Venue::ArXiv { eprint, .. } => {
+            lines.push("PB  - arXiv".to_string());
+            lines.push(format!("M1  - arXiv:{}", eprint));
+        }
+        Venue::Unknown => {}
+    }
+    if let Some(year) = publication_year(paper) {
+        lines.push(format!("PY  - {}", year));
+        if is_dated(paper) {
+            lines.push(format!("DA  - {}", paper.published_date.format("%Y/%m/%d")));
+        }
+    }
+    if !paper.doi.is_empty() {
+        lines.push(format!("DO  - {}", paper.doi));
+    }
+    if !paper.url.is_empty() {
+        lines.push(format!("UR  - {}", paper.url));
+    }
+    if !paper.abstract_text.is_empty() {
+        lines.push(format!(
+            "AB  - {}",
+            paper.abstract_text.split_whitespace().collect::<Vec<&str>>().join(" ")
+        ));
+    }
+    lines.push("ER  - ".to_string());
+    lines.join("\n")
+}
+
+/// A CSL-JSON item of the paper.
+pub fn to_csl_json(paper: &AcademicPaper, key: &str) -> serde_json::Value {
+    let venue = Venue::of_paper(paper);
+    let mut item = serde_json::json!({
+        "id": key,
+        "type": match venue {
+            Venue::Journal(_) => "article-journal",
+            Venue::Conference(_) => "paper-conference",
+            Venue::ArXiv { .. } | Venue::Unknown => "article",
+        },
+        "title": paper.title,
+        "author": paper
+            .authors
+            .iter()
+            .map(|author| {
+                let (family, given) = split_name(&author.name);
+                serde_json::json!({ "family": family, "given": given })
+            })
+            .collect::<Vec<serde_json::Value>>(),
+    });
+    let fields = item.as_object_mut().expect("CSL item is an object");
+    match venue {
+        Venue::Journal(name) | Venue::Conference(name) => {
+            fields.insert("container-title".into(), name.into());
+        }
+        Venue::ArXiv { eprint, .. } => {
+            fields.insert("publisher".into(), "arXiv".into());
+            fields.insert("number".into(), format!("arXiv:{}", eprint).into());
+        }
+        Venue::Unknown => {}
+    }
+    if let Some(year) = publication_year(paper) {
+        let date = paper.published_date;
+        let date_parts = if is_dated(paper) {
+            serde_json::json!([[date.year(), date.month(), date.day()]])
+        } else {
+            serde_json::json!([[year]])
+        };
+        fields.insert("issued".into(), serde_json::json!({ "date-parts": date_parts }));
+    }
+    if !paper.doi.is_empty() {
+        fields.insert("DOI".into(), paper.doi.clone().into());
+    }
+    if !paper.url.is_empty() {
+        fields.insert("URL".into(), paper.url.clone().into());
+    }
+    if !paper.abstract_text.is_empty() {
+        fields.insert("abstract".into(), paper.abstract_text.clone().into());
+    }
+    item
+}
+
+/// Exports `papers` in `format`, with the keys of [`citation_keys`].
+pub fn export_papers(papers: &[AcademicPaper], format: BibliographyFormat) -> String {
+    let keys = citation_keys(papers);
+    let entries = papers.iter().zip(keys.iter());
+    match format {
+        BibliographyFormat::BibTeX => {
+            entries
+                .map(|(paper, key)| to_bibtex(paper, key))
+                .collect::<Vec<String>>()
+                .join("\n\n")
+                + "\n"
+        }
+        BibliographyFormat::Ris => {
+            entries
+                .map(|(paper, key)| to_ris(paper, key))
+                .collect::<Vec<String>>()
+                .join("\n\n")
+                + "\n"
+        }
+        BibliographyFormat::CslJson => {
+            let items = entries
+                .map(|(paper, key)| to_csl_json(paper, key))
+                .collect::<Vec<serde_json::Value>>();
+            serde_json::to_string_pretty(&items).expect("CSL-JSON is serializable")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::academic_paper::Journal;
+    use chrono::{Local, TimeZone};
+    use shared::id::{AcademicPaperId, AuthorId, JournalId};
+    use std::str::FromStr;
+
+    fn paper(title: &str, authors: &[&str], journal: &str, arxiv_id: &str, year: i32) -> AcademicPaper {
+        AcademicPaper {
+            title: title.to_string(),
+            authors: authors
+                .iter()
+                .map(|name| Author::new(AuthorId::new(), String::new(), name.to_string(), 0))
+                .collect(),
+            journal: Journal::new(JournalId::new(), journal.to_string()),
+            arxiv_id: arxiv_id.to_string(),
+            primary_category: "cs.CL".to_string(),
+            published_date: Local.with_ymd_and_hms(year, 6, 12, 0, 0, 0).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_venue_of_paper() {
+        let preprint = paper("T", &[], "ArXiv", "http://arxiv.org/abs/2301.00001v2", 2023);
+        assert_eq!(
+            Venue::of_paper(&preprint),
+            Venue::ArXiv {
+                eprint: "2301.00001".into(),
+                primary_class: "cs.CL".into()
+            }
+        );
+        let conference = paper("T", &[], "Neural Information Processing Systems", "", 2017);
+        assert!(matches!(Venue::of_paper(&conference), Venue::Conference(_)));
+        let acronym = paper("T", &[], "Findings of EMNLP", "", 2023);
+        assert!(matches!(Venue::of_paper(&acronym), Venue::Conference(_)));
+        let journal = paper("T", &[], "Nature", "", 2020);
+        assert_eq!(Venue::of_paper(&journal), Venue::Journal("Nature".into()));
+        assert_eq!(
+            Venue::of_paper(&paper("T", &[], "Unknown Journal", "", 2020)),
+            Venue::Unknown
+        );
+        let mut canonical = paper("T", &[], "NeurIPS", "", 2017);
+        assert_eq!(
+            Venue::of_paper(&canonical),
+            Venue::Conference("Advances in Neural Information Processing Systems".into())
+        );
+        canonical.journal.venue_type = Some(VenueType::Workshop);
+        canonical.journal.name = "Instruction Tuning Workshop".into();
+        assert_eq!(
+            Venue::of_paper(&canonical),
+            Venue::Conference("Instruction Tuning Workshop".into())
+        );
+    }
+
+    #[test]
+    fn test_to_bibtex() {
+        let attention = paper(
+            "Attention Is All You Need",
+            &["Ashish Vaswani", "Noam Shazeer"],
+            "Neural Information Processing Systems",
+            "",
+            2017,
+        );
+        assert_eq!(
+            to_bibtex(&attention, "vaswani2017attention"),
+            "@inproceedings{vaswani2017attention,
+    title = {Attention Is All You Need},
+    author = {Vaswani, Ashish and Shazeer, Noam},
+    booktitle = {Neural Information Processing Systems},
+    year = {2017}
+}"
+        );
+
+        let mut preprint = paper(
+            "100% of R&D_{x}",
+            &["Müller, Jörg"],
+            "arXiv",
+            "http://arxiv.org/abs/2301.00001v1",
+            2023,
+        );
+        preprint.doi = "10.48550/arXiv.2301.00001".into();
+        let bibtex = to_bibtex(&preprint, &base_key(&preprint));
+        assert!(bibtex.starts_with("@misc{muller2023100,\n"), "{}", bibtex);
+        assert!(bibtex.contains(r"title = {100\% of R\&D\_\{x\}}"), "{}", bibtex);
+        assert!(bibtex.contains("author = {Müller, Jörg}"), "{}", bibtex);
+        assert!(bibtex.contains("eprint = {2301.00001},\n    archivePrefix = {arXiv},\n    primaryClass = {cs.CL}"));
+        assert!(bibtex.contains("doi = {10.48550/arXiv.2301.00001}"));
+    }
+
+    #[test]
+    fn test_citation_keys() {
+        let mut papers = vec![
+            paper("The Attention Mechanism", &["Ada Lovelace"], "Nature", "", 2024),
+            paper("Attention, again", &["Ada Lovelace"], "Nature", "", 2024),
+            paper("A Survey", &["Alan Turing"], "Nature", "", 2024),
+            paper("Untitled", &[], "Nature", "", 1970),
+        ];
+        let ids = [
+            "3f9a1000-0000-4000-8000-000000000001",
+            "3f9a2000-0000-4000-8000-000000000002",
+            "c0de0000-0000-4000-8000-000000000003",
+            "0a1b0000-0000-4000-8000-000000000004",
+        ];
+        for (paper, id) in papers.iter_mut().zip(ids) {
+            paper.paper_id = AcademicPaperId::from_str(id).unwrap();
+        }
+        assert_eq!(citation_key(&papers[0]), "lovelace2024attention3f9a");
+        // the keys that collide in the first characters of the ids get more of them
+        assert_eq!(
+            citation_keys(&papers),
+            vec![
+                "lovelace2024attention3f9a1",
+                "lovelace2024attention3f9a2",
+                "turing2024surveyc0de",
+                "anonymousuntitled0a1b"
+            ]
+        );
+        // the keys do not depend on the other papers of the export
+        assert_eq!(
+            citation_keys(&papers[2..]),
+            vec!["turing2024surveyc0de", "anonymousuntitled0a1b"]
+        );
+    }
+
+    #[test]
+    fn test_export_papers() {
+        let mut papers = vec![paper("A Paper", &["Ada Lovelace"], "Journal of Tests", "", 2024)];
+        papers[0].paper_id = AcademicPaperId::from_str("5eed0000-0000-4000-8000-000000000000").unwrap();
+        let ris = export_papers(&papers, BibliographyFormat::Ris);
+        assert!(ris.starts_with("TY  - JOUR\nID  - lovelace2024paper5eed\nTI  - A Paper\nAU  - Lovelace, Ada\nJO  - Journal of Tests\nPY  - 2024\nDA  - 2024/06/12\n"));
+        assert!(ris.ends_with("ER  - \n"));
+
+        let csl =
+            serde_json::from_str::<serde_json::Value>(&export_papers(&papers, BibliographyFormat::CslJson)).unwrap();
+        assert_eq!(csl[0]["type"], "article-journal");
+        assert_eq!(csl[0]["author"][0]["family"], "Lovelace");
+        assert_eq!(csl[0]["issued"]["date-parts"][0][0], 2024);
+        assert_eq!(csl[0]["container-title"], "Journal of Tests");
+
+        // a preprint of 2017 published at the conference of 2018 is cited with the year of the conference
+        let mut published = paper("A Paper", &["Ada Lovelace"], "ICLR", "", 2017);
+        published.venue_year = Some(2018);
+        assert!(to_bibtex(&published, &base_key(&published)).starts_with("@inproceedings{lovelace2018paper,"));
+        let ris = to_ris(&published, "key");
+        assert!(ris.contains("PY  - 2018\n") && !ris.contains("DA  - "), "{}", ris);
+        assert_eq!(
+            to_csl_json(&published, "key")["issued"]["date-parts"],
+            serde_json::json!([[2018]])
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use crate::models::academic_paper::{Journal, Task};
+use chrono::{DateTime, Datelike, Local, Months, NaiveDate};
+use derive_new::new;
+use shared::id::AcademicPaperId;
+
+/// A paper listed under a task, a journal or an author.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct PaperBrief {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub published_date: NaiveDate,
+    /// When the paper was added to the library.
+    pub created_at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct BrowseListOptions {
+    /// Only the entries whose name contains this, case-insensitively.
+    pub name: Option<String>,
+    /// The number of the most recently added papers listed for each entry.
+    pub recent_count: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// A task and the papers of the library on it.
+#[derive(Debug, Clone, new)]
+pub struct TaskSummary {
+    pub task: Task,
+    pub paper_count: i64,
+    /// The most recently added papers, newest first.
+    pub recent_papers: Vec<PaperBrief>,
+}
+
+/// A journal and the papers of the library published in it.
+#[derive(Debug, Clone, new)]
+pub struct JournalSummary {
+    pub journal: Journal,
+    pub paper_count: i64,
+    /// The most recently added papers, newest first.
+    pub recent_papers: Vec<PaperBrief>,
+}
+
+/// The papers added to the library in the month starting on `month`.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct MonthlyPaperCount {
+    /// The first day of the month.
+    pub month: NaiveDate,
+    pub paper_count: i64,
+}
+
+/// The papers on a task added per month.
+#[derive(Debug, Clone, new)]
+pub struct TaskTrend {
+    pub task: Task,
+    /// The papers added in all the months.
+    pub total: i64,
+    /// Every month from the first to the last one, including those without papers.
+    pub months: Vec<MonthlyPaperCount>,
+}
+
+/// The first day of the month of `date`.
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("The first day of a month is a valid date")
+}
+
+/// The trends of the tasks from `first_month` to `last_month`, the task with the most papers first.
+///
+/// `counts` has a count for each task and month with papers; the other months are filled with zero.
+pub fn task_trends(
+    counts: Vec<(Task, MonthlyPaperCount)>,
+    first_month: NaiveDate,
+    last_month: NaiveDate,
+) -> Vec<TaskTrend> {
+    let (first_month, last_month) = (month_start(first_month), month_start(last_month));
+    let mut months = vec![];
+    let mut month = first_month;
+    while month <= last_month {
+        months.push(month);
+        month = month + Months::new(1);
+    }
+
+    let mut trends: Vec<TaskTrend> = vec![];
+    for (task, count) in counts.into_iter() {
+        let task_id = task.task_id;
+        let index = match trends.iter().position(|trend| trend.task.task_id == task_id) {
+            Some(index) => index,
+            None => {
+                let empty = months.iter().map(|month| MonthlyPaperCount::new(*month, 0)).collect();
+                trends.push(TaskTrend::new(task, 0, empty));
+                trends.len() - 1
+            }
+        };
+        let trend = &mut trends[index];
+        if let Some(slot) = trend
+            .months
+            .iter_mut()
+            .find(|slot| slot.month == month_start(count.month))
+        {
+            slot.paper_count += count.paper_count;
+            trend.total += count.paper_count;
+        }
+    }
+    // stable sort keeps the order of `counts` between tasks with as many papers
+    trends.sort_by_key(|trend| std::cmp::Reverse(trend.total));
+    trends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::id::TaskId;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_task_trends() {
+        let (qa, summarization) = (
+            Task::new(TaskId::new(), "Question Answering".to_string()),
+            Task::new(TaskId::new(), "Summarization".to_string()),
+        );
+        let counts = vec![
+            (summarization.clone(), MonthlyPaperCount::new(date(2025, 12, 1), 1)),
+            (qa.clone(), MonthlyPaperCount::new(date(2025, 11, 1), 2)),
+            (qa.clone(), MonthlyPaperCount::new(date(2026, 1, 1), 3)),
+            // outside of the months
+            (qa.clone(), MonthlyPaperCount::new(date(2025, 9, 1), 4)),
+        ];
+        let trends = task_trends(counts, date(2025, 11, 15), date(2026, 1, 31));
+        assert_eq!(
+            trends
+                .iter()
+                .map(|trend| (trend.task.name.as_str(), trend.total))
+                .collect::<Vec<_>>(),
+            vec![("Question Answering", 5), ("Summarization", 1)]
+        );
+        assert_eq!(
+            trends[0].months,
+            vec![
+                MonthlyPaperCount::new(date(2025, 11, 1), 2),
+                MonthlyPaperCount::new(date(2025, 12, 1), 0),
+                MonthlyPaperCount::new(date(2026, 1, 1), 3),
+            ]
+        );
+        assert!(task_trends(vec![], date(2026, 1, 1), date(2025, 12, 1)).is_empty());
+    }
+}
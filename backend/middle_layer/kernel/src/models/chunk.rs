@@ -0,0 +1,223 @@
+use crate::models::academic_paper::PaperSection;
+use anyhow::Result;
+use derive_new::new;
+use std::ops::Range;
+use tiktoken_rs::{o200k_base, CoreBPE};
+
+/// A group of consecutive paper sections that fits into a single LLM request.
+#[derive(Debug, Clone, Default, new)]
+pub struct TextChunk {
+    pub section_titles: Vec<String>,
+    pub text: String,
+    pub token_count: usize,
+}
+
+impl TextChunk {
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    fn push(&mut self, title: &str, body: &str, token_count: usize) {
+        if !self.text.is_empty() {
+            self.text.push_str("\n\n");
+        }
+        self.section_titles.push(title.to_string());
+        self.text.push_str(body);
+        self.token_count += token_count;
+    }
+}
+
+/// Counts the number of tokens in `text` with the encoding used by the chat models.
+pub fn count_tokens(text: &str) -> Result<usize> {
+    let bpe = o200k_base()?;
+    Ok(bpe.encode_with_special_tokens(text).len())
+}
+
+/// Groups `sections` into chunks of at most `max_tokens` tokens, counting the `# title` headers.
+///
+/// Sections are kept whole and in order whenever possible. A section that is larger than
+/// `max_tokens` on its own is split on line boundaries, and its parts are titled `title (i/n)`.
+pub fn chunk_sections(sections: &[PaperSection], max_tokens: usize) -> Result<Vec<TextChunk>> {
+    let bpe = o200k_base()?;
+    let max_tokens = max_tokens.max(1);
+    let separator_tokens = bpe.encode_with_special_tokens("\n\n").len();
+
+    let mut chunks: Vec<TextChunk> = vec![];
+    let mut current = TextChunk::default();
+    for section in sections.iter() {
+        let body = format!("# {}\n\n{}", section.title, section.content);
+        let token_count = bpe.encode_with_special_tokens(&body).len();
+
+        if token_count > max_tokens {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            // reserve the header of the parts, whose numbering is not known until the section is split
+            let header = format!("# {} (9999/9999)\n\n", section.title);
+            let header_tokens = bpe.encode_with_special_tokens(&header).len();
+            let parts = split_text(&bpe, &section.content, max_tokens.saturating_sub(header_tokens).max(1));
+            let num_parts = parts.len();
+            for (i, part) in parts.into_iter().enumerate() {
+                let title = format!("{} ({}/{})", section.title, i + 1, num_parts);
+                let body = format!("# {}\n\n{}", title, part);
+                let token_count = bpe.encode_with_special_tokens(&body).len();
+                let mut chunk = TextChunk::default();
+                chunk.push(&title, &body, token_count);
+                chunks.push(chunk);
+            }
+            continue;
+        }
+
+        if !current.is_empty() && current.token_count + separator_tokens + token_count > max_tokens {
+            chunks.push(std::mem::take(&mut current));
+        }
+        let token_count = if current.is_empty() {
+            token_count
+        } else {
+            separator_tokens + token_count
+        };
+        current.push(&section.title, &body, token_count);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    Ok(chunks)
+}
+
+/// Splits `text` into parts of at most `max_tokens` tokens, preferring line boundaries.
+fn split_text(bpe: &CoreBPE, text: &str, max_tokens: usize) -> Vec<String> {
+    let mut parts: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut current_tokens = 0;
+    for line in text.lines() {
+        let line_tokens = bpe.encode_with_special_tokens(line).len() + 1;
+        if line_tokens > max_tokens {
+            if !current.is_empty() {
+                parts.push(std::mem::take(&mut current));
+                current_tokens = 0;
+            }
+            parts.extend(split_line(bpe, line, max_tokens));
+            continue;
+        }
+        if !current.is_empty() && current_tokens + line_tokens > max_tokens {
+            parts.push(std::mem::take(&mut current));
+            current_tokens = 0;
+        }
+        current.push_str(line);
+        current.push('\n');
+        current_tokens += line_tokens;
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Cuts a line that is too long by characters, shrinking the pieces until every piece fits into `max_tokens`.
+fn split_line(bpe: &CoreBPE, line: &str, max_tokens: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let line_tokens = bpe.encode_with_special_tokens(line).len().max(1);
+    // start from the characters per token of the whole line
+    let mut chars_per_part = (chars.len() * max_tokens / line_tokens).max(1);
+    loop {
+        let pieces = chars
+            .chunks(chars_per_part)
+            .map(|piece| piece.iter().collect::<String>())
+            .collect::<Vec<String>>();
+        if chars_per_part == 1
+            || pieces
+                .iter()
+                .all(|piece| bpe.encode_with_special_tokens(piece).len() <= max_tokens)
+        {
+            return pieces;
+        }
+        chars_per_part = chars_per_part * 3 / 4;
+    }
+}
+
+/// Groups consecutive items of `token_counts` into ranges of at most `max_tokens` tokens to be merged into one.
+///
+/// Every group but the last has at least two items even when they exceed `max_tokens`,
+/// so that merging each group always shortens the list.
+pub fn group_by_tokens(token_counts: &[usize], max_tokens: usize) -> Vec<Range<usize>> {
+    let mut groups = vec![];
+    let mut start = 0;
+    while start < token_counts.len() {
+        let mut end = start + 1;
+        let mut total = token_counts[start];
+        while end < token_counts.len() && (end - start < 2 || total + token_counts[end] <= max_tokens) {
+            total += token_counts[end];
+            end += 1;
+        }
+        groups.push(start..end);
+        start = end;
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(title: &str, words: usize) -> PaperSection {
+        PaperSection::new(title.to_string(), vec!["token"; words].join(" "))
+    }
+
+    #[test]
+    fn test_chunk_sections_keeps_sections_in_order() {
+        let sections = vec![
+            section("Introduction", 10),
+            section("Method", 10),
+            section("Results", 10),
+        ];
+        let chunks = chunk_sections(&sections, 1000).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].section_titles, vec!["Introduction", "Method", "Results"]);
+
+        let chunks = chunk_sections(&sections, 20).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| count_tokens(&c.text).unwrap() <= 20));
+        assert!(chunks.iter().all(|c| c.token_count == count_tokens(&c.text).unwrap()));
+        assert_eq!(chunks[2].section_titles, vec!["Results"]);
+    }
+
+    #[test]
+    fn test_chunk_sections_splits_oversized_section() {
+        let mut long = section("Experiments", 0);
+        long.content = (0..50)
+            .map(|i| format!("line {} of the experiments", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let chunks = chunk_sections(&[long], 40).unwrap();
+        assert!(chunks.len() > 1);
+        assert!(chunks[0].section_titles[0].starts_with("Experiments (1/"));
+        assert!(chunks.iter().all(|c| count_tokens(&c.text).unwrap() <= 40));
+        let joined = chunks.iter().map(|c| c.text.clone()).collect::<String>();
+        assert!(joined.contains("line 0 of the experiments"));
+        assert!(joined.contains("line 49 of the experiments"));
+    }
+
+    #[test]
+    fn test_chunk_sections_cuts_long_lines() {
+        let mut long = section("Appendix", 0);
+        long.content = "一二三四五六七八九十".repeat(40);
+        let chunks = chunk_sections(&[long.clone()], 30).unwrap();
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| count_tokens(&c.text).unwrap() <= 30));
+        let joined = chunks
+            .iter()
+            .map(|c| c.text.split_once("\n\n").unwrap().1)
+            .collect::<String>();
+        assert_eq!(joined, long.content);
+    }
+
+    #[test]
+    fn test_group_by_tokens() {
+        assert_eq!(group_by_tokens(&[10, 10, 10, 10, 10], 25), vec![0..2, 2..4, 4..5]);
+        assert_eq!(group_by_tokens(&[10, 10, 10], 100), vec![0..3]);
+        // pairs are merged even when they are over the budget
+        assert_eq!(group_by_tokens(&[50, 50, 50], 40), vec![0..2, 2..3]);
+        assert!(group_by_tokens(&[], 40).is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+use shared::id::AcademicPaperId;
+
+/// A paper in the citation graph, identified by its Semantic Scholar ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, new)]
+pub struct CitationNode {
+    pub ss_id: String,
+    pub title: String,
+    pub year: Option<i32>,
+    /// Set when the paper is in the library; otherwise the node is a placeholder.
+    pub paper_id: Option<AcademicPaperId>,
+}
+
+/// `citing_ss_id` cites `cited_ss_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, new)]
+pub struct CitationEdge {
+    pub citing_ss_id: String,
+    pub cited_ss_id: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, new)]
+pub struct CitationGraph {
+    pub nodes: Vec<CitationNode>,
+    pub edges: Vec<CitationEdge>,
+}
+
+/// A reference and the number of papers in the library that cite it.
+#[derive(Debug, Clone, PartialEq, Eq, new)]
+pub struct CitedReference {
+    pub node: CitationNode,
+    pub cited_by_count: i64,
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Local};
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+use shared::id::KeywordId;
+use strum::{Display, EnumString};
+
+/// What a keyword groups the papers by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+pub enum KeywordCategory {
+    /// A short label put on the papers, e.g. "rag" or "to-cite".
+    #[strum(serialize = "tag")]
+    #[serde(rename = "tag")]
+    Tag,
+    /// A named set of papers, e.g. "RAG survey" or "reading group Q3".
+    #[strum(serialize = "collection")]
+    #[serde(rename = "collection")]
+    Collection,
+}
+
+/// A user-managed tag or collection, with the number of its papers.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct Keyword {
+    pub keyword_id: KeywordId,
+    pub category: KeywordCategory,
+    pub name: String,
+    pub paper_count: i64,
+    pub created_at: DateTime<Local>,
+}
+
+/// The name trimmed and with the inner whitespace collapsed; `None` if nothing is left.
+pub fn normalize_keyword_name(name: &str) -> Option<String> {
+    let name = name.split_whitespace().collect::<Vec<&str>>().join(" ");
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_keyword_name() {
+        assert_eq!(
+            normalize_keyword_name("  RAG \t survey "),
+            Some("RAG survey".to_string())
+        );
+        assert_eq!(normalize_keyword_name("llm"), Some("llm".to_string()));
+        assert_eq!(normalize_keyword_name(" \n "), None);
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+
+/// The language of generated text (summaries, paper analyses...).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString, EnumIter)]
+pub enum Language {
+    #[default]
+    #[strum(serialize = "ja", ascii_case_insensitive)]
+    #[serde(rename = "ja")]
+    Ja,
+    #[strum(serialize = "en", ascii_case_insensitive)]
+    #[serde(rename = "en")]
+    En,
+}
+
+impl Language {
+    /// The deployment-wide output language, read from `OUTPUT_LANGUAGE` (defaults to Japanese).
+    pub fn from_env() -> Self {
+        std::env::var("OUTPUT_LANGUAGE")
+            .ok()
+            .and_then(|lang| Language::from_str(lang.trim()).ok())
+            .unwrap_or_default()
+    }
+
+    /// The name of the language as written in the (Japanese) prompts.
+    pub fn prompt_name(&self) -> &'static str {
+        match self {
+            Language::Ja => "日本語",
+            Language::En => "英語",
+        }
+    }
+
+    /// Picks the first supported language from an `Accept-Language` header value, honoring q-values.
+    pub fn from_accept_language(header: &str) -> Option<Self> {
+        let mut candidates = header
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.trim().split(';');
+                let tag = parts.next()?.trim();
+                let q = parts
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                let primary = tag.split('-').next()?;
+                Language::from_str(primary).ok().map(|lang| (lang, q))
+            })
+            .filter(|(_, q)| *q > 0.0)
+            .collect::<Vec<(Language, f32)>>();
+        // stable sort keeps the header order between equal q-values
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        candidates.first().map(|(lang, _)| *lang)
+    }
+
+    pub fn all() -> Vec<Language> {
+        Language::iter().collect()
+    }
+}
+
+/// Content generated in a specific language.
+pub trait Translation {
+    fn language(&self) -> Language;
+}
+
+/// Returns the translation in `preferred`, falling back to the deployment language and then to any translation.
+pub fn select_translation<T: Translation>(translations: &[T], preferred: Language) -> Option<&T> {
+    translations
+        .iter()
+        .find(|t| t.language() == preferred)
+        .or_else(|| translations.iter().find(|t| t.language() == Language::from_env()))
+        .or_else(|| translations.first())
+}
+
+/// Replaces the translation in the same language as `translation`, or appends it.
+pub fn upsert_translation<T: Translation>(translations: &mut Vec<T>, translation: T) {
+    match translations.iter_mut().find(|t| t.language() == translation.language()) {
+        Some(existing) => *existing = translation,
+        None => translations.push(translation),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Text(Language, &'static str);
+    impl Translation for Text {
+        fn language(&self) -> Language {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_from_accept_language() {
+        assert_eq!(
+            Language::from_accept_language("en-US,en;q=0.9,ja;q=0.8"),
+            Some(Language::En)
+        );
+        assert_eq!(
+            Language::from_accept_language("fr-FR, ja;q=0.5, en;q=0.7"),
+            Some(Language::En)
+        );
+        assert_eq!(Language::from_accept_language("ja-JP"), Some(Language::Ja));
+        assert_eq!(Language::from_accept_language("en;q=0, ja;q=0.1"), Some(Language::Ja));
+        assert_eq!(Language::from_accept_language("fr, de"), None);
+        assert_eq!(Language::from_accept_language(""), None);
+    }
+
+    #[test]
+    fn test_select_translation_falls_back() {
+        let translations = vec![Text(Language::En, "hello")];
+        assert_eq!(select_translation(&translations, Language::En).unwrap().1, "hello");
+        assert_eq!(select_translation(&translations, Language::Ja).unwrap().1, "hello");
+        assert!(select_translation::<Text>(&[], Language::Ja).is_none());
+
+        let mut translations = vec![Text(Language::Ja, "こんにちは"), Text(Language::En, "hello")];
+        assert_eq!(select_translation(&translations, Language::En).unwrap().1, "hello");
+        upsert_translation(&mut translations, Text(Language::En, "hi"));
+        assert_eq!(translations.len(), 2);
+        assert_eq!(select_translation(&translations, Language::En).unwrap().1, "hi");
+    }
+}
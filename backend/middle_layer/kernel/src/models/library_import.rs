@@ -0,0 +1,595 @@
+use crate::models::bibliography::BibliographyFormat;
+use anyhow::{anyhow, Result};
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An entry of a reference library, reduced to what is needed to find or fetch the paper.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, new)]
+pub struct BibliographyEntry {
+    /// The citation key, or the position in the file when the entry has none.
+    pub key: String,
+    pub title: String,
+    pub doi: Option<String>,
+    /// The arXiv ID without the version, e.g. `2301.00001`.
+    pub arxiv_id: Option<String>,
+    pub url: Option<String>,
+    pub year: Option<i32>,
+}
+
+/// Parses a BibTeX, RIS or CSL-JSON library.
+pub fn parse_library(text: &str, format: BibliographyFormat) -> Result<Vec<BibliographyEntry>> {
+    let entries = match format {
+        BibliographyFormat::BibTeX => parse_bibtex(text),
+        BibliographyFormat::Ris => parse_ris(text),
+        BibliographyFormat::CslJson => parse_csl_json(text)?,
+    };
+    Ok(entries)
+}
+
+fn is_new_style_arxiv_id(id: &str) -> bool {
+    match id.split_once('.') {
+        Some((yymm, number)) => {
+            yymm.len() == 4
+                && (4..=5).contains(&number.len())
+                && yymm.chars().chain(number.chars()).all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+fn is_old_style_arxiv_id(id: &str) -> bool {
+    match id.split_once('/') {
+        Some((archive, number)) => {
+            !archive.is_empty()
+                && archive.chars().all(|c| c.is_ascii_alphabetic() || c == '-' || c == '.')
+                && number.len() == 7
+                && number.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+/// Finds an arXiv ID in free text such as `arXiv:2301.00001v2`, an arXiv URL or an arXiv DOI.
+pub fn find_arxiv_id(text: &str) -> Option<String> {
+    let lowercase = text.to_ascii_lowercase();
+    for marker in ["arxiv.org/abs/", "arxiv.org/pdf/", "arxiv:", "arxiv."] {
+        for (start, _) in lowercase.match_indices(marker) {
+            let candidate = text[start + marker.len()..]
+                .trim_start()
+                .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '/' | '-')))
+                .next()
+                .unwrap_or_default();
+            let candidate = candidate.trim_end_matches(".pdf").trim_end_matches('.');
+            let id = match candidate.rfind('v') {
+                Some(i) if i + 1 < candidate.len() && candidate[i + 1..].chars().all(|c| c.is_ascii_digit()) => {
+                    &candidate[..i]
+                }
+                _ => candidate,
+            };
+            if is_new_style_arxiv_id(id) || is_old_style_arxiv_id(id) {
+                return Some(id.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// The DOI without a `doi:` or resolver prefix, unless it is blank.
+pub fn normalize_doi(doi: &str) -> Option<String> {
+    let doi = doi.trim();
+    let lowercase = doi.to_ascii_lowercase();
+    let prefix = [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ]
+    .into_iter()
+    .find(|prefix| lowercase.starts_with(prefix))
+    .map_or(0, str::len);
+    let doi = doi[prefix..].trim();
+    doi.starts_with("10.").then(|| doi.to_string())
+}
+
+fn parse_year(text: &str) -> Option<i32> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .find(|part| part.len() == 4)
+        .and_then(|year| year.parse().ok())
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Combines a LaTeX accent command with its letter, e.g. `\"o` with `o` into `ö`.
+fn accented(accent: char, letter: char) -> Option<char> {
+    let table: &[(char, &str, &str)] = &[
+        ('"', "aeiouyAEIOU", "äëïöüÿÄËÏÖÜ"),
+        ('\'', "aeiouyAEIOUY", "áéíóúýÁÉÍÓÚÝ"),
+        ('`', "aeiouAEIOU", "àèìòùÀÈÌÒÙ"),
+        ('^', "aeiouAEIOU", "âêîôûÂÊÎÔÛ"),
+        ('~', "anoANO", "ãñõÃÑÕ"),
+        ('c', "cC", "çÇ"),
+    ];
+    table
+        .iter()
+        .find(|(command, _, _)| *command == accent)
+        .and_then(|(_, letters, accented)| {
+            letters
+                .chars()
+                .position(|c| c == letter)
+                .and_then(|i| accented.chars().nth(i))
+        })
+}
+
+/// Turns a BibTeX value into plain text: accents are resolved, escapes unescaped and braces dropped.
+pub fn latex_to_text(value: &str) -> String {
+    let chars = value.chars().collect::<Vec<char>>();
+    let mut text = String::with_capacity(value.len());
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                let command = chars[i + 1];
+                if matches!(command, '"' | '\'' | '`' | '^' | '~' | 'c')
+                    && (command != 'c' || chars.get(i + 2).is_some_and(|c| matches!(c, '{' | ' ')))
+                {
+                    // \"o, \"{o} and \c{c}
+                    let mut j = i + 2;
+                    while chars.get(j).is_some_and(|c| matches!(c, '{' | ' ')) {
+                        j += 1;
+                    }
+                    if let Some(letter) = chars.get(j).copied() {
+                        text.push(accented(command, letter).unwrap_or(letter));
+                        i = j + 1;
+                        while chars.get(i) == Some(&'}') {
+                            i += 1;
+                        }
+                        continue;
+                    }
+                }
+                if command.is_ascii_alphabetic() {
+                    // commands such as \emph and \textbf keep only their argument
+                    let mut j = i + 1;
+                    while chars.get(j).is_some_and(|c| c.is_ascii_alphabetic()) {
+                        j += 1;
+                    }
+                    let name = chars[i + 1..j].iter().collect::<String>();
+                    match name.as_str() {
+                        "textbackslash" => text.push('\\'),
+                        "textasciitilde" => text.push('~'),
+                        "textasciicircum" => text.push('^'),
+                        "ss" => text.push('ß'),
+                        _ => {}
+                    }
+                    i = j;
+                    if chars.get(i) == Some(&' ') {
+                        i += 1;
+                    }
+                } else {
+                    text.push(command);
+                    i += 2;
+                }
+            }
+            '{' | '}' => i += 1,
+            '~' => {
+                text.push(' ');
+                i += 1;
+            }
+            c => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+    collapse_whitespace(&text)
+}
+
+const BIBTEX_MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+struct BibTeXParser {
+    chars: Vec<char>,
+    position: usize,
+    strings: HashMap<String, String>,
+    entries: Vec<BibliographyEntry>,
+}
+
+impl BibTeXParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn read_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let start = self.position;
+        while self.peek().is_some_and(&predicate) {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    /// Reads up to the brace or quote that closes the one at the current position.
+    fn read_delimited(&mut self) -> String {
+        let close = match self.peek() {
+            Some('"') => '"',
+            Some('(') => ')',
+            _ => '}',
+        };
+        self.position += 1;
+        let start = self.position;
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            match c {
+                '\\' => self.position += 1,
+                '{' => depth += 1,
+                '}' if depth > 0 => depth -= 1,
+                c if c == close && depth == 0 => break,
+                _ => {}
+            }
+            self.position += 1;
+        }
+        let value = self.chars[start..self.position.min(self.chars.len())].iter().collect();
+        self.position += 1;
+        value
+    }
+
+    /// Reads a field value, which may be a concatenation of quoted, braced and macro parts.
+    fn read_value(&mut self) -> String {
+        let mut value = String::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('{') | Some('"') => value.push_str(&self.read_delimited()),
+                Some(_) => {
+                    let token = self.read_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | ':' | '.'));
+                    let lowercase = token.to_lowercase();
+                    match self.strings.get(&lowercase) {
+                        Some(string) => value.push_str(string),
+                        None => match BIBTEX_MONTHS.iter().position(|month| *month == lowercase) {
+                            Some(month) => value.push_str(&(month + 1).to_string()),
+                            None => value.push_str(&token),
+                        },
+                    }
+                }
+                None => break,
+            }
+            self.skip_whitespace();
+            if self.peek() == Some('#') {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+        value
+    }
+
+    /// Reads the `name = value` pairs of an entry up to its closing brace.
+    fn read_fields(&mut self) -> HashMap<String, String> {
+        let mut fields = HashMap::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') | Some(')') => {
+                    self.position += 1;
+                    break;
+                }
+                None => break,
+                Some(_) => {
+                    let name = self.read_while(|c| !c.is_whitespace() && !matches!(c, '=' | ',' | '}' | ')'));
+                    self.skip_whitespace();
+                    if self.peek() != Some('=') {
+                        // a stray token, e.g. a missing comma
+                        if name.is_empty() {
+                            self.position += 1;
+                        }
+                        continue;
+                    }
+                    self.position += 1;
+                    let value = self.read_value();
+                    fields.insert(name.to_lowercase(), value);
+                }
+            }
+        }
+        fields
+    }
+
+    fn parse(mut self) -> Vec<BibliographyEntry> {
+        while let Some(offset) = self.chars[self.position..].iter().position(|c| *c == '@') {
+            self.position += offset + 1;
+            let entry_type = self.read_while(|c| c.is_ascii_alphanumeric()).to_lowercase();
+            self.skip_whitespace();
+            if !matches!(self.peek(), Some('{') | Some('(')) {
+                continue;
+            }
+            match entry_type.as_str() {
+                "comment" | "preamble" => {
+                    self.read_delimited();
+                }
+                "string" => {
+                    self.position += 1;
+                    for (name, value) in self.read_fields() {
+                        self.strings.insert(name, value);
+                    }
+                }
+                _ => {
+                    self.position += 1;
+                    self.skip_whitespace();
+                    let key = self.read_while(|c| !c.is_whitespace() && !matches!(c, ',' | '}' | ')'));
+                    let fields = self.read_fields();
+                    let key = if key.is_empty() {
+                        format!("#{}", self.entries.len() + 1)
+                    } else {
+                        key
+                    };
+                    self.entries.push(bibtex_entry(key, &fields));
+                }
+            }
+        }
+        self.entries
+    }
+}
+
+fn bibtex_entry(key: String, fields: &HashMap<String, String>) -> BibliographyEntry {
+    let field = |name: &str| {
+        fields
+            .get(name)
+            .map(|value| latex_to_text(value))
+            .filter(|value| !value.is_empty())
+    };
+    let doi = field("doi").and_then(|doi| normalize_doi(&doi));
+    let eprint_is_arxiv = field("archiveprefix")
+        .or_else(|| field("eprinttype"))
+        .is_none_or(|prefix| prefix.eq_ignore_ascii_case("arxiv"));
+    let arxiv_id = field("eprint")
+        .filter(|_| eprint_is_arxiv)
+        .and_then(|eprint| find_arxiv_id(&format!("arXiv:{}", eprint)))
+        .or_else(|| {
+            ["journal", "note", "url", "howpublished", "doi"]
+                .into_iter()
+                .find_map(|name| field(name).and_then(|value| find_arxiv_id(&value)))
+        });
+    BibliographyEntry {
+        key,
+        title: field("title").unwrap_or_default(),
+        doi,
+        arxiv_id,
+        url: field("url"),
+        year: field("year")
+            .or_else(|| field("date"))
+            .and_then(|year| parse_year(&year)),
+    }
+}
+
+pub fn parse_bibtex(text: &str) -> Vec<BibliographyEntry> {
+    BibTeXParser {
+        chars: text.chars().collect(),
+        position: 0,
+        strings: HashMap::new(),
+        entries: vec![],
+    }
+    .parse()
+}
+
+pub fn parse_ris(text: &str) -> Vec<BibliographyEntry> {
+    let mut entries = vec![];
+    let mut fields: Vec<(String, String)> = vec![];
+    for line in text.lines() {
+        let line = line.trim_start_matches('\u{feff}').trim_end();
+        // tags are two characters followed by "  - "
+        let Some((tag, value)) = line
+            .split_once("  -")
+            .filter(|(tag, _)| tag.len() == 2 && tag.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()))
+        else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match tag {
+            "TY" => fields = vec![],
+            "ER" => {
+                let index = entries.len() + 1;
+                entries.push(ris_entry(&fields, index));
+                fields = vec![];
+            }
+            _ => fields.push((tag.to_string(), value)),
+        }
+    }
+    entries
+}
+
+fn ris_entry(fields: &[(String, String)], index: usize) -> BibliographyEntry {
+    let field = |tags: &[&str]| {
+        tags.iter().find_map(|tag| {
+            fields
+                .iter()
+                .find(|(name, value)| name == tag && !value.is_empty())
+                .map(|(_, value)| value.clone())
+        })
+    };
+    let arxiv_id = fields
+        .iter()
+        .filter(|(name, _)| matches!(name.as_str(), "M1" | "N1" | "UR" | "L1" | "DO" | "JO" | "T2" | "AN"))
+        .find_map(|(_, value)| find_arxiv_id(value));
+    BibliographyEntry {
+        key: field(&["ID"]).unwrap_or_else(|| format!("#{}", index)),
+        title: field(&["TI", "T1", "CT", "BT"])
+            .map(|title| collapse_whitespace(&title))
+            .unwrap_or_default(),
+        doi: field(&["DO"]).and_then(|doi| normalize_doi(&doi)),
+        arxiv_id,
+        url: field(&["UR", "L1"]),
+        year: field(&["PY", "Y1", "DA"]).and_then(|year| parse_year(&year)),
+    }
+}
+
+pub fn parse_csl_json(text: &str) -> Result<Vec<BibliographyEntry>> {
+    let items = match serde_json::from_str::<serde_json::Value>(text)? {
+        serde_json::Value::Array(items) => items,
+        // a single item, or Zotero's `{"items": [...]}`
+        serde_json::Value::Object(mut object) => match object.remove("items") {
+            Some(serde_json::Value::Array(items)) => items,
+            _ => vec![serde_json::Value::Object(object)],
+        },
+        _ => return Err(anyhow!("CSL-JSON must be an array of items")),
+    };
+    let entries = items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let field = |name: &str| {
+                item.get(name)
+                    .and_then(|value| match value {
+                        serde_json::Value::String(value) => Some(value.trim().to_string()),
+                        serde_json::Value::Number(value) => Some(value.to_string()),
+                        _ => None,
+                    })
+                    .filter(|value| !value.is_empty())
+            };
+            let arxiv_id = ["number", "archive_location", "note", "URL", "DOI", "container-title"]
+                .into_iter()
+                .find_map(|name| field(name).and_then(|value| find_arxiv_id(&value)));
+            let year = item
+                .pointer("/issued/date-parts/0/0")
+                .and_then(|year| {
+                    year.as_i64()
+                        .or_else(|| year.as_str().and_then(|year| year.parse().ok()))
+                })
+                .map(|year| year as i32)
+                .or_else(|| item.pointer("/issued/raw").and_then(|raw| parse_year(raw.as_str()?)));
+            BibliographyEntry {
+                key: field("id").unwrap_or_else(|| format!("#{}", i + 1)),
+                title: field("title")
+                    .map(|title| collapse_whitespace(&title))
+                    .unwrap_or_default(),
+                doi: field("DOI").and_then(|doi| normalize_doi(&doi)),
+                arxiv_id,
+                url: field("URL"),
+                year,
+            }
+        })
+        .collect();
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_arxiv_id() {
+        assert_eq!(
+            find_arxiv_id("arXiv preprint arXiv:2301.00001v2"),
+            Some("2301.00001".into())
+        );
+        assert_eq!(
+            find_arxiv_id("https://arxiv.org/pdf/1706.03762.pdf"),
+            Some("1706.03762".into())
+        );
+        assert_eq!(find_arxiv_id("10.48550/arXiv.2106.09685"), Some("2106.09685".into()));
+        assert_eq!(
+            find_arxiv_id("http://arxiv.org/abs/hep-th/9901001v1"),
+            Some("hep-th/9901001".into())
+        );
+        assert_eq!(find_arxiv_id("Published on arxiv.org"), None);
+    }
+
+    #[test]
+    fn test_latex_to_text() {
+        assert_eq!(
+            latex_to_text(r#"{BERT}: Pre-training of \emph{Deep} Transformers"#),
+            "BERT: Pre-training of Deep Transformers"
+        );
+        assert_eq!(
+            latex_to_text(r#"M{\"u}ller and G\"odel and Fran\c{c}ois"#),
+            "Müller and Gödel and François"
+        );
+        assert_eq!(latex_to_text(r"R\&D at 100\%"), "R&D at 100%");
+    }
+
+    #[test]
+    fn test_parse_bibtex() {
+        let text = r#"
+@string{neurips = "Advances in Neural Information Processing Systems"}
+@comment{exported from Zotero}
+@inproceedings{vaswani2017attention,
+  title     = {Attention is {All} you Need},
+  author    = {Vaswani, Ashish and Shazeer, Noam},
+  booktitle = neurips # " 30",
+  year      = 2017,
+  month     = dec,
+}
+@article{devlin2018bert,
+  title={{BERT}: Pre-training of Deep Bidirectional Transformers},
+  journal={arXiv preprint arXiv:1810.04805},
+  year={2018}
+}
+@misc{hu2021lora,
+  title = "{LoRA}: Low-Rank Adaptation",
+  eprint = {2106.09685v2},
+  archivePrefix = {arXiv},
+  doi = {https://doi.org/10.48550/arXiv.2106.09685},
+}
+"#;
+        let entries = parse_bibtex(text);
+        assert_eq!(
+            entries,
+            vec![
+                BibliographyEntry::new(
+                    "vaswani2017attention".into(),
+                    "Attention is All you Need".into(),
+                    None,
+                    None,
+                    None,
+                    Some(2017)
+                ),
+                BibliographyEntry::new(
+                    "devlin2018bert".into(),
+                    "BERT: Pre-training of Deep Bidirectional Transformers".into(),
+                    None,
+                    Some("1810.04805".into()),
+                    None,
+                    Some(2018)
+                ),
+                BibliographyEntry::new(
+                    "hu2021lora".into(),
+                    "LoRA: Low-Rank Adaptation".into(),
+                    Some("10.48550/arXiv.2106.09685".into()),
+                    Some("2106.09685".into()),
+                    None,
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ris_and_csl_json() {
+        let ris = "TY  - JOUR\nTI  - A Paper\nAU  - Lovelace, Ada\nPY  - 2024///\nDO  - 10.1000/xyz\nER  - \n\nTY  - UNPB\nID  - preprint\nT1  - A Preprint\nUR  - https://arxiv.org/abs/2301.00001v1\nER  - \n";
+        let entries = parse_ris(ris);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].key, "#1");
+        assert_eq!(entries[0].doi.as_deref(), Some("10.1000/xyz"));
+        assert_eq!(entries[0].year, Some(2024));
+        assert_eq!(entries[1].key, "preprint");
+        assert_eq!(entries[1].arxiv_id.as_deref(), Some("2301.00001"));
+
+        let csl = r#"[{"id": "item1", "type": "article", "title": "A Preprint", "number": "arXiv:2301.00001", "issued": {"date-parts": [["2023", 1, 2]]}},
+                      {"type": "article-journal", "title": "A Paper", "DOI": "10.1000/XYZ"}]"#;
+        let entries = parse_csl_json(csl).unwrap();
+        assert_eq!(entries[0].key, "item1");
+        assert_eq!(entries[0].arxiv_id.as_deref(), Some("2301.00001"));
+        assert_eq!(entries[0].year, Some(2023));
+        assert_eq!(entries[1].key, "#2");
+        assert_eq!(entries[1].doi.as_deref(), Some("10.1000/XYZ"));
+        assert!(parse_csl_json("42").is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{
+    llm::{request_structured, LlmProvider, StructuredOutput},
+    models::{academic_paper::AcademicPaper, language::Language},
+};
+use derive_new::new;
+use openai_tools::common::{message::Message, role::Role, structured_output::Schema};
+use serde::Deserialize;
+use shared::errors::AppResult;
+use std::collections::HashMap;
+
+/// The largest number of papers a review is written from.
+pub const MAX_REVIEW_PAPERS: usize = 50;
+
+/// The papers of a review that share a task, by their index in the papers of the review.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct TaskGroup {
+    /// `None` for the papers without a task.
+    pub task: Option<String>,
+    pub papers: Vec<usize>,
+}
+
+/// Groups the papers by the task that is the most common in the set among their tasks,
+/// so that a paper on several tasks joins the largest group; the largest groups come first.
+pub fn group_by_task(papers: &[AcademicPaper]) -> Vec<TaskGroup> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for paper in papers.iter() {
+        let mut tasks = paper.tasks.iter().map(|task| task.name.trim()).collect::<Vec<&str>>();
+        tasks.sort_unstable();
+        tasks.dedup();
+        for task in tasks.into_iter().filter(|task| !task.is_empty()) {
+            *counts.entry(task.to_string()).or_default() += 1;
+        }
+    }
+
+    let mut groups: Vec<TaskGroup> = vec![];
+    for (index, paper) in papers.iter().enumerate() {
+        let task = paper
+            .tasks
+            .iter()
+            .map(|task| task.name.trim())
+            .filter(|task| !task.is_empty())
+            .max_by(|a, b| counts[*a].cmp(&counts[*b]).then_with(|| b.cmp(a)))
+            .map(str::to_string);
+        match groups.iter_mut().find(|group| group.task == task) {
+            Some(group) => group.papers.push(index),
+            None => groups.push(TaskGroup::new(task, vec![index])),
+        }
+    }
+    // the papers without a task go last
+    groups.sort_by(|a, b| {
+        a.task
+            .is_none()
+            .cmp(&b.task.is_none())
+            .then_with(|| b.papers.len().cmp(&a.papers.len()))
+            .then_with(|| a.task.cmp(&b.task))
+    });
+    groups
+}
+
+/// A paragraph of the review on the papers that take one approach.
+#[derive(Debug, Clone, PartialEq, Deserialize, new)]
+pub struct ReviewApproach {
+    pub name: String,
+    /// Cites the papers as `[@key]`.
+    pub paragraph: String,
+}
+
+/// The part of the review on one task.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct ReviewSection {
+    pub title: String,
+    pub approaches: Vec<ReviewApproach>,
+}
+
+/// A related-work draft citing the papers with the keys of their BibTeX export.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct LiteratureReview {
+    pub sections: Vec<ReviewSection>,
+    pub language: Language,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReviewSectionOutput {
+    pub approaches: Vec<ReviewApproach>,
+}
+
+impl StructuredOutput for ReviewSectionOutput {
+    fn json_schema() -> Schema {
+        let mut json_schema = Schema::chat_json_schema("review_section");
+        json_schema.add_array(
+            "approaches",
+            vec![
+                ("name", "アプローチの名称"),
+                (
+                    "paragraph",
+                    "そのアプローチをとる論文を[@key]の形式で引用しながら紹介する段落",
+                ),
+            ],
+        );
+        json_schema
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.approaches.is_empty() {
+            return Err("approaches must not be empty".into());
+        }
+        if self
+            .approaches
+            .iter()
+            .any(|approach| approach.name.trim().is_empty() || approach.paragraph.trim().is_empty())
+        {
+            return Err("the name and the paragraph of an approach must not be empty".into());
+        }
+        Ok(())
+    }
+}
+
+/// Removes the citations `[@key]` whose key is not in `keys`, so that the draft cites only the exported papers.
+pub fn retain_known_citations(paragraph: &str, keys: &[String]) -> String {
+    let mut result = String::with_capacity(paragraph.len());
+    let mut rest = paragraph;
+    while let Some(start) = rest.find("[@") {
+        let Some(length) = rest[start..].find(']') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let known = rest[start + 2..start + length]
+            .split(';')
+            .map(|key| key.trim().trim_start_matches('@'))
+            .filter(|key| keys.iter().any(|known| known == key))
+            .map(|key| format!("@{}", key))
+            .collect::<Vec<String>>();
+        if known.is_empty() {
+            // drop the space before the removed citation
+            let trimmed = result.trim_end().len();
+            result.truncate(trimmed);
+        } else {
+            result.push_str(&format!("[{}]", known.join("; ")));
+        }
+        rest = &rest[start + length + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+const REVIEW_SYSTEM_PROMPT: &str = r#"あなたは技術報告書の関連研究の章を執筆する研究者です．
+与えられた論文の手法を読み，アプローチごとに論文をまとめて，各アプローチの考え方と論文間の違いが伝わる段落を書きます．
+論文に言及するときは必ず与えられた引用キーを用いて[@key]の形式で引用し，与えられていないキーは使わないでください．
+誇張や論文に書かれていない主張は避けてください．
+"#;
+
+/// Writes the part of the review on the papers of a task, grouped by approach.
+///
+/// `papers` are the papers of the task with their citation keys.
+pub async fn write_review_section(
+    llm: &dyn LlmProvider,
+    task: Option<&str>,
+    papers: &[(&AcademicPaper, &str)],
+    language: Language,
+) -> AppResult<ReviewSection> {
+    let descriptions = papers
+        .iter()
+        .map(|(paper, key)| {
+            let methodology = paper
+                .translation(language)
+                .map(|translation| translation.methodology.clone())
+                .filter(|methodology| !methodology.trim().is_empty())
+                .unwrap_or_else(|| paper.abstract_text.clone());
+            format!("## [@{}] {}\n{}", key, paper.title, methodology)
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    let topic = match task {
+        Some(task) => format!("タスク「{}」", task),
+        None => "さまざまなタスク".to_string(),
+    };
+    let messages = vec![
+        Message::from_string(Role::System, REVIEW_SYSTEM_PROMPT),
+        Message::from_string(
+            Role::User,
+            format!(
+                r#"以下は{topic}に取り組む論文とその手法です．
+論文を手法のアプローチごとにまとめ，JSON形式で出力してください．すべての論文をいずれかのアプローチで引用してください．
+- [approaches] アプローチの名称 (name) と，そのアプローチの論文を[@key]の形式で引用しながら紹介する段落 (paragraph)．{language}で記述してください．
+
+{descriptions}"#,
+                topic = topic,
+                language = language.prompt_name(),
+                descriptions = descriptions,
+            ),
+        ),
+    ];
+    let output = request_structured::<ReviewSectionOutput>(llm, messages).await?;
+
+    let keys = papers.iter().map(|(_, key)| key.to_string()).collect::<Vec<String>>();
+    let other = match language {
+        Language::Ja => "その他",
+        Language::En => "Other",
+    };
+    Ok(ReviewSection::new(
+        task.unwrap_or(other).to_string(),
+        output
+            .approaches
+            .into_iter()
+            .map(|approach| {
+                ReviewApproach::new(
+                    approach.name.trim().to_string(),
+                    retain_known_citations(approach.paragraph.trim(), &keys),
+                )
+            })
+            .collect(),
+    ))
+}
+
+impl LiteratureReview {
+    /// The draft as Markdown with pandoc citations, to be rendered with the BibTeX export of the same papers.
+    pub fn to_markdown(&self) -> String {
+        let heading = match self.language {
+            Language::Ja => "関連研究",
+            Language::En => "Related Work",
+        };
+        let mut lines = vec![format!("# {}", heading)];
+        for section in self.sections.iter() {
+            lines.extend([String::new(), format!("## {}", section.title)]);
+            for approach in section.approaches.iter() {
+                lines.extend([String::new(), format!("**{}.** {}", approach.name, approach.paragraph)]);
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{academic_paper::Task, bibliography::citation_keys};
+    use shared::id::{AcademicPaperId, TaskId};
+    use std::str::FromStr;
+
+    fn paper(tasks: &[&str]) -> AcademicPaper {
+        AcademicPaper {
+            tasks: tasks
+                .iter()
+                .map(|name| Task::new(TaskId::new(), name.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_group_by_task() {
+        let papers = vec![
+            paper(&["Question Answering", "Retrieval"]),
+            paper(&["Retrieval"]),
+            paper(&[]),
+            paper(&["Question Answering"]),
+            paper(&["Summarization"]),
+            paper(&["Retrieval", "Summarization"]),
+        ];
+        assert_eq!(
+            group_by_task(&papers),
+            vec![
+                TaskGroup::new(Some("Retrieval".to_string()), vec![0, 1, 5]),
+                TaskGroup::new(Some("Question Answering".to_string()), vec![3]),
+                TaskGroup::new(Some("Summarization".to_string()), vec![4]),
+                TaskGroup::new(None, vec![2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retain_known_citations() {
+        let keys = vec!["hu2021lora".to_string(), "houlsby2019parameter".to_string()];
+        assert_eq!(
+            retain_known_citations(
+                "LoRA [@hu2021lora] and X [@made2020up] and [@houlsby2019parameter; @made2020up].",
+                &keys
+            ),
+            "LoRA [@hu2021lora] and X and [@houlsby2019parameter]."
+        );
+        assert_eq!(retain_known_citations("No citation [@", &keys), "No citation [@");
+    }
+
+    #[test]
+    fn test_citation_keys_of_review() {
+        // papers that share the key before the suffix, as the papers of a review often do
+        let mut library = vec![paper(&["Retrieval"]), paper(&["Retrieval"]), paper(&["Summarization"])];
+        for (paper, id) in library.iter_mut().zip(["1a", "2b", "3c"]) {
+            paper.paper_id = AcademicPaperId::from_str(&format!("{}000000-0000-4000-8000-000000000000", id)).unwrap();
+            paper.title = "Retrieval Augmented Generation".to_string();
+            paper.fill_bibtex().unwrap();
+        }
+        let library_keys = citation_keys(&library);
+        let review = library[1..].to_vec();
+        for (paper, key) in review.iter().zip(citation_keys(&review)) {
+            assert!(paper.bibtex.contains(&format!("{{{},", key)), "{}", paper.bibtex);
+            assert!(library_keys.contains(&key));
+        }
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let review = LiteratureReview::new(
+            vec![ReviewSection::new(
+                "Fine-tuning".to_string(),
+                vec![ReviewApproach::new(
+                    "Low-rank adaptation".to_string(),
+                    "LoRA freezes the weights [@hu2021lora].".to_string(),
+                )],
+            )],
+            Language::En,
+        );
+        assert_eq!(
+            review.to_markdown(),
+            "# Related Work\n\n## Fine-tuning\n\n**Low-rank adaptation.** LoRA freezes the weights [@hu2021lora].\n"
+        );
+    }
+}
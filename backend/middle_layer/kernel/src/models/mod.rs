@@ -1,4 +1,22 @@
 pub mod academic_paper;
+pub mod author;
+pub mod bibliography;
+pub mod browse;
+pub mod chunk;
+pub mod citation;
+pub mod keyword;
+pub mod language;
+pub mod library_import;
 pub mod list;
+pub mod literature_review;
+pub mod paper_candidate;
+pub mod paper_chunk;
+pub mod paper_comparison;
+pub mod paper_duplicate;
+pub mod paper_metadata;
 pub mod paper_note;
+pub mod paper_version;
+pub mod reading;
+pub mod task;
+pub mod venue;
 pub mod web_article;
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Local};
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+use shared::id::{AcademicPaperId, PaperCandidateId};
+use strum::{Display, EnumString};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Display, EnumString)]
+pub enum CandidateStatus {
+    /// Found by the watcher but not added to the library.
+    #[default]
+    #[strum(serialize = "candidate")]
+    #[serde(rename = "candidate")]
+    Candidate,
+    #[strum(serialize = "added")]
+    #[serde(rename = "added")]
+    Added,
+    #[strum(serialize = "dismissed")]
+    #[serde(rename = "dismissed")]
+    Dismissed,
+}
+
+/// A new arXiv submission found by `watch-arxiv`.
+#[derive(Debug, Clone, Default, PartialEq, new)]
+pub struct PaperCandidate {
+    pub candidate_id: PaperCandidateId,
+    /// arXiv ID without the version.
+    pub arxiv_id: String,
+    pub title: String,
+    pub abstract_text: String,
+    pub authors: Vec<String>,
+    pub categories: Vec<String>,
+    pub pdf_url: String,
+    pub published_date: DateTime<Local>,
+    pub score: i32,
+    /// The keywords and followed authors that contributed to `score`.
+    pub matched: Vec<String>,
+    pub status: CandidateStatus,
+    /// The paper created when the candidate was added.
+    pub paper_id: Option<AcademicPaperId>,
+}
+
+#[derive(Debug, Clone, Default, new)]
+pub struct PaperCandidateListOptions {
+    pub status: Option<CandidateStatus>,
+    pub limit: i64,
+    pub offset: i64,
+}
@@ -0,0 +1,212 @@
+use crate::{
+    llm::LlmProvider,
+    models::{academic_paper::AcademicPaper, chunk::chunk_sections},
+};
+use derive_new::new;
+use shared::{errors::AppResult, id::AcademicPaperId};
+use std::collections::HashMap;
+
+/// The size of a retrievable passage; small enough that several fit in one question.
+pub const PASSAGE_TOKENS: usize = 400;
+/// The constant of the reciprocal rank fusion, which damps the weight of the top ranks.
+const RRF_K: f64 = 60.0;
+
+/// A passage of the full text of a paper, indexed for retrieval.
+#[derive(Debug, Clone, Default, PartialEq, new)]
+pub struct PaperChunk {
+    pub paper_id: AcademicPaperId,
+    pub position: i32,
+    pub section_titles: Vec<String>,
+    pub content: String,
+    /// `None` if the LLM provider could not embed it; such passages are found by keywords only.
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// A passage with the full text search rank of the question, `0.0` if no keyword matched.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct PaperChunkMatch {
+    pub chunk: PaperChunk,
+    pub lexical_rank: f32,
+}
+
+/// A passage selected for a question, with its fused retrieval score.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct RetrievedPassage {
+    pub chunk: PaperChunk,
+    pub score: f64,
+}
+
+/// Splits the sections of `paper` into passages and embeds them.
+/// The passages are kept without embeddings when the provider cannot embed.
+pub async fn build_paper_chunks(paper: &AcademicPaper, llm: &dyn LlmProvider) -> AppResult<Vec<PaperChunk>> {
+    let chunks = chunk_sections(&paper.sections_for_summary(), PASSAGE_TOKENS)?;
+    let texts = chunks.iter().map(|chunk| chunk.text.clone()).collect::<Vec<String>>();
+    let embeddings = if texts.is_empty() {
+        vec![]
+    } else {
+        llm.embed(texts).await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to embed the passages of {}: {}", paper.title, e);
+            vec![]
+        })
+    };
+    let mut embeddings = embeddings.into_iter();
+    Ok(chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            PaperChunk::new(
+                paper.paper_id,
+                i as i32,
+                chunk.section_titles,
+                chunk.text,
+                embeddings.next(),
+            )
+        })
+        .collect())
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    let dot = a.iter().zip(b).map(|(x, y)| (*x as f64) * (*y as f64)).sum::<f64>();
+    let norm_a = a.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+    let norm_b = b.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// The `k` passages most relevant to the question, fusing the keyword ranking and, when the question
+/// is embedded, the semantic ranking by reciprocal rank fusion. The passages are returned in reading order,
+/// and the leading `k` passages are returned when nothing matches the question.
+pub fn retrieve_passages(
+    matches: Vec<PaperChunkMatch>,
+    query_embedding: Option<&[f32]>,
+    k: usize,
+) -> Vec<RetrievedPassage> {
+    let mut scores: HashMap<i32, f64> = HashMap::new();
+
+    let mut lexical = matches
+        .iter()
+        .filter(|m| m.lexical_rank > 0.0)
+        .collect::<Vec<&PaperChunkMatch>>();
+    lexical.sort_by(|a, b| b.lexical_rank.total_cmp(&a.lexical_rank));
+    for (rank, m) in lexical.into_iter().enumerate() {
+        *scores.entry(m.chunk.position).or_default() += 1.0 / (RRF_K + rank as f64 + 1.0);
+    }
+
+    if let Some(query_embedding) = query_embedding {
+        let mut semantic = matches
+            .iter()
+            .filter_map(|m| {
+                m.chunk
+                    .embedding
+                    .as_deref()
+                    .map(|embedding| (m.chunk.position, cosine_similarity(query_embedding, embedding)))
+            })
+            .collect::<Vec<(i32, f64)>>();
+        semantic.sort_by(|a, b| b.1.total_cmp(&a.1));
+        for (rank, (position, _)) in semantic.into_iter().enumerate() {
+            *scores.entry(position).or_default() += 1.0 / (RRF_K + rank as f64 + 1.0);
+        }
+    }
+
+    if scores.is_empty() {
+        let mut passages = matches
+            .into_iter()
+            .map(|m| RetrievedPassage::new(m.chunk, 0.0))
+            .collect::<Vec<RetrievedPassage>>();
+        passages.sort_by_key(|passage| passage.chunk.position);
+        passages.truncate(k);
+        return passages;
+    }
+
+    let mut passages = matches
+        .into_iter()
+        .filter_map(|m| {
+            scores
+                .get(&m.chunk.position)
+                .map(|score| RetrievedPassage::new(m.chunk, *score))
+        })
+        .collect::<Vec<RetrievedPassage>>();
+    passages.sort_by(|a, b| b.score.total_cmp(&a.score));
+    passages.truncate(k);
+    passages.sort_by_key(|passage| passage.chunk.position);
+    passages
+}
+
+/// The passages cited as `[n]` in `answer`, where `n` is the 1-based number given in the prompt.
+pub fn cited_passages<'a>(answer: &str, passages: &'a [RetrievedPassage]) -> Vec<&'a RetrievedPassage> {
+    let mut numbers = answer
+        .split('[')
+        .skip(1)
+        .filter_map(|part| part.split_once(']'))
+        .filter_map(|(number, _)| number.parse::<usize>().ok())
+        .filter(|n| (1..=passages.len()).contains(n))
+        .collect::<Vec<usize>>();
+    numbers.sort();
+    numbers.dedup();
+    numbers.into_iter().map(|n| &passages[n - 1]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(position: i32, embedding: Option<Vec<f32>>) -> PaperChunk {
+        PaperChunk::new(
+            AcademicPaperId::default(),
+            position,
+            vec![format!("Section {}", position)],
+            String::new(),
+            embedding,
+        )
+    }
+
+    #[test]
+    fn test_retrieve_passages() {
+        let matches = vec![
+            PaperChunkMatch::new(chunk(0, Some(vec![1.0, 0.0])), 0.0),
+            PaperChunkMatch::new(chunk(1, Some(vec![0.0, 1.0])), 0.5),
+            PaperChunkMatch::new(chunk(2, Some(vec![0.7, 0.7])), 0.1),
+            PaperChunkMatch::new(chunk(3, None), 0.0),
+        ];
+
+        // Keywords only: the passages without a match are never retrieved
+        let lexical = retrieve_passages(matches.clone(), None, 3);
+        assert_eq!(lexical.iter().map(|p| p.chunk.position).collect::<Vec<_>>(), vec![1, 2]);
+
+        // Both rankings: the passages found by both outrank chunk 0, the best semantic match only
+        let fused = retrieve_passages(matches.clone(), Some(&[1.0, 0.2]), 2);
+        assert_eq!(fused.iter().map(|p| p.chunk.position).collect::<Vec<_>>(), vec![1, 2]);
+        let fused = retrieve_passages(matches.clone(), Some(&[1.0, 0.2]), 10);
+        assert_eq!(
+            fused.iter().map(|p| p.chunk.position).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        // Nothing matches: the leading passages
+        let unmatched = matches
+            .into_iter()
+            .map(|m| PaperChunkMatch::new(m.chunk, 0.0))
+            .collect::<Vec<_>>();
+        let leading = retrieve_passages(unmatched, None, 2);
+        assert_eq!(leading.iter().map(|p| p.chunk.position).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-9);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-9);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_cited_passages() {
+        let passages = (0..3)
+            .map(|i| RetrievedPassage::new(chunk(i, None), 0.0))
+            .collect::<Vec<_>>();
+        let cited = cited_passages("LoRA freezes the weights [2][1], see [2] and [7].", &passages);
+        assert_eq!(cited.iter().map(|p| p.chunk.position).collect::<Vec<_>>(), vec![0, 1]);
+    }
+}
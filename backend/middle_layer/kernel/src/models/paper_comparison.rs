@@ -0,0 +1,311 @@
+use crate::{
+    llm::{request_structured, LlmProvider, StructuredOutput},
+    models::{academic_paper::AcademicPaper, language::Language},
+};
+use chrono::{DateTime, Local};
+use derive_new::new;
+use openai_tools::common::{message::Message, role::Role, structured_output::Schema};
+use serde::Deserialize;
+use shared::{
+    errors::{AppError, AppResult},
+    id::{AcademicPaperId, PaperComparisonId},
+};
+
+/// The number of papers that can be compared at once.
+pub const MIN_COMPARED_PAPERS: usize = 2;
+pub const MAX_COMPARED_PAPERS: usize = 10;
+
+/// A paper of a comparison, as it was when compared.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct ComparedPaper {
+    /// `None` once the paper is deleted from the library.
+    pub paper_id: Option<AcademicPaperId>,
+    pub title: String,
+}
+
+/// A dimension of a comparison and the value of each paper, in the order of the papers.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct ComparisonRow {
+    pub dimension: String,
+    pub values: Vec<String>,
+}
+
+/// A comparison matrix (dimensions × papers) with a narrative synthesis.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct PaperComparison {
+    pub paper_comparison_id: PaperComparisonId,
+    pub papers: Vec<ComparedPaper>,
+    pub rows: Vec<ComparisonRow>,
+    pub synthesis: String,
+    pub language: Language,
+    /// The model that generated the comparison.
+    pub model: String,
+    pub created_at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct PaperComparisonListOptions {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// A cell of the comparison matrix as the model fills it in.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ComparisonCell {
+    pub dimension: String,
+    /// The label of the paper in the prompt: `P1`, `P2`...
+    pub paper: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaperComparisonOutput {
+    pub cells: Vec<ComparisonCell>,
+    pub synthesis: String,
+}
+
+impl StructuredOutput for PaperComparisonOutput {
+    fn json_schema() -> Schema {
+        let mut json_schema = Schema::chat_json_schema("paper_comparison");
+        json_schema.add_array(
+            "cells",
+            vec![
+                ("dimension", "比較の観点の名称"),
+                ("paper", "論文のラベル (P1, P2, ...)"),
+                ("value", "その観点における論文の内容"),
+            ],
+        );
+        json_schema.add_property(
+            "synthesis",
+            "string",
+            "論文群の共通点・相違点と，目的に応じた手法の選び方を指定された出力言語で記述してください．",
+        );
+        json_schema
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.cells.is_empty() {
+            return Err("cells must not be empty".into());
+        }
+        if self.synthesis.trim().is_empty() {
+            return Err("synthesis must not be empty".into());
+        }
+        if let Some(cell) = self.cells.iter().find(|cell| paper_index(&cell.paper).is_none()) {
+            return Err(format!("paper must be a label such as P1: {}", cell.paper));
+        }
+        Ok(())
+    }
+}
+
+/// The 0-based index of a paper label such as `P1`.
+fn paper_index(label: &str) -> Option<usize> {
+    let number = label.trim().strip_prefix('P')?.parse::<usize>().ok()?;
+    number.checked_sub(1)
+}
+
+impl PaperComparisonOutput {
+    /// The rows of the matrix in the order the dimensions first appear; the missing cells are `-`
+    /// and the cells of unknown papers are dropped.
+    pub fn to_rows(&self, paper_count: usize) -> Vec<ComparisonRow> {
+        let mut rows: Vec<ComparisonRow> = vec![];
+        for cell in self.cells.iter() {
+            let Some(index) = paper_index(&cell.paper).filter(|index| *index < paper_count) else {
+                continue;
+            };
+            let dimension = cell.dimension.trim();
+            let row = match rows.iter().position(|row| row.dimension == dimension) {
+                Some(position) => &mut rows[position],
+                None => {
+                    rows.push(ComparisonRow::new(
+                        dimension.to_string(),
+                        vec!["-".to_string(); paper_count],
+                    ));
+                    rows.last_mut().expect("A row was just pushed")
+                }
+            };
+            row.values[index] = cell.value.trim().to_string();
+        }
+        rows
+    }
+}
+
+const COMPARISON_SYSTEM_PROMPT: &str = r#"あなたは研究論文の比較分析を専門とするアナリストです．
+複数の論文の手法，データセット，結果，利点・限界を横断的に読み，どの手法をどの状況で選ぶべきかを判断するための比較表を作成します．
+比較の観点は論文群に共通して比較できるものを選び，各セルには具体的な手法名，データセット名，数値などを簡潔に記述してください．
+論文に記載がない項目は推測せず「記載なし」としてください．
+"#;
+
+fn paper_prompt(index: usize, paper: &AcademicPaper, language: Language) -> String {
+    let analysis = match paper.translation(language) {
+        Some(translation) => format!(
+            "### 手法\n{}\n### データセット\n{}\n### 結果\n{}\n### 利点・限界・今後の展望\n{}",
+            translation.methodology,
+            translation.dataset,
+            translation.results,
+            translation.advantages_limitations_and_future_work
+        ),
+        None => format!("### アブストラクト\n{}", paper.abstract_text),
+    };
+    format!("## P{}: {}\n{}", index + 1, paper.title, analysis)
+}
+
+/// Asks the model to compare the papers with their stored analyses.
+pub async fn compare_papers(
+    llm: &dyn LlmProvider,
+    papers: &[AcademicPaper],
+    language: Language,
+) -> AppResult<PaperComparison> {
+    if !(MIN_COMPARED_PAPERS..=MAX_COMPARED_PAPERS).contains(&papers.len()) {
+        return Err(AppError::Unprocessable(format!(
+            "Between {} and {} papers can be compared: {}",
+            MIN_COMPARED_PAPERS,
+            MAX_COMPARED_PAPERS,
+            papers.len()
+        )));
+    }
+
+    let messages = vec![
+        Message::from_string(Role::System, COMPARISON_SYSTEM_PROMPT),
+        Message::from_string(
+            Role::User,
+            format!(
+                r#"以下の{count}本の論文を比較して，JSON形式で出力してください．
+- [cells] 比較表のセル．5〜8個の比較の観点 (例: 手法の概要，学習データ，評価データセット，主な結果，計算コスト，限界) それぞれについて，すべての論文 (P1〜P{count}) のセルを出力してください．観点の名称と内容は{language}で記述してください．
+- [synthesis] 論文群の共通点・相違点と，目的に応じた手法の選び方を{language}で記述してください．
+
+{papers}"#,
+                count = papers.len(),
+                language = language.prompt_name(),
+                papers = papers
+                    .iter()
+                    .enumerate()
+                    .map(|(index, paper)| paper_prompt(index, paper, language))
+                    .collect::<Vec<String>>()
+                    .join("\n\n"),
+            ),
+        ),
+    ];
+    let output = request_structured::<PaperComparisonOutput>(llm, messages).await?;
+
+    Ok(PaperComparison::new(
+        PaperComparisonId::new(),
+        papers
+            .iter()
+            .map(|paper| ComparedPaper::new(Some(paper.paper_id), paper.title.clone()))
+            .collect(),
+        output.to_rows(papers.len()),
+        output.synthesis.trim().to_string(),
+        language,
+        llm.model_id(),
+        Local::now(),
+    ))
+}
+
+/// A table cell on one line, with the pipes escaped.
+fn markdown_cell(text: &str) -> String {
+    text.trim()
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+impl PaperComparison {
+    /// The comparison as a Markdown document: the matrix, the synthesis and the compared papers.
+    pub fn to_markdown(&self) -> String {
+        let (heading, dimension, synthesis, papers) = match self.language {
+            Language::Ja => ("論文比較", "観点", "総合考察", "比較した論文"),
+            Language::En => ("Paper comparison", "Dimension", "Synthesis", "Compared papers"),
+        };
+        let mut lines = vec![format!("# {}", heading), String::new()];
+        lines.push(format!(
+            "| {} | {} |",
+            dimension,
+            (1..=self.papers.len())
+                .map(|number| format!("P{}", number))
+                .collect::<Vec<String>>()
+                .join(" | ")
+        ));
+        lines.push(format!("|{}", "---|".repeat(self.papers.len() + 1)));
+        for row in self.rows.iter() {
+            lines.push(format!(
+                "| {} | {} |",
+                markdown_cell(&row.dimension),
+                row.values
+                    .iter()
+                    .map(|value| markdown_cell(value))
+                    .collect::<Vec<String>>()
+                    .join(" | ")
+            ));
+        }
+        lines.extend([String::new(), format!("## {}", synthesis), String::new()]);
+        lines.push(self.synthesis.trim().to_string());
+        lines.extend([String::new(), format!("## {}", papers), String::new()]);
+        for (index, paper) in self.papers.iter().enumerate() {
+            lines.push(format!("- P{}: {}", index + 1, paper.title));
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(dimension: &str, paper: &str, value: &str) -> ComparisonCell {
+        ComparisonCell {
+            dimension: dimension.to_string(),
+            paper: paper.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_to_rows() {
+        let output = PaperComparisonOutput {
+            cells: vec![
+                cell("手法", "P1", "LoRA"),
+                cell("データセット", "P2", "GLUE"),
+                cell("手法", "P2", "Adapter"),
+                cell("手法", "P3", "unknown paper"),
+            ],
+            synthesis: "LoRA is cheaper.".to_string(),
+        };
+        assert!(output.validate().is_ok());
+        assert_eq!(
+            output.to_rows(2),
+            vec![
+                ComparisonRow::new("手法".to_string(), vec!["LoRA".to_string(), "Adapter".to_string()]),
+                ComparisonRow::new("データセット".to_string(), vec!["-".to_string(), "GLUE".to_string()]),
+            ]
+        );
+
+        let invalid = PaperComparisonOutput {
+            cells: vec![cell("手法", "LoRA", "LoRA")],
+            synthesis: "LoRA is cheaper.".to_string(),
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let comparison = PaperComparison::new(
+            PaperComparisonId::new(),
+            vec![
+                ComparedPaper::new(Some(AcademicPaperId::new()), "LoRA".to_string()),
+                ComparedPaper::new(None, "Adapter".to_string()),
+            ],
+            vec![ComparisonRow::new(
+                "Method".to_string(),
+                vec!["low-rank | update".to_string(), "bottleneck\nlayers".to_string()],
+            )],
+            "LoRA is cheaper.".to_string(),
+            Language::En,
+            "gpt".to_string(),
+            Local::now(),
+        );
+        assert_eq!(
+            comparison.to_markdown(),
+            "# Paper comparison\n\n| Dimension | P1 | P2 |\n|---|---|---|\n| Method | low-rank \\| update | bottleneck<br>layers |\n\n## Synthesis\n\nLoRA is cheaper.\n\n## Compared papers\n\n- P1: LoRA\n- P2: Adapter\n"
+        );
+    }
+}
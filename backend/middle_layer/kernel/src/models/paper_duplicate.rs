@@ -0,0 +1,213 @@
+use crate::models::library_import::{find_arxiv_id, normalize_doi};
+use chrono::{DateTime, Local};
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+use shared::id::AcademicPaperId;
+use std::collections::HashMap;
+use strum::Display;
+
+/// Why two papers are considered the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DuplicateReason {
+    SemanticScholarId,
+    ArxivId,
+    Doi,
+    Title,
+}
+
+/// The arXiv ID without the version, from a stored `arxiv_id` such as `http://arxiv.org/abs/2301.00001v2` or `2301.00001`.
+pub fn arxiv_base_id(arxiv_id: &str) -> Option<String> {
+    let arxiv_id = arxiv_id.trim();
+    if arxiv_id.is_empty() {
+        return None;
+    }
+    find_arxiv_id(arxiv_id).or_else(|| find_arxiv_id(&format!("arXiv:{}", arxiv_id)))
+}
+
+/// The title in lowercase with only its letters and digits, so that punctuation and spacing do not matter.
+pub fn normalize_title(title: &str) -> Option<String> {
+    let title = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<String>>()
+        .join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+/// The identifiers of a paper compared to find its duplicates.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct DuplicatePaper {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub ss_id: String,
+    pub arxiv_id: String,
+    pub doi: String,
+    pub created_at: DateTime<Local>,
+}
+
+impl DuplicatePaper {
+    fn keys(&self) -> Vec<(DuplicateReason, String)> {
+        let ss_id = Some(self.ss_id.trim().to_string()).filter(|ss_id| !ss_id.is_empty());
+        [
+            (DuplicateReason::SemanticScholarId, ss_id),
+            (DuplicateReason::ArxivId, arxiv_base_id(&self.arxiv_id)),
+            (
+                DuplicateReason::Doi,
+                normalize_doi(&self.doi).map(|doi| doi.to_lowercase()),
+            ),
+            (DuplicateReason::Title, normalize_title(&self.title)),
+        ]
+        .into_iter()
+        .filter_map(|(reason, key)| key.map(|key| (reason, key)))
+        .collect()
+    }
+}
+
+/// Papers that are the same paper, the oldest first, and the identifiers they share.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct DuplicateGroup {
+    pub papers: Vec<DuplicatePaper>,
+    pub reasons: Vec<DuplicateReason>,
+}
+
+fn find_root(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    let mut index = index;
+    while parents[index] != root {
+        let next = parents[index];
+        parents[index] = root;
+        index = next;
+    }
+    root
+}
+
+/// Groups the papers that share a Semantic Scholar ID, an arXiv ID (of any version), a DOI or a title.
+///
+/// A paper joins a group through any of them, so a group may be linked by several reasons.
+/// The groups are ordered by their oldest paper.
+pub fn find_duplicates(mut papers: Vec<DuplicatePaper>) -> Vec<DuplicateGroup> {
+    papers.sort_by_key(|paper| paper.created_at);
+    let mut parents = (0..papers.len()).collect::<Vec<usize>>();
+    let mut first_with_key: HashMap<(DuplicateReason, String), usize> = HashMap::new();
+    let mut links: Vec<(usize, DuplicateReason)> = vec![];
+    for (index, paper) in papers.iter().enumerate() {
+        for key in paper.keys() {
+            let reason = key.0;
+            match first_with_key.get(&key) {
+                Some(first) => {
+                    let (a, b) = (find_root(&mut parents, *first), find_root(&mut parents, index));
+                    parents[a.max(b)] = a.min(b);
+                    links.push((index, reason));
+                }
+                None => {
+                    first_with_key.insert(key, index);
+                }
+            }
+        }
+    }
+
+    let mut groups: Vec<(usize, DuplicateGroup)> = vec![];
+    let roots = (0..papers.len())
+        .map(|index| find_root(&mut parents, index))
+        .collect::<Vec<usize>>();
+    for (index, paper) in papers.into_iter().enumerate() {
+        match groups.iter_mut().find(|(root, _)| *root == roots[index]) {
+            Some((_, group)) => group.papers.push(paper),
+            None => groups.push((roots[index], DuplicateGroup::new(vec![paper], vec![]))),
+        }
+    }
+    for (index, reason) in links {
+        if let Some((_, group)) = groups.iter_mut().find(|(root, _)| *root == roots[index]) {
+            if !group.reasons.contains(&reason) {
+                group.reasons.push(reason);
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, mut group)| {
+            group.reasons.sort();
+            group
+        })
+        .filter(|group| group.papers.len() > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn paper(day: i64, title: &str, ss_id: &str, arxiv_id: &str, doi: &str) -> DuplicatePaper {
+        DuplicatePaper::new(
+            AcademicPaperId::new(),
+            title.to_string(),
+            ss_id.to_string(),
+            arxiv_id.to_string(),
+            doi.to_string(),
+            Local.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() + Duration::days(day),
+        )
+    }
+
+    #[test]
+    fn test_arxiv_base_id() {
+        assert_eq!(
+            arxiv_base_id("http://arxiv.org/abs/2106.09685v2"),
+            Some("2106.09685".to_string())
+        );
+        assert_eq!(arxiv_base_id("2106.09685v1"), Some("2106.09685".to_string()));
+        assert_eq!(arxiv_base_id(""), None);
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let papers = vec![
+            paper(
+                2,
+                "LoRA: Low-Rank Adaptation",
+                "",
+                "http://arxiv.org/abs/2106.09685v2",
+                "",
+            ),
+            paper(
+                0,
+                "LoRA: Low-Rank Adaptation of Large Language Models",
+                "ss-lora",
+                "",
+                "",
+            ),
+            paper(
+                1,
+                "LoRA - low-rank adaptation of large language models",
+                "",
+                "2106.09685v1",
+                "",
+            ),
+            paper(3, "Attention Is All You Need", "", "", "10.5555/attention"),
+            paper(4, "Transformer", "", "", "https://doi.org/10.5555/ATTENTION"),
+            paper(5, "BERT", "", "", ""),
+        ];
+        let groups = find_duplicates(papers);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups[0].papers.iter().map(|p| p.ss_id.as_str()).collect::<Vec<_>>(),
+            vec!["ss-lora", "", ""]
+        );
+        assert_eq!(
+            groups[0].reasons,
+            vec![DuplicateReason::ArxivId, DuplicateReason::Title]
+        );
+        assert_eq!(
+            groups[1].papers.iter().map(|p| p.title.as_str()).collect::<Vec<_>>(),
+            vec!["Attention Is All You Need", "Transformer"]
+        );
+        assert_eq!(groups[1].reasons, vec![DuplicateReason::Doi]);
+    }
+}
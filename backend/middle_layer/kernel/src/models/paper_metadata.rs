@@ -0,0 +1,123 @@
+use crate::models::library_import::find_arxiv_id;
+use chrono::{DateTime, Local};
+use derive_new::new;
+use shared::id::AcademicPaperId;
+
+/// The metadata of a stored paper that keeps changing after it is added.
+/// The AI-generated summary is deliberately not part of it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaperMetadata {
+    pub paper_id: AcademicPaperId,
+    pub ss_id: String,
+    pub arxiv_id: String,
+    pub doi: String,
+    pub journal_name: String,
+    pub citations_count: i32,
+    pub influential_citation_count: i32,
+    pub references_count: i32,
+}
+
+/// A field of a paper that was changed by a metadata refresh.
+#[derive(Debug, Clone, PartialEq, Eq, new)]
+pub struct MetadataChange {
+    pub paper_id: AcademicPaperId,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub changed_at: DateTime<Local>,
+}
+
+/// The citations a paper gained since a point in time, summed over its refreshes.
+#[derive(Debug, Clone, PartialEq, Eq, new)]
+pub struct CitationGain {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub citations_count: i32,
+    pub citations_gained: i64,
+}
+
+impl PaperMetadata {
+    /// The ID to look the paper up by on Semantic Scholar: its paper ID, else `arXiv:` or `DOI:`.
+    pub fn ss_lookup_id(&self) -> Option<String> {
+        if !self.ss_id.is_empty() {
+            return Some(self.ss_id.clone());
+        }
+        if let Some(arxiv_id) =
+            find_arxiv_id(&self.arxiv_id).or_else(|| find_arxiv_id(&format!("arXiv:{}", self.arxiv_id)))
+        {
+            return Some(format!("arXiv:{}", arxiv_id));
+        }
+        (!self.doi.is_empty()).then(|| format!("DOI:{}", self.doi))
+    }
+
+    /// The fields that differ in `updated`, as `(field, old value, new value)`.
+    pub fn changes(&self, updated: &PaperMetadata) -> Vec<(&'static str, String, String)> {
+        let fields: [(&'static str, String, String); 6] = [
+            ("ss_id", self.ss_id.clone(), updated.ss_id.clone()),
+            ("doi", self.doi.clone(), updated.doi.clone()),
+            ("journal", self.journal_name.clone(), updated.journal_name.clone()),
+            (
+                "citations_count",
+                self.citations_count.to_string(),
+                updated.citations_count.to_string(),
+            ),
+            (
+                "influential_citation_count",
+                self.influential_citation_count.to_string(),
+                updated.influential_citation_count.to_string(),
+            ),
+            (
+                "references_count",
+                self.references_count.to_string(),
+                updated.references_count.to_string(),
+            ),
+        ];
+        fields.into_iter().filter(|(_, old, new)| old != new).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ss_lookup_id() {
+        let mut metadata = PaperMetadata {
+            arxiv_id: "http://arxiv.org/abs/2106.09685v2".into(),
+            doi: "10.1000/xyz".into(),
+            ..Default::default()
+        };
+        assert_eq!(metadata.ss_lookup_id().as_deref(), Some("arXiv:2106.09685"));
+        metadata.arxiv_id = String::new();
+        assert_eq!(metadata.ss_lookup_id().as_deref(), Some("DOI:10.1000/xyz"));
+        metadata.ss_id = "abc".into();
+        assert_eq!(metadata.ss_lookup_id().as_deref(), Some("abc"));
+        assert_eq!(PaperMetadata::default().ss_lookup_id(), None);
+    }
+
+    #[test]
+    fn test_changes() {
+        let old = PaperMetadata {
+            journal_name: "arXiv".into(),
+            citations_count: 10,
+            ..Default::default()
+        };
+        let new = PaperMetadata {
+            journal_name: "Neural Information Processing Systems".into(),
+            citations_count: 12,
+            ..old.clone()
+        };
+        assert_eq!(
+            old.changes(&new),
+            vec![
+                (
+                    "journal",
+                    "arXiv".into(),
+                    "Neural Information Processing Systems".into()
+                ),
+                ("citations_count", "10".into(), "12".into()),
+            ]
+        );
+        assert!(old.changes(&old).is_empty());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::models::academic_paper::AcademicPaper;
 use chrono::{DateTime, Local};
 use derive_new::new;
-use shared::id::PaperNoteId;
+use serde::{Deserialize, Serialize};
+use shared::id::{PaperNoteId, PaperNoteMessageId};
+use strum::{Display, EnumString};
 
 #[derive(Debug, Clone, Default, new)]
 pub struct PaperNote {
@@ -10,3 +12,88 @@ pub struct PaperNote {
     pub note: String,
     pub note_timestamp: DateTime<Local>,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Display, EnumString)]
+pub enum PaperNoteMessageRole {
+    /// A question to the agent.
+    #[default]
+    #[strum(serialize = "user")]
+    #[serde(rename = "user")]
+    User,
+    /// An answer of the agent.
+    #[strum(serialize = "assistant")]
+    #[serde(rename = "assistant")]
+    Assistant,
+}
+
+/// One turn of the conversation with the agent about a paper note.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaperNoteMessage {
+    pub paper_note_message_id: PaperNoteMessageId,
+    pub paper_note_id: PaperNoteId,
+    pub position: i32,
+    pub role: PaperNoteMessageRole,
+    pub content: String,
+    /// The model that generated an answer, empty for a question.
+    pub model: String,
+    pub created_at: DateTime<Local>,
+    pub updated_at: DateTime<Local>,
+}
+
+/// The question answered by the message `answer_id` and the messages before the question,
+/// or `None` if `answer_id` is not an answer in `messages`, which are in the order of the conversation.
+pub fn split_conversation(
+    messages: &[PaperNoteMessage],
+    answer_id: PaperNoteMessageId,
+) -> Option<(&[PaperNoteMessage], &PaperNoteMessage)> {
+    let answer = messages
+        .iter()
+        .position(|m| m.paper_note_message_id == answer_id && m.role == PaperNoteMessageRole::Assistant)?;
+    // The question is the message right before its answer; an answer whose question was deleted is left as it is
+    let question = answer.checked_sub(1)?;
+    match messages[question].role {
+        PaperNoteMessageRole::User => Some((&messages[..question], &messages[question])),
+        PaperNoteMessageRole::Assistant => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(position: i32, role: PaperNoteMessageRole) -> PaperNoteMessage {
+        PaperNoteMessage {
+            paper_note_message_id: PaperNoteMessageId::new(),
+            position,
+            role,
+            content: format!("message {}", position),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_split_conversation() {
+        use PaperNoteMessageRole::*;
+        let messages = vec![
+            message(0, User),
+            message(1, Assistant),
+            message(2, User),
+            message(3, Assistant),
+        ];
+
+        let (history, question) = split_conversation(&messages, messages[3].paper_note_message_id).unwrap();
+        assert_eq!(history, &messages[..2]);
+        assert_eq!(question, &messages[2]);
+
+        let (history, question) = split_conversation(&messages, messages[1].paper_note_message_id).unwrap();
+        assert!(history.is_empty());
+        assert_eq!(question, &messages[0]);
+
+        // A question is not regenerated
+        assert!(split_conversation(&messages, messages[2].paper_note_message_id).is_none());
+        // An answer whose question was deleted has nothing to answer
+        assert!(split_conversation(&messages[1..], messages[1].paper_note_message_id).is_none());
+        let without_second_question = [&messages[..2], &messages[3..]].concat();
+        assert!(split_conversation(&without_second_question, messages[3].paper_note_message_id).is_none());
+    }
+}
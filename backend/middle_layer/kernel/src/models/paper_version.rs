@@ -0,0 +1,256 @@
+use crate::{
+    llm::{request_text, LlmProvider},
+    models::{academic_paper::PaperSection, language::Language, paper_duplicate::arxiv_base_id},
+};
+use chrono::{DateTime, Local};
+use derive_new::new;
+use openai_tools::common::{message::Message, role::Role};
+use shared::{errors::AppResult, id::AcademicPaperId};
+use strum::Display;
+
+/// The characters of a section kept in the prompt for each side of a change.
+const MAX_CHANGE_CHARS: usize = 4000;
+
+/// The version of an arXiv ID such as `http://arxiv.org/abs/2106.09685v2`, if it has one.
+pub fn arxiv_version(arxiv_id: &str) -> Option<i32> {
+    let arxiv_id = arxiv_id.trim().trim_end_matches('/');
+    let (_, version) = arxiv_id.rsplit_once('v')?;
+    version.parse().ok().filter(|version| *version > 0)
+}
+
+/// A paper on arXiv whose versions are tracked.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct VersionedPaper {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub arxiv_id: String,
+    /// The latest version recorded in the version list.
+    pub latest_version: Option<i32>,
+}
+
+impl VersionedPaper {
+    /// The arXiv ID without the version.
+    pub fn base_id(&self) -> Option<String> {
+        arxiv_base_id(&self.arxiv_id)
+    }
+
+    /// The version the stored sections were parsed from: the latest recorded one, else the one in `arxiv_id`.
+    pub fn known_version(&self) -> Option<i32> {
+        self.latest_version.or_else(|| arxiv_version(&self.arxiv_id))
+    }
+}
+
+/// A version of a paper on arXiv and what changed from the previous one.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct PaperVersion {
+    pub paper_id: AcademicPaperId,
+    pub version: i32,
+    pub submitted_at: Option<DateTime<Local>>,
+    pub pdf_sha256: String,
+    /// The titles of the sections added, removed or modified since the previous version.
+    pub changed_sections: Vec<String>,
+    /// Empty for the first version seen.
+    pub change_summary: String,
+    pub detected_at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum SectionChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// A section that differs between two versions of a paper.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct SectionChange {
+    pub title: String,
+    pub kind: SectionChangeKind,
+    /// Empty for an added section.
+    pub old_content: String,
+    /// Empty for a removed section.
+    pub new_content: String,
+}
+
+/// The title without its numbering, so that "3 Method" and "4. Method" are the same section.
+fn section_key(title: &str) -> String {
+    title
+        .trim_start_matches(|c: char| c.is_ascii_digit() || c == '.' || c.is_whitespace())
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// The sections added, modified or removed in `new`, matched to `old` by title.
+/// The added and modified sections come in the order of `new`, then the removed ones.
+pub fn diff_sections(old: &[PaperSection], new: &[PaperSection]) -> Vec<SectionChange> {
+    let mut matched = vec![false; old.len()];
+    let mut changes: Vec<SectionChange> = vec![];
+    for section in new.iter() {
+        let key = section_key(&section.title);
+        let position = old
+            .iter()
+            .enumerate()
+            .position(|(i, old)| !matched[i] && section_key(&old.title) == key);
+        match position {
+            Some(i) => {
+                matched[i] = true;
+                if collapse_whitespace(&old[i].content) != collapse_whitespace(&section.content) {
+                    changes.push(SectionChange::new(
+                        section.title.clone(),
+                        SectionChangeKind::Modified,
+                        old[i].content.clone(),
+                        section.content.clone(),
+                    ));
+                }
+            }
+            None => changes.push(SectionChange::new(
+                section.title.clone(),
+                SectionChangeKind::Added,
+                String::new(),
+                section.content.clone(),
+            )),
+        }
+    }
+    for (section, _) in old.iter().zip(matched).filter(|(_, matched)| !matched) {
+        changes.push(SectionChange::new(
+            section.title.clone(),
+            SectionChangeKind::Removed,
+            section.content.clone(),
+            String::new(),
+        ));
+    }
+    changes
+}
+
+fn clip(text: &str) -> String {
+    match text.char_indices().nth(MAX_CHANGE_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn change_prompt(change: &SectionChange) -> String {
+    match change.kind {
+        SectionChangeKind::Added => format!("## 追加: {}\n{}", change.title, clip(&change.new_content)),
+        SectionChangeKind::Removed => format!("## 削除: {}\n{}", change.title, clip(&change.old_content)),
+        SectionChangeKind::Modified => format!(
+            "## 変更: {}\n### 旧版\n{}\n### 新版\n{}",
+            change.title,
+            clip(&change.old_content),
+            clip(&change.new_content)
+        ),
+    }
+}
+
+const VERSION_SYSTEM_PROMPT: &str = r#"あなたは研究論文の改訂内容を読者に伝える編集者です．
+論文の旧版と新版でのセクションの差分を読み，新しい実験・結果の数値・主張・手法の変更など，読者の理解に影響する変更を優先して要約します．
+表記の修正や言い回しの変更のような軽微な変更は，まとめて一言で触れる程度にしてください．
+差分に書かれていない変更を推測しないでください．
+"#;
+
+/// Summarizes what changed between two versions of a paper from the differing sections.
+pub async fn summarize_version_changes(
+    llm: &dyn LlmProvider,
+    title: &str,
+    from_version: Option<i32>,
+    to_version: i32,
+    changes: &[SectionChange],
+    language: Language,
+) -> AppResult<String> {
+    if changes.is_empty() {
+        return Ok(match language {
+            Language::Ja => "本文に変更はありません．",
+            Language::En => "The text is unchanged.",
+        }
+        .to_string());
+    }
+    let from = from_version.map_or_else(|| "以前の版".to_string(), |version| format!("v{}", version));
+    let messages = vec![
+        Message::from_string(Role::System, VERSION_SYSTEM_PROMPT),
+        Message::from_string(
+            Role::User,
+            format!(
+                r#"論文「{title}」の{from}からv{to}への変更点を，{language}のMarkdownの箇条書き (3〜7項目) で要約してください．
+
+{changes}"#,
+                title = title,
+                from = from,
+                to = to_version,
+                language = language.prompt_name(),
+                changes = changes.iter().map(change_prompt).collect::<Vec<String>>().join("\n\n"),
+            ),
+        ),
+    ];
+    Ok(request_text(llm, messages).await?.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(title: &str, content: &str) -> PaperSection {
+        PaperSection::new(title.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn test_arxiv_version() {
+        assert_eq!(arxiv_version("http://arxiv.org/abs/2106.09685v2"), Some(2));
+        assert_eq!(arxiv_version("2106.09685v10"), Some(10));
+        assert_eq!(arxiv_version("2106.09685"), None);
+        assert_eq!(arxiv_version("cs/0112017"), None);
+
+        let paper = VersionedPaper::new(
+            AcademicPaperId::new(),
+            "LoRA".to_string(),
+            "http://arxiv.org/abs/2106.09685v1".to_string(),
+            None,
+        );
+        assert_eq!(paper.base_id(), Some("2106.09685".to_string()));
+        assert_eq!(paper.known_version(), Some(1));
+        assert_eq!(
+            VersionedPaper {
+                latest_version: Some(3),
+                ..paper
+            }
+            .known_version(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_diff_sections() {
+        let old = vec![
+            section("1 Introduction", "We adapt   large models."),
+            section("2 Method", "Low-rank updates."),
+            section("3 Appendix", "Proofs."),
+        ];
+        let new = vec![
+            section("1. Introduction", "We adapt large models."),
+            section("2. Method", "Low-rank updates with scaling."),
+            section("3. Experiments on GLUE", "New results."),
+        ];
+        let changes = diff_sections(&old, &new);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| (change.title.as_str(), change.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("2. Method", SectionChangeKind::Modified),
+                ("3. Experiments on GLUE", SectionChangeKind::Added),
+                ("3 Appendix", SectionChangeKind::Removed),
+            ]
+        );
+        assert_eq!(changes[0].old_content, "Low-rank updates.");
+        assert_eq!(changes[0].new_content, "Low-rank updates with scaling.");
+        assert!(diff_sections(&old, &old).is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::models::academic_paper::Status;
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeZone};
+use derive_new::new;
+use shared::id::AcademicPaperId;
+
+/// A change of the reading status of a paper.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct PaperStatusChange {
+    pub paper_id: AcademicPaperId,
+    /// `None` for the first recorded status of the paper.
+    pub from_status: Option<Status>,
+    pub to_status: Status,
+    pub changed_at: DateTime<Local>,
+}
+
+/// A paper in the reading queue.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct ReadingQueueItem {
+    pub paper_id: AcademicPaperId,
+    pub title: String,
+    pub url: String,
+    pub status: Status,
+    /// Higher is read first.
+    pub priority: i32,
+    pub due_date: Option<NaiveDate>,
+    pub added_at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct ReadingQueueListOptions {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// The reading done in the week starting on `week_start`.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct WeeklyReadingStats {
+    /// The Monday of the week.
+    pub week_start: NaiveDate,
+    /// The number of papers moved to `Completed` in the week.
+    pub completed: i64,
+    /// The hours the papers spent in `DeepDive` in the week.
+    pub deep_dive_hours: f64,
+}
+
+/// The Monday of the week of `date`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+fn local_midnight(date: NaiveDate) -> DateTime<Local> {
+    let midnight = date.and_hms_opt(0, 0, 0).expect("Midnight is a valid time");
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&midnight))
+}
+
+/// The reading stats of the weeks from `first_week` to the week of `until`.
+///
+/// `changes` must include, for each paper, the last change before `first_week`,
+/// so that a deep dive started earlier is counted; a deep dive still going on counts until `until`.
+pub fn weekly_reading_stats(
+    changes: &[PaperStatusChange],
+    first_week: NaiveDate,
+    until: DateTime<Local>,
+) -> Vec<WeeklyReadingStats> {
+    let mut changes = changes.iter().collect::<Vec<&PaperStatusChange>>();
+    changes.sort_by_key(|change| (change.paper_id.raw(), change.changed_at));
+
+    let mut deep_dives: Vec<(DateTime<Local>, DateTime<Local>)> = vec![];
+    let mut started: Option<(AcademicPaperId, DateTime<Local>)> = None;
+    for change in changes.iter() {
+        if let Some((paper_id, start)) = started {
+            if paper_id != change.paper_id {
+                deep_dives.push((start, until));
+                started = None;
+            } else if change.to_status != Status::DeepDive {
+                deep_dives.push((start, change.changed_at));
+                started = None;
+            }
+        }
+        if change.to_status == Status::DeepDive && started.is_none() {
+            started = Some((change.paper_id, change.changed_at));
+        }
+    }
+    if let Some((_, start)) = started {
+        deep_dives.push((start, until));
+    }
+
+    let mut stats = vec![];
+    let mut week = week_start(first_week);
+    while local_midnight(week) <= until {
+        let next_week = week + Days::new(7);
+        let (from, to) = (local_midnight(week), local_midnight(next_week).min(until));
+        let completed = changes
+            .iter()
+            .filter(|change| {
+                change.to_status == Status::Completed && from <= change.changed_at && change.changed_at < to
+            })
+            .count() as i64;
+        let seconds = deep_dives
+            .iter()
+            .map(|(start, end)| ((*end).min(to) - (*start).max(from)).num_seconds().max(0))
+            .sum::<i64>();
+        stats.push(WeeklyReadingStats::new(
+            week,
+            completed,
+            (seconds as f64 / 36.0).round() / 100.0,
+        ));
+        week = next_week;
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 10, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_week_start() {
+        let monday = NaiveDate::from_ymd_opt(2025, 10, 6).unwrap();
+        assert_eq!(week_start(monday), monday);
+        assert_eq!(week_start(NaiveDate::from_ymd_opt(2025, 10, 12).unwrap()), monday);
+    }
+
+    #[test]
+    fn test_weekly_reading_stats() {
+        let (a, b, c) = (AcademicPaperId::new(), AcademicPaperId::new(), AcademicPaperId::new());
+        let changes = vec![
+            // a: two days of deep dive, then completed
+            PaperStatusChange::new(a, Some(Status::New), Status::DeepDive, at(6, 9)),
+            PaperStatusChange::new(a, Some(Status::DeepDive), Status::Completed, at(8, 9)),
+            // b: a deep dive that goes on into the next week
+            PaperStatusChange::new(b, Some(Status::New), Status::DeepDive, at(12, 12)),
+            // c: completed without a deep dive
+            PaperStatusChange::new(c, Some(Status::New), Status::Completed, at(14, 9)),
+        ];
+
+        let stats = weekly_reading_stats(&changes, NaiveDate::from_ymd_opt(2025, 10, 8).unwrap(), at(14, 12));
+        assert_eq!(
+            stats,
+            vec![
+                WeeklyReadingStats::new(NaiveDate::from_ymd_opt(2025, 10, 6).unwrap(), 1, 60.0),
+                WeeklyReadingStats::new(NaiveDate::from_ymd_opt(2025, 10, 13).unwrap(), 1, 36.0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,348 @@
+use crate::{
+    llm::LlmProvider,
+    models::{academic_paper::Task, paper_chunk::cosine_similarity, paper_duplicate::normalize_title},
+};
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+use shared::{id::TaskId, utils::levenshtein_dist};
+use strum::Display;
+
+/// The cosine similarity from which two task names are taken for the same task.
+pub const TASK_EMBEDDING_THRESHOLD: f64 = 0.92;
+/// The number of tasks, the most used first, listed to the LLM and compared by embedding.
+pub const MAX_VOCABULARY_TASKS: usize = 200;
+/// The shortest word that may differ from another by a typo or a plural.
+const MIN_SPELLING_WORD_LEN: usize = 5;
+
+/// How a task name was found to be a variant of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskMatchReason {
+    Name,
+    Alias,
+    Acronym,
+    Spelling,
+    Embedding,
+}
+
+/// The key shared by the spellings of a task name, e.g. `named entity recognition` for `Named-Entity Recognition`.
+pub fn task_key(name: &str) -> String {
+    normalize_title(name).unwrap_or_default()
+}
+
+/// Whether `name` is written as an acronym, such as `NER` or `T2I`.
+fn is_acronym(name: &str) -> bool {
+    let name = name.trim();
+    (2..=8).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && name.chars().any(|c| c.is_ascii_uppercase())
+}
+
+/// The initials of the words of `key`, if it has several words.
+fn initials(key: &str) -> Option<String> {
+    let words = key.split(' ').collect::<Vec<&str>>();
+    (words.len() > 1).then(|| words.iter().filter_map(|word| word.chars().next()).collect())
+}
+
+/// Whether the keys differ only by typos or plurals, one letter in each of their long words at most.
+fn is_misspelling(a: &str, b: &str) -> bool {
+    let (a, b) = (a.split(' ').collect::<Vec<&str>>(), b.split(' ').collect::<Vec<&str>>());
+    a.len() == b.len()
+        && a != b
+        && a.iter().zip(b.iter()).all(|(a, b)| {
+            a == b
+                || (a.len().min(b.len()) >= MIN_SPELLING_WORD_LEN
+                    && a.chars().chain(b.chars()).all(char::is_alphabetic)
+                    && levenshtein_dist(a, b) == 1)
+        })
+}
+
+/// A task of the library with the other names it was extracted under.
+#[derive(Debug, Clone, new)]
+pub struct TaskEntry {
+    pub task: Task,
+    pub aliases: Vec<String>,
+    pub paper_count: i64,
+    /// The embedding of the name, `None` until the vocabulary is embedded.
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl TaskEntry {
+    fn names(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.task.name).chain(self.aliases.iter())
+    }
+
+    /// How `name` matches this task without embeddings, if it does.
+    fn match_name(&self, name: &str) -> Option<TaskMatchReason> {
+        let key = task_key(name);
+        if key.is_empty() {
+            return None;
+        }
+        if task_key(&self.task.name) == key {
+            return Some(TaskMatchReason::Name);
+        }
+        if self.aliases.iter().any(|alias| task_key(alias) == key) {
+            return Some(TaskMatchReason::Alias);
+        }
+        let is_acronym_of =
+            |short: &str, long: &str| is_acronym(short) && initials(&task_key(long)) == Some(task_key(short));
+        if self
+            .names()
+            .any(|known| is_acronym_of(name, known) || is_acronym_of(known, name))
+        {
+            return Some(TaskMatchReason::Acronym);
+        }
+        if self.names().any(|known| is_misspelling(&task_key(known), &key)) {
+            return Some(TaskMatchReason::Spelling);
+        }
+        None
+    }
+}
+
+/// Two tasks that look like the same task, to be reviewed before they are merged.
+#[derive(Debug, Clone, new)]
+pub struct SimilarTasks {
+    /// The task with more papers, kept by the merge.
+    pub task: TaskEntry,
+    pub duplicate: TaskEntry,
+    pub reason: TaskMatchReason,
+}
+
+/// The tasks of the library, the one with the most papers first.
+#[derive(Debug, Clone, Default, new)]
+pub struct TaskVocabulary {
+    pub entries: Vec<TaskEntry>,
+}
+
+impl TaskVocabulary {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The task `name` is a variant of and how it matched, the task with the most papers among the closest matches.
+    ///
+    /// `embedding` is the embedding of `name`; it is compared to the embedded entries when nothing else matches.
+    pub fn find(&self, name: &str, embedding: Option<&[f32]>) -> Option<(&TaskEntry, TaskMatchReason)> {
+        let matched = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.match_name(name).map(|reason| (entry, reason)))
+            .min_by_key(|(_, reason)| *reason);
+        if matched.is_some() {
+            return matched;
+        }
+        let embedding = embedding?;
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let similarity = cosine_similarity(embedding, entry.embedding.as_deref()?);
+                (similarity >= TASK_EMBEDDING_THRESHOLD).then_some((entry, similarity))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entry, _)| (entry, TaskMatchReason::Embedding))
+    }
+
+    /// The names of the most used tasks, given to the LLM so that it reuses them.
+    pub fn prompt_names(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .take(MAX_VOCABULARY_TASKS)
+            .map(|entry| entry.task.name.as_str())
+            .collect()
+    }
+
+    /// Embeds the names of the most used tasks that are not embedded yet.
+    /// The vocabulary is kept as it is when the provider cannot embed.
+    pub async fn embed(&mut self, llm: &dyn LlmProvider) {
+        let indices = (0..self.entries.len().min(MAX_VOCABULARY_TASKS))
+            .filter(|i| self.entries[*i].embedding.is_none())
+            .collect::<Vec<usize>>();
+        if indices.is_empty() {
+            return;
+        }
+        let names = indices
+            .iter()
+            .map(|i| self.entries[*i].task.name.clone())
+            .collect::<Vec<String>>();
+        match llm.embed(names).await {
+            Ok(embeddings) => {
+                for (i, embedding) in indices.into_iter().zip(embeddings) {
+                    self.entries[i].embedding = Some(embedding);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to embed the task vocabulary: {}", e),
+        }
+    }
+
+    /// Maps the extracted task names onto the tasks of the vocabulary.
+    ///
+    /// A name that matches a task keeps its spelling but takes the ID of the task, so that storing it links
+    /// the paper to the task and records the name as an alias. The names nothing matches by spelling are
+    /// compared by embedding, and the others become new tasks. Names of the same task are listed once.
+    pub async fn normalize(&mut self, llm: &dyn LlmProvider, names: Vec<String>) -> Vec<Task> {
+        let names = names
+            .into_iter()
+            .filter(|name| !task_key(name).is_empty())
+            .collect::<Vec<String>>();
+        let unmatched = names
+            .iter()
+            .filter(|name| self.find(name, None).is_none())
+            .cloned()
+            .collect::<Vec<String>>();
+        let mut embeddings = vec![];
+        if !unmatched.is_empty() && !self.is_empty() {
+            self.embed(llm).await;
+            if self.entries.iter().any(|entry| entry.embedding.is_some()) {
+                embeddings = llm.embed(unmatched.clone()).await.unwrap_or_else(|e| {
+                    tracing::warn!("Failed to embed the task names: {}", e);
+                    vec![]
+                });
+            }
+        }
+
+        let mut tasks: Vec<Task> = vec![];
+        for name in names.into_iter() {
+            let embedding = unmatched
+                .iter()
+                .position(|unmatched| *unmatched == name)
+                .and_then(|i| embeddings.get(i))
+                .map(Vec::as_slice);
+            let task = match self.find(&name, embedding) {
+                Some((entry, reason)) => {
+                    tracing::info!("Task \"{}\" is \"{}\" ({})", name, entry.task.name, reason);
+                    Task::new(entry.task.task_id, name)
+                }
+                None => Task::new(TaskId::new(), name),
+            };
+            let is_listed = tasks
+                .iter()
+                .any(|listed| listed.task_id == task.task_id || task_key(&listed.name) == task_key(&task.name));
+            if !is_listed {
+                tasks.push(task);
+            }
+        }
+        tasks
+    }
+
+    /// The pairs of tasks that look like the same task, the pair with the most papers first.
+    /// Embedded entries are also compared by embedding.
+    pub fn similar_tasks(&self) -> Vec<SimilarTasks> {
+        let mut pairs = vec![];
+        for (i, task) in self.entries.iter().enumerate() {
+            for duplicate in self.entries.iter().skip(i + 1) {
+                let reason = duplicate
+                    .names()
+                    .filter_map(|name| task.match_name(name))
+                    .min()
+                    .or_else(|| {
+                        let similarity = cosine_similarity(task.embedding.as_deref()?, duplicate.embedding.as_deref()?);
+                        (similarity >= TASK_EMBEDDING_THRESHOLD).then_some(TaskMatchReason::Embedding)
+                    });
+                if let Some(reason) = reason {
+                    pairs.push(SimilarTasks::new(task.clone(), duplicate.clone(), reason));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::canned::CannedProvider;
+
+    fn entry(name: &str, aliases: &[&str], paper_count: i64) -> TaskEntry {
+        TaskEntry::new(
+            Task::new(TaskId::new(), name.to_string()),
+            aliases.iter().map(|alias| alias.to_string()).collect(),
+            paper_count,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_task_key() {
+        assert_eq!(task_key("Named-Entity Recognition"), "named entity recognition");
+        assert_eq!(task_key("  named entity  recognition. "), "named entity recognition");
+        assert_eq!(task_key("--"), "");
+    }
+
+    #[test]
+    fn test_find() {
+        let vocabulary = TaskVocabulary::new(vec![
+            entry("Named Entity Recognition", &["Entity Tagging"], 5),
+            entry("Question Answering", &[], 3),
+            entry("3D Object Detection", &[], 2),
+        ]);
+        let find = |name: &str| {
+            vocabulary
+                .find(name, None)
+                .map(|(entry, reason)| (entry.task.name.as_str(), reason))
+        };
+        assert_eq!(
+            find("named-entity recognition"),
+            Some(("Named Entity Recognition", TaskMatchReason::Name))
+        );
+        assert_eq!(
+            find("entity tagging"),
+            Some(("Named Entity Recognition", TaskMatchReason::Alias))
+        );
+        assert_eq!(
+            find("NER"),
+            Some(("Named Entity Recognition", TaskMatchReason::Acronym))
+        );
+        assert_eq!(find("QA"), Some(("Question Answering", TaskMatchReason::Acronym)));
+        assert_eq!(
+            find("Named Entity Recognitions"),
+            Some(("Named Entity Recognition", TaskMatchReason::Spelling))
+        );
+        // neither a lowercase word nor a digit is taken for a variant
+        assert_eq!(find("qa"), None);
+        assert_eq!(find("2D Object Detection"), None);
+        assert_eq!(find("Summarization"), None);
+
+        let mut vocabulary = vocabulary.clone();
+        vocabulary.entries[1].embedding = Some(vec![1.0, 0.0]);
+        assert_eq!(
+            vocabulary
+                .find("Reading Comprehension", Some(&[0.99, 0.05]))
+                .map(|(entry, reason)| (entry.task.name.as_str(), reason)),
+            Some(("Question Answering", TaskMatchReason::Embedding))
+        );
+        assert!(vocabulary.find("Reading Comprehension", Some(&[0.0, 1.0])).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_normalize() {
+        let mut vocabulary = TaskVocabulary::new(vec![entry("Named Entity Recognition", &[], 5)]);
+        let names = ["NER", "named entity recognition", "Summarization", "summarization", ""]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        // the canned provider cannot embed, so only the spellings are compared
+        let tasks = vocabulary
+            .normalize(&CannedProvider::new("canned".to_string(), vec![]), names)
+            .await;
+        assert_eq!(
+            tasks.iter().map(|task| task.name.as_str()).collect::<Vec<_>>(),
+            vec!["NER", "Summarization"]
+        );
+        assert_eq!(tasks[0].task_id, vocabulary.entries[0].task.task_id);
+    }
+
+    #[test]
+    fn test_similar_tasks() {
+        let vocabulary = TaskVocabulary::new(vec![
+            entry("Named Entity Recognition", &[], 5),
+            entry("Question Answering", &[], 3),
+            entry("NER", &[], 1),
+        ]);
+        let pairs = vocabulary.similar_tasks();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].task.task.name, "Named Entity Recognition");
+        assert_eq!(pairs[0].duplicate.task.name, "NER");
+        assert_eq!(pairs[0].reason, TaskMatchReason::Acronym);
+    }
+}
@@ -0,0 +1,437 @@
+use chrono::{DateTime, Datelike, Local};
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// The name stored when neither arXiv nor Semantic Scholar knows where a paper was published.
+pub const UNKNOWN_VENUE: &str = "Unknown Journal";
+
+/// What kind of venue a paper was published in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum VenueType {
+    Conference,
+    Journal,
+    Workshop,
+    Preprint,
+}
+
+/// A well-known venue: its canonical name, its full name for citations, its type and the other names it goes by.
+struct KnownVenue {
+    name: &'static str,
+    full_name: &'static str,
+    venue_type: VenueType,
+    aliases: &'static [&'static str],
+}
+
+const fn known(
+    name: &'static str,
+    full_name: &'static str,
+    venue_type: VenueType,
+    aliases: &'static [&'static str],
+) -> KnownVenue {
+    KnownVenue {
+        name,
+        full_name,
+        venue_type,
+        aliases,
+    }
+}
+
+const KNOWN_VENUES: [KnownVenue; 28] = [
+    known(
+        "NeurIPS",
+        "Advances in Neural Information Processing Systems",
+        VenueType::Conference,
+        &[
+            "NIPS",
+            "Neural Information Processing Systems",
+            "Conference on Neural Information Processing Systems",
+        ],
+    ),
+    known(
+        "ICML",
+        "International Conference on Machine Learning",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "ICLR",
+        "International Conference on Learning Representations",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "AAAI",
+        "AAAI Conference on Artificial Intelligence",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "IJCAI",
+        "International Joint Conference on Artificial Intelligence",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "ACL",
+        "Annual Meeting of the Association for Computational Linguistics",
+        VenueType::Conference,
+        &["Association for Computational Linguistics"],
+    ),
+    known(
+        "Findings of ACL",
+        "Findings of the Association for Computational Linguistics",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "EMNLP",
+        "Conference on Empirical Methods in Natural Language Processing",
+        VenueType::Conference,
+        &["Empirical Methods in Natural Language Processing"],
+    ),
+    known(
+        "NAACL",
+        "Conference of the North American Chapter of the Association for Computational Linguistics",
+        VenueType::Conference,
+        &["North American Chapter of the Association for Computational Linguistics"],
+    ),
+    known(
+        "EACL",
+        "Conference of the European Chapter of the Association for Computational Linguistics",
+        VenueType::Conference,
+        &["European Chapter of the Association for Computational Linguistics"],
+    ),
+    known(
+        "COLING",
+        "International Conference on Computational Linguistics",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "CVPR",
+        "IEEE/CVF Conference on Computer Vision and Pattern Recognition",
+        VenueType::Conference,
+        &["Computer Vision and Pattern Recognition"],
+    ),
+    known(
+        "ICCV",
+        "IEEE/CVF International Conference on Computer Vision",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "ECCV",
+        "European Conference on Computer Vision",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "KDD",
+        "ACM SIGKDD Conference on Knowledge Discovery and Data Mining",
+        VenueType::Conference,
+        &["Knowledge Discovery and Data Mining"],
+    ),
+    known(
+        "SIGIR",
+        "International ACM SIGIR Conference on Research and Development in Information Retrieval",
+        VenueType::Conference,
+        &["Annual International ACM SIGIR Conference on Research and Development in Information Retrieval"],
+    ),
+    known(
+        "WWW",
+        "The ACM Web Conference",
+        VenueType::Conference,
+        &["The Web Conference", "World Wide Web Conference"],
+    ),
+    known(
+        "ICASSP",
+        "IEEE International Conference on Acoustics, Speech and Signal Processing",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "Interspeech",
+        "Annual Conference of the International Speech Communication Association",
+        VenueType::Conference,
+        &["Conference of the International Speech Communication Association"],
+    ),
+    known("CoRL", "Conference on Robot Learning", VenueType::Conference, &[]),
+    known(
+        "ICRA",
+        "IEEE International Conference on Robotics and Automation",
+        VenueType::Conference,
+        &[],
+    ),
+    known(
+        "TACL",
+        "Transactions of the Association for Computational Linguistics",
+        VenueType::Journal,
+        &[],
+    ),
+    known(
+        "JMLR",
+        "Journal of Machine Learning Research",
+        VenueType::Journal,
+        &["J. Mach. Learn. Res."],
+    ),
+    known(
+        "TMLR",
+        "Transactions on Machine Learning Research",
+        VenueType::Journal,
+        &["Trans. Mach. Learn. Res."],
+    ),
+    known(
+        "TPAMI",
+        "IEEE Transactions on Pattern Analysis and Machine Intelligence",
+        VenueType::Journal,
+        &["IEEE Trans. Pattern Anal. Mach. Intell."],
+    ),
+    known("arXiv", "arXiv", VenueType::Preprint, &["arXiv.org", "CoRR"]),
+    known("bioRxiv", "bioRxiv", VenueType::Preprint, &[]),
+    known("medRxiv", "medRxiv", VenueType::Preprint, &[]),
+];
+
+/// The words of venue names that do not tell venues apart, e.g. the `Proceedings of the` of proceedings.
+const VENUE_STOP_WORDS: [&str; 11] = [
+    "proceedings",
+    "of",
+    "the",
+    "in",
+    "on",
+    "and",
+    "annual",
+    "international",
+    "ieee",
+    "cvf",
+    "acm",
+];
+const CONFERENCE_KEYWORDS: [&str; 6] = [
+    "proceedings",
+    "conference",
+    "symposium",
+    "meeting of the association",
+    "neural information processing systems",
+    "international joint conference",
+];
+const CONFERENCE_ACRONYMS: [&str; 24] = [
+    "NeurIPS",
+    "NIPS",
+    "ICML",
+    "ICLR",
+    "ACL",
+    "EMNLP",
+    "NAACL",
+    "EACL",
+    "COLING",
+    "CVPR",
+    "ICCV",
+    "ECCV",
+    "AAAI",
+    "IJCAI",
+    "KDD",
+    "SIGIR",
+    "WWW",
+    "CHI",
+    "USENIX",
+    "CCS",
+    "NDSS",
+    "INTERSPEECH",
+    "ICASSP",
+    "ISCA",
+];
+
+/// Whether `word` is an edition of a venue, such as `2023` or `37th`.
+fn is_edition(word: &str) -> bool {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    !digits.is_empty()
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && ["", "st", "nd", "rd", "th"].contains(&word[digits.len()..].to_ascii_lowercase().as_str())
+}
+
+/// The key shared by the names of a venue: the lowercase words without the editions and the stop words,
+/// e.g. `conference machine learning` for `Proceedings of the 40th International Conference on Machine Learning`.
+pub fn venue_key(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !is_edition(word))
+        .map(str::to_lowercase)
+        .filter(|word| !VENUE_STOP_WORDS.contains(&word.as_str()))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// The year in a venue name such as `NeurIPS 2023`.
+fn year_in(name: &str) -> Option<i32> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| word.len() == 4)
+        .filter_map(|word| word.parse::<i32>().ok())
+        .find(|year| (1950..=2100).contains(year))
+}
+
+fn known_venue(name: &str) -> Option<&'static KnownVenue> {
+    let find = |key: &str| {
+        KNOWN_VENUES.iter().find(|venue| {
+            std::iter::once(&venue.name)
+                .chain(std::iter::once(&venue.full_name))
+                .chain(venue.aliases.iter())
+                .any(|known| venue_key(known) == key)
+        })
+    };
+    let key = venue_key(name);
+    if key.is_empty() {
+        return None;
+    }
+    // `Findings of the Association for Computational Linguistics: ACL 2023` is named before the colon
+    find(&key).or_else(|| name.split_once(':').and_then(|(head, _)| find(&venue_key(head))))
+}
+
+/// The full name of a well-known venue from its canonical name, e.g. for the `booktitle` of a BibTeX entry.
+pub fn full_venue_name(name: &str) -> Option<&'static str> {
+    KNOWN_VENUES
+        .iter()
+        .find(|venue| venue.name == name)
+        .map(|venue| venue.full_name)
+}
+
+impl VenueType {
+    /// Guesses the type of a venue that is not well known from the words of its name.
+    pub fn guess(name: &str) -> Self {
+        if let Some(venue) = known_venue(name) {
+            return venue.venue_type;
+        }
+        let lowercase = name.to_lowercase();
+        if lowercase.contains("workshop") {
+            return Self::Workshop;
+        }
+        if lowercase.contains("arxiv") || lowercase.contains("preprint") {
+            return Self::Preprint;
+        }
+        let is_conference = CONFERENCE_KEYWORDS.iter().any(|keyword| lowercase.contains(keyword))
+            || name
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|word| CONFERENCE_ACRONYMS.contains(&word));
+        if is_conference {
+            Self::Conference
+        } else {
+            Self::Journal
+        }
+    }
+}
+
+/// A venue name as stored: the canonical name, the type and the year of the edition, if the name has one.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct NormalizedVenue {
+    pub name: String,
+    pub venue_type: VenueType,
+    pub year: Option<i32>,
+}
+
+/// Normalizes a venue name from Semantic Scholar or arXiv.
+///
+/// The names of a well-known venue become its canonical name, e.g. `NeurIPS` for `NIPS` or
+/// `Advances in Neural Information Processing Systems 36`; the editions are dropped from the other names.
+/// A workshop keeps its own name even if it is held at a well-known conference.
+pub fn normalize_venue(name: &str) -> NormalizedVenue {
+    let name = name.trim();
+    if name.is_empty() || name == UNKNOWN_VENUE {
+        return NormalizedVenue::new(UNKNOWN_VENUE.to_string(), VenueType::Journal, None);
+    }
+    let year = year_in(name);
+    let is_workshop = name.to_lowercase().contains("workshop");
+    if let Some(venue) = known_venue(name).filter(|_| !is_workshop) {
+        return NormalizedVenue::new(venue.name.to_string(), venue.venue_type, year);
+    }
+    let cleaned = name
+        .split_whitespace()
+        .filter(|word| !is_edition(word.trim_matches(|c: char| !c.is_alphanumeric())))
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .trim_matches(|c: char| c.is_whitespace() || c == ',' || c == ':' || c == '-')
+        .to_string();
+    let name = if cleaned.is_empty() { name.to_string() } else { cleaned };
+    let venue_type = VenueType::guess(&name);
+    NormalizedVenue::new(name, venue_type, year)
+}
+
+/// The year of the venue of a paper: the year in the venue name, else the year the paper was published,
+/// unless it is a preprint (unknown dates are stored as 1970-01-01).
+pub fn venue_year(venue: &NormalizedVenue, published_date: DateTime<Local>) -> Option<i32> {
+    let is_dated = venue.venue_type != VenueType::Preprint && venue.name != UNKNOWN_VENUE;
+    venue
+        .year
+        .or_else(|| Some(published_date.year()).filter(|year| is_dated && *year > 1970))
+}
+
+/// A tier or rank the user gives to a venue, e.g. `A*` or `Q1`; `None` clears it.
+#[derive(Debug, Clone, PartialEq, new)]
+pub struct VenueTier {
+    pub name: String,
+    pub tier: Option<String>,
+}
+
+/// What re-normalizing the stored venues changed.
+#[derive(Debug, Clone, Default, PartialEq, new)]
+pub struct VenueNormalization {
+    /// The venues renamed to their canonical name or classified.
+    pub normalized: i64,
+    /// The venues merged into another one with the same canonical name.
+    pub merged: i64,
+    /// The papers whose venue year was filled.
+    pub venue_years: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_normalize_venue() {
+        for name in [
+            "NeurIPS",
+            "NIPS",
+            "Neural Information Processing Systems",
+            "Advances in Neural Information Processing Systems 36",
+            "NeurIPS 2023",
+        ] {
+            assert_eq!(normalize_venue(name).name, "NeurIPS", "{}", name);
+        }
+        assert_eq!(
+            normalize_venue("Proceedings of the 40th International Conference on Machine Learning"),
+            NormalizedVenue::new("ICML".into(), VenueType::Conference, None)
+        );
+        assert_eq!(
+            normalize_venue("Findings of the Association for Computational Linguistics: ACL 2023"),
+            NormalizedVenue::new("Findings of ACL".into(), VenueType::Conference, Some(2023))
+        );
+        assert_eq!(
+            normalize_venue("ArXiv"),
+            NormalizedVenue::new("arXiv".into(), VenueType::Preprint, None)
+        );
+        // a workshop at a well-known conference is a venue of its own
+        assert_eq!(
+            normalize_venue("NeurIPS 2023 Workshop on Instruction Tuning"),
+            NormalizedVenue::new(
+                "NeurIPS Workshop on Instruction Tuning".into(),
+                VenueType::Workshop,
+                Some(2023)
+            )
+        );
+        assert_eq!(
+            normalize_venue("Nature"),
+            NormalizedVenue::new("Nature".into(), VenueType::Journal, None)
+        );
+        assert_eq!(normalize_venue("").name, UNKNOWN_VENUE);
+    }
+
+    #[test]
+    fn test_venue_year() {
+        let published = Local.with_ymd_and_hms(2017, 6, 12, 0, 0, 0).unwrap();
+        assert_eq!(venue_year(&normalize_venue("NeurIPS 2018"), published), Some(2018));
+        assert_eq!(venue_year(&normalize_venue("NeurIPS"), published), Some(2017));
+        assert_eq!(venue_year(&normalize_venue("arXiv"), published), None);
+        assert_eq!(venue_year(&normalize_venue(UNKNOWN_VENUE), published), None);
+    }
+}
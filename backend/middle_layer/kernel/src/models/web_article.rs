@@ -1,16 +1,16 @@
+use crate::{
+    llm::{batch::BatchRequest, parse_structured, request_structured, LlmProvider, StructuredOutput},
+    models::language::{select_translation, upsert_translation, Language, Translation},
+};
 use chrono::{DateTime, Local};
 use derive_new::new;
-use dotenvy::dotenv;
-use openai_tools::{
-    chat::request::ChatCompletion,
-    common::{message::Message, role::Role, structured_output::Schema},
-};
+use openai_tools::common::{message::Message, role::Role, structured_output::Schema};
 use serde::{Deserialize, Serialize};
 use shared::{
-    errors::{AppError, AppResult},
+    errors::AppResult,
     id::{WebArticleId, WebSiteId},
 };
-use strum::{Display, EnumString};
+use strum::{Display, EnumIter, EnumString};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Display, EnumString)]
 pub enum Status {
@@ -23,6 +23,36 @@ pub enum Status {
     Archived,
 }
 
+/// The versions of the prompt and schema used by [`WebArticle::fill_attributes`], recorded by evaluation runs.
+/// Add a new version instead of editing an existing one, so that the runs of every version can be compared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString, EnumIter)]
+pub enum AttributePromptVersion {
+    #[default]
+    #[strum(serialize = "2025-10-19")]
+    #[serde(rename = "2025-10-19")]
+    V20251019,
+    /// Spells out the criteria of every category.
+    #[strum(serialize = "2025-11-02")]
+    #[serde(rename = "2025-11-02")]
+    V20251102,
+}
+
+/// Whether the AI-generated attributes of an article have been filled in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+pub enum EnrichmentStatus {
+    /// Waiting for a batch job to classify and summarize the article.
+    #[serde(rename = "pending")]
+    #[strum(serialize = "pending")]
+    Pending,
+    #[default]
+    #[serde(rename = "done")]
+    #[strum(serialize = "done")]
+    Done,
+    #[serde(rename = "failed")]
+    #[strum(serialize = "failed")]
+    Failed,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebArticleProperty {
     pub summary: Option<String>,
@@ -48,6 +78,56 @@ impl Default for WebArticleProperty {
     }
 }
 
+impl StructuredOutput for WebArticleProperty {
+    fn json_schema() -> Schema {
+        let mut json_schema = Schema::chat_json_schema("web_article");
+        json_schema.add_property(
+            "summary",
+            "string",
+            "記事の要約を指定された出力言語で記述してください．",
+        );
+        json_schema.add_property(
+            "is_new_technology_related",
+            "boolean",
+                "この記事が新しい技術に関するものであるかどうか．新しい技術とは，データサイエンスやAIに関する技術を指し，例えば新しいモデルやライブラリ，AI技術を用いた新しいサービスなどが挙げられる．",
+        );
+        json_schema.add_property(
+            "is_new_product_related",
+            "boolean",
+                "この記事が商品の紹介に関するものであるかどうか．商品とは，新しい製品やサービスを指し，例えば新しいスマートフォン，新しいソフトウェア，新しいサービスなどが挙げられる．また，商品のレビューも含む．"
+        );
+        json_schema.add_property(
+            "is_new_academic_paper_related",
+            "boolean",
+            "この記事が新しい論文に関するものであるかどうか．新しい論文とは，新しい研究成果を指し，例えば新しいアルゴリズム，新しいモデル，新しいデータセットなどが挙げられる．"
+        );
+        json_schema.add_property(
+            "is_ai_related",
+            "boolean",
+            "この記事がAIに関わるものであるかどうか．AIに関わるものとは，人工知能や機械学習，LLMや自然言語処理などの技術を指し，例えばAI技術を用いた新しいサービスの紹介やAIに関わる技術の論文紹介などが挙げられる．"
+        );
+        json_schema.add_property(
+            "is_security_related",
+            "boolean",
+                "この記事がセキュリティに関わるものであるかどうか．セキュリティに関わるものとは，情報セキュリティやサイバーセキュリティなどの技術を指し，例えば新しいセキュリティ技術の紹介や情報漏えいなどのセキュリティ事故，サイバー攻撃の報告，脆弱性のレポートなどが挙げられる．"
+        );
+        json_schema.add_property(
+            "is_it_related",
+            "boolean",
+            "この記事がITに関わるものであるかどうか．ITに関わるものとは，情報技術や情報通信技術などの技術を指し，例えば新しいIT技術の紹介やITに関わる論文紹介，IT技術を用いた企業の取組み事例紹介・プレスリリースなどが挙げられる．"
+        );
+
+        json_schema
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self.summary.as_deref() {
+            Some(summary) if !summary.trim().is_empty() => Ok(()),
+            _ => Err("summary must not be empty".into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, new, Default)]
 pub struct WebSite {
     pub site_id: WebSiteId,
@@ -65,7 +145,7 @@ pub struct WebArticle {
     pub text: String,
     pub html: String,
     pub timestamp: DateTime<Local>,
-    pub summary: String,
+    pub translations: Vec<WebArticleTranslation>,
     pub is_new_technology_related: bool,
     pub is_new_product_related: bool,
     pub is_new_academic_paper_related: bool,
@@ -73,14 +153,104 @@ pub struct WebArticle {
     pub is_security_related: bool,
     pub is_it_related: bool,
     pub status: Status,
+    pub enrichment_status: EnrichmentStatus,
+    /// The batch job that is filling in the attributes, while `enrichment_status` is pending.
+    pub enrichment_batch_id: Option<String>,
+}
+
+/// The AI-generated summary of an article in one language.
+#[derive(Debug, Clone, Default, PartialEq, Eq, new)]
+pub struct WebArticleTranslation {
+    pub language: Language,
+    pub summary: String,
+}
+
+impl Translation for WebArticleTranslation {
+    fn language(&self) -> Language {
+        self.language
+    }
+}
+
+/// The items to extract, as written in the prompt of `version`.
+fn attribute_instructions(language: Language, version: AttributePromptVersion) -> String {
+    match version {
+        AttributePromptVersion::V20251019 => format!(
+            r#"- この記事の要約（{language}で記述してください）: summary (string)
+- この記事は新しい技術に関するものかどうか: is_new_technology_related (true or false)
+- この記事が商品の紹介かどうか: is_new_product_related (true or false)
+- この記事は新しい論文の紹介に関わるものかどうか: is_new_academic_paper_related (true or false)
+- この記事はデータ分析やLLMなどAIに関わるものかどうか: is_ai_related (true or false)
+- この記事はセキュリティに関わるものかどうか: is_security_related (true or false)
+- この記事はITに関わるものかどうか: is_it_related (true or false)
+"#,
+            language = language.prompt_name(),
+        ),
+        AttributePromptVersion::V20251102 => format!(
+            r#"- この記事の要約（{language}で記述してください）: summary (string)
+- この記事は新しい技術に関するものかどうか: is_new_technology_related (true or false)
+  新しく発表・公開された技術，手法，ライブラリ，規格を主題とする記事はtrue，既存技術の解説や使い方だけの記事はfalseです．
+- この記事が商品の紹介かどうか: is_new_product_related (true or false)
+  新しい製品やサービスの発表，発売，提供開始を主題とする記事はtrueです．
+- この記事は新しい論文の紹介に関わるものかどうか: is_new_academic_paper_related (true or false)
+  特定の論文やプレプリントの内容を紹介している記事はtrueです．
+- この記事はデータ分析やLLMなどAIに関わるものかどうか: is_ai_related (true or false)
+  機械学習，LLM，データ分析が記事の主題であればtrueです．言及されているだけであればfalseです．
+- この記事はセキュリティに関わるものかどうか: is_security_related (true or false)
+  脆弱性，攻撃，インシデント，防御策が記事の主題であればtrueです．
+- この記事はITに関わるものかどうか: is_it_related (true or false)
+  ソフトウェア，ハードウェア，ネットワーク，ITサービスに関わる記事はtrueです．
+"#,
+            language = language.prompt_name(),
+        ),
+    }
 }
 
 impl WebArticle {
-    pub async fn fill_attributes(&mut self) -> AppResult<()> {
-        dotenv().ok();
-        let model_id = std::env::var("OPENAI_MODEL_ID").expect("OPENAI_MODEL_ID must be set");
-        let mut chat = ChatCompletion::new();
-        let messages = vec![
+    /// Returns the summary in `language`, falling back to any other available language.
+    pub fn summary(&self, language: Language) -> Option<&str> {
+        select_translation(&self.translations, language).map(|t| t.summary.as_str())
+    }
+
+    pub async fn fill_attributes(&mut self, llm: &dyn LlmProvider, language: Language) -> AppResult<()> {
+        self.fill_attributes_with_prompt(llm, language, AttributePromptVersion::default())
+            .await
+    }
+
+    /// Fills in the attributes with the prompt of `version`, to evaluate a prompt before it becomes the default.
+    pub async fn fill_attributes_with_prompt(
+        &mut self,
+        llm: &dyn LlmProvider,
+        language: Language,
+        version: AttributePromptVersion,
+    ) -> AppResult<()> {
+        let properties: WebArticleProperty = request_structured(llm, self.attribute_messages(language, version))
+            .await
+            .map_err(|e| {
+                tracing::warn!("Failed to fill attributes of WebArticle: {}", e);
+                e
+            })?;
+        self.apply_attributes(properties, language);
+        Ok(())
+    }
+
+    /// The request that fills in the attributes as part of a batch job, identified by the article id.
+    pub fn attribute_batch_request(&self, language: Language) -> BatchRequest {
+        BatchRequest::new(
+            self.article_id.to_string(),
+            self.attribute_messages(language, AttributePromptVersion::default()),
+            Some(WebArticleProperty::json_schema()),
+        )
+    }
+
+    /// Fills in the attributes from the reply to [`WebArticle::attribute_batch_request`].
+    pub fn apply_attribute_batch_reply(&mut self, reply: &str, language: Language) -> Result<(), String> {
+        let properties = parse_structured::<WebArticleProperty>(reply)?;
+        self.apply_attributes(properties, language);
+        Ok(())
+    }
+
+    fn attribute_messages(&self, language: Language, version: AttributePromptVersion) -> Vec<Message> {
+        vec![
             Message::from_string(
                 Role::System,
                 r#"あなたは「綾瀬 智理（あやせ ちり）」という名のAIです．  
@@ -114,14 +284,7 @@ impl WebArticle {
                 Role::User,
                 format!(
                     r#"与えられたWeb記事のタイトルと本文のHTMLから次の情報を抽出してください．
-- この記事の要約: summary (string)
-- この記事は新しい技術に関するものかどうか: is_new_technology_related (true or false)
-- この記事が商品の紹介かどうか: is_new_product_related (true or false)
-- この記事は新しい論文の紹介に関わるものかどうか: is_new_academic_paper_related (true or false)
-- この記事はデータ分析やLLMなどAIに関わるものかどうか: is_ai_related (true or false)
-- この記事はセキュリティに関わるものかどうか: is_security_related (true or false)
-- この記事はITに関わるものかどうか: is_it_related (true or false)
-
+{instructions}
 [記事のURL]
 {url}
 
@@ -131,70 +294,38 @@ impl WebArticle {
 [本文のHTML]
 {html}
 "#,
+                    instructions = attribute_instructions(language, version),
                     url = self.url,
                     title = self.title,
                     html = self.html,
                 ),
             ),
-        ];
+        ]
+    }
 
-        let mut json_schema = Schema::chat_json_schema("web_article");
-        json_schema.add_property("summary", "string", "記事の要約を日本語で記述してください．");
-        json_schema.add_property(
-            "is_new_technology_related",
-            "boolean",
-                "この記事が新しい技術に関するものであるかどうか．新しい技術とは，データサイエンスやAIに関する技術を指し，例えば新しいモデルやライブラリ，AI技術を用いた新しいサービスなどが挙げられる．",
-        );
-        json_schema.add_property(
-            "is_new_product_related",
-            "boolean",
-                "この記事が商品の紹介に関するものであるかどうか．商品とは，新しい製品やサービスを指し，例えば新しいスマートフォン，新しいソフトウェア，新しいサービスなどが挙げられる．また，商品のレビューも含む．"
-        );
-        json_schema.add_property(
-            "is_new_academic_paper_related",
-            "boolean",
-            "この記事が新しい論文に関するものであるかどうか．新しい論文とは，新しい研究成果を指し，例えば新しいアルゴリズム，新しいモデル，新しいデータセットなどが挙げられる．"
-        );
-        json_schema.add_property(
-            "is_ai_related",
-            "boolean",
-            "この記事がAIに関わるものであるかどうか．AIに関わるものとは，人工知能や機械学習，LLMや自然言語処理などの技術を指し，例えばAI技術を用いた新しいサービスの紹介やAIに関わる技術の論文紹介などが挙げられる．"
-        );
-        json_schema.add_property(
-            "is_security_related",
-            "boolean",
-                "この記事がセキュリティに関わるものであるかどうか．セキュリティに関わるものとは，情報セキュリティやサイバーセキュリティなどの技術を指し，例えば新しいセキュリティ技術の紹介や情報漏えいなどのセキュリティ事故，サイバー攻撃の報告，脆弱性のレポートなどが挙げられる．"
-        );
-        json_schema.add_property(
-            "is_it_related",
-            "boolean",
-            "この記事がITに関わるものであるかどうか．ITに関わるものとは，情報技術や情報通信技術などの技術を指し，例えば新しいIT技術の紹介やITに関わる論文紹介，IT技術を用いた企業の取組み事例紹介・プレスリリースなどが挙げられる．"
+    fn apply_attributes(&mut self, properties: WebArticleProperty, language: Language) {
+        upsert_translation(
+            &mut self.translations,
+            WebArticleTranslation::new(language, properties.summary.unwrap_or("NO SUMMARY".to_string())),
         );
+        self.is_new_technology_related = properties.is_new_technology_related.unwrap_or(false);
+        self.is_new_product_related = properties.is_new_product_related.unwrap_or(false);
+        self.is_new_academic_paper_related = properties.is_new_academic_paper_related.unwrap_or(false);
+        self.is_ai_related = properties.is_ai_related.unwrap_or(false);
+        self.is_security_related = properties.is_security_related.unwrap_or(false);
+        self.is_it_related = properties.is_it_related.unwrap_or(false);
+        self.enrichment_status = EnrichmentStatus::Done;
+        self.enrichment_batch_id = None;
+    }
 
-        chat.model_id(model_id)
-            .messages(messages)
-            .temperature(1.0)
-            .json_schema(json_schema);
-
-        let response = chat.chat().await.unwrap();
-        match serde_json::from_str::<WebArticleProperty>(
-            &response.choices[0].message.content.clone().unwrap().text.unwrap(),
-        ) {
-            Ok(properties) => {
-                self.summary = properties.summary.unwrap_or("NO SUMMARY".to_string());
-                self.is_new_technology_related = properties.is_new_technology_related.unwrap_or(false);
-                self.is_new_product_related = properties.is_new_product_related.unwrap_or(false);
-                self.is_new_academic_paper_related = properties.is_new_academic_paper_related.unwrap_or(false);
-                self.is_ai_related = properties.is_ai_related.unwrap_or(false);
-                self.is_security_related = properties.is_security_related.unwrap_or(false);
-                self.is_it_related = properties.is_it_related.unwrap_or(false);
-                Ok(())
-            }
-            Err(e) => {
-                tracing::warn!("Failed to parse WebArticleProperty: {}", e);
-                Err(AppError::JsonParseError(e))
-            }
-        }
+    /// Whether the article is on any topic that is worth collecting.
+    pub fn is_relevant(&self) -> bool {
+        self.is_ai_related
+            || self.is_new_technology_related
+            || self.is_new_product_related
+            || self.is_new_academic_paper_related
+            || self.is_security_related
+            || self.is_it_related
     }
 }
 
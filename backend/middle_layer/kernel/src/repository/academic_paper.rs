@@ -1,9 +1,15 @@
 use crate::models::{
     academic_paper::{AcademicPaper, AcademicPaperListOptions, Author, AuthorListOptions, Journal, Task},
+    author::{AuthorSummary, CoauthorshipGraph},
+    browse::{BrowseListOptions, JournalSummary, TaskSummary, TaskTrend},
     list::PaginatedList,
+    paper_duplicate::DuplicateGroup,
+    task::TaskVocabulary,
+    venue::{VenueNormalization, VenueTier},
 };
 use async_trait::async_trait;
-use shared::errors::AppResult;
+use chrono::{DateTime, Local, NaiveDate};
+use shared::{errors::AppResult, id::AuthorId};
 use sqlx::{Postgres as Pg, Transaction as T};
 
 #[async_trait]
@@ -11,11 +17,30 @@ pub trait AuthorRepository: Send + Sync {
     async fn select_author_by_id(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<Author>;
     async fn select_author_by_ssid(&self, tx: &mut T<'_, Pg>, ss_id: &str) -> AppResult<Author>;
     async fn select_all_authors(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<Author>>;
+    /// Authors with their number of papers in the library, the most prolific first.
     async fn select_all_authors_paginated(
         &self,
         tx: &mut T<'_, Pg>,
         options: AuthorListOptions,
-    ) -> AppResult<PaginatedList<Author>>;
+    ) -> AppResult<PaginatedList<AuthorSummary>>;
+    async fn select_author_summary_by_id(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<AuthorSummary>;
+    /// The co-authors of `id` and the edges between them, or the network of the `limit` most prolific authors.
+    async fn select_coauthorship_graph(
+        &self,
+        tx: &mut T<'_, Pg>,
+        id: Option<&str>,
+        limit: i64,
+    ) -> AppResult<CoauthorshipGraph>;
+    /// Authors with a Semantic Scholar ID whose h-index was last refreshed before `refreshed_before`.
+    async fn select_authors_to_refresh(
+        &self,
+        tx: &mut T<'_, Pg>,
+        refreshed_before: DateTime<Local>,
+        limit: i64,
+    ) -> AppResult<Vec<Author>>;
+    async fn update_author_h_index(&self, tx: &mut T<'_, Pg>, id: AuthorId, h_index: i32) -> AppResult<()>;
+    /// Moves the papers of `duplicate_id` to `id` and deletes `duplicate_id`.
+    async fn merge_authors(&self, tx: &mut T<'_, Pg>, id: &str, duplicate_id: &str) -> AppResult<Author>;
     async fn create_author(&self, tx: &mut T<'_, Pg>, author: Author) -> AppResult<Author>;
     async fn delete_author(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<()>;
 }
@@ -23,9 +48,25 @@ pub trait AuthorRepository: Send + Sync {
 #[async_trait]
 pub trait JournalRepository: Send + Sync {
     async fn select_journal_by_id(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<Journal>;
+    /// The venue of this name, of its canonical name or of which it is an alias.
     async fn select_journal_by_name(&self, tx: &mut T<'_, Pg>, name: &str) -> AppResult<Journal>;
     async fn select_all_journals(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<Journal>>;
+    /// Journals with their number of papers in the library, the one with the most papers first.
+    async fn select_journal_summaries(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: BrowseListOptions,
+    ) -> AppResult<PaginatedList<JournalSummary>>;
+    /// Returns the venue the name of `journal` is a variant of, recording the name as an alias;
+    /// a new venue is created under the canonical name only if none exists.
     async fn create_journal(&self, tx: &mut T<'_, Pg>, journal: Journal) -> AppResult<Journal>;
+    /// Sets the tiers of the venues, creating the venues that do not exist yet.
+    async fn update_journal_tiers(&self, tx: &mut T<'_, Pg>, tiers: Vec<VenueTier>) -> AppResult<Vec<Journal>>;
+    /// Moves the papers and the aliases of `duplicate_id` to `id`, keeps its name as an alias and deletes it.
+    async fn merge_journals(&self, tx: &mut T<'_, Pg>, id: &str, duplicate_id: &str) -> AppResult<Journal>;
+    /// Renames and classifies the stored venues by their canonical names, merges the venues with the same one
+    /// and fills the venue years of the papers.
+    async fn normalize_journals(&self, tx: &mut T<'_, Pg>) -> AppResult<VenueNormalization>;
     async fn delete_journal(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<()>;
 }
 
@@ -34,7 +75,26 @@ pub trait TaskRepository: Send + Sync {
     async fn select_task_by_id(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<Task>;
     async fn select_task_by_name(&self, tx: &mut T<'_, Pg>, name: &str) -> AppResult<Task>;
     async fn select_all_tasks(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<Task>>;
+    /// Tasks with their number of papers in the library, the one with the most papers first.
+    async fn select_task_summaries(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: BrowseListOptions,
+    ) -> AppResult<PaginatedList<TaskSummary>>;
+    /// The papers added per month since `first_month` for the `limit` tasks with the most of them.
+    async fn select_task_trends(
+        &self,
+        tx: &mut T<'_, Pg>,
+        first_month: NaiveDate,
+        limit: i64,
+    ) -> AppResult<Vec<TaskTrend>>;
+    /// All the tasks with their aliases and number of papers, the one with the most papers first.
+    async fn select_task_vocabulary(&self, tx: &mut T<'_, Pg>) -> AppResult<TaskVocabulary>;
+    /// Returns the task with the ID of `task` or, failing that, the task its name is a variant of,
+    /// recording the name as an alias; a new task is created only if neither exists.
     async fn create_task(&self, tx: &mut T<'_, Pg>, task: Task) -> AppResult<Task>;
+    /// Moves the papers and the aliases of `duplicate_id` to `id`, keeps its name as an alias and deletes it.
+    async fn merge_tasks(&self, tx: &mut T<'_, Pg>, id: &str, duplicate_id: &str) -> AppResult<Task>;
     async fn delete_task(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<()>;
 }
 
@@ -43,9 +103,22 @@ pub trait AcademicPaperRepository: Send + Sync {
     async fn select_todays_articles(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<AcademicPaper>>;
     async fn select_academic_paper_by_arxiv_id(&self, tx: &mut T<'_, Pg>, arxiv_id: &str) -> AppResult<AcademicPaper>;
     async fn select_academic_paper_by_ss_id(&self, tx: &mut T<'_, Pg>, ss_id: &str) -> AppResult<AcademicPaper>;
+    /// The paper with the DOI (case-insensitively) or the arXiv ID (of any version), if it is in the library.
+    async fn select_academic_paper_by_external_id(
+        &self,
+        tx: &mut T<'_, Pg>,
+        doi: Option<&str>,
+        arxiv_id: Option<&str>,
+    ) -> AppResult<Option<AcademicPaper>>;
     async fn select_academic_paper_by_id(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<AcademicPaper>;
+    /// Papers whose title contains the words of `title` in order, ignoring case and punctuation.
     async fn select_academic_paper_by_title(&self, tx: &mut T<'_, Pg>, title: &str) -> AppResult<Vec<AcademicPaper>>;
     async fn select_all_academic_papers(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<AcademicPaper>>;
+    async fn select_academic_papers_by_author(
+        &self,
+        tx: &mut T<'_, Pg>,
+        author_id: &str,
+    ) -> AppResult<Vec<AcademicPaper>>;
     async fn select_academic_papers_by_keyword(
         &self,
         tx: &mut T<'_, Pg>,
@@ -62,10 +135,16 @@ pub trait AcademicPaperRepository: Send + Sync {
         tx: &mut T<'_, Pg>,
         academic_paper: AcademicPaper,
     ) -> AppResult<AcademicPaper>;
+    /// Keeps the status of the paper, which only [`crate::repository::reading::ReadingRepository::update_paper_status`] changes.
     async fn update_academic_paper(
         &self,
         tx: &mut T<'_, Pg>,
         academic_paper: AcademicPaper,
     ) -> AppResult<AcademicPaper>;
     async fn delete_academic_paper(&self, tx: &mut T<'_, Pg>, id: &str) -> AppResult<()>;
+    /// The groups of papers in the library that share a Semantic Scholar ID, an arXiv ID, a DOI or a title.
+    async fn select_duplicate_academic_papers(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<DuplicateGroup>>;
+    /// Moves the notes, tags and relations of `duplicate_id` to `id` and deletes `duplicate_id`.
+    async fn merge_academic_papers(&self, tx: &mut T<'_, Pg>, id: &str, duplicate_id: &str)
+        -> AppResult<AcademicPaper>;
 }
@@ -0,0 +1,25 @@
+use crate::models::{
+    academic_paper::AcademicPaper,
+    citation::{CitationGraph, CitedReference},
+};
+use async_trait::async_trait;
+use shared::errors::AppResult;
+use sqlx::{Postgres as Pg, Transaction as T};
+
+#[async_trait]
+pub trait CitationRepository: Send + Sync {
+    /// Stores the nodes and edges of `graph`, keeping the edges that are already stored.
+    async fn save_citation_graph(&self, tx: &mut T<'_, Pg>, graph: &CitationGraph) -> AppResult<()>;
+    /// Papers in the library that cite the paper `ss_id`.
+    async fn select_library_papers_citing(&self, tx: &mut T<'_, Pg>, ss_id: &str) -> AppResult<Vec<AcademicPaper>>;
+    /// The references cited by the most papers in the library.
+    async fn select_most_cited_references(&self, tx: &mut T<'_, Pg>, limit: i64) -> AppResult<Vec<CitedReference>>;
+    /// The papers within `hops` citations of `ss_id` in either direction, nearest first, and the edges between them.
+    async fn select_citation_neighbourhood(
+        &self,
+        tx: &mut T<'_, Pg>,
+        ss_id: &str,
+        hops: i32,
+        max_nodes: i64,
+    ) -> AppResult<CitationGraph>;
+}
@@ -0,0 +1,35 @@
+use crate::models::keyword::{Keyword, KeywordCategory};
+use async_trait::async_trait;
+use shared::{
+    errors::AppResult,
+    id::{AcademicPaperId, KeywordId},
+};
+use sqlx::{Postgres as Pg, Transaction as T};
+
+#[async_trait]
+pub trait KeywordRepository: Send + Sync {
+    /// The tags or collections, by name.
+    async fn select_keywords(&self, tx: &mut T<'_, Pg>, category: KeywordCategory) -> AppResult<Vec<Keyword>>;
+    /// The tags and collections the paper belongs to, by category and name.
+    async fn select_paper_keywords(&self, tx: &mut T<'_, Pg>, paper_id: AcademicPaperId) -> AppResult<Vec<Keyword>>;
+    /// Creates the tag or collection, or returns the one with the same name, compared case-insensitively.
+    async fn create_keyword(&self, tx: &mut T<'_, Pg>, category: KeywordCategory, name: &str) -> AppResult<Keyword>;
+    /// Fails with [`shared::errors::AppError::Unprocessable`] if another one of the category has the name.
+    async fn rename_keyword(&self, tx: &mut T<'_, Pg>, keyword_id: KeywordId, name: &str) -> AppResult<Keyword>;
+    /// Deletes the tag or collection; the papers themselves are kept.
+    async fn delete_keyword(&self, tx: &mut T<'_, Pg>, keyword_id: KeywordId) -> AppResult<()>;
+    /// Returns the number of papers newly added; the papers already in it are skipped.
+    async fn add_papers_to_keyword(
+        &self,
+        tx: &mut T<'_, Pg>,
+        keyword_id: KeywordId,
+        paper_ids: &[AcademicPaperId],
+    ) -> AppResult<u64>;
+    /// Returns the number of papers removed.
+    async fn remove_papers_from_keyword(
+        &self,
+        tx: &mut T<'_, Pg>,
+        keyword_id: KeywordId,
+        paper_ids: &[AcademicPaperId],
+    ) -> AppResult<u64>;
+}
@@ -0,0 +1,38 @@
+use crate::models::{
+    list::PaginatedList,
+    paper_candidate::{CandidateStatus, PaperCandidate, PaperCandidateListOptions},
+};
+use async_trait::async_trait;
+use shared::{
+    errors::AppResult,
+    id::{AcademicPaperId, PaperCandidateId},
+};
+use sqlx::{Postgres as Pg, Transaction as T};
+
+#[async_trait]
+pub trait PaperCandidateRepository: Send + Sync {
+    /// Stores new candidates, skipping arXiv IDs that are already stored.
+    async fn create_paper_candidates(&self, tx: &mut T<'_, Pg>, candidates: &[PaperCandidate]) -> AppResult<()>;
+    async fn select_paper_candidate_by_id(
+        &self,
+        tx: &mut T<'_, Pg>,
+        candidate_id: PaperCandidateId,
+    ) -> AppResult<PaperCandidate>;
+    /// Candidates ordered by score, best first.
+    async fn select_paginated_paper_candidates(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: PaperCandidateListOptions,
+    ) -> AppResult<PaginatedList<PaperCandidate>>;
+    /// The arXiv IDs among `arxiv_ids` that are already candidates or papers in the library.
+    async fn select_known_arxiv_ids(&self, tx: &mut T<'_, Pg>, arxiv_ids: &[String]) -> AppResult<Vec<String>>;
+    /// The arXiv IDs among `arxiv_ids` of the papers that are already in the library.
+    async fn select_library_arxiv_ids(&self, tx: &mut T<'_, Pg>, arxiv_ids: &[String]) -> AppResult<Vec<String>>;
+    async fn update_paper_candidate_status(
+        &self,
+        tx: &mut T<'_, Pg>,
+        candidate_id: PaperCandidateId,
+        status: CandidateStatus,
+        paper_id: Option<AcademicPaperId>,
+    ) -> AppResult<()>;
+}
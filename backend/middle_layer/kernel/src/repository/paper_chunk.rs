@@ -0,0 +1,24 @@
+use crate::models::paper_chunk::{PaperChunk, PaperChunkMatch};
+use async_trait::async_trait;
+use shared::{errors::AppResult, id::AcademicPaperId};
+use sqlx::{Postgres as Pg, Transaction as T};
+
+#[async_trait]
+pub trait PaperChunkRepository: Send + Sync {
+    /// Replaces the indexed passages of a paper.
+    async fn replace_paper_chunks(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        chunks: &[PaperChunk],
+    ) -> AppResult<()>;
+    /// All passages of a paper in reading order, ranked against `query` by full text search.
+    async fn select_paper_chunk_matches(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        query: &str,
+    ) -> AppResult<Vec<PaperChunkMatch>>;
+    /// The papers with stored sections that are not indexed yet.
+    async fn select_unindexed_paper_ids(&self, tx: &mut T<'_, Pg>) -> AppResult<Vec<AcademicPaperId>>;
+}
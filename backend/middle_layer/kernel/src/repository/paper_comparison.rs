@@ -0,0 +1,38 @@
+use crate::models::{
+    language::Language,
+    list::PaginatedList,
+    paper_comparison::{PaperComparison, PaperComparisonListOptions},
+};
+use async_trait::async_trait;
+use shared::{
+    errors::AppResult,
+    id::{AcademicPaperId, PaperComparisonId},
+};
+use sqlx::{Postgres as Pg, Transaction as T};
+
+#[async_trait]
+pub trait PaperComparisonRepository: Send + Sync {
+    /// Compares the papers, in the given order, with their stored analyses and saves the comparison.
+    async fn compare_papers(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_ids: Vec<AcademicPaperId>,
+        language: Language,
+    ) -> AppResult<PaperComparison>;
+    async fn select_paper_comparison(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_comparison_id: PaperComparisonId,
+    ) -> AppResult<PaperComparison>;
+    /// The saved comparisons, the newest first.
+    async fn select_paper_comparisons(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: PaperComparisonListOptions,
+    ) -> AppResult<PaginatedList<PaperComparison>>;
+    async fn delete_paper_comparison(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_comparison_id: PaperComparisonId,
+    ) -> AppResult<()>;
+}
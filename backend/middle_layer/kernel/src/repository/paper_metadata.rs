@@ -0,0 +1,32 @@
+use crate::models::paper_metadata::{CitationGain, MetadataChange, PaperMetadata};
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use shared::errors::AppResult;
+use sqlx::{Postgres as Pg, Transaction as T};
+
+#[async_trait]
+pub trait PaperMetadataRepository: Send + Sync {
+    /// The papers whose metadata was not refreshed since `refreshed_before`, never refreshed ones first.
+    async fn select_papers_to_refresh(
+        &self,
+        tx: &mut T<'_, Pg>,
+        refreshed_before: DateTime<Local>,
+        limit: i64,
+    ) -> AppResult<Vec<PaperMetadata>>;
+    /// Stores `updated` and records the fields that differ from `current` in the history.
+    async fn update_paper_metadata(
+        &self,
+        tx: &mut T<'_, Pg>,
+        current: &PaperMetadata,
+        updated: &PaperMetadata,
+    ) -> AppResult<Vec<MetadataChange>>;
+    /// The metadata changes of a paper, newest first.
+    async fn select_metadata_history(&self, tx: &mut T<'_, Pg>, paper_id: &str) -> AppResult<Vec<MetadataChange>>;
+    /// The papers that gained the most citations since `since`.
+    async fn select_citation_gains(
+        &self,
+        tx: &mut T<'_, Pg>,
+        since: DateTime<Local>,
+        limit: i64,
+    ) -> AppResult<Vec<CitationGain>>;
+}
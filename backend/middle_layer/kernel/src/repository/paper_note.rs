@@ -1,8 +1,8 @@
-use crate::models::paper_note::PaperNote;
+use crate::models::paper_note::{PaperNote, PaperNoteMessage};
 use async_trait::async_trait;
 use shared::{
     errors::AppResult,
-    id::{AcademicPaperId, PaperNoteId},
+    id::{AcademicPaperId, PaperNoteId, PaperNoteMessageId},
 };
 use sqlx::{Postgres as Pg, Transaction as T};
 
@@ -12,6 +12,29 @@ pub trait PaperNoteRepository: Send + Sync {
     async fn create_paper_note(&self, tx: &mut T<'_, Pg>, paper_note: PaperNote) -> AppResult<PaperNote>;
     async fn update_paper_note(&self, tx: &mut T<'_, Pg>, paper_note: PaperNote) -> AppResult<PaperNote>;
     async fn delete_paper_note(&self, tx: &mut T<'_, Pg>, paper_note_id: PaperNoteId) -> AppResult<()>;
-    async fn ask_to_agent(&self, tx: &mut T<'_, Pg>, paper_note_id: PaperNoteId, query: String)
-        -> AppResult<PaperNote>;
+    /// Asks the agent `query` with the previous turns of the conversation as context,
+    /// and returns the question and the answer.
+    async fn ask_to_agent(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_note_id: PaperNoteId,
+        query: String,
+    ) -> AppResult<Vec<PaperNoteMessage>>;
+    /// The conversation with the agent about the note, in order.
+    async fn select_paper_note_messages(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_note_id: PaperNoteId,
+    ) -> AppResult<Vec<PaperNoteMessage>>;
+    /// Answers the question of an answer again, with the turns before it as context.
+    async fn regenerate_paper_note_message(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_note_message_id: PaperNoteMessageId,
+    ) -> AppResult<PaperNoteMessage>;
+    async fn delete_paper_note_message(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_note_message_id: PaperNoteMessageId,
+    ) -> AppResult<()>;
 }
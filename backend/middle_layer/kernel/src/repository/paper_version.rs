@@ -0,0 +1,25 @@
+use crate::models::paper_version::{PaperVersion, VersionedPaper};
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use shared::{errors::AppResult, id::AcademicPaperId};
+use sqlx::{Postgres as Pg, Transaction as T};
+
+#[async_trait]
+pub trait PaperVersionRepository: Send + Sync {
+    /// The papers on arXiv whose versions were not checked since `checked_before`, never checked ones first.
+    async fn select_papers_to_check_versions(
+        &self,
+        tx: &mut T<'_, Pg>,
+        checked_before: DateTime<Local>,
+        limit: i64,
+    ) -> AppResult<Vec<VersionedPaper>>;
+    async fn update_versions_checked_at(&self, tx: &mut T<'_, Pg>, paper_ids: &[AcademicPaperId]) -> AppResult<()>;
+    /// Records a version of a paper, replacing the record of the same version.
+    async fn create_paper_version(&self, tx: &mut T<'_, Pg>, version: PaperVersion) -> AppResult<PaperVersion>;
+    /// The versions of a paper, newest first.
+    async fn select_paper_versions(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+    ) -> AppResult<Vec<PaperVersion>>;
+}
@@ -0,0 +1,49 @@
+use crate::models::{
+    academic_paper::Status,
+    list::PaginatedList,
+    reading::{PaperStatusChange, ReadingQueueItem, ReadingQueueListOptions},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDate};
+use shared::{errors::AppResult, id::AcademicPaperId};
+use sqlx::{Postgres as Pg, Transaction as T};
+
+#[async_trait]
+pub trait ReadingRepository: Send + Sync {
+    /// Moves the paper to `status` and records the change; a paper moved to `Completed` leaves the reading queue.
+    /// Fails with [`shared::errors::AppError::Unprocessable`] if the transition is not allowed.
+    async fn update_paper_status(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        status: Status,
+    ) -> AppResult<PaperStatusChange>;
+    /// The status changes of the paper, oldest first.
+    async fn select_paper_status_history(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+    ) -> AppResult<Vec<PaperStatusChange>>;
+    /// The status changes from `since` until `until`, and the last change of each paper before `since`.
+    async fn select_status_changes(
+        &self,
+        tx: &mut T<'_, Pg>,
+        since: DateTime<Local>,
+        until: DateTime<Local>,
+    ) -> AppResult<Vec<PaperStatusChange>>;
+    /// Adds the paper to the reading queue, or updates its priority and due date.
+    async fn upsert_reading_queue_item(
+        &self,
+        tx: &mut T<'_, Pg>,
+        paper_id: AcademicPaperId,
+        priority: i32,
+        due_date: Option<NaiveDate>,
+    ) -> AppResult<ReadingQueueItem>;
+    async fn delete_reading_queue_item(&self, tx: &mut T<'_, Pg>, paper_id: AcademicPaperId) -> AppResult<()>;
+    /// The papers to read, the highest priority first, then the earliest due date.
+    async fn select_reading_queue(
+        &self,
+        tx: &mut T<'_, Pg>,
+        options: ReadingQueueListOptions,
+    ) -> AppResult<PaginatedList<ReadingQueueItem>>;
+}
@@ -29,6 +29,8 @@ pub trait WebArticleRepository: Send + Sync {
     async fn select_web_article_by_url(&self, url: &str) -> AppResult<WebArticle>;
     async fn select_or_create_web_article(&self, web_article: WebArticle) -> AppResult<WebArticle>;
     async fn select_all_web_articles(&self) -> AppResult<Vec<WebArticle>>;
+    /// Articles stored by a batch-mode collection whose attributes have not been filled in yet.
+    async fn select_pending_web_articles(&self) -> AppResult<Vec<WebArticle>>;
     async fn select_paginated_web_articles(
         &self,
         options: WebArticleListOptions,
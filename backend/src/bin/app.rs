@@ -1,9 +1,18 @@
 use clap::{Parser, Subcommand};
 use commands::{
     add_academic_paper::{add_academic_paper, AddAcademicPaperArgs},
+    check_versions::{check_versions, CheckVersionsArgs},
     collect_articles::{collect_articles, CollectArticlesArgs},
+    eval::{eval, EvalArgs},
+    export_library::{export_library, ExportLibraryArgs},
+    fetch_citations::{fetch_citations, FetchCitationsArgs},
+    import_library::{import_library, ImportLibraryArgs},
+    index_papers::{index_papers, IndexPapersArgs},
     notify_web_articles_to_slack::{notify_to_slack, NotifyWebArticlesToSlackArgs},
+    refresh_authors::{refresh_authors, RefreshAuthorsArgs},
+    refresh_papers::{refresh_papers, RefreshPapersArgs},
     start_dashboard::{start_dashboard, StartDashboardArgs},
+    watch_arxiv::{watch_arxiv, WatchArxivArgs},
 };
 use shared::logger::init_logger;
 
@@ -24,6 +33,24 @@ enum SubCommands {
     AddAcademicPaper(AddAcademicPaperArgs),
     /// Notify web articles to Slack
     NotifyWebArticlesToSlack(NotifyWebArticlesToSlackArgs),
+    /// Evaluate the article classification prompt against a labeled dataset
+    Eval(EvalArgs),
+    /// Watch arXiv categories and add relevant new papers
+    WatchArxiv(WatchArxivArgs),
+    /// Fetch the references and citations of the papers into the citation graph
+    FetchCitations(FetchCitationsArgs),
+    /// Refresh the h-index of the authors from Semantic Scholar
+    RefreshAuthors(RefreshAuthorsArgs),
+    /// Refresh the citation counts and venues of the papers from Semantic Scholar
+    RefreshPapers(RefreshPapersArgs),
+    /// Check the papers on arXiv for new versions and summarize what changed
+    CheckVersions(CheckVersionsArgs),
+    /// Export the library as BibTeX, RIS or CSL-JSON
+    ExportLibrary(ExportLibraryArgs),
+    /// Import a BibTeX, RIS or CSL-JSON library, adding the papers that are not in the database yet
+    ImportLibrary(ImportLibraryArgs),
+    /// Index the full text of the papers for the retrieval of passages in questions
+    IndexPapers(IndexPapersArgs),
 }
 
 #[tokio::main]
@@ -36,5 +63,14 @@ async fn main() {
         SubCommands::StartDashboard(args) => start_dashboard(args).await,
         SubCommands::AddAcademicPaper(args) => add_academic_paper(args).await,
         SubCommands::NotifyWebArticlesToSlack(args) => notify_to_slack(args).await,
+        SubCommands::Eval(args) => eval(args).await,
+        SubCommands::WatchArxiv(args) => watch_arxiv(args).await,
+        SubCommands::FetchCitations(args) => fetch_citations(args).await,
+        SubCommands::RefreshAuthors(args) => refresh_authors(args).await,
+        SubCommands::RefreshPapers(args) => refresh_papers(args).await,
+        SubCommands::CheckVersions(args) => check_versions(args).await,
+        SubCommands::ExportLibrary(args) => export_library(args).await,
+        SubCommands::ImportLibrary(args) => import_library(args).await,
+        SubCommands::IndexPapers(args) => index_papers(args).await,
     }
 }